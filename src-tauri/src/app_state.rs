@@ -5,6 +5,7 @@ use std::sync::Arc;
 use anyhow::Result;
 use tokio::sync::{watch, Mutex as TokioMutex};
 
+use crate::core::batch_engine::BatchSignal;
 use crate::core::clone_engine::CloneSignal;
 use crate::core::index_engine::IndexSignal;
 use crate::core::storage::sqlite::SqliteStorage;
@@ -13,6 +14,7 @@ pub struct AppState {
     pub storage: Arc<SqliteStorage>,
     pub clone_signals: Arc<TokioMutex<HashMap<String, watch::Sender<CloneSignal>>>>,
     pub index_signals: Arc<TokioMutex<HashMap<String, watch::Sender<IndexSignal>>>>,
+    pub batch_signals: Arc<TokioMutex<HashMap<String, watch::Sender<BatchSignal>>>>,
 }
 
 impl AppState {
@@ -22,6 +24,7 @@ impl AppState {
             storage: Arc::new(storage),
             clone_signals: Arc::new(TokioMutex::new(HashMap::new())),
             index_signals: Arc::new(TokioMutex::new(HashMap::new())),
+            batch_signals: Arc::new(TokioMutex::new(HashMap::new())),
         })
     }
 }
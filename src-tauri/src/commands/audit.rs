@@ -0,0 +1,33 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::core::storage::repositories::audit_repo;
+use crate::models::{AuditEntry, AuditLogFilter};
+
+#[tauri::command]
+pub fn audit_log_list(state: State<'_, AppState>, filter: AuditLogFilter) -> Result<Vec<AuditEntry>, String> {
+    audit_repo::list(&state.storage, &filter).map_err(|e| e.to_string())
+}
+
+/// Write the entries matching `filter` to `dest_path` as JSON Lines, oldest
+/// first. Returns how many were written.
+#[tauri::command]
+pub fn audit_log_export(
+    state: State<'_, AppState>,
+    filter: AuditLogFilter,
+    dest_path: String,
+) -> Result<usize, String> {
+    let entries = audit_repo::list(&state.storage, &filter).map_err(|e| e.to_string())?;
+
+    let file = File::create(&dest_path).map_err(|e| format!("Could not create {dest_path}: {e}"))?;
+    let mut writer = BufWriter::new(file);
+    for entry in entries.iter().rev() {
+        serde_json::to_writer(&mut writer, entry).map_err(|e| e.to_string())?;
+        writer.write_all(b"\n").map_err(|e| e.to_string())?;
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+    Ok(entries.len())
+}
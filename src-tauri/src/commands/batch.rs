@@ -0,0 +1,437 @@
+use std::sync::Arc;
+
+use aws_sdk_s3::types::StorageClass;
+use tauri::State;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use crate::app_state::AppState;
+use crate::commands::objects::resolve_target_and_credentials;
+use crate::core::audit;
+use crate::core::batch_engine::{self, BatchSignal};
+use crate::core::guard;
+use crate::core::s3;
+use crate::core::trash;
+use crate::core::storage::repositories::{batch_repo, targets_repo};
+use crate::models::{
+    BatchJob, BatchJobItem, DeletePrefixParams, EmptyBucketParams, ObjectTag, RestoreJobParams,
+    StorageClassJobParams, StorageTarget, TagJobParams, TargetCredentials, UploadDirectoryParams,
+};
+
+fn now_epoch() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+/// Persist a new job over `keys`, or over everything under `prefix` when no
+/// keys are given, and start it.
+async fn create_and_spawn(
+    state: &AppState,
+    app: tauri::AppHandle,
+    kind: &str,
+    target_id: String,
+    bucket: String,
+    prefix: String,
+    keys: Option<Vec<String>>,
+    params: serde_json::Value,
+) -> Result<BatchJob, String> {
+    let now = now_epoch();
+    let id = Uuid::now_v7().to_string();
+
+    let items: Vec<BatchJobItem> = keys
+        .as_ref()
+        .map(|keys| {
+            keys.iter()
+                .map(|key| batch_engine::new_item(&id, key.clone(), None, 0, now))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let job = BatchJob {
+        id,
+        kind: kind.to_string(),
+        status: "pending".to_string(),
+        target_id,
+        bucket,
+        prefix,
+        params,
+        enumeration_token: None,
+        enumeration_complete: keys.is_some(),
+        total_items: items.len() as i64,
+        completed_items: 0,
+        failed_items: 0,
+        skipped_items: 0,
+        created_at: now,
+        updated_at: now,
+        completed_at: None,
+        initiator: audit::MANUAL.to_string(),
+    };
+
+    batch_repo::insert_job(&state.storage, &job).map_err(|e| e.to_string())?;
+    if !items.is_empty() {
+        batch_repo::insert_items_batch(&state.storage, &items).map_err(|e| e.to_string())?;
+    }
+
+    spawn_job(state, app, job.id.clone()).await;
+    Ok(job)
+}
+
+/// Reject changes to protected objects: each of `keys`, or everything
+/// under `prefix`.
+fn check_protected(target: &StorageTarget, bucket: &str, prefix: &str, keys: Option<&[String]>) -> Result<(), String> {
+    match keys {
+        Some(keys) => keys.iter().try_for_each(|key| guard::check_key(target, bucket, key)),
+        None => guard::check_prefix(target, bucket, prefix),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// The stored form of `prefix` and `keys`, which is what jobs list and
+/// work on when the target encrypts names.
+fn stored_keys(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    prefix: &str,
+    keys: Option<Vec<String>>,
+) -> Result<(String, Option<Vec<String>>), String> {
+    let cse = s3::ClientEncryption::for_target(target, credentials).map_err(|e| e.to_string())?;
+    let keys = keys.map(|keys| keys.iter().map(|key| cse.encode_key(key)).collect());
+    Ok((cse.encode_key(prefix), keys))
+}
+
+/// Re-check a job before it runs again, in case its target was made
+/// read-only or protected since it started.
+fn check_rerun(state: &AppState, job_id: &str) -> Result<(), String> {
+    let job = batch_repo::get_job(&state.storage, job_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Batch job not found".to_string())?;
+    let target = targets_repo::find_by_id(&state.storage, &job.target_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Target not found.".to_string())?;
+    if job.kind == batch_engine::KIND_EMPTY_BUCKET {
+        guard::check_prefix(&target, &job.bucket, "")
+    } else {
+        guard::check_writable(&target)
+    }
+    .map_err(|e| e.to_string())
+}
+
+async fn spawn_job(state: &AppState, app: tauri::AppHandle, job_id: String) {
+    let (signal_tx, signal_rx) = watch::channel(BatchSignal::Run);
+    {
+        let mut signals = state.batch_signals.lock().await;
+        signals.insert(job_id.clone(), signal_tx);
+    }
+
+    let storage = Arc::clone(&state.storage);
+    tokio::spawn(async move {
+        batch_engine::run_batch_job(app, storage, signal_rx, job_id).await;
+    });
+}
+
+#[tauri::command]
+pub async fn batch_tag_start(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    target_id: String,
+    bucket: String,
+    prefix: String,
+    keys: Option<Vec<String>>,
+    mode: String,
+    tags: Vec<ObjectTag>,
+) -> Result<BatchJob, String> {
+    match mode.as_str() {
+        "merge" | "replace" => s3::validate_tags(&tags).map_err(|e| e.to_string())?,
+        "remove" if tags.is_empty() => return Err("No tag keys to remove.".to_string()),
+        "remove" => {}
+        other => return Err(format!("Unknown tag mode: {other}")),
+    }
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let (prefix, keys) = stored_keys(&target, &credentials, &prefix, keys)?;
+
+    let params = serde_json::to_value(TagJobParams { mode, tags }).map_err(|e| e.to_string())?;
+    create_and_spawn(
+        &state,
+        app,
+        batch_engine::KIND_TAG,
+        target_id,
+        bucket,
+        prefix,
+        keys,
+        params,
+    )
+    .await
+}
+
+/// Change the storage class of `keys`, or of everything under `prefix`, by
+/// copying each object onto itself.
+#[tauri::command]
+pub async fn batch_storage_class_start(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    target_id: String,
+    bucket: String,
+    prefix: String,
+    keys: Option<Vec<String>>,
+    storage_class: String,
+) -> Result<BatchJob, String> {
+    if !StorageClass::values().contains(&storage_class.as_str()) {
+        return Err(format!("Unknown storage class: {storage_class}"));
+    }
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    check_protected(&target, &bucket, &prefix, keys.as_deref())?;
+    let (prefix, keys) = stored_keys(&target, &credentials, &prefix, keys)?;
+
+    let params = serde_json::to_value(StorageClassJobParams { storage_class })
+        .map_err(|e| e.to_string())?;
+    create_and_spawn(
+        &state,
+        app,
+        batch_engine::KIND_STORAGE_CLASS,
+        target_id,
+        bucket,
+        prefix,
+        keys,
+        params,
+    )
+    .await
+}
+
+/// Request restores for the archived objects among `keys`, or under
+/// `prefix`. Objects that are already readable are skipped.
+#[tauri::command]
+pub async fn batch_restore_start(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    target_id: String,
+    bucket: String,
+    prefix: String,
+    keys: Option<Vec<String>>,
+    days: i32,
+    tier: String,
+) -> Result<BatchJob, String> {
+    s3::validate_restore_request(days, &tier).map_err(|e| e.to_string())?;
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let (prefix, keys) = stored_keys(&target, &credentials, &prefix, keys)?;
+
+    let params = serde_json::to_value(RestoreJobParams { days, tier }).map_err(|e| e.to_string())?;
+    create_and_spawn(
+        &state,
+        app,
+        batch_engine::KIND_RESTORE,
+        target_id,
+        bucket,
+        prefix,
+        keys,
+        params,
+    )
+    .await
+}
+
+/// Delete every version, delete marker and incomplete multipart upload in
+/// `bucket`, then the bucket itself if `delete_bucket` is set.
+#[tauri::command]
+pub async fn batch_empty_bucket_start(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    target_id: String,
+    bucket: String,
+    delete_bucket: bool,
+    bypass_governance: Option<bool>,
+    confirm: bool,
+) -> Result<BatchJob, String> {
+    if !confirm {
+        return Err("Emptying a bucket permanently deletes all of its data and must be confirmed.".to_string());
+    }
+    let (target, _) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_prefix(&target, &bucket, "").map_err(|e| e.to_string())?;
+
+    let params = serde_json::to_value(EmptyBucketParams {
+        delete_bucket,
+        bypass_governance: bypass_governance.unwrap_or(false),
+    })
+    .map_err(|e| e.to_string())?;
+    create_and_spawn(
+        &state,
+        app,
+        batch_engine::KIND_EMPTY_BUCKET,
+        target_id,
+        bucket,
+        String::new(),
+        None,
+        params,
+    )
+    .await
+}
+
+/// Delete everything under `prefix` in batches of 1000 keys, or every
+/// version and delete marker under it if `all_versions` is set.
+#[tauri::command]
+pub async fn batch_delete_prefix_start(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    target_id: String,
+    bucket: String,
+    prefix: String,
+    all_versions: bool,
+    bypass_governance: Option<bool>,
+    permanent: Option<bool>,
+) -> Result<BatchJob, String> {
+    if prefix.is_empty() {
+        return Err("A prefix is required. Empty the bucket to delete everything in it.".to_string());
+    }
+
+    // Enumeration lists stored keys, so the prefix has to be stored-form too
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    if target.trash_enabled && !permanent.unwrap_or(false) && !trash::is_trashed(&prefix) {
+        return Err("Trash is on for this source: move the folder to the trash instead.".to_string());
+    }
+    guard::check_prefix(&target, &bucket, &prefix).map_err(|e| e.to_string())?;
+    let cse = s3::ClientEncryption::for_target(&target, &credentials).map_err(|e| e.to_string())?;
+
+    let params = serde_json::to_value(DeletePrefixParams {
+        all_versions,
+        bypass_governance: bypass_governance.unwrap_or(false),
+    })
+    .map_err(|e| e.to_string())?;
+    create_and_spawn(
+        &state,
+        app,
+        batch_engine::KIND_DELETE_PREFIX,
+        target_id,
+        bucket,
+        cse.encode_key(&prefix),
+        None,
+        params,
+    )
+    .await
+}
+
+/// Upload the files under the local folder `params.root` to
+/// `params.dest_prefix`. The file list is walked once and kept with the
+/// job, so an interrupted upload picks up where it stopped.
+#[tauri::command]
+pub async fn batch_upload_directory_start(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    target_id: String,
+    bucket: String,
+    mut params: UploadDirectoryParams,
+) -> Result<BatchJob, String> {
+    batch_engine::validate_upload_params(&params).map_err(|e| e.to_string())?;
+    if !params.dest_prefix.is_empty() && !params.dest_prefix.ends_with('/') {
+        params.dest_prefix.push('/');
+    }
+
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let cse = s3::ClientEncryption::for_target(&target, &credentials).map_err(|e| e.to_string())?;
+
+    let prefix = cse.encode_key(&params.dest_prefix);
+    let params = serde_json::to_value(params).map_err(|e| e.to_string())?;
+    create_and_spawn(
+        &state,
+        app,
+        batch_engine::KIND_UPLOAD_DIRECTORY,
+        target_id,
+        bucket,
+        prefix,
+        None,
+        params,
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn batch_pause(state: State<'_, AppState>, job_id: String) -> Result<(), String> {
+    let signals = state.batch_signals.lock().await;
+    if let Some(tx) = signals.get(&job_id) {
+        let _ = tx.send(BatchSignal::Pause);
+        Ok(())
+    } else {
+        Err("Batch job not running".to_string())
+    }
+}
+
+#[tauri::command]
+pub async fn batch_resume(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    job_id: String,
+) -> Result<(), String> {
+    check_rerun(&state, &job_id)?;
+    batch_repo::reset_active_items(&state.storage, &job_id).map_err(|e| e.to_string())?;
+
+    {
+        let signals = state.batch_signals.lock().await;
+        if let Some(tx) = signals.get(&job_id) {
+            let _ = tx.send(BatchSignal::Run);
+            return Ok(());
+        }
+    }
+
+    spawn_job(&state, app, job_id).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn batch_cancel(state: State<'_, AppState>, job_id: String) -> Result<(), String> {
+    let mut signals = state.batch_signals.lock().await;
+    if let Some(tx) = signals.remove(&job_id) {
+        let _ = tx.send(BatchSignal::Cancel);
+    } else {
+        batch_repo::update_job_status(&state.storage, &job_id, "cancelled")
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn batch_retry_failed(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    job_id: String,
+) -> Result<(), String> {
+    check_rerun(&state, &job_id)?;
+    batch_repo::reset_failed_items(&state.storage, &job_id).map_err(|e| e.to_string())?;
+    spawn_job(&state, app, job_id).await;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn batch_job_list(state: State<'_, AppState>) -> Result<Vec<BatchJob>, String> {
+    batch_repo::list_jobs(&state.storage).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn batch_job_get(state: State<'_, AppState>, job_id: String) -> Result<Option<BatchJob>, String> {
+    batch_repo::get_job(&state.storage, &job_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn batch_job_delete(state: State<'_, AppState>, job_id: String) -> Result<(), String> {
+    batch_repo::delete_job(&state.storage, &job_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn batch_job_items_list(
+    state: State<'_, AppState>,
+    job_id: String,
+    status_filter: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<Vec<BatchJobItem>, String> {
+    batch_repo::list_items(
+        &state.storage,
+        &job_id,
+        status_filter.as_deref(),
+        limit.unwrap_or(100),
+        offset.unwrap_or(0),
+    )
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,357 @@
+use tauri::State;
+
+use super::objects::resolve_target_and_credentials;
+use crate::app_state::AppState;
+use crate::core::audit;
+use crate::core::guard;
+use crate::core::multipart_janitor;
+use crate::core::s3;
+use crate::core::storage::repositories::index_repo;
+use crate::models::{
+    BucketAccessSummary, CorsRule, LifecyclePreview, LifecyclePreviewObject, LifecycleRule,
+    MultipartAbortResult, MultipartUploadRef, MultipartUploadSummary, PublicAccessBlock, TagFilter,
+};
+
+const PREVIEW_LIMIT: usize = 500;
+
+#[tauri::command]
+pub async fn target_bucket_lifecycle_get(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<Vec<LifecycleRule>, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    s3::get_bucket_lifecycle(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replace the bucket's lifecycle rules. An empty list removes them.
+#[tauri::command]
+pub async fn target_bucket_lifecycle_put(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    rules: Vec<LifecycleRule>,
+) -> Result<Vec<LifecycleRule>, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::put_bucket_lifecycle(&target, &credentials, &bucket, &rules)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_prefix(&state.storage, audit::MANUAL, "putLifecycle", &target_id, &bucket, "", &result);
+    result?;
+    s3::get_bucket_lifecycle(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_bucket_lifecycle_delete(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::delete_bucket_lifecycle(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_prefix(&state.storage, audit::MANUAL, "deleteLifecycle", &target_id, &bucket, "", &result);
+    result
+}
+
+#[tauri::command]
+pub fn target_bucket_lifecycle_validate(rules: Vec<LifecycleRule>) -> Result<(), String> {
+    s3::validate_lifecycle_rules(&rules).map_err(|e| e.to_string())
+}
+
+/// Which current objects in the local index `rule` would expire or
+/// transition today. Noncurrent versions, delete markers and incomplete
+/// uploads are not indexed, so those actions are not previewed.
+#[tauri::command]
+pub fn target_bucket_lifecycle_preview(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    rule: LifecycleRule,
+) -> Result<LifecyclePreview, String> {
+    // The index holds readable keys, but the rule has to be one that can be saved
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    let cse = s3::ClientEncryption::for_target(&target, &credentials).map_err(|e| e.to_string())?;
+    s3::stored_rule_prefix(&cse, &rule.prefix).map_err(|e| e.to_string())?;
+    let index_state = index_repo::get_index_state(&state.storage, &target_id, &bucket)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Bucket is not indexed. Build the index to preview lifecycle rules.".to_string())?;
+    if !rule.tags.is_empty() && !index_state.include_tags {
+        return Err("This rule filters on tags, but the bucket index was built without tags.".to_string());
+    }
+
+    let tag_filters: Vec<TagFilter> = rule
+        .tags
+        .iter()
+        .map(|t| TagFilter {
+            key: t.key.clone(),
+            value: Some(t.value.clone()),
+        })
+        .collect();
+    let now = chrono::Utc::now();
+    let matches = index_repo::lifecycle_matches(
+        &state.storage,
+        &target_id,
+        &bucket,
+        &rule.prefix,
+        &tag_filters,
+        &s3::lifecycle_schedule(&rule, now),
+        PREVIEW_LIMIT,
+    )
+    .map_err(|e| e.to_string())?;
+
+    let preview = LifecyclePreview {
+        matched_objects: matches.matched,
+        affected_objects: matches.affected,
+        affected_size: matches.affected_size,
+        truncated: matches.affected > matches.sample.len() as i64,
+        objects: matches
+            .sample
+            .into_iter()
+            .filter_map(|obj| {
+                let (action, transition_storage_class) = s3::lifecycle_action(
+                    &rule,
+                    obj.size,
+                    obj.last_modified.as_deref(),
+                    obj.storage_class.as_deref(),
+                    now,
+                )?;
+                Some(LifecyclePreviewObject {
+                    key: obj.key,
+                    size: obj.size,
+                    last_modified: obj.last_modified,
+                    storage_class: obj.storage_class,
+                    action: action.to_string(),
+                    transition_storage_class,
+                })
+            })
+            .collect(),
+        last_indexed_at: index_state.last_indexed_at,
+    };
+
+    Ok(preview)
+}
+
+#[tauri::command]
+pub async fn target_bucket_policy_get(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<Option<String>, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    s3::get_bucket_policy(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_bucket_policy_put(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    policy: String,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::put_bucket_policy(&target, &credentials, &bucket, &policy)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_prefix(&state.storage, audit::MANUAL, "putPolicy", &target_id, &bucket, "", &result);
+    result
+}
+
+#[tauri::command]
+pub async fn target_bucket_policy_delete(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::delete_bucket_policy(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_prefix(&state.storage, audit::MANUAL, "deletePolicy", &target_id, &bucket, "", &result);
+    result
+}
+
+#[tauri::command]
+pub fn target_bucket_policy_validate(bucket: String, policy: String) -> Result<(), String> {
+    s3::validate_bucket_policy(&policy, &bucket).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_bucket_cors_get(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<Vec<CorsRule>, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    s3::get_bucket_cors(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Replace the bucket's CORS rules. An empty list removes them.
+#[tauri::command]
+pub async fn target_bucket_cors_put(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    rules: Vec<CorsRule>,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::put_bucket_cors(&target, &credentials, &bucket, &rules)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_prefix(&state.storage, audit::MANUAL, "putCors", &target_id, &bucket, "", &result);
+    result
+}
+
+#[tauri::command]
+pub async fn target_bucket_cors_delete(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::delete_bucket_cors(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_prefix(&state.storage, audit::MANUAL, "deleteCors", &target_id, &bucket, "", &result);
+    result
+}
+
+#[tauri::command]
+pub fn target_bucket_cors_validate(rules: Vec<CorsRule>) -> Result<(), String> {
+    s3::validate_cors_rules(&rules).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_bucket_public_access_block_get(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<Option<PublicAccessBlock>, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    s3::get_public_access_block(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_bucket_public_access_block_put(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    settings: PublicAccessBlock,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::put_public_access_block(&target, &credentials, &bucket, &settings)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_prefix(&state.storage, audit::MANUAL, "putPublicAccessBlock", &target_id, &bucket, "", &result);
+    result
+}
+
+#[tauri::command]
+pub async fn target_bucket_public_access_block_delete(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::delete_public_access_block(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_prefix(&state.storage, audit::MANUAL, "deletePublicAccessBlock", &target_id, &bucket, "", &result);
+    result
+}
+
+/// Who can read or list the bucket, derived from its policy and ACL.
+#[tauri::command]
+pub async fn target_bucket_access_summary(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<BucketAccessSummary, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    s3::get_bucket_access_summary(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Incomplete multipart uploads in the bucket, oldest first.
+#[tauri::command]
+pub async fn target_bucket_multipart_uploads_list(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    prefix: Option<String>,
+) -> Result<Vec<MultipartUploadSummary>, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    let client = s3::build_client(&target, &credentials)
+        .await
+        .map_err(|e| e.to_string())?;
+    s3::list_multipart_uploads(&client, &bucket, prefix.as_deref().unwrap_or(""))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_bucket_multipart_uploads_abort(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    uploads: Vec<MultipartUploadRef>,
+) -> Result<MultipartAbortResult, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let client = s3::build_client(&target, &credentials)
+        .await
+        .map_err(|e| e.to_string())?;
+    let result = s3::abort_multipart_uploads(&client, &bucket, &uploads).await;
+    let keys: Vec<String> = uploads.into_iter().map(|u| u.key).collect();
+    audit::record_keys(&state.storage, audit::MANUAL, "abortMultipart", &target_id, &bucket, &keys, &audit::outcome(&result.errors));
+    Ok(result)
+}
+
+/// Abort every upload in the bucket started at least `older_than_days` ago.
+#[tauri::command]
+pub async fn target_bucket_multipart_uploads_abort_stale(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    older_than_days: i64,
+) -> Result<MultipartAbortResult, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let client = s3::build_client(&target, &credentials)
+        .await
+        .map_err(|e| e.to_string())?;
+    let result = s3::abort_stale_multipart_uploads(&client, &bucket, older_than_days)
+        .await
+        .map_err(|e| e.to_string());
+    match &result {
+        Ok(r) => audit::record_prefix(&state.storage, audit::MANUAL, "abortMultipart", &target_id, &bucket, "", &audit::outcome(&r.errors)),
+        Err(_) => audit::record_prefix(&state.storage, audit::MANUAL, "abortMultipart", &target_id, &bucket, "", &result),
+    }
+    result
+}
+
+/// Run the background multipart sweep now. Returns `None` if it is off.
+#[tauri::command]
+pub async fn multipart_sweep_run(state: State<'_, AppState>) -> Result<Option<MultipartAbortResult>, String> {
+    multipart_janitor::sweep(&state.storage).await.map_err(|e| e.to_string())
+}
@@ -5,9 +5,11 @@ use tokio::sync::watch;
 use uuid::Uuid;
 
 use crate::app_state::AppState;
+use crate::core::audit;
 use crate::core::clone_engine::{self, CloneSignal};
-use crate::core::storage::repositories::clone_repo;
-use crate::models::{CloneJob, CloneJobItem};
+use crate::core::guard;
+use crate::core::storage::repositories::{clone_repo, targets_repo};
+use crate::models::{CloneJob, CloneJobItem, StorageTarget};
 
 fn now_epoch() -> i64 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -29,10 +31,73 @@ pub async fn clone_start(
     dest_prefix: String,
     conflict_policy: String,
 ) -> Result<CloneJob, String> {
+    let job = new_job(
+        source_target_id,
+        source_bucket,
+        source_prefix,
+        dest_target_id,
+        dest_bucket,
+        dest_prefix,
+        conflict_policy,
+        false,
+    );
+    start_job(&state, app, job).await
+}
+
+/// Move or rename an object or folder. An empty source prefix or one ending
+/// in `/` moves everything under it to `dest_prefix`; otherwise it is a
+/// single key that is moved to the key `dest_prefix`. Runs as a clone job that deletes each
+/// source once its copy is verified.
+#[tauri::command]
+pub async fn move_start(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    source_target_id: String,
+    source_bucket: String,
+    source_prefix: String,
+    dest_target_id: String,
+    dest_bucket: String,
+    dest_prefix: String,
+    conflict_policy: String,
+) -> Result<CloneJob, String> {
+    let is_folder = source_prefix.is_empty() || source_prefix.ends_with('/');
+    if is_folder != (dest_prefix.is_empty() || dest_prefix.ends_with('/')) {
+        return Err("A folder can only be moved to a folder, and an object to an object.".to_string());
+    }
+    if source_target_id == dest_target_id
+        && source_bucket == dest_bucket
+        && (dest_prefix == source_prefix || (is_folder && dest_prefix.starts_with(&source_prefix)))
+    {
+        return Err("Can't move an object or folder onto itself or into itself.".to_string());
+    }
+
+    let job = new_job(
+        source_target_id,
+        source_bucket,
+        source_prefix,
+        dest_target_id,
+        dest_bucket,
+        dest_prefix,
+        conflict_policy,
+        true,
+    );
+    start_job(&state, app, job).await
+}
+
+pub(crate) fn new_job(
+    source_target_id: String,
+    source_bucket: String,
+    source_prefix: String,
+    dest_target_id: String,
+    dest_bucket: String,
+    dest_prefix: String,
+    conflict_policy: String,
+    delete_source: bool,
+) -> CloneJob {
     let now = now_epoch();
     let is_same_target = source_target_id == dest_target_id;
 
-    let job = CloneJob {
+    CloneJob {
         id: Uuid::now_v7().to_string(),
         status: "pending".to_string(),
         source_target_id,
@@ -54,8 +119,54 @@ pub async fn clone_start(
         created_at: now,
         updated_at: now,
         completed_at: None,
+        delete_source,
+        initiator: audit::CLONE.to_string(),
+    }
+}
+
+/// Reject a job that would delete protected objects at its source or
+/// overwrite them at its destination. Jobs that skip existing objects
+/// only need a writable destination.
+fn check_job(state: &AppState, job: &CloneJob) -> Result<(), String> {
+    let find_target = |id: &str| -> Result<StorageTarget, String> {
+        targets_repo::find_by_id(&state.storage, id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "Target not found.".to_string())
     };
+    let single_object = clone_engine::is_single_object_move(job);
 
+    if job.delete_source {
+        let source = find_target(&job.source_target_id)?;
+        if single_object {
+            guard::check_key(&source, &job.source_bucket, &job.source_prefix)
+        } else {
+            guard::check_prefix(&source, &job.source_bucket, &job.source_prefix)
+        }
+        .map_err(|e| e.to_string())?;
+    }
+
+    let dest = find_target(&job.dest_target_id)?;
+    if job.conflict_policy == "skip" {
+        guard::check_writable(&dest)
+    } else if single_object {
+        guard::check_key(&dest, &job.dest_bucket, &job.dest_prefix)
+    } else {
+        guard::check_prefix(&dest, &job.dest_bucket, &job.dest_prefix)
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Re-check a job before it runs again, in case a target was made
+/// read-only or protected since it started.
+fn check_rerun(state: &AppState, job_id: &str) -> Result<(), String> {
+    let job = clone_repo::get_job(&state.storage, job_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Clone job not found".to_string())?;
+    check_job(state, &job)
+}
+
+pub(crate) async fn start_job(state: &AppState, app: tauri::AppHandle, job: CloneJob) -> Result<CloneJob, String> {
+    check_job(state, &job)?;
     clone_repo::insert_job(&state.storage, &job).map_err(|e| e.to_string())?;
 
     // Create signal channel and spawn engine
@@ -94,6 +205,8 @@ pub async fn clone_resume(
     app: tauri::AppHandle,
     job_id: String,
 ) -> Result<(), String> {
+    check_rerun(&state, &job_id)?;
+
     // Reset any active items back to pending
     clone_repo::reset_active_items(&state.storage, &job_id).map_err(|e| e.to_string())?;
 
@@ -159,6 +272,7 @@ pub async fn clone_retry_failed(
     app: tauri::AppHandle,
     job_id: String,
 ) -> Result<(), String> {
+    check_rerun(&state, &job_id)?;
     clone_repo::reset_failed_items(&state.storage, &job_id).map_err(|e| e.to_string())?;
 
     // Re-run the job
@@ -32,3 +32,8 @@ pub fn list_directory_files(path: String) -> Result<Vec<DirectoryFileEntry>, Str
     entries.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
     Ok(entries)
 }
+
+#[tauri::command]
+pub fn path_is_directory(path: String) -> bool {
+    Path::new(&path).is_dir()
+}
@@ -4,7 +4,7 @@ use tokio::sync::watch;
 use crate::app_state::AppState;
 use crate::core::index_engine::{self, IndexSignal};
 use crate::core::storage::repositories::index_repo;
-use crate::models::{BucketIndexState, S3ObjectEntry, S3ObjectListPage};
+use crate::models::{BucketIndexState, S3ObjectEntry, S3ObjectListPage, TagFilter};
 
 fn index_key(target_id: &str, bucket: &str) -> String {
     format!("{target_id}:{bucket}")
@@ -17,6 +17,7 @@ pub async fn index_start(
     target_id: String,
     bucket: String,
     fresh: bool,
+    include_tags: Option<bool>,
 ) -> Result<BucketIndexState, String> {
     let key = index_key(&target_id, &bucket);
 
@@ -37,6 +38,10 @@ pub async fn index_start(
     // Create/update index state
     index_repo::upsert_index_state(&state.storage, &target_id, &bucket, "indexing")
         .map_err(|e| e.to_string())?;
+    if let Some(include_tags) = include_tags {
+        index_repo::set_include_tags(&state.storage, &target_id, &bucket, include_tags)
+            .map_err(|e| e.to_string())?;
+    }
 
     let index_state = index_repo::get_index_state(&state.storage, &target_id, &bucket)
         .map_err(|e| e.to_string())?
@@ -145,8 +150,10 @@ pub async fn index_search(
     target_id: String,
     bucket: String,
     query: String,
+    tags: Option<Vec<TagFilter>>,
     limit: i64,
 ) -> Result<Vec<S3ObjectEntry>, String> {
-    index_repo::search(&state.storage, &target_id, &bucket, &query, limit)
+    let tags = tags.unwrap_or_default();
+    index_repo::search(&state.storage, &target_id, &bucket, &query, &tags, limit)
         .map_err(|e| e.to_string())
 }
@@ -1,9 +1,14 @@
+pub mod audit;
+pub mod batch;
+pub mod buckets;
 pub mod clone;
 pub mod files;
 pub mod indexing;
 pub mod objects;
 pub mod settings;
+pub mod shares;
 pub mod sync;
 pub mod targets;
 pub mod transfers;
+pub mod trash;
 
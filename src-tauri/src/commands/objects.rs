@@ -1,10 +1,23 @@
+use std::slice;
+
 use serde::Serialize;
 use tauri::{Emitter, State};
 
 use crate::app_state::AppState;
+use crate::core::audit;
+use crate::core::guard;
+use crate::core::restore_poller;
 use crate::core::s3;
-use crate::core::storage::repositories::{bucket_stats_repo, credentials_repo, targets_repo};
-use crate::models::{BucketStats, CachedBucketStats, S3ObjectEntry, S3ObjectListPage};
+use crate::core::trash;
+use crate::core::upload_plan;
+use crate::core::storage::repositories::{
+    bucket_stats_repo, credentials_repo, index_repo, restore_repo, settings_repo, targets_repo,
+};
+use crate::models::{
+    BucketStats, CachedBucketStats, DeleteObjectsResult, ObjectMetadata, ObjectMetadataUpdate, ObjectRestore, ObjectTag,
+    ObjectVersionPage, PresignResponseOverrides, PresignedPost, PresignedRequest, S3ObjectEntry,
+    S3ObjectListPage, ServerSideEncryption, UploadPlan, UploadPlanFile,
+};
 use log::info;
 
 #[derive(Clone, Serialize)]
@@ -15,7 +28,7 @@ struct DownloadProgress {
     bytes_total: u64,
 }
 
-fn resolve_target_and_credentials(
+pub(crate) fn resolve_target_and_credentials(
     state: &AppState,
     target_id: &str,
 ) -> Result<(crate::models::StorageTarget, crate::models::TargetCredentials), String> {
@@ -63,9 +76,47 @@ pub async fn target_object_upload(
     bucket: String,
     key: String,
     source_path: String,
+    encryption: Option<ServerSideEncryption>,
+    initiator: Option<String>,
 ) -> Result<(), String> {
     let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
-    s3::put_object(&target, &credentials, &bucket, &key, &source_path)
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    if guard::is_protected(&target, &bucket, &key)
+        && s3::head_object(&target, &credentials, &bucket, &key)
+            .await
+            .map_err(|e| e.to_string())?
+            .is_some()
+    {
+        return Err(format!("{bucket}/{key} is protected and can't be overwritten."));
+    }
+    let settings = settings_repo::get(&state.storage).map_err(|e| e.to_string())?;
+    let result = s3::put_object(
+        &target,
+        &credentials,
+        &bucket,
+        &key,
+        &source_path,
+        settings.preserve_timestamps,
+        encryption.as_ref(),
+    )
+    .await
+    .map_err(|e| e.to_string());
+    let initiator = audit::initiator(initiator.as_deref());
+    audit::record_keys(&state.storage, initiator, "upload", &target_id, &bucket, &[key], &result);
+    result
+}
+
+/// Sort `files` into new, changed and unchanged against what is already in
+/// the bucket, so an upload can skip the unchanged ones.
+#[tauri::command]
+pub async fn target_upload_plan(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    files: Vec<UploadPlanFile>,
+) -> Result<UploadPlan, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    upload_plan::plan(&target, &credentials, &bucket, files)
         .await
         .map_err(|e| e.to_string())
 }
@@ -77,10 +128,13 @@ pub async fn target_object_download(
     target_id: String,
     bucket: String,
     key: String,
+    version_id: Option<String>,
     dest_path: String,
     transfer_id: String,
+    encryption: Option<ServerSideEncryption>,
 ) -> Result<(), String> {
     let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    let settings = settings_repo::get(&state.storage).map_err(|e| e.to_string())?;
 
     let app_clone = app.clone();
     let tid = transfer_id.clone();
@@ -90,7 +144,10 @@ pub async fn target_object_download(
         &credentials,
         &bucket,
         &key,
+        version_id.as_deref(),
         &dest_path,
+        settings.preserve_timestamps,
+        encryption.as_ref(),
         move |done, total| {
             let _ = app_clone.emit(
                 "download-progress",
@@ -112,11 +169,36 @@ pub async fn target_objects_delete(
     target_id: String,
     bucket: String,
     keys: Vec<String>,
-) -> Result<(), String> {
+    permanent: Option<bool>,
+) -> Result<DeleteObjectsResult, String> {
     let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
-    s3::delete_objects(&target, &credentials, &bucket, keys)
-        .await
-        .map_err(|e| e.to_string())
+    for key in &keys {
+        guard::check_key(&target, &bucket, key).map_err(|e| e.to_string())?;
+    }
+    let to_trash = target.trash_enabled && !permanent.unwrap_or(false);
+    let operation = if to_trash { "trash" } else { "delete" };
+    let result = if to_trash {
+        trash::move_to_trash(&target, &credentials, &bucket, keys.clone()).await
+    } else {
+        s3::delete_objects(&target, &credentials, &bucket, keys.clone()).await
+    }
+    .map_err(|e| e.to_string());
+    let result = match result {
+        Ok(result) => {
+            audit::record_deletes(&state.storage, audit::MANUAL, operation, &target_id, &bucket, &result);
+            result
+        }
+        Err(e) => {
+            audit::record_keys::<(), _>(&state.storage, audit::MANUAL, operation, &target_id, &bucket, &keys, &Err(&e));
+            return Err(e);
+        }
+    };
+
+    // Keys S3 refused are still there, so they stay in the index
+    if !result.deleted.is_empty() {
+        let _ = index_repo::remove_objects(&state.storage, &target_id, &bucket, &result.deleted);
+    }
+    Ok(result)
 }
 
 #[tauri::command]
@@ -125,11 +207,15 @@ pub async fn target_folder_create(
     target_id: String,
     bucket: String,
     key: String,
+    encryption: Option<ServerSideEncryption>,
 ) -> Result<(), String> {
     let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
-    s3::create_folder(&target, &credentials, &bucket, &key)
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::create_folder(&target, &credentials, &bucket, &key, encryption.as_ref())
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string());
+    audit::record_keys(&state.storage, audit::MANUAL, "createFolder", &target_id, &bucket, &[key], &result);
+    result
 }
 
 #[tauri::command]
@@ -204,14 +290,286 @@ pub async fn target_object_presign(
     target_id: String,
     bucket: String,
     key: String,
+    version_id: Option<String>,
     expires_in_secs: u64,
+    encryption: Option<ServerSideEncryption>,
+    response: Option<PresignResponseOverrides>,
 ) -> Result<String, String> {
     let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
-    s3::presign_object(&target, &credentials, &bucket, &key, expires_in_secs)
+    s3::presign_object(
+        &target,
+        &credentials,
+        &bucket,
+        &key,
+        version_id.as_deref(),
+        expires_in_secs,
+        encryption.as_ref(),
+        response.as_ref(),
+    )
         .await
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn target_object_presign_put(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+    expires_in_secs: u64,
+    content_type: Option<String>,
+    content_length: Option<i64>,
+    encryption: Option<ServerSideEncryption>,
+) -> Result<PresignedRequest, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_key(&target, &bucket, &key).map_err(|e| e.to_string())?;
+    s3::presign_put_object(
+        &target,
+        &credentials,
+        &bucket,
+        &key,
+        expires_in_secs,
+        content_type.as_deref(),
+        content_length,
+        encryption.as_ref(),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn target_presign_post(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key_prefix: String,
+    expires_in_secs: u64,
+    max_size: Option<i64>,
+    content_type_prefix: Option<String>,
+) -> Result<PresignedPost, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_prefix(&target, &bucket, &key_prefix).map_err(|e| e.to_string())?;
+    s3::presign_post(
+        &target,
+        &credentials,
+        &bucket,
+        &key_prefix,
+        expires_in_secs,
+        max_size,
+        content_type_prefix.as_deref(),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_object_metadata_get(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+    encryption: Option<ServerSideEncryption>,
+) -> Result<ObjectMetadata, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    s3::head_object_details(&target, &credentials, &bucket, &key, encryption.as_ref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_object_metadata_update(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+    update: ObjectMetadataUpdate,
+) -> Result<ObjectMetadata, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_key(&target, &bucket, &key).map_err(|e| e.to_string())?;
+
+    let non_empty = |value: Option<String>| {
+        value
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let headers = s3::ObjectHeaders {
+        content_type: non_empty(update.content_type),
+        cache_control: non_empty(update.cache_control),
+        content_disposition: non_empty(update.content_disposition),
+        content_encoding: non_empty(update.content_encoding),
+        metadata: update
+            .metadata
+            .into_iter()
+            .map(|(k, v)| (k.trim().to_ascii_lowercase(), v))
+            .filter(|(k, _)| !k.is_empty())
+            .collect(),
+        ..Default::default()
+    };
+
+    let result = s3::update_object_metadata(&target, &credentials, &bucket, &key, &headers)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_keys(&state.storage, audit::MANUAL, "updateMetadata", &target_id, &bucket, slice::from_ref(&key), &result);
+    result?;
+    info!("Updated metadata for {bucket}/{key}");
+
+    s3::head_object_details(&target, &credentials, &bucket, &key, None)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_object_tags_get(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+) -> Result<Vec<ObjectTag>, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    let tags = s3::get_object_tagging(&target, &credentials, &bucket, &key)
+        .await
+        .map_err(|e| e.to_string())?;
+    let _ = index_repo::replace_object_tags(
+        &state.storage,
+        &target_id,
+        &bucket,
+        &[(key, tags.clone())],
+    );
+    Ok(tags)
+}
+
+#[tauri::command]
+pub async fn target_object_tags_put(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+    tags: Vec<ObjectTag>,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::put_object_tagging(&target, &credentials, &bucket, &key, &tags)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_keys(&state.storage, audit::MANUAL, "putTags", &target_id, &bucket, slice::from_ref(&key), &result);
+    result?;
+    let _ = index_repo::replace_object_tags(&state.storage, &target_id, &bucket, &[(key, tags)]);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn target_object_tags_delete(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::delete_object_tagging(&target, &credentials, &bucket, &key)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_keys(&state.storage, audit::MANUAL, "deleteTags", &target_id, &bucket, slice::from_ref(&key), &result);
+    result?;
+    let _ = index_repo::replace_object_tags(&state.storage, &target_id, &bucket, &[(key, Vec::new())]);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn target_bucket_versioning_get(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<Option<String>, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    s3::get_bucket_versioning(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_object_versions_list(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    prefix: String,
+    max_keys: i32,
+    key_marker: Option<String>,
+    version_id_marker: Option<String>,
+) -> Result<ObjectVersionPage, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    s3::list_object_versions(
+        &target,
+        &credentials,
+        &bucket,
+        &prefix,
+        max_keys,
+        key_marker,
+        version_id_marker,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn target_object_version_restore(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+    version_id: String,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_key(&target, &bucket, &key).map_err(|e| e.to_string())?;
+    let result = s3::restore_object_version(&target, &credentials, &bucket, &key, &version_id)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_keys(&state.storage, audit::MANUAL, "restoreVersion", &target_id, &bucket, slice::from_ref(&key), &result);
+    result?;
+    info!("Restored {bucket}/{key} to version {version_id}");
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn target_object_undelete(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+) -> Result<(), String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let result = s3::undelete_object(&target, &credentials, &bucket, &key)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_keys(&state.storage, audit::MANUAL, "undelete", &target_id, &bucket, &[key], &result);
+    result
+}
+
+/// Permanently delete a single version. Unlike a plain delete this cannot be
+/// undone, so callers must pass `confirm` after asking the user.
+#[tauri::command]
+pub async fn target_object_version_delete(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+    version_id: String,
+    confirm: bool,
+) -> Result<(), String> {
+    if !confirm {
+        return Err("Permanently deleting a version cannot be undone and must be confirmed.".to_string());
+    }
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_key(&target, &bucket, &key).map_err(|e| e.to_string())?;
+    let result = s3::delete_object_version(&target, &credentials, &bucket, &key, &version_id)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_keys(&state.storage, audit::MANUAL, "deleteVersion", &target_id, &bucket, slice::from_ref(&key), &result);
+    result?;
+    info!("Permanently deleted version {version_id} of {bucket}/{key}");
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn bucket_stats_cache_list(
     state: State<'_, AppState>,
@@ -230,3 +588,81 @@ pub async fn bucket_stats_cache_upsert(
     bucket_stats_repo::upsert(&state.storage, &target_id, &bucket, object_count, total_size)
         .map_err(|e| e.to_string())
 }
+
+/// Ask S3 to restore an archived object for `days` and track the request
+/// until the restored copy is readable.
+#[tauri::command]
+pub async fn target_object_restore(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+    version_id: Option<String>,
+    days: i32,
+    tier: String,
+) -> Result<ObjectRestore, String> {
+    s3::validate_restore_request(days, &tier).map_err(|e| e.to_string())?;
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    let client = s3::build_client(&target, &credentials)
+        .await
+        .map_err(|e| e.to_string())?;
+    let stored_key = s3::ClientEncryption::for_target(&target, &credentials)
+        .map_err(|e| e.to_string())?
+        .encode_key(&key);
+
+    let status = s3::restore_status(&client, &bucket, &stored_key, version_id.as_deref())
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("{key} does not exist."))?;
+    if !s3::is_archived_class(status.storage_class.as_deref()) {
+        return Err(format!("{key} is not archived and can be downloaded directly."));
+    }
+    let result = s3::restore_object(&client, &bucket, &stored_key, version_id.as_deref(), days, &tier)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_keys(&state.storage, audit::MANUAL, "requestRestore", &target_id, &bucket, slice::from_ref(&key), &result);
+    result?;
+
+    let restore = ObjectRestore {
+        id: uuid::Uuid::now_v7().to_string(),
+        target_id,
+        bucket,
+        key,
+        version_id,
+        tier,
+        days,
+        status: "pending".to_string(),
+        expiry_date: None,
+        error_message: None,
+        requested_at: chrono::Utc::now().timestamp(),
+        last_checked_at: None,
+        completed_at: None,
+    };
+    restore_repo::record_request(&state.storage, &restore).map_err(|e| e.to_string())?;
+    info!("Requested {} restore of {}/{} for {days} days", restore.tier, restore.bucket, restore.key);
+    Ok(restore)
+}
+
+#[tauri::command]
+pub fn object_restores_list(state: State<'_, AppState>) -> Result<Vec<ObjectRestore>, String> {
+    restore_repo::list(&state.storage).map_err(|e| e.to_string())
+}
+
+/// Stop tracking a restore. The restore itself is not cancelled.
+#[tauri::command]
+pub fn object_restore_delete(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    restore_repo::delete(&state.storage, &id).map_err(|e| e.to_string())
+}
+
+/// Check pending restores now instead of waiting for the next poll.
+/// Returns how many completed.
+#[tauri::command]
+pub async fn object_restores_check(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+) -> Result<usize, String> {
+    restore_poller::check_pending(&app, &state.storage)
+        .await
+        .map_err(|e| e.to_string())
+}
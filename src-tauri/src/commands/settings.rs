@@ -1,6 +1,7 @@
 use tauri::State;
 
 use crate::app_state::AppState;
+use crate::core::bandwidth;
 use crate::core::storage::repositories::settings_repo;
 use crate::models::AppSettings;
 
@@ -14,5 +15,10 @@ pub fn settings_upsert(
     state: State<'_, AppState>,
     settings: AppSettings,
 ) -> Result<AppSettings, String> {
-    settings_repo::upsert(&state.storage, &settings).map_err(|e| e.to_string())
+    if settings.multipart_sweep_enabled && settings.multipart_sweep_days < 1 {
+        return Err("Multipart uploads must be at least 1 day old to be swept.".to_string());
+    }
+    let saved = settings_repo::upsert(&state.storage, &settings).map_err(|e| e.to_string())?;
+    bandwidth::apply_settings(&saved);
+    Ok(saved)
 }
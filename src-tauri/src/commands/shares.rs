@@ -0,0 +1,194 @@
+use tauri::State;
+use uuid::Uuid;
+
+use crate::app_state::AppState;
+use crate::commands::objects::resolve_target_and_credentials;
+use crate::core::s3;
+use crate::core::storage::repositories::share_links_repo;
+use crate::models::{PresignResponseOverrides, ShareLink, StorageTarget, TargetCredentials};
+
+/// Presign `key` and describe the result as a share link.
+async fn presign_link(
+    presigner: &s3::ObjectPresigner,
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    id: String,
+    bucket: String,
+    key: String,
+    version_id: Option<String>,
+    expires_in_secs: u64,
+    response: Option<PresignResponseOverrides>,
+    note: Option<String>,
+) -> Result<ShareLink, String> {
+    let url = presigner
+        .presign(&bucket, &key, version_id.as_deref(), expires_in_secs, response.as_ref())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let now = chrono::Utc::now().timestamp();
+    Ok(ShareLink {
+        id,
+        target_id: target.id.clone(),
+        bucket,
+        key,
+        version_id,
+        url,
+        expires_in_secs: expires_in_secs as i64,
+        response,
+        signing_key_id: credentials.access_key_id.clone(),
+        note: note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+        created_at: now,
+        expires_at: now + expires_in_secs as i64,
+    })
+}
+
+#[tauri::command]
+pub async fn share_link_create(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    key: String,
+    version_id: Option<String>,
+    expires_in_secs: u64,
+    response: Option<PresignResponseOverrides>,
+    note: Option<String>,
+) -> Result<ShareLink, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    let presigner = s3::ObjectPresigner::new(&target, &credentials, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let link = presign_link(
+        &presigner,
+        &target,
+        &credentials,
+        Uuid::now_v7().to_string(),
+        bucket,
+        key,
+        version_id,
+        expires_in_secs,
+        response,
+        note,
+    )
+    .await?;
+    share_links_repo::upsert(&state.storage, &link).map_err(|e| e.to_string())?;
+    Ok(link)
+}
+
+/// Share links, optionally only "active" or "expired" ones.
+#[tauri::command]
+pub fn share_links_list(
+    state: State<'_, AppState>,
+    target_id: Option<String>,
+    status: Option<String>,
+) -> Result<Vec<ShareLink>, String> {
+    let expired = match status.as_deref() {
+        None => None,
+        Some("active") => Some(false),
+        Some("expired") => Some(true),
+        Some(other) => return Err(format!("Unknown share link status: {other}")),
+    };
+    share_links_repo::list(&state.storage, target_id.as_deref(), expired).map_err(|e| e.to_string())
+}
+
+/// Presign a link again with its original parameters, with a fresh expiry.
+#[tauri::command]
+pub async fn share_link_regenerate(state: State<'_, AppState>, id: String) -> Result<ShareLink, String> {
+    let link = share_links_repo::get(&state.storage, &id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Share link not found.".to_string())?;
+    let (target, credentials) = resolve_target_and_credentials(&state, &link.target_id)?;
+    let presigner = s3::ObjectPresigner::new(&target, &credentials, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let regenerated = presign_link(
+        &presigner,
+        &target,
+        &credentials,
+        link.id,
+        link.bucket,
+        link.key,
+        link.version_id,
+        link.expires_in_secs.max(1) as u64,
+        link.response,
+        link.note,
+    )
+    .await?;
+    share_links_repo::upsert(&state.storage, &regenerated).map_err(|e| e.to_string())?;
+    Ok(regenerated)
+}
+
+/// Forget share links. The URLs themselves keep working until they expire.
+#[tauri::command]
+pub fn share_links_delete(state: State<'_, AppState>, ids: Vec<String>) -> Result<(), String> {
+    share_links_repo::delete_many(&state.storage, &ids).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn share_links_purge_expired(state: State<'_, AppState>) -> Result<usize, String> {
+    share_links_repo::delete_expired(&state.storage, chrono::Utc::now().timestamp())
+        .map_err(|e| e.to_string())
+}
+
+/// Create a share link for every object under `prefix` and write them to a
+/// CSV file at `dest_path`. Returns the number of links.
+#[tauri::command]
+pub async fn share_links_generate_prefix(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    prefix: String,
+    expires_in_secs: u64,
+    note: Option<String>,
+    dest_path: String,
+) -> Result<usize, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    let objects = s3::list_objects_recursive(&target, &credentials, &bucket, &prefix)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let presigner = s3::ObjectPresigner::new(&target, &credentials, None)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut links = Vec::with_capacity(objects.len());
+    for object in &objects {
+        let link = presign_link(
+            &presigner,
+            &target,
+            &credentials,
+            Uuid::now_v7().to_string(),
+            bucket.clone(),
+            object.key.clone(),
+            None,
+            expires_in_secs,
+            None,
+            note.clone(),
+        )
+        .await?;
+        links.push(link);
+    }
+    share_links_repo::upsert_many(&state.storage, &links).map_err(|e| e.to_string())?;
+
+    let mut csv = String::from("key,url,expires_at\n");
+    for link in &links {
+        let expires_at = chrono::DateTime::from_timestamp(link.expires_at, 0)
+            .map(|d| d.to_rfc3339())
+            .unwrap_or_default();
+        csv.push_str(&format!(
+            "{},{},{}\n",
+            csv_field(&link.key),
+            csv_field(&link.url),
+            expires_at
+        ));
+    }
+
+    std::fs::write(&dest_path, csv).map_err(|e| format!("Failed to write {dest_path}: {e}"))?;
+    Ok(objects.len())
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
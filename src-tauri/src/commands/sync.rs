@@ -1,7 +1,8 @@
 use tauri::State;
 
 use crate::app_state::AppState;
-use crate::core::storage::repositories::sync_profiles_repo;
+use crate::core::guard;
+use crate::core::storage::repositories::{sync_profiles_repo, targets_repo};
 use crate::models::SyncProfile;
 
 #[tauri::command]
@@ -11,6 +12,19 @@ pub fn sync_profiles_list(state: State<'_, AppState>) -> Result<Vec<SyncProfile>
 
 #[tauri::command]
 pub fn sync_profiles_upsert(state: State<'_, AppState>, profile: SyncProfile) -> Result<SyncProfile, String> {
+    let target = targets_repo::find_by_id(&state.storage, &profile.target_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Target not found.".to_string())?;
+    // Profiles that can delete or overwrite remote objects must stay out of
+    // protected prefixes
+    let destructive = profile.delete_policy == "propagate"
+        || matches!(profile.conflict_policy.as_str(), "newestMtimeWins" | "localAlwaysWins");
+    if destructive {
+        guard::check_prefix(&target, &profile.bucket, &profile.prefix)
+    } else {
+        guard::check_writable(&target)
+    }
+    .map_err(|e| e.to_string())?;
     sync_profiles_repo::upsert(&state.storage, profile).map_err(|e| e.to_string())
 }
 
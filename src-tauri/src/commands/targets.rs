@@ -1,8 +1,13 @@
 use tauri::State;
 
 use crate::app_state::AppState;
+use crate::core::audit;
+use crate::core::bandwidth;
+use crate::core::guard;
 use crate::core::s3;
-use crate::core::storage::repositories::{credentials_repo, targets_repo};
+use crate::core::storage::repositories::{
+    bucket_stats_repo, credentials_repo, index_repo, targets_repo,
+};
 use crate::models::{S3BucketSummary, S3ConnectionResult, StorageTarget, TargetCredentials};
 
 #[tauri::command]
@@ -11,13 +16,45 @@ pub fn targets_list(state: State<'_, AppState>) -> Result<Vec<StorageTarget>, St
 }
 
 #[tauri::command]
-pub fn targets_upsert(state: State<'_, AppState>, target: StorageTarget) -> Result<StorageTarget, String> {
-    targets_repo::upsert(&state.storage, target).map_err(|e| e.to_string())
+pub fn targets_upsert(state: State<'_, AppState>, mut target: StorageTarget) -> Result<StorageTarget, String> {
+    // The SSE-C key is checked when the credentials are saved
+    match target.encryption_mode.as_deref() {
+        None | Some("sseC") => {}
+        Some(mode) => {
+            s3::Encryption::from_settings(mode, target.encryption_kms_key_id.as_deref(), None)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    if target.trash_retention_days < 0 {
+        return Err("Trash retention can't be negative.".to_string());
+    }
+    target.protected_prefixes = target
+        .protected_prefixes
+        .iter()
+        .map(|p| p.trim().to_string())
+        .filter(|p| !p.is_empty())
+        .collect();
+    for pattern in &target.protected_prefixes {
+        guard::validate_pattern(pattern).map_err(|e| e.to_string())?;
+    }
+    let target_id = target.id.clone();
+    let saved = targets_repo::upsert(&state.storage, target).map_err(|e| e.to_string());
+    audit::record_target(&state.storage, "saveTarget", &target_id, &saved);
+    let saved = saved?;
+    bandwidth::set_target_limit(&saved.id, saved.bandwidth_limit_bytes_per_sec);
+    Ok(saved)
 }
 
 #[tauri::command]
 pub fn targets_delete(state: State<'_, AppState>, ids: Vec<String>) -> Result<(), String> {
-    targets_repo::delete_many(&state.storage, ids).map_err(|e| e.to_string())
+    for id in &ids {
+        bandwidth::set_target_limit(id, None);
+    }
+    let result = targets_repo::delete_many(&state.storage, ids.clone()).map_err(|e| e.to_string());
+    for id in &ids {
+        audit::record_target(&state.storage, "deleteTarget", id, &result);
+    }
+    result
 }
 
 #[tauri::command]
@@ -31,7 +68,22 @@ pub fn target_credentials_upsert(
     target_id: String,
     credentials: TargetCredentials,
 ) -> Result<(), String> {
-    credentials_repo::upsert(&state.storage, &target_id, &credentials).map_err(|e| e.to_string())
+    if let Some(key) = credentials.sse_customer_key.as_deref().filter(|k| !k.is_empty()) {
+        s3::Encryption::from_settings("sseC", None, Some(key)).map_err(|e| e.to_string())?;
+    }
+    if let Some(key) = credentials.client_encryption_key.as_deref().filter(|k| !k.is_empty()) {
+        s3::parse_client_encryption_key(key).map_err(|e| e.to_string())?;
+    }
+    let result = credentials_repo::upsert(&state.storage, &target_id, &credentials).map_err(|e| e.to_string());
+    audit::record_target(&state.storage, "saveCredentials", &target_id, &result);
+    result
+}
+
+/// A new random key for client-side encryption. Losing it makes the
+/// target's encrypted objects unreadable.
+#[tauri::command]
+pub fn target_client_encryption_key_generate() -> String {
+    s3::generate_client_encryption_key()
 }
 
 #[tauri::command]
@@ -55,6 +107,67 @@ pub async fn target_buckets_list(state: State<'_, AppState>, target_id: String)
     s3::list_buckets(&target, &credentials).await.map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn target_bucket_create(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    region: Option<String>,
+    object_lock: Option<bool>,
+) -> Result<(), String> {
+    let bucket = bucket.trim();
+    if bucket.is_empty() {
+        return Err("Bucket name is required.".to_string());
+    }
+
+    let target = targets_repo::find_by_id(&state.storage, &target_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Target not found.".to_string())?;
+    let credentials = credentials_repo::get(&state.storage, &target_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Credentials not found for target.".to_string())?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+
+    let result = s3::create_bucket(
+        &target,
+        &credentials,
+        bucket,
+        region.as_deref(),
+        object_lock.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| e.to_string());
+    audit::record_prefix(&state.storage, audit::MANUAL, "createBucket", &target_id, bucket, "", &result);
+    result
+}
+
+/// Delete an empty bucket. Use `batch_empty_bucket_start` for buckets that
+/// still hold objects.
+#[tauri::command]
+pub async fn target_bucket_delete(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<(), String> {
+    let target = targets_repo::find_by_id(&state.storage, &target_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Target not found.".to_string())?;
+    let credentials = credentials_repo::get(&state.storage, &target_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Credentials not found for target.".to_string())?;
+    guard::check_prefix(&target, &bucket, "").map_err(|e| e.to_string())?;
+
+    let result = s3::delete_bucket(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string());
+    audit::record_prefix(&state.storage, audit::MANUAL, "deleteBucket", &target_id, &bucket, "", &result);
+    result?;
+
+    let _ = index_repo::delete_index(&state.storage, &target_id, &bucket);
+    let _ = bucket_stats_repo::delete(&state.storage, &target_id, &bucket);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn target_connection_test(
     state: State<'_, AppState>,
@@ -0,0 +1,115 @@
+use tauri::State;
+
+use crate::app_state::AppState;
+use crate::commands::clone::{new_job, start_job};
+use crate::commands::objects::resolve_target_and_credentials;
+use crate::core::audit;
+use crate::core::guard;
+use crate::core::storage::repositories::index_repo;
+use crate::core::trash;
+use crate::models::{CloneJob, DeleteObjectsResult, TrashEntry, TrashRestoreResult};
+
+#[tauri::command]
+pub async fn trash_list(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<Vec<TrashEntry>, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    trash::list(&target, &credentials, &bucket)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn trash_restore(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+    trash_keys: Vec<String>,
+    overwrite: bool,
+) -> Result<TrashRestoreResult, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    guard::check_writable(&target).map_err(|e| e.to_string())?;
+    if overwrite {
+        let location = trash::location(&target, &bucket);
+        for (_, original_key) in trash_keys.iter().filter_map(|key| location.parse(key)) {
+            guard::check_key(&target, &bucket, &original_key).map_err(|e| e.to_string())?;
+        }
+    }
+    let result = trash::restore(&target, &credentials, &bucket, trash_keys, overwrite)
+        .await
+        .map_err(|e| e.to_string());
+    match &result {
+        Ok(r) => {
+            audit::record_batch(&state.storage, audit::MANUAL, "restoreFromTrash", &target_id, &bucket, &r.restored, &[]);
+            if let Err(error) = audit::outcome(&r.errors) {
+                audit::record_keys::<(), _>(&state.storage, audit::MANUAL, "restoreFromTrash", &target_id, &bucket, &[], &Err(error));
+            }
+        }
+        Err(_) => audit::record_keys(&state.storage, audit::MANUAL, "restoreFromTrash", &target_id, &bucket, &[], &result),
+    }
+    result
+}
+
+/// Delete everything in the trash of `bucket` for good.
+#[tauri::command]
+pub async fn trash_empty(
+    state: State<'_, AppState>,
+    target_id: String,
+    bucket: String,
+) -> Result<DeleteObjectsResult, String> {
+    let (target, credentials) = resolve_target_and_credentials(&state, &target_id)?;
+    let location = trash::location(&target, &bucket);
+    guard::check_prefix(&target, &location.bucket, &location.prefix).map_err(|e| e.to_string())?;
+    let result = trash::purge(&target, &credentials, &bucket, None)
+        .await
+        .map_err(|e| e.to_string());
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            audit::record_prefix::<(), _>(&state.storage, audit::MANUAL, "emptyTrash", &target_id, &location.bucket, &location.prefix, &Err(&e));
+            return Err(e);
+        }
+    };
+    audit::record_deletes(&state.storage, audit::MANUAL, "emptyTrash", &target_id, &location.bucket, &result);
+
+    if !result.deleted.is_empty() {
+        let _ = index_repo::remove_objects(&state.storage, &target_id, &location.bucket, &result.deleted);
+    }
+    Ok(result)
+}
+
+/// Move a folder into the trash. Runs as a move job, so each object is
+/// only deleted once its copy in the trash is verified.
+#[tauri::command]
+pub async fn trash_prefix_start(
+    state: State<'_, AppState>,
+    app: tauri::AppHandle,
+    target_id: String,
+    bucket: String,
+    prefix: String,
+) -> Result<CloneJob, String> {
+    if prefix.is_empty() || !prefix.ends_with('/') {
+        return Err("Only folders can be moved to the trash as a job.".to_string());
+    }
+    if trash::is_trashed(&prefix) {
+        return Err("This folder is already in the trash.".to_string());
+    }
+
+    let (target, _) = resolve_target_and_credentials(&state, &target_id)?;
+    let location = trash::location(&target, &bucket);
+    let dest_prefix = format!("{}{prefix}", location.new_folder());
+
+    let job = new_job(
+        target_id.clone(),
+        bucket,
+        prefix,
+        target_id,
+        location.bucket,
+        dest_prefix,
+        "overwrite".to_string(),
+        true,
+    );
+    start_job(&state, app, job).await
+}
@@ -0,0 +1,143 @@
+use std::fmt::Display;
+
+use uuid::Uuid;
+
+use crate::core::storage::repositories::audit_repo;
+use crate::core::storage::sqlite::SqliteStorage;
+use crate::models::{AuditEntry, DeleteObjectsResult};
+
+pub const MANUAL: &str = "manual";
+pub const CLONE: &str = "clone";
+pub const SCHEDULE: &str = "schedule";
+
+const SUCCESS: &str = "success";
+const FAILURE: &str = "failure";
+
+/// The initiator a caller named, or `MANUAL` for none or an unknown one.
+pub fn initiator(name: Option<&str>) -> &'static str {
+    match name {
+        Some(CLONE) => CLONE,
+        Some(SCHEDULE) => SCHEDULE,
+        _ => MANUAL,
+    }
+}
+
+/// Record a write to `keys` in `bucket`.
+pub fn record_keys<T, E: Display>(
+    storage: &SqliteStorage,
+    initiator: &str,
+    operation: &str,
+    target_id: &str,
+    bucket: &str,
+    keys: &[String],
+    result: &Result<T, E>,
+) {
+    let mut entry = new_entry(initiator, operation, target_id, Some(bucket), result);
+    entry.keys = keys.to_vec();
+    insert(storage, entry);
+}
+
+/// Record a write to everything under `prefix`. An empty prefix stands
+/// for the bucket itself.
+pub fn record_prefix<T, E: Display>(
+    storage: &SqliteStorage,
+    initiator: &str,
+    operation: &str,
+    target_id: &str,
+    bucket: &str,
+    prefix: &str,
+    result: &Result<T, E>,
+) {
+    let mut entry = new_entry(initiator, operation, target_id, Some(bucket), result);
+    entry.prefix = Some(prefix.to_string());
+    insert(storage, entry);
+}
+
+/// Record a change to a target or its credentials.
+pub fn record_target<T, E: Display>(storage: &SqliteStorage, operation: &str, target_id: &str, result: &Result<T, E>) {
+    insert(storage, new_entry(MANUAL, operation, target_id, None, result));
+}
+
+/// Record a bulk write that succeeded for some keys and failed for others,
+/// as one entry for each outcome. Failures keep the first error.
+pub fn record_batch(
+    storage: &SqliteStorage,
+    initiator: &str,
+    operation: &str,
+    target_id: &str,
+    bucket: &str,
+    succeeded: &[String],
+    failed: &[(String, String)],
+) {
+    if !succeeded.is_empty() {
+        record_keys::<(), String>(storage, initiator, operation, target_id, bucket, succeeded, &Ok(()));
+    }
+    if let Some((_, error)) = failed.first() {
+        let keys: Vec<String> = failed.iter().map(|(key, _)| key.clone()).collect();
+        record_keys::<(), _>(storage, initiator, operation, target_id, bucket, &keys, &Err(error));
+    }
+}
+
+/// Record a bulk delete from what S3 reported for each key.
+pub fn record_deletes(
+    storage: &SqliteStorage,
+    initiator: &str,
+    operation: &str,
+    target_id: &str,
+    bucket: &str,
+    result: &DeleteObjectsResult,
+) {
+    let failed: Vec<(String, String)> = result
+        .failed
+        .iter()
+        .map(|f| {
+            let message = f.message.clone().or_else(|| f.code.clone()).unwrap_or_else(|| "Delete failed".to_string());
+            (f.key.clone(), message)
+        })
+        .collect();
+    record_batch(storage, initiator, operation, target_id, bucket, &result.deleted, &failed);
+}
+
+/// A failure carrying the first of `errors`, if there are any.
+pub fn outcome(errors: &[String]) -> Result<(), &str> {
+    match errors.first() {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+fn new_entry<T, E: Display>(
+    initiator: &str,
+    operation: &str,
+    target_id: &str,
+    bucket: Option<&str>,
+    result: &Result<T, E>,
+) -> AuditEntry {
+    AuditEntry {
+        id: Uuid::now_v7().to_string(),
+        timestamp: now_epoch(),
+        initiator: initiator.to_string(),
+        operation: operation.to_string(),
+        target_id: target_id.to_string(),
+        bucket: bucket.map(str::to_string),
+        keys: Vec::new(),
+        prefix: None,
+        result: if result.is_ok() { SUCCESS } else { FAILURE }.to_string(),
+        error: result.as_ref().err().map(|e| e.to_string()),
+    }
+}
+
+// The write has already happened, so a failure to record it is only logged
+fn insert(storage: &SqliteStorage, entry: AuditEntry) {
+    if let Err(e) = audit_repo::insert(storage, &entry) {
+        log::error!("Failed to record {} in the audit log: {e}", entry.operation);
+    }
+}
+
+fn now_epoch() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+use chrono::{Local, Timelike};
+
+use crate::models::AppSettings;
+
+/// Longest single sleep while waiting for tokens, so that cap changes made
+/// while a transfer is running take effect promptly.
+const MAX_WAIT: Duration = Duration::from_millis(250);
+
+/// Token-bucket limiter. A rate of 0 means unlimited. The rate is read on
+/// every acquire, so it can be changed while transfers are in progress.
+struct RateLimiter {
+    bytes_per_sec: AtomicU64,
+    bucket: Mutex<TokenBucket>,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    fn new(bytes_per_sec: u64) -> Self {
+        Self {
+            bytes_per_sec: AtomicU64::new(bytes_per_sec),
+            bucket: Mutex::new(TokenBucket {
+                tokens: bytes_per_sec as f64,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    fn set_rate(&self, bytes_per_sec: u64) {
+        self.bytes_per_sec.store(bytes_per_sec, Ordering::Relaxed);
+    }
+
+    async fn acquire(&self, bytes: u64) {
+        loop {
+            let rate = self.bytes_per_sec.load(Ordering::Relaxed);
+            if rate == 0 || !schedule_active() {
+                return;
+            }
+
+            let wait = {
+                let Ok(mut bucket) = self.bucket.lock() else {
+                    return;
+                };
+                let now = Instant::now();
+                let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+                bucket.last_refill = now;
+
+                // At most one second worth of burst
+                let capacity = rate as f64;
+                bucket.tokens = (bucket.tokens + elapsed * capacity).min(capacity);
+
+                // Chunks larger than the bucket are let through once it is
+                // full and paid back as debt on the next acquire
+                let needed = (bytes as f64).min(capacity);
+                if bucket.tokens >= needed {
+                    bucket.tokens -= bytes as f64;
+                    return;
+                }
+                Duration::from_secs_f64((needed - bucket.tokens) / capacity)
+            };
+
+            tokio::time::sleep(wait.min(MAX_WAIT)).await;
+        }
+    }
+}
+
+/// Time-of-day window (local time, minutes since midnight) during which
+/// caps are enforced. Windows may wrap past midnight; one that starts and
+/// ends at the same minute lasts all day.
+#[derive(Clone, Copy)]
+struct Schedule {
+    enabled: bool,
+    start_minute: u32,
+    end_minute: u32,
+}
+
+impl Schedule {
+    fn contains(&self, minute: u32) -> bool {
+        if self.start_minute == self.end_minute {
+            true
+        } else if self.start_minute < self.end_minute {
+            minute >= self.start_minute && minute < self.end_minute
+        } else {
+            minute >= self.start_minute || minute < self.end_minute
+        }
+    }
+}
+
+struct Bandwidth {
+    global: RateLimiter,
+    targets: RwLock<HashMap<String, Arc<RateLimiter>>>,
+    schedule: RwLock<Schedule>,
+}
+
+fn registry() -> &'static Bandwidth {
+    static REGISTRY: OnceLock<Bandwidth> = OnceLock::new();
+    REGISTRY.get_or_init(|| Bandwidth {
+        global: RateLimiter::new(0),
+        targets: RwLock::new(HashMap::new()),
+        schedule: RwLock::new(Schedule {
+            enabled: false,
+            start_minute: 0,
+            end_minute: 0,
+        }),
+    })
+}
+
+fn schedule_active() -> bool {
+    let schedule = match registry().schedule.read() {
+        Ok(schedule) => *schedule,
+        Err(_) => return true,
+    };
+    if !schedule.enabled {
+        return true;
+    }
+    let now = Local::now();
+    schedule.contains(now.hour() * 60 + now.minute())
+}
+
+/// Apply the global cap and schedule from app settings.
+pub fn apply_settings(settings: &AppSettings) {
+    let registry = registry();
+    registry
+        .global
+        .set_rate(settings.bandwidth_limit_bytes_per_sec.max(0) as u64);
+    if let Ok(mut schedule) = registry.schedule.write() {
+        *schedule = Schedule {
+            enabled: settings.bandwidth_schedule_enabled,
+            start_minute: settings.bandwidth_schedule_start_minute.clamp(0, 1439) as u32,
+            end_minute: settings.bandwidth_schedule_end_minute.clamp(0, 1439) as u32,
+        };
+    }
+}
+
+/// Set (or clear, with `None` or 0) the cap for a single target.
+pub fn set_target_limit(target_id: &str, bytes_per_sec: Option<i64>) {
+    let rate = bytes_per_sec.unwrap_or(0).max(0) as u64;
+    let Ok(mut targets) = registry().targets.write() else {
+        return;
+    };
+    if rate == 0 {
+        targets.remove(target_id);
+    } else if let Some(limiter) = targets.get(target_id) {
+        limiter.set_rate(rate);
+    } else {
+        targets.insert(target_id.to_string(), Arc::new(RateLimiter::new(rate)));
+    }
+}
+
+/// Wait until `bytes` may be transferred under the global cap and the caps
+/// of every target involved in the transfer.
+pub async fn throttle(target_ids: &[&str], bytes: u64) {
+    let registry = registry();
+    registry.global.acquire(bytes).await;
+
+    for target_id in target_ids {
+        let limiter = registry
+            .targets
+            .read()
+            .ok()
+            .and_then(|targets| targets.get(*target_id).cloned());
+        if let Some(limiter) = limiter {
+            limiter.acquire(bytes).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(start_minute: u32, end_minute: u32) -> Schedule {
+        Schedule {
+            enabled: true,
+            start_minute,
+            end_minute,
+        }
+    }
+
+    #[test]
+    fn window_within_a_day() {
+        let schedule = window(9 * 60, 18 * 60);
+        assert!(schedule.contains(9 * 60));
+        assert!(schedule.contains(17 * 60 + 59));
+        assert!(!schedule.contains(18 * 60));
+        assert!(!schedule.contains(0));
+    }
+
+    #[test]
+    fn window_wrapping_past_midnight() {
+        let schedule = window(22 * 60, 6 * 60);
+        assert!(schedule.contains(23 * 60));
+        assert!(schedule.contains(0));
+        assert!(!schedule.contains(6 * 60));
+        assert!(!schedule.contains(12 * 60));
+    }
+
+    #[test]
+    fn window_starting_where_it_ends_lasts_all_day() {
+        let schedule = window(8 * 60, 8 * 60);
+        assert!(schedule.contains(0));
+        assert!(schedule.contains(8 * 60));
+        assert!(schedule.contains(1439));
+    }
+}
@@ -0,0 +1,1269 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::error::ProvideErrorMetadata;
+use aws_sdk_s3::types::StorageClass;
+use aws_sdk_s3::Client;
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{watch, Semaphore};
+use uuid::Uuid;
+
+use crate::core::audit;
+use crate::core::guard;
+use crate::core::s3;
+use crate::core::storage::repositories::{
+    batch_repo, bucket_stats_repo, credentials_repo, index_repo, restore_repo, settings_repo,
+    targets_repo,
+};
+use crate::core::storage::sqlite::SqliteStorage;
+use crate::core::upload_plan;
+use crate::models::{
+    BatchJob, BatchJobItem, BatchProgressEvent, DeletePrefixParams, EmptyBucketParams,
+    ObjectRestore, ObjectTag, RestoreJobParams, StorageClassJobParams, StorageTarget, TagJobParams,
+    TargetCredentials, UploadDirectoryParams, UploadPlanFile,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BatchSignal {
+    Run,
+    Pause,
+    Cancel,
+}
+
+pub const KIND_TAG: &str = "tag";
+pub const KIND_EMPTY_BUCKET: &str = "emptyBucket";
+pub const KIND_STORAGE_CLASS: &str = "storageClass";
+pub const KIND_RESTORE: &str = "restore";
+pub const KIND_DELETE_PREFIX: &str = "deletePrefix";
+pub const KIND_UPLOAD_DIRECTORY: &str = "uploadDirectory";
+
+pub const SYMLINKS_SKIP: &str = "skip";
+pub const SYMLINKS_FOLLOW: &str = "follow";
+
+const CONCURRENCY: usize = 16;
+const BATCH_SIZE: i64 = 200;
+// DeleteObjects takes up to 1000 keys per request
+const DELETE_BATCH_SIZE: i64 = 1000;
+// Walked files are inserted this many at a time
+const WALK_CHUNK_SIZE: usize = 1000;
+const PROGRESS_THROTTLE_MS: u128 = 200;
+
+/// What a job does to each of its items, decoded from the job's kind and
+/// params once per run.
+enum Action {
+    Tag(TagJobParams),
+    EmptyBucket(EmptyBucketParams),
+    StorageClass(StorageClassJobParams),
+    Restore(RestoreJobParams),
+    DeletePrefix(DeletePrefixParams),
+    UploadDirectory(UploadDirectoryParams),
+}
+
+impl Action {
+    fn from_job(job: &BatchJob) -> Result<Self> {
+        match job.kind.as_str() {
+            KIND_TAG => {
+                let params: TagJobParams = serde_json::from_value(job.params.clone())
+                    .map_err(|e| anyhow!("Invalid tag job params: {e}"))?;
+                Ok(Action::Tag(params))
+            }
+            KIND_EMPTY_BUCKET => {
+                let params: EmptyBucketParams = serde_json::from_value(job.params.clone())
+                    .map_err(|e| anyhow!("Invalid empty bucket job params: {e}"))?;
+                Ok(Action::EmptyBucket(params))
+            }
+            KIND_STORAGE_CLASS => {
+                let params: StorageClassJobParams = serde_json::from_value(job.params.clone())
+                    .map_err(|e| anyhow!("Invalid storage class job params: {e}"))?;
+                Ok(Action::StorageClass(params))
+            }
+            KIND_RESTORE => {
+                let params: RestoreJobParams = serde_json::from_value(job.params.clone())
+                    .map_err(|e| anyhow!("Invalid restore job params: {e}"))?;
+                Ok(Action::Restore(params))
+            }
+            KIND_DELETE_PREFIX => {
+                let params: DeletePrefixParams = serde_json::from_value(job.params.clone())
+                    .map_err(|e| anyhow!("Invalid delete prefix job params: {e}"))?;
+                Ok(Action::DeletePrefix(params))
+            }
+            KIND_UPLOAD_DIRECTORY => {
+                let params: UploadDirectoryParams = serde_json::from_value(job.params.clone())
+                    .map_err(|e| anyhow!("Invalid upload directory job params: {e}"))?;
+                Ok(Action::UploadDirectory(params))
+            }
+            other => Err(anyhow!("Unknown batch job kind: {other}")),
+        }
+    }
+
+    fn batch_size(&self) -> i64 {
+        match self {
+            Action::EmptyBucket(_) | Action::DeletePrefix(_) => DELETE_BATCH_SIZE,
+            _ => BATCH_SIZE,
+        }
+    }
+}
+
+/// Where enumeration of an `emptyBucket` or `deletePrefix` job left off,
+/// stored as JSON in the job's enumeration token. Versions are listed
+/// first, then incomplete multipart uploads.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EmptyBucketCursor {
+    uploads: bool,
+    /// The provider has no ListObjectVersions, so plain listing is used.
+    unversioned: bool,
+    marker: Option<String>,
+    secondary_marker: Option<String>,
+}
+
+enum ItemOutcome {
+    Completed,
+    Skipped,
+}
+
+pub async fn run_batch_job(
+    app: AppHandle,
+    storage: Arc<SqliteStorage>,
+    mut signal_rx: watch::Receiver<BatchSignal>,
+    job_id: String,
+) {
+    let result = run_batch_job_inner(&app, &storage, &mut signal_rx, &job_id).await;
+
+    if let Err(e) = result {
+        log::error!("Batch job {} failed: {}", job_id, e);
+        let _ = batch_repo::complete_job(&storage, &job_id, "failed");
+        let _ = app.emit(
+            "batch-status-change",
+            serde_json::json!({"jobId": job_id, "status": "failed", "error": e.to_string()}),
+        );
+    }
+}
+
+async fn run_batch_job_inner(
+    app: &AppHandle,
+    storage: &Arc<SqliteStorage>,
+    signal_rx: &mut watch::Receiver<BatchSignal>,
+    job_id: &str,
+) -> Result<()> {
+    let job = batch_repo::get_job(storage, job_id)?
+        .ok_or_else(|| anyhow!("Batch job not found: {job_id}"))?;
+
+    let target = targets_repo::find_by_id(storage, &job.target_id)?
+        .ok_or_else(|| anyhow!("Target not found"))?;
+    let creds = credentials_repo::get(storage, &job.target_id)?
+        .ok_or_else(|| anyhow!("Target credentials not found"))?;
+    let client = s3::build_client(&target, &creds).await?;
+    let cse = s3::ClientEncryption::for_target(&target, &creds)?;
+    let action = Arc::new(Action::from_job(&job)?);
+
+    // Phase 1: Enumeration (if not complete)
+    if !job.enumeration_complete {
+        if check_signal(signal_rx, storage, app, job_id).await? {
+            return Ok(());
+        }
+        batch_repo::update_job_status(storage, job_id, "enumerating")?;
+        emit_status_change(app, job_id, "enumerating");
+
+        match action.as_ref() {
+            Action::Tag(_) | Action::StorageClass(_) | Action::Restore(_) => {
+                enumerate_objects(app, storage, signal_rx, &client, &job).await?
+            }
+            Action::EmptyBucket(_) => {
+                let start = EmptyBucketCursor::default();
+                enumerate_bucket_contents(app, storage, signal_rx, &client, &job, start, true).await?
+            }
+            Action::DeletePrefix(params) => {
+                // Plain listing keeps folder markers, which have to go too
+                let start = EmptyBucketCursor {
+                    unversioned: !params.all_versions,
+                    ..Default::default()
+                };
+                enumerate_bucket_contents(app, storage, signal_rx, &client, &job, start, false).await?
+            }
+            Action::UploadDirectory(params) => {
+                enumerate_directory(app, storage, signal_rx, &job, &cse, params).await?
+            }
+        }
+    }
+
+    // Phase 2: Execution
+    if check_signal(signal_rx, storage, app, job_id).await? {
+        return Ok(());
+    }
+    batch_repo::update_job_status(storage, job_id, "running")?;
+    emit_status_change(app, job_id, "running");
+
+    let mut last_progress_emit = Instant::now();
+
+    loop {
+        if check_signal(signal_rx, storage, app, job_id).await? {
+            return Ok(());
+        }
+
+        let batch = batch_repo::list_pending_items(storage, job_id, action.batch_size())?;
+        if batch.is_empty() {
+            break;
+        }
+
+        for item in &batch {
+            let _ = batch_repo::update_item_status(storage, &item.id, "active", None);
+        }
+
+        let batch_item_ids: Vec<String> = batch.iter().map(|item| item.id.clone()).collect();
+        let batch_keys: HashMap<String, String> =
+            batch.iter().map(|item| (item.id.clone(), item.key.clone())).collect();
+
+        // Object versions go out in bulk DeleteObjects requests and local
+        // files are uploaded at the user's upload concurrency; everything
+        // else is processed item by item
+        let mut results = Vec::new();
+        let single = match action.as_ref() {
+            Action::EmptyBucket(params) => {
+                let (versions, uploads): (Vec<_>, Vec<_>) =
+                    batch.into_iter().partition(|item| item.upload_id.is_none());
+                results = delete_items(&client, &job.bucket, versions, params.bypass_governance).await;
+                uploads
+            }
+            Action::DeletePrefix(params) => {
+                results = delete_items(&client, &job.bucket, batch, params.bypass_governance).await;
+                Vec::new()
+            }
+            Action::UploadDirectory(params) => {
+                results = upload_items(storage, &target, &creds, &job, &cse, params, batch).await?;
+                Vec::new()
+            }
+            _ => batch,
+        };
+
+        if let Action::DeletePrefix(params) = action.as_ref() {
+            remove_deleted_from_index(storage, &job, &cse, params.all_versions, &batch_keys, &results);
+        }
+        results.extend(process_items(&client, storage, &job, &cse, &action, single).await?);
+        audit_batch(storage, &job, &cse, &batch_keys, &results);
+
+        let mut processed_ids = HashSet::new();
+        for (item_id, result) in results {
+            processed_ids.insert(item_id.clone());
+            let _ = match result {
+                Ok(ItemOutcome::Completed) => {
+                    batch_repo::update_item_status(storage, &item_id, "completed", None)
+                }
+                Ok(ItemOutcome::Skipped) => {
+                    batch_repo::update_item_status(storage, &item_id, "skipped", None)
+                }
+                Err(e) => {
+                    batch_repo::update_item_status(storage, &item_id, "failed", Some(&e.to_string()))
+                }
+            };
+        }
+
+        // Safety net: mark any items that weren't processed (e.g., task panic) as failed
+        for id in &batch_item_ids {
+            if !processed_ids.contains(id) {
+                let _ = batch_repo::update_item_status(
+                    storage,
+                    id,
+                    "failed",
+                    Some("Task failed unexpectedly"),
+                );
+            }
+        }
+
+        let counts = batch_repo::count_items_by_status(storage, job_id)?;
+        let _ = batch_repo::update_job_progress(
+            storage,
+            job_id,
+            counts.completed,
+            counts.failed,
+            counts.skipped,
+        );
+
+        if last_progress_emit.elapsed().as_millis() >= PROGRESS_THROTTLE_MS {
+            if let Ok(Some(job)) = batch_repo::get_job(storage, job_id) {
+                emit_progress(app, &job);
+            }
+            last_progress_emit = Instant::now();
+        }
+    }
+
+    let counts = batch_repo::count_items_by_status(storage, job_id)?;
+    let _ = batch_repo::update_job_progress(
+        storage,
+        job_id,
+        counts.completed,
+        counts.failed,
+        counts.skipped,
+    );
+
+    if let Action::EmptyBucket(params) = action.as_ref() {
+        if counts.pending == 0 {
+            finish_empty_bucket(storage, &client, &job, params, counts.failed).await?;
+        }
+    }
+
+    let final_status = if counts.pending == 0 { "completed" } else { "failed" };
+    batch_repo::complete_job(storage, job_id, final_status)?;
+    emit_status_change(app, job_id, final_status);
+
+    if let Ok(Some(job)) = batch_repo::get_job(storage, job_id) {
+        emit_progress(app, &job);
+    }
+
+    Ok(())
+}
+
+/// Run `items` through `process_item` concurrently, returning each item's
+/// id with its outcome.
+async fn process_items(
+    client: &Client,
+    storage: &Arc<SqliteStorage>,
+    job: &BatchJob,
+    cse: &s3::ClientEncryption,
+    action: &Arc<Action>,
+    items: Vec<BatchJobItem>,
+) -> Result<Vec<(String, Result<ItemOutcome>)>> {
+    let semaphore = Arc::new(Semaphore::new(CONCURRENCY));
+    let mut handles = Vec::new();
+
+    for item in items {
+        let permit = semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| anyhow!("Semaphore error: {e}"))?;
+
+        let client = client.clone();
+        let storage = storage.clone();
+        let job = job.clone();
+        let cse = cse.clone();
+        let action = action.clone();
+
+        handles.push(tokio::spawn(async move {
+            let result = process_item(&client, &storage, &job, &cse, &action, &item).await;
+            drop(permit);
+            (item.id, result)
+        }));
+    }
+
+    let mut results = Vec::new();
+    for handle in handles {
+        match handle.await {
+            Ok(result) => results.push(result),
+            Err(e) => log::error!("Batch task join error: {e}"),
+        }
+    }
+    Ok(results)
+}
+
+/// Delete object versions with DeleteObjects. A failed request fails
+/// every item in it; otherwise only the entries S3 reports as errors.
+async fn delete_items(
+    client: &Client,
+    bucket: &str,
+    items: Vec<BatchJobItem>,
+    bypass_governance: bool,
+) -> Vec<(String, Result<ItemOutcome>)> {
+    let mut results = Vec::new();
+
+    for chunk in items.chunks(DELETE_BATCH_SIZE as usize) {
+        let objects: Vec<(String, Option<String>)> = chunk
+            .iter()
+            .map(|item| (item.key.clone(), item.version_id.clone()))
+            .collect();
+
+        match s3::delete_object_batch(client, bucket, &objects, bypass_governance).await {
+            Ok(errors) => {
+                let errors: HashMap<(String, Option<String>), String> = errors
+                    .into_iter()
+                    .filter(|e| !matches!(e.code.as_deref(), Some("NoSuchKey" | "NoSuchVersion")))
+                    .map(|e| {
+                        let message = match (e.code, e.message) {
+                            (Some(code), Some(message)) => format!("{code}: {message}"),
+                            (code, message) => code.or(message).unwrap_or_else(|| "Delete failed".to_string()),
+                        };
+                        ((e.key, e.version_id), message)
+                    })
+                    .collect();
+
+                for item in chunk {
+                    let result = match errors.get(&(item.key.clone(), item.version_id.clone())) {
+                        Some(message) => Err(anyhow!("{message}")),
+                        None => Ok(ItemOutcome::Completed),
+                    };
+                    results.push((item.id.clone(), result));
+                }
+            }
+            Err(e) => {
+                let message = e.to_string();
+                for item in chunk {
+                    results.push((item.id.clone(), Err(anyhow!("{message}"))));
+                }
+            }
+        }
+    }
+
+    results
+}
+
+/// List every object under the job's prefix into pending items,
+/// checkpointing the continuation token along with each page.
+async fn enumerate_objects(
+    app: &AppHandle,
+    storage: &Arc<SqliteStorage>,
+    signal_rx: &mut watch::Receiver<BatchSignal>,
+    client: &Client,
+    job: &BatchJob,
+) -> Result<()> {
+    let mut continuation_token = job.enumeration_token.clone();
+    let mut total_items = job.total_items;
+
+    loop {
+        if check_signal(signal_rx, storage, app, &job.id).await? {
+            return Ok(());
+        }
+
+        let mut req = client
+            .list_objects_v2()
+            .bucket(&job.bucket)
+            .prefix(&job.prefix);
+        if let Some(token) = &continuation_token {
+            req = req.continuation_token(token);
+        }
+
+        let output = req
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 list objects failed during enumeration: {e}"))?;
+
+        let now = now_epoch();
+        let items: Vec<BatchJobItem> = output
+            .contents
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|obj| {
+                let key = obj.key.filter(|k| !k.is_empty() && !k.ends_with('/'))?;
+                Some(new_item(&job.id, key, None, obj.size.unwrap_or(0), now))
+            })
+            .collect();
+
+        total_items += items.len() as i64;
+        continuation_token = output
+            .next_continuation_token
+            .filter(|_| output.is_truncated == Some(true));
+        let complete = continuation_token.is_none();
+        batch_repo::insert_enumerated_items(
+            storage,
+            &job.id,
+            &items,
+            continuation_token.as_deref(),
+            total_items,
+            complete,
+        )?;
+        if complete {
+            return Ok(());
+        }
+
+        if let Ok(Some(job)) = batch_repo::get_job(storage, &job.id) {
+            emit_progress(app, &job);
+        }
+    }
+}
+
+/// List every version and delete marker under the job's prefix, then
+/// every incomplete multipart upload if `include_uploads` is set, into
+/// pending items. Enumeration begins at `start` unless the job has a
+/// cursor checkpointed from an earlier run.
+async fn enumerate_bucket_contents(
+    app: &AppHandle,
+    storage: &Arc<SqliteStorage>,
+    signal_rx: &mut watch::Receiver<BatchSignal>,
+    client: &Client,
+    job: &BatchJob,
+    start: EmptyBucketCursor,
+    include_uploads: bool,
+) -> Result<()> {
+    let mut cursor: EmptyBucketCursor = job
+        .enumeration_token
+        .as_deref()
+        .and_then(|token| serde_json::from_str(token).ok())
+        .unwrap_or(start);
+    let mut total_items = job.total_items;
+
+    loop {
+        if check_signal(signal_rx, storage, app, &job.id).await? {
+            return Ok(());
+        }
+
+        let (items, next) = if cursor.uploads {
+            list_uploads_page(client, job, &cursor).await?
+        } else if cursor.unversioned {
+            list_objects_page(client, job, &cursor).await?
+        } else {
+            match list_versions_page(client, job, &cursor).await? {
+                Some(page) => page,
+                None => {
+                    log::warn!("ListObjectVersions not supported, emptying {} without versions", job.bucket);
+                    cursor.unversioned = true;
+                    continue;
+                }
+            }
+        };
+
+        total_items += items.len() as i64;
+        let next = next.or_else(|| {
+            (include_uploads && !cursor.uploads).then(|| EmptyBucketCursor {
+                uploads: true,
+                ..Default::default()
+            })
+        });
+        let Some(next) = next else {
+            batch_repo::insert_enumerated_items(storage, &job.id, &items, None, total_items, true)?;
+            return Ok(());
+        };
+        cursor = next;
+        let token = serde_json::to_string(&cursor)?;
+        batch_repo::insert_enumerated_items(storage, &job.id, &items, Some(&token), total_items, false)?;
+
+        if let Ok(Some(job)) = batch_repo::get_job(storage, &job.id) {
+            emit_progress(app, &job);
+        }
+    }
+}
+
+/// Walk the job's local folder into pending items, one for each file that
+/// passes its filters. Files the walk could not read become failed items.
+/// A walk is not resumable, so one cut short starts over.
+async fn enumerate_directory(
+    app: &AppHandle,
+    storage: &Arc<SqliteStorage>,
+    signal_rx: &mut watch::Receiver<BatchSignal>,
+    job: &BatchJob,
+    cse: &s3::ClientEncryption,
+    params: &UploadDirectoryParams,
+) -> Result<()> {
+    batch_repo::delete_items(storage, &job.id)?;
+    let walk_params = params.clone();
+    let files = tokio::task::spawn_blocking(move || walk_directory(&walk_params))
+        .await
+        .map_err(|e| anyhow!("Directory walk failed: {e}"))??;
+
+    let mut total_items = 0;
+    for chunk in files.chunks(WALK_CHUNK_SIZE) {
+        if check_signal(signal_rx, storage, app, &job.id).await? {
+            return Ok(());
+        }
+
+        let now = now_epoch();
+        let items: Vec<BatchJobItem> = chunk
+            .iter()
+            .map(|file| {
+                let key = cse.encode_key(&format!("{}{}", params.dest_prefix, file.relative_path));
+                let item = new_item(&job.id, key, None, file.size, now);
+                match &file.error {
+                    Some(error) => BatchJobItem {
+                        status: "failed".to_string(),
+                        error_message: Some(error.clone()),
+                        ..item
+                    },
+                    None => item,
+                }
+            })
+            .collect();
+
+        total_items += items.len() as i64;
+        batch_repo::insert_enumerated_items(storage, &job.id, &items, None, total_items, false)?;
+
+        if let Ok(Some(job)) = batch_repo::get_job(storage, &job.id) {
+            emit_progress(app, &job);
+        }
+    }
+
+    batch_repo::save_enumeration_state(storage, &job.id, None, total_items, true)?;
+    Ok(())
+}
+
+/// A file found by `walk_directory`.
+pub struct WalkedFile {
+    /// Path below the root, `/`-separated.
+    pub relative_path: String,
+    pub size: i64,
+    /// Why the file could not be read, if it couldn't.
+    pub error: Option<String>,
+}
+
+/// Check the parameters of an `uploadDirectory` job before it starts.
+pub fn validate_upload_params(params: &UploadDirectoryParams) -> Result<()> {
+    if !Path::new(&params.root).is_dir() {
+        return Err(anyhow!("{} is not a directory", params.root));
+    }
+    if params.symlinks != SYMLINKS_SKIP && params.symlinks != SYMLINKS_FOLLOW {
+        return Err(anyhow!("Unknown symlink policy: {}", params.symlinks));
+    }
+    build_overrides(params).map(|_| ())
+}
+
+/// Include globs as whitelist overrides and exclude globs as negated ones.
+fn build_overrides(params: &UploadDirectoryParams) -> Result<Override> {
+    let mut overrides = OverrideBuilder::new(&params.root);
+    for glob in &params.include {
+        overrides
+            .add(glob)
+            .map_err(|e| anyhow!("Invalid include pattern \"{glob}\": {e}"))?;
+    }
+    for glob in &params.exclude {
+        overrides
+            .add(&format!("!{glob}"))
+            .map_err(|e| anyhow!("Invalid exclude pattern \"{glob}\": {e}"))?;
+    }
+    overrides.build().map_err(|e| anyhow!("Invalid file patterns: {e}"))
+}
+
+/// The files under `params.root` that pass its filters, in name order.
+pub fn walk_directory(params: &UploadDirectoryParams) -> Result<Vec<WalkedFile>> {
+    let root = Path::new(&params.root);
+    if !root.is_dir() {
+        return Err(anyhow!("{} is not a directory", params.root));
+    }
+    let overrides = build_overrides(params)?;
+
+    let walker = WalkBuilder::new(root)
+        .standard_filters(false)
+        .hidden(!params.show_hidden)
+        .parents(params.respect_ignore_files)
+        .ignore(params.respect_ignore_files)
+        .git_ignore(params.respect_ignore_files)
+        .git_exclude(params.respect_ignore_files)
+        .require_git(false)
+        .follow_links(params.symlinks == SYMLINKS_FOLLOW)
+        .overrides(overrides)
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+
+    let relative = |path: &Path| {
+        path.strip_prefix(root)
+            .unwrap_or(path)
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    };
+
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                match error_path(&e) {
+                    Some(path) => files.push(WalkedFile {
+                        relative_path: relative(path),
+                        size: 0,
+                        error: Some(e.to_string()),
+                    }),
+                    None => log::warn!("Skipped part of {} while walking it: {e}", params.root),
+                }
+                continue;
+            }
+        };
+        // Unfollowed symlinks are neither files nor folders, so they drop out here
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        files.push(WalkedFile {
+            relative_path: relative(entry.path()),
+            size: entry.metadata().map(|m| m.len() as i64).unwrap_or(0),
+            error: None,
+        });
+    }
+    Ok(files)
+}
+
+fn error_path(error: &ignore::Error) -> Option<&Path> {
+    match error {
+        ignore::Error::WithPath { path, .. } => Some(path),
+        ignore::Error::Loop { child, .. } => Some(child),
+        ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => error_path(err),
+        _ => None,
+    }
+}
+
+/// Upload the local files behind `items`, as many at once as the upload
+/// concurrency setting allows.
+async fn upload_items(
+    storage: &SqliteStorage,
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    job: &BatchJob,
+    cse: &s3::ClientEncryption,
+    params: &UploadDirectoryParams,
+    items: Vec<BatchJobItem>,
+) -> Result<Vec<(String, Result<ItemOutcome>)>> {
+    let settings = settings_repo::get(storage)?;
+    let semaphore = Arc::new(Semaphore::new(settings.concurrent_uploads.max(1) as usize));
+    let mut handles = Vec::new();
+
+    for item in items {
+        let permit = semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| anyhow!("Semaphore error: {e}"))?;
+
+        let target = target.clone();
+        let credentials = credentials.clone();
+        let bucket = job.bucket.clone();
+        let params = params.clone();
+        let key = cse.decode_key(&item.key);
+        let preserve_timestamps = settings.preserve_timestamps;
+
+        handles.push(tokio::spawn(async move {
+            let result =
+                upload_file(&target, &credentials, &bucket, &params, &key, preserve_timestamps).await;
+            drop(permit);
+            (item.id, result)
+        }));
+    }
+
+    let mut results = Vec::new();
+    for handle in handles {
+        match handle.await {
+            Ok(result) => results.push(result),
+            Err(e) => log::error!("Upload task join error: {e}"),
+        }
+    }
+    Ok(results)
+}
+
+/// Upload the local file behind `key`. With `skip_identical` a file whose
+/// content is already there is skipped; protected objects are never
+/// overwritten.
+async fn upload_file(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    params: &UploadDirectoryParams,
+    key: &str,
+    preserve_timestamps: bool,
+) -> Result<ItemOutcome> {
+    let relative = key
+        .strip_prefix(&params.dest_prefix)
+        .ok_or_else(|| anyhow!("{key} is outside {}", params.dest_prefix))?;
+    let local_path = relative
+        .split('/')
+        .fold(PathBuf::from(&params.root), |path, part| path.join(part))
+        .to_string_lossy()
+        .to_string();
+
+    if params.skip_identical {
+        let size = std::fs::metadata(&local_path)
+            .map_err(|e| anyhow!("Failed to read file {local_path}: {e}"))?
+            .len() as i64;
+        let file = UploadPlanFile {
+            local_path: local_path.clone(),
+            key: key.to_string(),
+        };
+        let status = upload_plan::file_status(target, credentials, bucket, &file, size, None).await?;
+        if status == upload_plan::UNCHANGED {
+            return Ok(ItemOutcome::Skipped);
+        }
+    }
+    if guard::is_protected(target, bucket, key) && s3::head_object(target, credentials, bucket, key).await?.is_some() {
+        return Err(anyhow!("{bucket}/{key} is protected and can't be overwritten."));
+    }
+
+    s3::put_object(target, credentials, bucket, key, &local_path, preserve_timestamps, None).await?;
+    Ok(ItemOutcome::Completed)
+}
+
+type EnumerationPage = (Vec<BatchJobItem>, Option<EmptyBucketCursor>);
+
+/// One page of versions and delete markers, or `None` if the provider does
+/// not implement ListObjectVersions.
+async fn list_versions_page(
+    client: &Client,
+    job: &BatchJob,
+    cursor: &EmptyBucketCursor,
+) -> Result<Option<EnumerationPage>> {
+    let output = match client
+        .list_object_versions()
+        .bucket(&job.bucket)
+        .prefix(&job.prefix)
+        .set_key_marker(cursor.marker.clone())
+        .set_version_id_marker(cursor.secondary_marker.clone())
+        .send()
+        .await
+    {
+        Ok(output) => output,
+        Err(e) if cursor.marker.is_none() && e.code() == Some("NotImplemented") => return Ok(None),
+        Err(e) => return Err(anyhow!("S3 list object versions failed during enumeration: {e}")),
+    };
+
+    let now = now_epoch();
+    let mut items = Vec::new();
+    for v in output.versions() {
+        if let Some(key) = v.key().filter(|k| !k.is_empty()) {
+            let version_id = v.version_id().unwrap_or("null").to_string();
+            items.push(new_item(&job.id, key.to_string(), Some(version_id), v.size().unwrap_or(0), now));
+        }
+    }
+    for m in output.delete_markers() {
+        if let Some(key) = m.key().filter(|k| !k.is_empty()) {
+            let version_id = m.version_id().unwrap_or("null").to_string();
+            items.push(new_item(&job.id, key.to_string(), Some(version_id), 0, now));
+        }
+    }
+
+    let next = (output.is_truncated() == Some(true)).then(|| EmptyBucketCursor {
+        marker: output.next_key_marker().map(|s| s.to_string()),
+        secondary_marker: output.next_version_id_marker().map(|s| s.to_string()),
+        ..Default::default()
+    });
+    Ok(Some((items, next)))
+}
+
+/// One page of current objects, for providers without versioning support.
+async fn list_objects_page(
+    client: &Client,
+    job: &BatchJob,
+    cursor: &EmptyBucketCursor,
+) -> Result<EnumerationPage> {
+    let output = client
+        .list_objects_v2()
+        .bucket(&job.bucket)
+        .prefix(&job.prefix)
+        .set_continuation_token(cursor.marker.clone())
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 list objects failed during enumeration: {e}"))?;
+
+    let now = now_epoch();
+    let items = output
+        .contents()
+        .iter()
+        .filter_map(|obj| {
+            let key = obj.key().filter(|k| !k.is_empty())?;
+            Some(new_item(&job.id, key.to_string(), None, obj.size().unwrap_or(0), now))
+        })
+        .collect();
+
+    let next = (output.is_truncated() == Some(true) && output.next_continuation_token().is_some())
+        .then(|| EmptyBucketCursor {
+            unversioned: true,
+            marker: output.next_continuation_token().map(|s| s.to_string()),
+            ..Default::default()
+        });
+    Ok((items, next))
+}
+
+/// One page of incomplete multipart uploads.
+async fn list_uploads_page(
+    client: &Client,
+    job: &BatchJob,
+    cursor: &EmptyBucketCursor,
+) -> Result<EnumerationPage> {
+    let output = client
+        .list_multipart_uploads()
+        .bucket(&job.bucket)
+        .prefix(&job.prefix)
+        .set_key_marker(cursor.marker.clone())
+        .set_upload_id_marker(cursor.secondary_marker.clone())
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 list multipart uploads failed during enumeration: {e}"))?;
+
+    let now = now_epoch();
+    let items = output
+        .uploads()
+        .iter()
+        .filter_map(|upload| {
+            let key = upload.key()?;
+            let upload_id = upload.upload_id()?;
+            Some(BatchJobItem {
+                upload_id: Some(upload_id.to_string()),
+                ..new_item(&job.id, key.to_string(), None, 0, now)
+            })
+        })
+        .collect();
+
+    let next = (output.is_truncated() == Some(true)).then(|| EmptyBucketCursor {
+        uploads: true,
+        marker: output.next_key_marker().map(|s| s.to_string()),
+        secondary_marker: output.next_upload_id_marker().map(|s| s.to_string()),
+        ..Default::default()
+    });
+    Ok((items, next))
+}
+
+/// Drop the keys of a batch that are now gone from the bucket index. When
+/// deleting every version, a key is gone only once none of its versions
+/// failed here or are still to be deleted by a later batch. The index holds
+/// readable names, so stored keys are decoded first.
+fn remove_deleted_from_index(
+    storage: &SqliteStorage,
+    job: &BatchJob,
+    cse: &s3::ClientEncryption,
+    all_versions: bool,
+    keys: &HashMap<String, String>,
+    results: &[(String, Result<ItemOutcome>)],
+) {
+    let mut deleted = HashSet::new();
+    let mut remaining = HashSet::new();
+    for (item_id, result) in results {
+        let Some(key) = keys.get(item_id) else {
+            continue;
+        };
+        if result.is_ok() {
+            deleted.insert(key.as_str());
+        } else {
+            remaining.insert(key.as_str());
+        }
+    }
+    if all_versions {
+        deleted.retain(|key| !remaining.contains(key));
+        let candidates: Vec<&str> = deleted.iter().copied().collect();
+        match batch_repo::keys_with_unfinished_items(storage, &job.id, &candidates) {
+            Ok(unfinished) => deleted.retain(|key| !unfinished.contains(*key)),
+            Err(e) => {
+                log::warn!("Could not check remaining versions for job {}: {e}", job.id);
+                return;
+            }
+        }
+    }
+
+    let deleted: Vec<String> = deleted.into_iter().map(|key| cse.decode_key(key)).collect();
+    if !deleted.is_empty() {
+        let _ = index_repo::remove_objects(storage, &job.target_id, &job.bucket, &deleted);
+    }
+}
+
+/// Record what a batch did in the audit log, by readable key.
+fn audit_batch(
+    storage: &SqliteStorage,
+    job: &BatchJob,
+    cse: &s3::ClientEncryption,
+    keys: &HashMap<String, String>,
+    results: &[(String, Result<ItemOutcome>)],
+) {
+    let mut succeeded = Vec::new();
+    let mut failed = Vec::new();
+    for (item_id, result) in results {
+        let Some(key) = keys.get(item_id) else {
+            continue;
+        };
+        match result {
+            Ok(ItemOutcome::Completed) => succeeded.push(cse.decode_key(key)),
+            Ok(ItemOutcome::Skipped) => {}
+            Err(e) => failed.push((cse.decode_key(key), e.to_string())),
+        }
+    }
+    audit::record_batch(storage, &job.initiator, &job.kind, &job.target_id, &job.bucket, &succeeded, &failed);
+}
+
+/// Once every item is gone: delete the bucket if asked to, and drop or
+/// reset what the app has cached about it.
+async fn finish_empty_bucket(
+    storage: &SqliteStorage,
+    client: &Client,
+    job: &BatchJob,
+    params: &EmptyBucketParams,
+    failed_items: i64,
+) -> Result<()> {
+    if params.delete_bucket {
+        if failed_items > 0 {
+            return Err(anyhow!(
+                "{failed_items} item(s) could not be deleted, so the bucket was kept"
+            ));
+        }
+        let result = client
+            .delete_bucket()
+            .bucket(&job.bucket)
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 delete bucket failed: {e}"));
+        audit::record_prefix(storage, &job.initiator, "deleteBucket", &job.target_id, &job.bucket, "", &result);
+        result?;
+        index_repo::delete_index(storage, &job.target_id, &job.bucket)?;
+        bucket_stats_repo::delete(storage, &job.target_id, &job.bucket)?;
+    } else if failed_items == 0 {
+        index_repo::clear_objects(storage, &job.target_id, &job.bucket)?;
+        bucket_stats_repo::upsert(storage, &job.target_id, &job.bucket, 0, 0)?;
+    }
+    Ok(())
+}
+
+/// A pending item for `key`, ready to insert.
+pub fn new_item(
+    job_id: &str,
+    key: String,
+    version_id: Option<String>,
+    size: i64,
+    now: i64,
+) -> BatchJobItem {
+    BatchJobItem {
+        id: Uuid::now_v7().to_string(),
+        job_id: job_id.to_string(),
+        key,
+        version_id,
+        upload_id: None,
+        size,
+        status: "pending".to_string(),
+        error_message: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Items hold stored keys; the index and restore records use readable ones.
+async fn process_item(
+    client: &Client,
+    storage: &SqliteStorage,
+    job: &BatchJob,
+    cse: &s3::ClientEncryption,
+    action: &Action,
+    item: &BatchJobItem,
+) -> Result<ItemOutcome> {
+    match action {
+        Action::Tag(params) => {
+            let Some(tags) = resolve_tags(client, &job.bucket, &item.key, params).await? else {
+                return Ok(ItemOutcome::Skipped);
+            };
+            s3::put_tags(client, &job.bucket, &item.key, &tags).await?;
+            let _ = index_repo::replace_object_tags(
+                storage,
+                &job.target_id,
+                &job.bucket,
+                &[(cse.decode_key(&item.key), tags)],
+            );
+            Ok(ItemOutcome::Completed)
+        }
+        Action::EmptyBucket(_) => {
+            // Versions are deleted in bulk by `delete_items`
+            let Some(upload_id) = &item.upload_id else {
+                return Err(anyhow!("Not a multipart upload: {}", item.key));
+            };
+            if s3::abort_multipart_upload(client, &job.bucket, &item.key, upload_id).await? {
+                Ok(ItemOutcome::Completed)
+            } else {
+                Ok(ItemOutcome::Skipped)
+            }
+        }
+        Action::StorageClass(params) => change_storage_class(client, storage, job, cse, item, params).await,
+        Action::Restore(params) => request_restore(client, storage, job, cse, item, params).await,
+        Action::DeletePrefix(_) => Err(anyhow!("Deletes are batched: {}", item.key)),
+        Action::UploadDirectory(_) => Err(anyhow!("Uploads are handled by upload_items: {}", item.key)),
+    }
+}
+
+/// Rewrite the object onto itself with the new storage class. Objects over
+/// the single-copy limit are copied part by part, so their tags are read
+/// beforehand and put back afterwards.
+async fn change_storage_class(
+    client: &Client,
+    storage: &SqliteStorage,
+    job: &BatchJob,
+    cse: &s3::ClientEncryption,
+    item: &BatchJobItem,
+    params: &StorageClassJobParams,
+) -> Result<ItemOutcome> {
+    let head = client
+        .head_object()
+        .bucket(&job.bucket)
+        .key(&item.key)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 head object failed for {}: {e}", item.key))?;
+
+    // S3 leaves the header out for STANDARD objects
+    let current = head.storage_class().map(|c| c.as_str()).unwrap_or("STANDARD");
+    if current == params.storage_class {
+        return Ok(ItemOutcome::Skipped);
+    }
+    if s3::is_archived_class(Some(current)) {
+        let (ongoing, _) = head.restore().map(s3::parse_restore_header).unwrap_or_default();
+        if ongoing != Some(false) {
+            return Err(anyhow!("{} is archived in {current} and must be restored first", item.key));
+        }
+    }
+
+    let size = head.content_length().unwrap_or(0);
+    let tags = if s3::copy_keeps_tags(size) {
+        None
+    } else {
+        Some(s3::get_tags(client, &job.bucket, &item.key).await?)
+    };
+
+    // Keep SSE-S3/SSE-KMS; SSE-C objects already failed the HEAD above
+    let encryption = s3::Encryption::from_head(&head, &s3::Encryption::None);
+    let options = s3::CopyOptions {
+        storage_class: Some(StorageClass::from(params.storage_class.as_str())),
+        encryption: Some(&encryption),
+        ..Default::default()
+    };
+    s3::copy_with_client(client, &job.bucket, &item.key, &job.bucket, &item.key, size, &options).await?;
+
+    if let Some(tags) = tags.filter(|t| !t.is_empty()) {
+        s3::put_tags(client, &job.bucket, &item.key, &tags).await?;
+    }
+    let _ = index_repo::set_storage_class(
+        storage,
+        &job.target_id,
+        &job.bucket,
+        &cse.decode_key(&item.key),
+        &params.storage_class,
+    );
+    Ok(ItemOutcome::Completed)
+}
+
+/// Start a restore of an archived object and track it until it is readable.
+async fn request_restore(
+    client: &Client,
+    storage: &SqliteStorage,
+    job: &BatchJob,
+    cse: &s3::ClientEncryption,
+    item: &BatchJobItem,
+    params: &RestoreJobParams,
+) -> Result<ItemOutcome> {
+    let version_id = item.version_id.as_deref();
+    let status = s3::restore_status(client, &job.bucket, &item.key, version_id)
+        .await?
+        .ok_or_else(|| anyhow!("{} no longer exists", item.key))?;
+    if status.is_readable() {
+        return Ok(ItemOutcome::Skipped);
+    }
+
+    let started = s3::restore_object(
+        client,
+        &job.bucket,
+        &item.key,
+        version_id,
+        params.days,
+        &params.tier,
+    )
+    .await?;
+    if !started && status.ongoing.is_none() {
+        return Err(anyhow!("S3 did not accept the restore request for {}", item.key));
+    }
+
+    restore_repo::record_request(
+        storage,
+        &ObjectRestore {
+            id: Uuid::now_v7().to_string(),
+            target_id: job.target_id.clone(),
+            bucket: job.bucket.clone(),
+            key: cse.decode_key(&item.key),
+            version_id: item.version_id.clone(),
+            tier: params.tier.clone(),
+            days: params.days,
+            status: "pending".to_string(),
+            expiry_date: None,
+            error_message: None,
+            requested_at: now_epoch(),
+            last_checked_at: None,
+            completed_at: None,
+        },
+    )?;
+    Ok(ItemOutcome::Completed)
+}
+
+/// The tag set `key` should end up with, or `None` if it already has it.
+async fn resolve_tags(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    params: &TagJobParams,
+) -> Result<Option<Vec<ObjectTag>>> {
+    if params.mode == "replace" {
+        return Ok(Some(params.tags.clone()));
+    }
+
+    let current = s3::get_tags(client, bucket, key).await?;
+    let mut next = current.clone();
+    match params.mode.as_str() {
+        "merge" => {
+            for tag in &params.tags {
+                match next.iter_mut().find(|t| t.key == tag.key) {
+                    Some(existing) => existing.value = tag.value.clone(),
+                    None => next.push(tag.clone()),
+                }
+            }
+        }
+        "remove" => next.retain(|t| !params.tags.iter().any(|r| r.key == t.key)),
+        other => return Err(anyhow!("Unknown tag mode: {other}")),
+    }
+
+    Ok(if next == current { None } else { Some(next) })
+}
+
+async fn check_signal(
+    signal_rx: &mut watch::Receiver<BatchSignal>,
+    storage: &Arc<SqliteStorage>,
+    app: &AppHandle,
+    job_id: &str,
+) -> Result<bool> {
+    let current = { signal_rx.borrow().clone() };
+    match current {
+        BatchSignal::Run => Ok(false),
+        BatchSignal::Pause => {
+            batch_repo::update_job_status(storage, job_id, "paused")?;
+            emit_status_change(app, job_id, "paused");
+            loop {
+                let _ = signal_rx.changed().await;
+                let next = { signal_rx.borrow().clone() };
+                match next {
+                    BatchSignal::Run => {
+                        batch_repo::update_job_status(storage, job_id, "running")?;
+                        emit_status_change(app, job_id, "running");
+                        return Ok(false);
+                    }
+                    BatchSignal::Cancel => {
+                        batch_repo::update_job_status(storage, job_id, "cancelled")?;
+                        emit_status_change(app, job_id, "cancelled");
+                        return Ok(true);
+                    }
+                    BatchSignal::Pause => continue,
+                }
+            }
+        }
+        BatchSignal::Cancel => {
+            batch_repo::update_job_status(storage, job_id, "cancelled")?;
+            emit_status_change(app, job_id, "cancelled");
+            Ok(true)
+        }
+    }
+}
+
+fn emit_status_change(app: &AppHandle, job_id: &str, status: &str) {
+    let _ = app.emit(
+        "batch-status-change",
+        serde_json::json!({"jobId": job_id, "status": status}),
+    );
+}
+
+fn emit_progress(app: &AppHandle, job: &BatchJob) {
+    let _ = app.emit(
+        "batch-progress",
+        BatchProgressEvent {
+            job_id: job.id.clone(),
+            kind: job.kind.clone(),
+            status: job.status.clone(),
+            total_items: job.total_items,
+            completed_items: job.completed_items,
+            failed_items: job.failed_items,
+            skipped_items: job.skipped_items,
+        },
+    );
+}
+
+fn now_epoch() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
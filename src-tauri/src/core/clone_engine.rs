@@ -1,4 +1,5 @@
-use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use anyhow::{anyhow, Result};
@@ -6,10 +7,13 @@ use tauri::{AppHandle, Emitter};
 use tokio::sync::{watch, Semaphore};
 use uuid::Uuid;
 
+use crate::core::audit;
 use crate::core::s3;
-use crate::core::storage::repositories::{clone_repo, credentials_repo, targets_repo};
+use crate::core::storage::repositories::{clone_repo, credentials_repo, index_repo, targets_repo};
 use crate::core::storage::sqlite::SqliteStorage;
-use crate::models::{CloneJobItem, CloneProgressEvent, StorageTarget, TargetCredentials};
+use crate::models::{
+    CloneJob, CloneJobItem, CloneProgressEvent, ObjectMetadata, StorageTarget, TargetCredentials,
+};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum CloneSignal {
@@ -21,6 +25,8 @@ pub enum CloneSignal {
 const SAME_TARGET_CONCURRENCY: usize = 20;
 const CROSS_TARGET_CONCURRENCY: usize = 4;
 const BATCH_SIZE: i64 = 100;
+// Keys per DeleteObjects request when a move removes its sources
+const DELETE_BATCH_SIZE: i64 = 1000;
 const PROGRESS_THROTTLE_MS: u128 = 200;
 
 pub async fn run_clone_job(
@@ -68,19 +74,7 @@ async fn run_clone_job_inner(
         clone_repo::update_job_status(storage, job_id, "enumerating")?;
         emit_status_change(app, job_id, "enumerating");
 
-        enumerate_source(
-            app,
-            storage,
-            signal_rx,
-            job_id,
-            &source_target,
-            &source_creds,
-            &job.source_bucket,
-            &job.source_prefix,
-            &job.dest_prefix,
-            job.enumeration_token.as_deref(),
-        )
-        .await?;
+        enumerate_source(app, storage, signal_rx, &job, &source_target, &source_creds).await?;
     }
 
     // Phase 2: Execution
@@ -96,7 +90,6 @@ async fn run_clone_job_inner(
         CROSS_TARGET_CONCURRENCY
     };
 
-    let temp_dir = std::env::temp_dir();
     let mut last_progress_emit = Instant::now();
 
     loop {
@@ -114,6 +107,10 @@ async fn run_clone_job_inner(
             let _ = clone_repo::update_item_status(storage, &item.id, "active", None);
         }
 
+        let job_snapshot = clone_repo::get_job(storage, job_id)?
+            .ok_or_else(|| anyhow!("Job disappeared"))?;
+        let byte_progress = Arc::new(ByteProgress::new(app.clone(), job_snapshot));
+
         let semaphore = Arc::new(Semaphore::new(concurrency));
         let mut handles = Vec::new();
         let mut batch_item_ids: Vec<String> = Vec::new();
@@ -132,25 +129,33 @@ async fn run_clone_job_inner(
             let dt = dest_target.clone();
             let dc = dest_creds.clone();
             let is_same = job.is_same_target;
+            let delete_source = job.delete_source;
             let conflict_policy = job.conflict_policy.clone();
             let source_bucket = job.source_bucket.clone();
             let dest_bucket = job.dest_bucket.clone();
-            let td = temp_dir.clone();
+            let progress = byte_progress.clone();
 
             let handle = tokio::spawn(async move {
+                let seen = AtomicU64::new(0);
+                let on_progress = |done: u64, _total: u64| progress.report(&seen, done, &item.source_key);
                 let result = process_item(
                     &item,
                     &conflict_policy,
                     is_same,
+                    delete_source,
                     &st,
                     &sc,
                     &source_bucket,
                     &dt,
                     &dc,
                     &dest_bucket,
-                    &td,
+                    on_progress,
                 )
                 .await;
+                // A retry starts the item over, so its bytes must not count twice
+                if result.is_err() {
+                    progress.roll_back(&seen);
+                }
                 drop(permit);
                 (item, result)
             });
@@ -159,6 +164,8 @@ async fn run_clone_job_inner(
 
         // Collect results
         let mut processed_ids = std::collections::HashSet::new();
+        let mut copied_keys = Vec::new();
+        let mut failed_keys = Vec::new();
         for handle in handles {
             match handle.await {
                 Ok((item, Ok(outcome))) => {
@@ -168,6 +175,13 @@ async fn run_clone_job_inner(
                             let _ = clone_repo::update_item_status(
                                 storage, &item.id, "completed", None,
                             );
+                            copied_keys.push(item.dest_key);
+                        }
+                        ItemOutcome::Copied => {
+                            let _ = clone_repo::update_item_status(
+                                storage, &item.id, "copied", None,
+                            );
+                            copied_keys.push(item.dest_key);
                         }
                         ItemOutcome::Skipped => {
                             let _ = clone_repo::update_item_status(
@@ -184,12 +198,22 @@ async fn run_clone_job_inner(
                         "failed",
                         Some(&e.to_string()),
                     );
+                    failed_keys.push((item.dest_key, e.to_string()));
                 }
                 Err(e) => {
                     log::error!("Clone task join error: {e}");
                 }
             }
         }
+        audit::record_batch(
+            storage,
+            &job.initiator,
+            "copy",
+            &job.dest_target_id,
+            &job.dest_bucket,
+            &copied_keys,
+            &failed_keys,
+        );
 
         // Safety net: mark any items that weren't processed (e.g., task panic) as failed
         for id in &batch_item_ids {
@@ -222,6 +246,19 @@ async fn run_clone_job_inner(
         }
     }
 
+    // Phase 3: Delete the sources of verified copies (moves only)
+    if job.delete_source {
+        if check_signal(signal_rx, storage, app, job_id).await? {
+            return Ok(());
+        }
+        clone_repo::update_job_status(storage, job_id, "deleting")?;
+        emit_status_change(app, job_id, "deleting");
+
+        if delete_moved_sources(app, storage, signal_rx, &job, &source_target, &source_creds).await? {
+            return Ok(());
+        }
+    }
+
     // Final status
     let counts = clone_repo::count_items_by_status(storage, job_id)?;
     let _ = clone_repo::update_job_progress(
@@ -233,9 +270,7 @@ async fn run_clone_job_inner(
         counts.total_transferred_bytes,
     );
 
-    let final_status = if counts.failed > 0 && counts.pending == 0 {
-        "completed"
-    } else if counts.pending == 0 {
+    let final_status = if counts.pending == 0 && counts.copied == 0 {
         "completed"
     } else {
         "failed"
@@ -252,19 +287,26 @@ async fn run_clone_job_inner(
     Ok(())
 }
 
+/// List the source into pending items. Folder markers are only moved, so
+/// that a move leaves no empty folders behind but keeps them at the
+/// destination; a plain copy creates folders as it copies their objects.
 async fn enumerate_source(
     app: &AppHandle,
     storage: &Arc<SqliteStorage>,
     signal_rx: &mut watch::Receiver<CloneSignal>,
-    job_id: &str,
+    job: &CloneJob,
     source_target: &StorageTarget,
     source_creds: &TargetCredentials,
-    source_bucket: &str,
-    source_prefix: &str,
-    dest_prefix: &str,
-    resume_token: Option<&str>,
 ) -> Result<()> {
+    let job_id = job.id.as_str();
+    let source_bucket = job.source_bucket.as_str();
+    let source_prefix = job.source_prefix.as_str();
+    let exact_key = is_single_object_move(job);
+    let resume_token = job.enumeration_token.as_deref();
     let client = s3::build_client(source_target, source_creds).await?;
+    // Items hold readable keys; encrypted names are re-encoded per target
+    let cse = s3::ClientEncryption::for_target(source_target, source_creds)?;
+    let stored_prefix = cse.encode_key(source_prefix);
     let mut continuation_token: Option<String> = resume_token.map(|s| s.to_string());
     let mut total_items: i64 = 0;
     let mut total_bytes: i64 = 0;
@@ -287,7 +329,7 @@ async fn enumerate_source(
         let mut req = client
             .list_objects_v2()
             .bucket(source_bucket)
-            .prefix(source_prefix);
+            .prefix(&stored_prefix);
 
         if let Some(token) = &continuation_token {
             req = req.continuation_token(token);
@@ -302,13 +344,14 @@ async fn enumerate_source(
 
         if let Some(contents) = output.contents {
             for obj in contents {
-                let key = obj.key.unwrap_or_default();
-                if key.is_empty() || key.ends_with('/') {
+                let key = cse.decode_key(&obj.key.unwrap_or_default());
+                let size = obj.size.unwrap_or(0);
+                let skipped_folder = key.ends_with('/') && !(job.delete_source && is_folder_marker(&key, size));
+                if key.is_empty() || skipped_folder || (exact_key && key != source_prefix) {
                     continue;
                 }
 
-                let size = obj.size.unwrap_or(0);
-                let dest_key = compute_dest_key(&key, source_prefix, dest_prefix);
+                let dest_key = compute_dest_key(&key, source_prefix, &job.dest_prefix);
 
                 batch_items.push(CloneJobItem {
                     id: Uuid::now_v7().to_string(),
@@ -366,22 +409,40 @@ async fn enumerate_source(
 
 enum ItemOutcome {
     Completed,
+    /// Copied and verified; the source is deleted in a later phase.
+    Copied,
     Skipped,
 }
 
+fn is_folder_marker(key: &str, size: i64) -> bool {
+    key.ends_with('/') && size == 0
+}
+
+/// A move whose source prefix names one object rather than a folder.
+pub(crate) fn is_single_object_move(job: &CloneJob) -> bool {
+    job.delete_source && !job.source_prefix.is_empty() && !job.source_prefix.ends_with('/')
+}
+
 async fn process_item(
     item: &CloneJobItem,
     conflict_policy: &str,
     is_same_target: bool,
+    delete_source: bool,
     source_target: &StorageTarget,
     source_creds: &TargetCredentials,
     source_bucket: &str,
     dest_target: &StorageTarget,
     dest_creds: &TargetCredentials,
     dest_bucket: &str,
-    temp_dir: &std::path::Path,
+    on_progress: impl Fn(u64, u64),
 ) -> Result<ItemOutcome> {
-    // Conflict resolution
+    // Conflict resolution. An existing folder marker is as good as the
+    // copy, and skipping it would leave the source folder behind.
+    let conflict_policy = if is_folder_marker(&item.source_key, item.size) {
+        "overwrite"
+    } else {
+        conflict_policy
+    };
     match conflict_policy {
         "skip" => {
             if let Some(_) =
@@ -417,8 +478,10 @@ async fn process_item(
             dest_bucket,
             &item.dest_key,
             item.size,
+            &s3::CopyOptions::default(),
         )
         .await?;
+        on_progress(item.size as u64, item.size as u64);
     } else {
         s3::cross_target_copy(
             source_target,
@@ -429,15 +492,182 @@ async fn process_item(
             dest_creds,
             dest_bucket,
             &item.dest_key,
-            temp_dir,
-            |_, _| {},
+            on_progress,
+        )
+        .await?;
+    }
+
+    if delete_source {
+        verify_copy(
+            item,
+            source_target,
+            source_creds,
+            source_bucket,
+            dest_target,
+            dest_creds,
+            dest_bucket,
         )
         .await?;
+        return Ok(ItemOutcome::Copied);
     }
 
     Ok(ItemOutcome::Completed)
 }
 
+/// Check a copy against its source before the source may be deleted. Sizes
+/// must match. The content is compared by MD5 where S3 vouches for the
+/// copy's digest, i.e. its ETag is a plain MD5 (single-part, not KMS or
+/// customer-key encrypted and not client-side encrypted), against the
+/// source's ETag or the MD5 recorded when it was uploaded. Otherwise
+/// matching additional checksums are compared, when both sides hold the
+/// same bytes.
+async fn verify_copy(
+    item: &CloneJobItem,
+    source_target: &StorageTarget,
+    source_creds: &TargetCredentials,
+    source_bucket: &str,
+    dest_target: &StorageTarget,
+    dest_creds: &TargetCredentials,
+    dest_bucket: &str,
+) -> Result<()> {
+    let source = s3::head_object_details(source_target, source_creds, source_bucket, &item.source_key, None).await?;
+    let dest = s3::head_object_details(dest_target, dest_creds, dest_bucket, &item.dest_key, None)
+        .await
+        .map_err(|e| anyhow!("Copy of {} could not be verified: {e}", item.source_key))?;
+
+    if source.size != dest.size {
+        return Err(anyhow!(
+            "Copy of {} has {} bytes instead of {}; source kept",
+            item.source_key,
+            dest.size,
+            source.size
+        ));
+    }
+
+    let original_md5 = etag_md5(&source).or_else(|| s3::stored_md5(Some(&source.metadata)));
+    let differs = match (etag_md5(&dest), original_md5) {
+        (Some(copy), Some(original)) => copy != original,
+        _ => !is_client_encrypted(&source)
+            && !is_client_encrypted(&dest)
+            && checksums(&source)
+                .zip(checksums(&dest))
+                .any(|(a, b)| a.is_some() && b.is_some() && a != b),
+    };
+    if differs {
+        return Err(anyhow!("Copy of {} differs from its source; source kept", item.source_key));
+    }
+    Ok(())
+}
+
+fn is_client_encrypted(meta: &ObjectMetadata) -> bool {
+    meta.metadata.keys().any(|k| k.starts_with("mahzen-cse"))
+}
+
+/// The object's MD5, if its ETag is one.
+fn etag_md5(meta: &ObjectMetadata) -> Option<String> {
+    let etag = meta.etag.as_deref().map(s3::unquote_etag)?;
+    let plain = !etag.contains('-')
+        && meta.server_side_encryption.as_deref() != Some("aws:kms")
+        && meta.sse_customer_algorithm.is_none()
+        && !is_client_encrypted(meta);
+    plain.then(|| etag.to_string())
+}
+
+/// Full-object additional checksums, in a fixed order. Composite checksums
+/// of multipart uploads depend on the part sizes, so they are left out.
+fn checksums(meta: &ObjectMetadata) -> impl Iterator<Item = Option<&str>> {
+    [
+        &meta.checksum_crc32,
+        &meta.checksum_crc32c,
+        &meta.checksum_crc64nvme,
+        &meta.checksum_sha1,
+        &meta.checksum_sha256,
+    ]
+    .into_iter()
+    .map(|checksum| checksum.as_deref().filter(|c| !c.contains('-')))
+}
+
+/// Delete the source objects of verified copies, up to 1000 per request,
+/// and drop them from the bucket index. Returns `true` if the job was
+/// cancelled meanwhile.
+async fn delete_moved_sources(
+    app: &AppHandle,
+    storage: &Arc<SqliteStorage>,
+    signal_rx: &mut watch::Receiver<CloneSignal>,
+    job: &CloneJob,
+    source_target: &StorageTarget,
+    source_creds: &TargetCredentials,
+) -> Result<bool> {
+    let client = s3::build_client(source_target, source_creds).await?;
+    let cse = s3::ClientEncryption::for_target(source_target, source_creds)?;
+
+    loop {
+        if check_signal(signal_rx, storage, app, &job.id).await? {
+            return Ok(true);
+        }
+
+        let batch = clone_repo::list_copied_items(storage, &job.id, DELETE_BATCH_SIZE)?;
+        if batch.is_empty() {
+            return Ok(false);
+        }
+
+        let objects: Vec<(String, Option<String>)> = batch
+            .iter()
+            .map(|item| (cse.encode_key(&item.source_key), None))
+            .collect();
+        let failures: std::collections::HashMap<String, String> =
+            match s3::delete_object_batch(&client, &job.source_bucket, &objects, false).await {
+                Ok(errors) => errors
+                    .into_iter()
+                    .map(|e| {
+                        let message = e.message.or(e.code).unwrap_or_else(|| "Delete failed".to_string());
+                        (e.key, message)
+                    })
+                    .collect(),
+                Err(e) => objects.iter().map(|(key, _)| (key.clone(), e.to_string())).collect(),
+            };
+
+        let mut deleted = Vec::new();
+        let mut failed = Vec::new();
+        for (item, (stored_key, _)) in batch.iter().zip(&objects) {
+            match failures.get(stored_key) {
+                Some(message) => {
+                    failed.push((item.source_key.clone(), message.clone()));
+                    let message = format!("Copied, but the source could not be deleted: {message}");
+                    let _ = clone_repo::update_item_status(storage, &item.id, "failed", Some(&message));
+                }
+                None => {
+                    let _ = clone_repo::update_item_status(storage, &item.id, "completed", None);
+                    deleted.push(item.source_key.clone());
+                }
+            }
+        }
+        audit::record_batch(
+            storage,
+            &job.initiator,
+            "delete",
+            &job.source_target_id,
+            &job.source_bucket,
+            &deleted,
+            &failed,
+        );
+        let _ = index_repo::remove_objects(storage, &job.source_target_id, &job.source_bucket, &deleted);
+
+        let counts = clone_repo::count_items_by_status(storage, &job.id)?;
+        let _ = clone_repo::update_job_progress(
+            storage,
+            &job.id,
+            counts.completed,
+            counts.failed,
+            counts.skipped,
+            counts.total_transferred_bytes,
+        );
+        if let Ok(Some(job)) = clone_repo::get_job(storage, &job.id) {
+            emit_progress(app, &job);
+        }
+    }
+}
+
 fn compute_dest_key(source_key: &str, source_prefix: &str, dest_prefix: &str) -> String {
     let relative = source_key.strip_prefix(source_prefix).unwrap_or(source_key);
     format!("{}{}", dest_prefix, relative)
@@ -490,21 +720,95 @@ fn emit_status_change(app: &AppHandle, job_id: &str, status: &str) {
     );
 }
 
-fn emit_progress(app: &AppHandle, job: &crate::models::CloneJob) {
-    let _ = app.emit(
-        "clone-progress",
-        CloneProgressEvent {
-            job_id: job.id.clone(),
-            status: job.status.clone(),
-            total_items: job.total_items,
-            completed_items: job.completed_items,
-            failed_items: job.failed_items,
-            skipped_items: job.skipped_items,
-            total_bytes: job.total_bytes,
-            transferred_bytes: job.transferred_bytes,
-            current_key: None,
-        },
-    );
+fn emit_progress(app: &AppHandle, job: &CloneJob) {
+    let _ = app.emit("clone-progress", progress_event(job, job.transferred_bytes, None));
+}
+
+fn progress_event(
+    job: &CloneJob,
+    transferred_bytes: i64,
+    current_key: Option<String>,
+) -> CloneProgressEvent {
+    CloneProgressEvent {
+        job_id: job.id.clone(),
+        status: job.status.clone(),
+        total_items: job.total_items,
+        completed_items: job.completed_items,
+        failed_items: job.failed_items,
+        skipped_items: job.skipped_items,
+        total_bytes: job.total_bytes,
+        transferred_bytes,
+        current_key,
+    }
+}
+
+/// Bytes moved so far by the items of one batch. Each item reports its
+/// running count through its own `seen` counter.
+#[derive(Default)]
+struct InFlightBytes {
+    total: AtomicI64,
+}
+
+impl InFlightBytes {
+    /// Record that an item has now moved `done` bytes; returns the total.
+    fn report(&self, seen: &AtomicU64, done: u64) -> i64 {
+        let delta = done as i64 - seen.swap(done, Ordering::Relaxed) as i64;
+        self.total.fetch_add(delta, Ordering::Relaxed) + delta
+    }
+
+    /// Take a failed item's bytes back out; returns the total.
+    fn roll_back(&self, seen: &AtomicU64) -> i64 {
+        let delta = seen.swap(0, Ordering::Relaxed) as i64;
+        self.total.fetch_sub(delta, Ordering::Relaxed) - delta
+    }
+}
+
+/// Byte-level progress for the items of one batch. Job counters are only
+/// persisted between batches, so in-flight bytes are added on top of the
+/// snapshot taken when the batch started.
+struct ByteProgress {
+    app: AppHandle,
+    job: CloneJob,
+    in_flight: InFlightBytes,
+    last_emit: Mutex<Instant>,
+}
+
+impl ByteProgress {
+    fn new(app: AppHandle, job: CloneJob) -> Self {
+        Self {
+            app,
+            job,
+            in_flight: InFlightBytes::default(),
+            last_emit: Mutex::new(Instant::now()),
+        }
+    }
+
+    fn report(&self, seen: &AtomicU64, done: u64, current_key: &str) {
+        let in_flight = self.in_flight.report(seen, done);
+
+        {
+            let Ok(mut last_emit) = self.last_emit.lock() else {
+                return;
+            };
+            if last_emit.elapsed().as_millis() < PROGRESS_THROTTLE_MS {
+                return;
+            }
+            *last_emit = Instant::now();
+        }
+
+        let _ = self.app.emit(
+            "clone-progress",
+            progress_event(
+                &self.job,
+                self.job.transferred_bytes + in_flight,
+                Some(current_key.to_string()),
+            ),
+        );
+    }
+
+    fn roll_back(&self, seen: &AtomicU64) {
+        self.in_flight.roll_back(seen);
+    }
 }
 
 fn now_epoch() -> i64 {
@@ -514,3 +818,24 @@ fn now_epoch() -> i64 {
         .map(|d| d.as_secs() as i64)
         .unwrap_or(0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_item_bytes_are_rolled_back() {
+        let bytes = InFlightBytes::default();
+        let (first, second) = (AtomicU64::new(0), AtomicU64::new(0));
+
+        bytes.report(&first, 40);
+        bytes.report(&second, 10);
+        assert_eq!(bytes.report(&first, 100), 110);
+
+        // The second item fails partway and is retried from the start
+        assert_eq!(bytes.report(&second, 60), 160);
+        assert_eq!(bytes.roll_back(&second), 100);
+        assert_eq!(bytes.report(&second, 30), 130);
+        assert_eq!(bytes.report(&second, 100), 200);
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::{anyhow, Result};
+
+use crate::models::StorageTarget;
+
+/// Fail if the target is marked read-only.
+pub fn check_writable(target: &StorageTarget) -> Result<()> {
+    if target.read_only {
+        return Err(anyhow!("{} is read-only.", target.name));
+    }
+    Ok(())
+}
+
+/// Fail if `key` may not be deleted or overwritten.
+pub fn check_key(target: &StorageTarget, bucket: &str, key: &str) -> Result<()> {
+    check_writable(target)?;
+    match protecting_pattern(target, bucket, key, false) {
+        Some(pattern) => Err(anyhow!("{bucket}/{key} is protected by \"{pattern}\" on {}.", target.name)),
+        None => Ok(()),
+    }
+}
+
+/// Fail if anything under `prefix` may not be deleted or overwritten. An
+/// empty prefix stands for the whole bucket.
+pub fn check_prefix(target: &StorageTarget, bucket: &str, prefix: &str) -> Result<()> {
+    check_writable(target)?;
+    match protecting_pattern(target, bucket, prefix, true) {
+        Some(pattern) => Err(anyhow!(
+            "{bucket}/{prefix} contains objects protected by \"{pattern}\" on {}.",
+            target.name
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Whether `key` falls under one of the target's protected patterns.
+pub fn is_protected(target: &StorageTarget, bucket: &str, key: &str) -> bool {
+    protecting_pattern(target, bucket, key, false).is_some()
+}
+
+/// Check a pattern for use as a protected prefix.
+pub fn validate_pattern(pattern: &str) -> Result<()> {
+    if pattern.starts_with('/') {
+        return Err(anyhow!("Protected prefix \"{pattern}\" must start with a bucket name, not a slash."));
+    }
+    Ok(())
+}
+
+/// The first protected pattern that covers `bucket/path`. Patterns are
+/// prefixes of `bucket/key` in which `*` stands for any run of characters
+/// within one path segment. When `path` is itself a prefix, a pattern that
+/// covers only part of what lies under it also counts.
+fn protecting_pattern<'a>(
+    target: &'a StorageTarget,
+    bucket: &str,
+    path: &str,
+    path_is_prefix: bool,
+) -> Option<&'a str> {
+    let full = format!("{bucket}/{path}");
+    target
+        .protected_prefixes
+        .iter()
+        .map(|p| p.trim())
+        .filter(|p| !p.is_empty())
+        .find(|pattern| overlaps(pattern.as_bytes(), full.as_bytes(), path_is_prefix))
+}
+
+fn overlaps(pattern: &[u8], path: &[u8], path_is_prefix: bool) -> bool {
+    match pattern.split_first() {
+        None => true,
+        Some((b'*', rest)) => {
+            let segment = path.iter().position(|&c| c == b'/').unwrap_or(path.len());
+            (0..=segment).any(|i| overlaps(rest, &path[i..], path_is_prefix))
+        }
+        Some((&c, rest)) => match path.split_first() {
+            None => path_is_prefix,
+            Some((&d, path_rest)) => c == d && overlaps(rest, path_rest, path_is_prefix),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::overlaps;
+
+    fn matches(pattern: &str, key: &str) -> bool {
+        overlaps(pattern.as_bytes(), key.as_bytes(), false)
+    }
+
+    fn covers(pattern: &str, prefix: &str) -> bool {
+        overlaps(pattern.as_bytes(), prefix.as_bytes(), true)
+    }
+
+    #[test]
+    fn keys_under_a_prefix_match() {
+        assert!(matches("b/logs/", "b/logs/2024/a.txt"));
+        assert!(matches("b/logs/", "b/logs/"));
+        assert!(!matches("b/logs/", "b/log"));
+        assert!(!matches("b/logs/", "b/other/a.txt"));
+        assert!(matches("", "b/anything"));
+    }
+
+    #[test]
+    fn wildcards_stay_within_one_folder() {
+        assert!(matches("b/media/*/originals/", "b/media/2024/originals/x.jpg"));
+        assert!(matches("b/media/*/originals/", "b/media//originals/x.jpg"));
+        assert!(!matches("b/media/*/originals/", "b/media/2024/06/originals/x.jpg"));
+        assert!(matches("b/tmp*", "b/tmp-file"));
+        assert!(!matches("b/*.bak", "b/dir/file.bak"));
+    }
+
+    #[test]
+    fn prefixes_overlap_when_they_contain_protected_keys() {
+        assert!(covers("b/logs/", "b/"));
+        assert!(covers("b/logs/", ""));
+        assert!(covers("b/media/*/originals/", "b/media/2024/"));
+        assert!(!covers("b/logs/", "b/other/"));
+        assert!(!covers("b/media/*/originals/", "b/media/2024/thumbs/"));
+    }
+}
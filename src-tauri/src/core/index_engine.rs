@@ -3,13 +3,15 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use anyhow::{anyhow, Result};
+use aws_sdk_s3::Client;
 use tauri::{AppHandle, Emitter};
-use tokio::sync::watch;
+use tokio::sync::{watch, Semaphore};
+use tokio::task::JoinSet;
 
 use crate::core::s3;
 use crate::core::storage::repositories::{credentials_repo, index_repo, targets_repo};
 use crate::core::storage::sqlite::SqliteStorage;
-use crate::models::{BucketIndexObject, IndexProgressEvent};
+use crate::models::{BucketIndexObject, IndexProgressEvent, ObjectTag};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum IndexSignal {
@@ -18,6 +20,7 @@ pub enum IndexSignal {
 }
 
 const PROGRESS_THROTTLE_MS: u128 = 300;
+const TAG_FETCH_CONCURRENCY: usize = 16;
 
 pub async fn run_index(
     app: AppHandle,
@@ -69,6 +72,10 @@ async fn run_index_inner(
         .as_ref()
         .map(|s| s.total_size)
         .unwrap_or(0);
+    let mut include_tags = existing_state
+        .as_ref()
+        .map(|s| s.include_tags)
+        .unwrap_or(false);
 
     // If not resuming (fresh index), clear existing objects
     if resume_token.is_none() {
@@ -81,6 +88,8 @@ async fn run_index_inner(
     emit_status(app, target_id, bucket, "indexing");
 
     let client = s3::build_client(&target, &creds).await?;
+    // The index holds readable keys, even when names are stored encrypted
+    let cse = s3::ClientEncryption::for_target(&target, &creds)?;
     let mut continuation_token = resume_token;
     let mut last_progress_emit = Instant::now();
 
@@ -118,7 +127,7 @@ async fn run_index_inner(
 
         if let Some(contents) = output.contents {
             for obj in contents {
-                let key = obj.key.unwrap_or_default();
+                let key = cse.decode_key(&obj.key.unwrap_or_default());
                 if key.is_empty() {
                     continue;
                 }
@@ -201,6 +210,24 @@ async fn run_index_inner(
             index_repo::insert_objects_batch(storage, &batch)?;
         }
 
+        if include_tags {
+            let keys: Vec<String> = batch
+                .iter()
+                .filter(|obj| !obj.is_folder)
+                .map(|obj| obj.key.clone())
+                .collect();
+            if !keys.is_empty() {
+                let tagged = fetch_tags(&client, &cse, bucket, &keys).await;
+                // Providers without tagging support fail every request; stop
+                // asking rather than doubling the cost of the whole run
+                if tagged.is_empty() {
+                    log::warn!("Could not read tags in {target_id}/{bucket}, indexing without tags");
+                    include_tags = false;
+                }
+                index_repo::replace_object_tags(storage, target_id, bucket, &tagged)?;
+            }
+        }
+
         let is_truncated = output.is_truncated == Some(true);
         if is_truncated {
             continuation_token = output.next_continuation_token;
@@ -254,6 +281,38 @@ async fn run_index_inner(
     Ok(())
 }
 
+/// Fetch the tags of `keys` concurrently. Keys whose tags cannot be read
+/// are left out.
+async fn fetch_tags(
+    client: &Client,
+    cse: &s3::ClientEncryption,
+    bucket: &str,
+    keys: &[String],
+) -> Vec<(String, Vec<ObjectTag>)> {
+    let semaphore = Arc::new(Semaphore::new(TAG_FETCH_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    for key in keys {
+        let client = client.clone();
+        let bucket = bucket.to_string();
+        let key = key.clone();
+        let stored_key = cse.encode_key(&key);
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok()?;
+            let tags = s3::get_tags(&client, &bucket, &stored_key).await.ok()?;
+            Some((key, tags))
+        });
+    }
+
+    let mut result = Vec::with_capacity(keys.len());
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(Some(entry)) = joined {
+            result.push(entry);
+        }
+    }
+    result
+}
+
 fn compute_parent_prefix(key: &str) -> String {
     // For "a/b/c.txt" → "a/b/"
     // For "a/b/" → "a/"
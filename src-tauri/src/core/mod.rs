@@ -1,4 +1,12 @@
+pub mod audit;
+pub mod bandwidth;
+pub mod batch_engine;
 pub mod clone_engine;
+pub mod guard;
 pub mod index_engine;
+pub mod multipart_janitor;
+pub mod restore_poller;
 pub mod s3;
 pub mod storage;
+pub mod trash;
+pub mod upload_plan;
@@ -0,0 +1,85 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tauri::{AppHandle, Emitter};
+
+use crate::core::audit;
+use crate::core::s3;
+use crate::core::storage::repositories::{credentials_repo, settings_repo, targets_repo};
+use crate::core::storage::sqlite::SqliteStorage;
+use crate::models::MultipartAbortResult;
+
+// Abandoned uploads are measured in days, so a few sweeps a day are plenty
+const SWEEP_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+
+/// Abort stale multipart uploads every few hours for the lifetime of the
+/// app, while the sweep is turned on in settings. Emits
+/// `multipart-sweep-complete` after each sweep that aborted anything.
+pub async fn run(app: AppHandle, storage: Arc<SqliteStorage>) {
+    loop {
+        match sweep(&storage).await {
+            Ok(Some(result)) if result.aborted > 0 || !result.errors.is_empty() => {
+                log::info!("Multipart sweep aborted {} upload(s)", result.aborted);
+                let _ = app.emit("multipart-sweep-complete", &result);
+            }
+            Ok(_) => {}
+            Err(e) => log::error!("Multipart sweep failed: {e}"),
+        }
+        tokio::time::sleep(SWEEP_INTERVAL).await;
+    }
+}
+
+/// Abort uploads older than the configured age in every bucket of the
+/// targets that opted in, or just the scoped bucket of a scoped target.
+/// Returns `None` if the sweep is turned off.
+pub async fn sweep(storage: &SqliteStorage) -> Result<Option<MultipartAbortResult>> {
+    let settings = settings_repo::get(storage)?;
+    if !settings.multipart_sweep_enabled || settings.multipart_sweep_days < 1 {
+        return Ok(None);
+    }
+
+    let mut total = MultipartAbortResult::default();
+    for target in targets_repo::list(storage)?
+        .into_iter()
+        .filter(|t| t.multipart_sweep && !t.read_only)
+    {
+        let Some(credentials) = credentials_repo::get(storage, &target.id)? else {
+            continue;
+        };
+        let client = match s3::build_client(&target, &credentials).await {
+            Ok(client) => client,
+            Err(e) => {
+                log::warn!("Skipping multipart sweep for target {}: {e}", target.id);
+                continue;
+            }
+        };
+
+        let buckets = match target.scoped_bucket.as_deref().filter(|b| !b.is_empty()) {
+            Some(bucket) => vec![bucket.to_string()],
+            None => match s3::list_buckets(&target, &credentials).await {
+                Ok(buckets) => buckets.into_iter().map(|b| b.name).collect(),
+                Err(e) => {
+                    log::warn!("Skipping multipart sweep for target {}: {e}", target.id);
+                    continue;
+                }
+            },
+        };
+
+        for bucket in buckets {
+            match s3::abort_stale_multipart_uploads(&client, &bucket, settings.multipart_sweep_days).await {
+                Ok(result) => {
+                    if result.aborted > 0 || !result.errors.is_empty() {
+                        let outcome = audit::outcome(&result.errors);
+                        audit::record_prefix(storage, audit::SCHEDULE, "abortMultipart", &target.id, &bucket, "", &outcome);
+                    }
+                    total.aborted += result.aborted;
+                    total.errors.extend(result.errors);
+                }
+                Err(e) => total.errors.push(format!("{bucket}: {e}")),
+            }
+        }
+    }
+
+    Ok(Some(total))
+}
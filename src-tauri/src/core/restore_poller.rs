@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::Client;
+use tauri::{AppHandle, Emitter};
+
+use crate::core::s3;
+use crate::core::storage::repositories::{credentials_repo, restore_repo, targets_repo};
+use crate::core::storage::sqlite::SqliteStorage;
+
+// Standard restores take hours, so there is no point in checking often
+const POLL_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Check pending archive restores every few minutes for the lifetime of the
+/// app, emitting `object-restore-complete` as objects become readable.
+pub async fn run(app: AppHandle, storage: Arc<SqliteStorage>) {
+    loop {
+        if let Err(e) = check_pending(&app, &storage).await {
+            log::error!("Restore status check failed: {e}");
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// HEAD every object with a pending restore and record where it stands.
+/// Returns how many restores completed.
+pub async fn check_pending(app: &AppHandle, storage: &SqliteStorage) -> Result<usize> {
+    let pending = restore_repo::list_pending(storage)?;
+    let mut clients: HashMap<String, Option<(Client, s3::ClientEncryption)>> = HashMap::new();
+    let mut completed = 0;
+
+    for restore in pending {
+        if !clients.contains_key(&restore.target_id) {
+            let client = match build_client(storage, &restore.target_id).await {
+                Ok(client) => Some(client),
+                Err(e) => {
+                    log::warn!("Skipping restore checks for target {}: {e}", restore.target_id);
+                    None
+                }
+            };
+            clients.insert(restore.target_id.clone(), client);
+        }
+        let Some((client, cse)) = clients.get(&restore.target_id).and_then(|c| c.as_ref()) else {
+            continue;
+        };
+
+        // Only a missing object ends the restore; other errors may pass
+        // and are recorded for the next check to clear
+        let status = match s3::restore_status(
+            client,
+            &restore.bucket,
+            &cse.encode_key(&restore.key),
+            restore.version_id.as_deref(),
+        )
+        .await
+        {
+            Ok(Some(status)) => status,
+            Ok(None) => {
+                let message = "The object no longer exists";
+                restore_repo::update_status(storage, &restore.id, "failed", None, Some(message))?;
+                continue;
+            }
+            Err(e) => {
+                log::warn!("Restore check failed for {}/{}: {e}", restore.bucket, restore.key);
+                restore_repo::update_status(storage, &restore.id, "pending", None, Some(&e.to_string()))?;
+                continue;
+            }
+        };
+
+        match status.ongoing {
+            Some(true) => {
+                restore_repo::update_status(storage, &restore.id, "pending", None, None)?;
+            }
+            // The restored copy has already been removed again
+            None if s3::is_archived_class(status.storage_class.as_deref()) => {
+                restore_repo::update_status(storage, &restore.id, "expired", None, None)?;
+            }
+            // Restored, or moved out of the archive tier altogether
+            _ => {
+                restore_repo::update_status(
+                    storage,
+                    &restore.id,
+                    "restored",
+                    status.expiry_date.as_deref(),
+                    None,
+                )?;
+                completed += 1;
+                let mut restored = restore.clone();
+                restored.status = "restored".to_string();
+                restored.expiry_date = status.expiry_date;
+                let _ = app.emit("object-restore-complete", &restored);
+            }
+        }
+    }
+
+    Ok(completed)
+}
+
+async fn build_client(storage: &SqliteStorage, target_id: &str) -> Result<(Client, s3::ClientEncryption)> {
+    let target = targets_repo::find_by_id(storage, target_id)?
+        .ok_or_else(|| anyhow!("Target not found"))?;
+    let creds = credentials_repo::get(storage, target_id)?
+        .ok_or_else(|| anyhow!("Target credentials not found"))?;
+    let cse = s3::ClientEncryption::for_target(&target, &creds)?;
+    Ok((s3::build_client(&target, &creds).await?, cse))
+}
@@ -0,0 +1,502 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::error::ProvideErrorMetadata;
+use aws_sdk_s3::types::{Permission, PublicAccessBlockConfiguration};
+use aws_sdk_s3::Client;
+use serde_json::Value;
+
+use super::build_client;
+use crate::models::{
+    BucketAccessSummary, BucketReader, PublicAccessBlock, StorageTarget, TargetCredentials,
+};
+
+const MAX_POLICY_BYTES: usize = 20 * 1024;
+const ALL_USERS_URI: &str = "http://acs.amazonaws.com/groups/global/AllUsers";
+const AUTHENTICATED_USERS_URI: &str = "http://acs.amazonaws.com/groups/global/AuthenticatedUsers";
+
+// --- Bucket policy ---
+
+/// The bucket policy JSON, or `None` if the bucket has no policy.
+pub async fn get_bucket_policy(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<Option<String>> {
+    let client = build_client(target, credentials).await?;
+    fetch_policy(&client, bucket).await
+}
+
+async fn fetch_policy(client: &Client, bucket: &str) -> Result<Option<String>> {
+    match client.get_bucket_policy().bucket(bucket).send().await {
+        Ok(output) => Ok(output.policy().map(|p| p.to_string())),
+        Err(e) if e.code() == Some("NoSuchBucketPolicy") => Ok(None),
+        Err(e) => Err(anyhow!("S3 get bucket policy failed: {e}")),
+    }
+}
+
+/// Validate `policy` and set it as the bucket policy.
+pub async fn put_bucket_policy(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    policy: &str,
+) -> Result<()> {
+    validate_bucket_policy(policy, bucket)?;
+
+    let client = build_client(target, credentials).await?;
+    client
+        .put_bucket_policy()
+        .bucket(bucket)
+        .policy(policy)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 put bucket policy failed: {e}"))?;
+    Ok(())
+}
+
+pub async fn delete_bucket_policy(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<()> {
+    let client = build_client(target, credentials).await?;
+    client
+        .delete_bucket_policy()
+        .bucket(bucket)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 delete bucket policy failed: {e}"))?;
+    Ok(())
+}
+
+/// Check that `policy` is a well-formed bucket policy for `bucket`: valid
+/// JSON, known statement elements, S3 actions only, and resources inside
+/// this bucket.
+pub fn validate_bucket_policy(policy: &str, bucket: &str) -> Result<()> {
+    if policy.len() > MAX_POLICY_BYTES {
+        return Err(anyhow!("Bucket policies are limited to {MAX_POLICY_BYTES} bytes"));
+    }
+    let doc: Value = serde_json::from_str(policy).map_err(|e| anyhow!("Policy is not valid JSON: {e}"))?;
+    let doc = doc
+        .as_object()
+        .ok_or_else(|| anyhow!("Policy must be a JSON object"))?;
+
+    for key in doc.keys() {
+        if !matches!(key.as_str(), "Version" | "Id" | "Statement") {
+            return Err(anyhow!("Unknown policy element \"{key}\""));
+        }
+    }
+    if let Some(version) = doc.get("Version") {
+        if !matches!(version.as_str(), Some("2012-10-17" | "2008-10-17")) {
+            return Err(anyhow!("Version must be \"2012-10-17\" or \"2008-10-17\""));
+        }
+    }
+
+    let statements = statements(doc.get("Statement").unwrap_or(&Value::Null));
+    if statements.is_empty() {
+        return Err(anyhow!("Policy must have at least one statement"));
+    }
+
+    let mut sids = HashSet::new();
+    for (index, statement) in statements.iter().enumerate() {
+        let label = match statement.get("Sid").and_then(Value::as_str) {
+            Some(sid) => {
+                if !sids.insert(sid) {
+                    return Err(anyhow!("Duplicate statement Sid \"{sid}\""));
+                }
+                format!("Statement \"{sid}\"")
+            }
+            None => format!("Statement {}", index + 1),
+        };
+        validate_statement(statement, bucket).map_err(|e| anyhow!("{label}: {e}"))?;
+    }
+    Ok(())
+}
+
+fn validate_statement(statement: &Value, bucket: &str) -> Result<()> {
+    let statement = statement
+        .as_object()
+        .ok_or_else(|| anyhow!("must be a JSON object"))?;
+
+    for key in statement.keys() {
+        if !matches!(
+            key.as_str(),
+            "Sid" | "Effect" | "Principal" | "NotPrincipal" | "Action" | "NotAction"
+                | "Resource" | "NotResource" | "Condition"
+        ) {
+            return Err(anyhow!("unknown element \"{key}\""));
+        }
+    }
+    if statement.get("Sid").is_some_and(|sid| !sid.is_string()) {
+        return Err(anyhow!("Sid must be a string"));
+    }
+    if !matches!(statement.get("Effect").and_then(Value::as_str), Some("Allow" | "Deny")) {
+        return Err(anyhow!("Effect must be \"Allow\" or \"Deny\""));
+    }
+
+    let principal = exactly_one(statement, "Principal", "NotPrincipal")?;
+    validate_principal(principal)?;
+
+    let actions = strings(exactly_one(statement, "Action", "NotAction")?)
+        .ok_or_else(|| anyhow!("Action must be a string or a list of strings"))?;
+    if actions.is_empty() {
+        return Err(anyhow!("Action must not be empty"));
+    }
+    for action in actions {
+        if action != "*" && !action.to_ascii_lowercase().starts_with("s3:") {
+            return Err(anyhow!("\"{action}\" is not an S3 action"));
+        }
+    }
+
+    let resources = strings(exactly_one(statement, "Resource", "NotResource")?)
+        .ok_or_else(|| anyhow!("Resource must be a string or a list of strings"))?;
+    if resources.is_empty() {
+        return Err(anyhow!("Resource must not be empty"));
+    }
+    for resource in resources {
+        if resource == "*" {
+            continue;
+        }
+        let resource_bucket = resource
+            .split_once(":s3:::")
+            .filter(|(partition, _)| partition.starts_with("arn:"))
+            .map(|(_, rest)| rest.split('/').next().unwrap_or_default())
+            .ok_or_else(|| anyhow!("\"{resource}\" is not an S3 resource ARN"))?;
+        if !wildcard_match(resource_bucket, bucket) {
+            return Err(anyhow!("\"{resource}\" is not in bucket {bucket}"));
+        }
+    }
+
+    if let Some(condition) = statement.get("Condition") {
+        let valid = condition
+            .as_object()
+            .is_some_and(|ops| ops.values().all(Value::is_object));
+        if !valid {
+            return Err(anyhow!("Condition must map operators to key/value objects"));
+        }
+    }
+    Ok(())
+}
+
+fn exactly_one<'a>(
+    statement: &'a serde_json::Map<String, Value>,
+    key: &str,
+    not_key: &str,
+) -> Result<&'a Value> {
+    match (statement.get(key), statement.get(not_key)) {
+        (Some(value), None) | (None, Some(value)) => Ok(value),
+        (Some(_), Some(_)) => Err(anyhow!("cannot have both {key} and {not_key}")),
+        (None, None) => Err(anyhow!("needs {key} or {not_key}")),
+    }
+}
+
+fn validate_principal(principal: &Value) -> Result<()> {
+    if principal.as_str() == Some("*") {
+        return Ok(());
+    }
+    let map = principal
+        .as_object()
+        .filter(|m| !m.is_empty())
+        .ok_or_else(|| anyhow!("Principal must be \"*\" or an object such as {{\"AWS\": ...}}"))?;
+    for (kind, value) in map {
+        if !matches!(kind.as_str(), "AWS" | "Service" | "Federated" | "CanonicalUser") {
+            return Err(anyhow!("unknown principal type \"{kind}\""));
+        }
+        if strings(value).is_none_or(|v| v.is_empty()) {
+            return Err(anyhow!("{kind} principal must be a string or a list of strings"));
+        }
+    }
+    Ok(())
+}
+
+/// The policy's statements, whether given as one object or a list.
+fn statements(value: &Value) -> Vec<&Value> {
+    match value {
+        Value::Array(items) => items.iter().collect(),
+        Value::Object(_) => vec![value],
+        _ => Vec::new(),
+    }
+}
+
+/// A string or list of strings as a list, or `None` for anything else.
+fn strings(value: &Value) -> Option<Vec<&str>> {
+    match value {
+        Value::String(s) => Some(vec![s.as_str()]),
+        Value::Array(items) => items.iter().map(Value::as_str).collect(),
+        _ => None,
+    }
+}
+
+/// Case-insensitive IAM-style match where `*` matches any run of
+/// characters and `?` any single character.
+fn wildcard_match(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_ascii_lowercase().chars().collect();
+    let value: Vec<char> = value.to_ascii_lowercase().chars().collect();
+    let (mut p, mut v) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            p += 1;
+            v += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, v));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            v = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+// --- Public access block ---
+
+/// The bucket's public access block, or `None` if it has none.
+pub async fn get_public_access_block(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<Option<PublicAccessBlock>> {
+    let client = build_client(target, credentials).await?;
+    fetch_public_access_block(&client, bucket).await
+}
+
+async fn fetch_public_access_block(client: &Client, bucket: &str) -> Result<Option<PublicAccessBlock>> {
+    let output = match client.get_public_access_block().bucket(bucket).send().await {
+        Ok(output) => output,
+        Err(e) if e.code() == Some("NoSuchPublicAccessBlockConfiguration") => return Ok(None),
+        Err(e) => return Err(anyhow!("S3 get public access block failed: {e}")),
+    };
+
+    Ok(output.public_access_block_configuration().map(|c| PublicAccessBlock {
+        block_public_acls: c.block_public_acls().unwrap_or(false),
+        ignore_public_acls: c.ignore_public_acls().unwrap_or(false),
+        block_public_policy: c.block_public_policy().unwrap_or(false),
+        restrict_public_buckets: c.restrict_public_buckets().unwrap_or(false),
+    }))
+}
+
+pub async fn put_public_access_block(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    settings: &PublicAccessBlock,
+) -> Result<()> {
+    let configuration = PublicAccessBlockConfiguration::builder()
+        .block_public_acls(settings.block_public_acls)
+        .ignore_public_acls(settings.ignore_public_acls)
+        .block_public_policy(settings.block_public_policy)
+        .restrict_public_buckets(settings.restrict_public_buckets)
+        .build();
+
+    let client = build_client(target, credentials).await?;
+    client
+        .put_public_access_block()
+        .bucket(bucket)
+        .public_access_block_configuration(configuration)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 put public access block failed: {e}"))?;
+    Ok(())
+}
+
+pub async fn delete_public_access_block(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<()> {
+    let client = build_client(target, credentials).await?;
+    client
+        .delete_public_access_block()
+        .bucket(bucket)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 delete public access block failed: {e}"))?;
+    Ok(())
+}
+
+// --- Access summary ---
+
+/// Who can read `bucket`, from its policy, ACL and public access block.
+/// Parts the provider does not support or the credentials cannot read are
+/// reported as warnings instead of failing the summary.
+pub async fn get_bucket_access_summary(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<BucketAccessSummary> {
+    let client = build_client(target, credentials).await?;
+    let mut warnings = Vec::new();
+
+    let public_access_block = fetch_public_access_block(&client, bucket)
+        .await
+        .unwrap_or_else(|e| {
+            warnings.push(e.to_string());
+            None
+        });
+    let pab = public_access_block.clone().unwrap_or(PublicAccessBlock {
+        block_public_acls: false,
+        ignore_public_acls: false,
+        block_public_policy: false,
+        restrict_public_buckets: false,
+    });
+
+    let mut readers = Vec::new();
+    let mut has_deny_statements = false;
+    match fetch_policy(&client, bucket).await {
+        Ok(Some(policy)) => match serde_json::from_str::<Value>(&policy) {
+            Ok(doc) => {
+                has_deny_statements = statements(doc.get("Statement").unwrap_or(&Value::Null))
+                    .iter()
+                    .any(|s| s.get("Effect").and_then(Value::as_str) == Some("Deny"));
+                readers.extend(policy_readers(&doc, &pab));
+            }
+            Err(e) => warnings.push(format!("Bucket policy is not valid JSON: {e}")),
+        },
+        Ok(None) => {}
+        Err(e) => warnings.push(e.to_string()),
+    }
+
+    match client.get_bucket_acl().bucket(bucket).send().await {
+        Ok(output) => {
+            for grant in output.grants() {
+                let Some(grantee) = grant.grantee() else { continue };
+                let permissions: Vec<String> = match grant.permission() {
+                    Some(Permission::Read) | Some(Permission::FullControl) => vec!["list".to_string()],
+                    _ => continue,
+                };
+                let is_public = grantee.uri() == Some(ALL_USERS_URI);
+                let principal = match grantee.uri() {
+                    Some(ALL_USERS_URI) => "*".to_string(),
+                    Some(AUTHENTICATED_USERS_URI) => "Any authenticated AWS user".to_string(),
+                    Some(uri) => uri.to_string(),
+                    None => grantee
+                        .display_name()
+                        .or(grantee.email_address())
+                        .or(grantee.id())
+                        .unwrap_or("unknown")
+                        .to_string(),
+                };
+                readers.push(BucketReader {
+                    blocked: (is_public || grantee.uri() == Some(AUTHENTICATED_USERS_URI))
+                        && pab.ignore_public_acls,
+                    principal,
+                    source: "acl".to_string(),
+                    permissions,
+                    resources: vec![format!("arn:aws:s3:::{bucket}")],
+                    conditional: false,
+                    is_public,
+                });
+            }
+        }
+        Err(e) => warnings.push(format!("S3 get bucket ACL failed: {e}")),
+    }
+
+    let policy_is_public = client
+        .get_bucket_policy_status()
+        .bucket(bucket)
+        .send()
+        .await
+        .ok()
+        .and_then(|o| o.policy_status().and_then(|s| s.is_public()));
+
+    let effective_public = |permission: &str| {
+        readers.iter().any(|r| {
+            r.is_public && !r.blocked && !r.conditional && r.permissions.iter().any(|p| p == permission)
+        })
+    };
+    Ok(BucketAccessSummary {
+        public_read: effective_public("read"),
+        public_list: effective_public("list"),
+        readers,
+        policy_is_public,
+        public_access_block,
+        has_deny_statements,
+        warnings,
+    })
+}
+
+/// Readers granted by the policy's Allow statements. A statement grants
+/// "read" if its actions cover s3:GetObject and "list" if they cover
+/// s3:ListBucket.
+fn policy_readers(doc: &Value, pab: &PublicAccessBlock) -> Vec<BucketReader> {
+    let mut readers = Vec::new();
+
+    for statement in statements(doc.get("Statement").unwrap_or(&Value::Null)) {
+        if statement.get("Effect").and_then(Value::as_str) != Some("Allow") {
+            continue;
+        }
+
+        let covers = |action: &str| match (statement.get("Action"), statement.get("NotAction")) {
+            (Some(actions), _) => strings(actions)
+                .unwrap_or_default()
+                .iter()
+                .any(|pattern| wildcard_match(pattern, action)),
+            (None, Some(not_actions)) => !strings(not_actions)
+                .unwrap_or_default()
+                .iter()
+                .any(|pattern| wildcard_match(pattern, action)),
+            (None, None) => false,
+        };
+        let mut permissions = Vec::new();
+        if covers("s3:GetObject") {
+            permissions.push("read".to_string());
+        }
+        if covers("s3:ListBucket") {
+            permissions.push("list".to_string());
+        }
+        if permissions.is_empty() {
+            continue;
+        }
+
+        let resources: Vec<String> = statement
+            .get("Resource")
+            .and_then(strings)
+            .unwrap_or_else(|| vec!["*"])
+            .into_iter()
+            .map(|r| r.to_string())
+            .collect();
+        let conditional = statement.get("Condition").is_some();
+
+        // NotPrincipal with Allow grants everyone except the listed principals
+        let principals: Vec<(String, bool)> = match (statement.get("Principal"), statement.get("NotPrincipal")) {
+            (Some(Value::String(s)), _) if s == "*" => vec![("*".to_string(), true)],
+            (Some(Value::Object(map)), _) => map
+                .iter()
+                .flat_map(|(kind, value)| {
+                    strings(value)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(move |p| {
+                            if p == "*" && kind == "AWS" {
+                                ("*".to_string(), true)
+                            } else {
+                                (format!("{kind}: {p}"), false)
+                            }
+                        })
+                })
+                .collect(),
+            (None, Some(_)) => vec![("* (except NotPrincipal)".to_string(), true)],
+            _ => Vec::new(),
+        };
+
+        for (principal, is_public) in principals {
+            readers.push(BucketReader {
+                principal,
+                source: "policy".to_string(),
+                permissions: permissions.clone(),
+                resources: resources.clone(),
+                conditional,
+                is_public,
+                blocked: is_public && pab.restrict_public_buckets,
+            });
+        }
+    }
+
+    readers
+}
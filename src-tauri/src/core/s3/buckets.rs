@@ -0,0 +1,64 @@
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::types::{BucketLocationConstraint, CreateBucketConfiguration};
+
+use super::build_client;
+use crate::models::{StorageTarget, TargetCredentials};
+
+/// Create `bucket`, in `region` if given or the target's region otherwise.
+/// Object lock can only be turned on at creation time, and it also enables
+/// versioning.
+pub async fn create_bucket(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    region: Option<&str>,
+    object_lock: bool,
+) -> Result<()> {
+    let region = region.map(str::trim).filter(|r| !r.is_empty());
+    let mut target = target.clone();
+    if let Some(region) = region {
+        target.region = Some(region.to_string());
+    }
+    let client = build_client(&target, credentials).await?;
+
+    // us-east-1 is the default location and must not be sent as a constraint
+    let location = target
+        .region
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty() && *r != "us-east-1" && *r != "auto");
+    let configuration = location.map(|r| {
+        CreateBucketConfiguration::builder()
+            .location_constraint(BucketLocationConstraint::from(r))
+            .build()
+    });
+
+    let mut req = client
+        .create_bucket()
+        .bucket(bucket)
+        .set_create_bucket_configuration(configuration);
+    if object_lock {
+        req = req.object_lock_enabled_for_bucket(true);
+    }
+    req.send()
+        .await
+        .map_err(|e| anyhow!("S3 create bucket failed: {e}"))?;
+    Ok(())
+}
+
+/// Delete `bucket`. S3 refuses unless it holds no objects, versions or
+/// delete markers.
+pub async fn delete_bucket(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<()> {
+    let client = build_client(target, credentials).await?;
+    client
+        .delete_bucket()
+        .bucket(bucket)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 delete bucket failed: {e}"))?;
+    Ok(())
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use aws_sdk_s3::types::ServerSideEncryption as SseAlgorithm;
+use md5::{Digest, Md5};
+
+use super::{build_client, cse, headers, send_read, ClientEncryption, Encryption};
+use crate::models::{StorageTarget, TargetCredentials};
+
+/// What is known about a remote object's content without downloading it.
+pub struct ObjectFingerprint {
+    /// Size of the readable content.
+    pub size: i64,
+    pub etag: Option<String>,
+    /// Hex MD5 recorded in the object's metadata at upload.
+    pub md5: Option<String>,
+    /// Whether the ETag is of ciphertext rather than of the content.
+    pub client_encrypted: bool,
+}
+
+/// Hex MD5 of `data`.
+pub fn md5_hex(data: &[u8]) -> String {
+    format!("{:x}", Md5::digest(data))
+}
+
+/// Hex MD5 of a local file.
+pub fn file_md5(path: &str) -> Result<String> {
+    let mut file = File::open(path).map_err(|e| anyhow!("Failed to read file {path}: {e}"))?;
+    let mut hasher = Md5::new();
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let n = file.read(&mut buf).map_err(|e| anyhow!("Failed to read file {path}: {e}"))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// The ETag S3 gives a multipart upload of a local file in parts of
+/// `part_size` bytes: the MD5 of the parts' MD5s, a dash and the part count.
+pub fn file_multipart_etag(path: &str, part_size: i64) -> Result<String> {
+    let mut file = File::open(path).map_err(|e| anyhow!("Failed to read file {path}: {e}"))?;
+    let mut digests = Md5::new();
+    let mut part = Md5::new();
+    let mut part_len: i64 = 0;
+    let mut parts = 0;
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let want = std::cmp::min(buf.len() as i64, part_size - part_len) as usize;
+        let n = file
+            .read(&mut buf[..want])
+            .map_err(|e| anyhow!("Failed to read file {path}: {e}"))?;
+        if n == 0 {
+            break;
+        }
+        part.update(&buf[..n]);
+        part_len += n as i64;
+        if part_len == part_size {
+            digests.update(part.finalize_reset());
+            part_len = 0;
+            parts += 1;
+        }
+    }
+    if part_len > 0 || parts == 0 {
+        digests.update(part.finalize());
+        parts += 1;
+    }
+    Ok(format!("{:x}-{parts}", digests.finalize()))
+}
+
+/// An ETag without its surrounding quotes.
+pub fn unquote_etag(etag: &str) -> &str {
+    etag.trim_matches('"')
+}
+
+/// The MD5 recorded in an object's metadata, if any.
+pub fn stored_md5(metadata: Option<&HashMap<String, String>>) -> Option<String> {
+    metadata?.get(headers::MD5_METADATA_KEY).cloned()
+}
+
+/// Whether `etag` has the shape of a hex MD5 digest.
+fn is_md5_hex(etag: &str) -> bool {
+    etag.len() == 32 && etag.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Hex MD5 of an object's readable content, if known: its ETag when that
+/// is a plain digest (single-part, not KMS, customer-key or client-side
+/// encrypted), otherwise the MD5 recorded at upload. Some providers send
+/// ETags that are not MD5s at all, so only 32 hex digits are trusted.
+pub fn content_md5(head: &HeadObjectOutput) -> Option<String> {
+    let encrypted = head.sse_customer_algorithm().is_some()
+        || matches!(head.server_side_encryption(), Some(SseAlgorithm::AwsKms | SseAlgorithm::AwsKmsDsse))
+        || cse::plaintext_size(head.metadata()).is_some();
+    head.e_tag()
+        .map(unquote_etag)
+        .filter(|etag| !encrypted && is_md5_hex(etag))
+        .map(str::to_ascii_lowercase)
+        .or_else(|| stored_md5(head.metadata()))
+}
+
+/// Size, ETag and recorded MD5 of an object, or `None` if it does not exist.
+pub async fn object_fingerprint(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+) -> Result<Option<ObjectFingerprint>> {
+    let encryption = Encryption::resolve(target, credentials, None)?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
+    let client = build_client(target, credentials).await?;
+    let stored_key = cse.encode_key(key);
+    let head = send_read(&encryption, |encryption| {
+        encryption.apply_head(client.head_object()).bucket(bucket).key(&stored_key).send()
+    })
+    .await;
+    match head {
+        Ok((head, _)) => {
+            let plaintext_size = cse::plaintext_size(head.metadata());
+            Ok(Some(ObjectFingerprint {
+                size: plaintext_size.unwrap_or_else(|| head.content_length().unwrap_or(0)),
+                etag: head.e_tag().map(|s| unquote_etag(s).to_string()),
+                md5: stored_md5(head.metadata()),
+                client_encrypted: plaintext_size.is_some(),
+            }))
+        }
+        Err(e) => {
+            if let aws_sdk_s3::error::SdkError::ServiceError(service_err) = &e {
+                if service_err.err().is_not_found() {
+                    return Ok(None);
+                }
+            }
+            Err(anyhow!("S3 head object failed: {e}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, data: &[u8]) -> String {
+        let path = std::env::temp_dir().join(format!("mahzen-checksum-{}-{name}", std::process::id()));
+        std::fs::write(&path, data).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    fn etag_of(parts: &[&[u8]]) -> String {
+        let mut digests = Md5::new();
+        for part in parts {
+            digests.update(Md5::digest(part));
+        }
+        format!("{:x}-{}", digests.finalize(), parts.len())
+    }
+
+    #[test]
+    fn multipart_etag_hashes_each_part() {
+        let path = temp_file("uneven", b"0123456789");
+        assert_eq!(file_multipart_etag(&path, 4).unwrap(), etag_of(&[b"0123", b"4567", b"89"]));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn multipart_etag_has_no_empty_last_part() {
+        let path = temp_file("even", b"01234567");
+        assert_eq!(file_multipart_etag(&path, 4).unwrap(), etag_of(&[b"0123", b"4567"]));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn multipart_etag_of_small_and_empty_files_has_one_part() {
+        let path = temp_file("small", b"abc");
+        assert_eq!(file_multipart_etag(&path, 4).unwrap(), etag_of(&[b"abc"]));
+        std::fs::remove_file(path).unwrap();
+
+        let path = temp_file("empty", b"");
+        assert_eq!(file_multipart_etag(&path, 4).unwrap(), etag_of(&[b""]));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn content_md5_only_trusts_md5_shaped_etags() {
+        let head = |etag: &str| HeadObjectOutput::builder().e_tag(etag).build();
+        let md5 = "9e107d9d372bb6826bd81d3542a419d6";
+
+        assert_eq!(content_md5(&head(&format!("\"{md5}\""))).as_deref(), Some(md5));
+        assert_eq!(content_md5(&head("\"9E107D9D372BB6826BD81D3542A419D6\"")).as_deref(), Some(md5));
+        assert_eq!(content_md5(&head(&format!("\"{md5}-3\""))), None);
+        assert_eq!(content_md5(&head("\"0x8DC5A1B2C3D4E5F\"")), None);
+        assert_eq!(content_md5(&head("\"9e107d9d372bb6826bd81d3542a419dz\"")), None);
+
+        let recorded = HeadObjectOutput::builder()
+            .e_tag("\"not-an-md5\"")
+            .metadata(headers::MD5_METADATA_KEY, md5)
+            .build();
+        assert_eq!(content_md5(&recorded).as_deref(), Some(md5));
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::error::ProvideErrorMetadata;
+use aws_sdk_s3::types::{CorsConfiguration, CorsRule as SdkCorsRule};
+
+use super::build_client;
+use crate::models::{CorsRule, StorageTarget, TargetCredentials};
+
+const MAX_RULES: usize = 100;
+
+/// The bucket's CORS rules, or an empty list if it has none.
+pub async fn get_bucket_cors(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<Vec<CorsRule>> {
+    let client = build_client(target, credentials).await?;
+    let output = match client.get_bucket_cors().bucket(bucket).send().await {
+        Ok(output) => output,
+        Err(e) if e.code() == Some("NoSuchCORSConfiguration") => return Ok(Vec::new()),
+        Err(e) => return Err(anyhow!("S3 get bucket CORS failed: {e}")),
+    };
+
+    Ok(output
+        .cors_rules()
+        .iter()
+        .map(|rule| CorsRule {
+            id: rule.id().map(|s| s.to_string()),
+            allowed_methods: rule.allowed_methods().to_vec(),
+            allowed_origins: rule.allowed_origins().to_vec(),
+            allowed_headers: rule.allowed_headers().to_vec(),
+            expose_headers: rule.expose_headers().to_vec(),
+            max_age_seconds: rule.max_age_seconds(),
+        })
+        .collect())
+}
+
+/// Replace the bucket's CORS rules after validating them. An empty list
+/// removes the configuration.
+pub async fn put_bucket_cors(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    rules: &[CorsRule],
+) -> Result<()> {
+    if rules.is_empty() {
+        return delete_bucket_cors(target, credentials, bucket).await;
+    }
+    validate_cors_rules(rules)?;
+
+    let sdk_rules = rules
+        .iter()
+        .map(|rule| {
+            SdkCorsRule::builder()
+                .set_id(rule.id.clone().filter(|id| !id.is_empty()))
+                .set_allowed_methods(Some(rule.allowed_methods.clone()))
+                .set_allowed_origins(Some(rule.allowed_origins.clone()))
+                .set_allowed_headers(Some(rule.allowed_headers.clone()).filter(|h| !h.is_empty()))
+                .set_expose_headers(Some(rule.expose_headers.clone()).filter(|h| !h.is_empty()))
+                .set_max_age_seconds(rule.max_age_seconds)
+                .build()
+                .map_err(|e| anyhow!("Failed to build CORS rule: {e}"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let configuration = CorsConfiguration::builder()
+        .set_cors_rules(Some(sdk_rules))
+        .build()
+        .map_err(|e| anyhow!("Failed to build CORS configuration: {e}"))?;
+
+    let client = build_client(target, credentials).await?;
+    client
+        .put_bucket_cors()
+        .bucket(bucket)
+        .cors_configuration(configuration)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 put bucket CORS failed: {e}"))?;
+    Ok(())
+}
+
+pub async fn delete_bucket_cors(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<()> {
+    let client = build_client(target, credentials).await?;
+    client
+        .delete_bucket_cors()
+        .bucket(bucket)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 delete bucket CORS failed: {e}"))?;
+    Ok(())
+}
+
+pub fn validate_cors_rules(rules: &[CorsRule]) -> Result<()> {
+    if rules.len() > MAX_RULES {
+        return Err(anyhow!("A bucket can have at most {MAX_RULES} CORS rules"));
+    }
+
+    for (index, rule) in rules.iter().enumerate() {
+        let label = match rule.id.as_deref().filter(|id| !id.is_empty()) {
+            Some(id) => format!("CORS rule \"{id}\""),
+            None => format!("CORS rule {}", index + 1),
+        };
+        validate_rule(rule).map_err(|e| anyhow!("{label}: {e}"))?;
+    }
+    Ok(())
+}
+
+fn validate_rule(rule: &CorsRule) -> Result<()> {
+    if rule.id.as_ref().is_some_and(|id| id.len() > 255) {
+        return Err(anyhow!("ID must be at most 255 characters"));
+    }
+
+    if rule.allowed_methods.is_empty() {
+        return Err(anyhow!("needs at least one allowed method"));
+    }
+    for method in &rule.allowed_methods {
+        if !matches!(method.as_str(), "GET" | "PUT" | "POST" | "DELETE" | "HEAD") {
+            return Err(anyhow!("\"{method}\" is not an allowed method (GET, PUT, POST, DELETE or HEAD)"));
+        }
+    }
+
+    if rule.allowed_origins.is_empty() {
+        return Err(anyhow!("needs at least one allowed origin"));
+    }
+    for origin in &rule.allowed_origins {
+        check_wildcards("origin", origin)?;
+    }
+    for header in &rule.allowed_headers {
+        check_wildcards("header", header)?;
+    }
+    for header in &rule.expose_headers {
+        if header.trim().is_empty() || header.contains('*') {
+            return Err(anyhow!("exposed header \"{header}\" must be a header name without wildcards"));
+        }
+    }
+
+    if rule.max_age_seconds.is_some_and(|s| s < 0) {
+        return Err(anyhow!("max age cannot be negative"));
+    }
+    Ok(())
+}
+
+fn check_wildcards(kind: &str, value: &str) -> Result<()> {
+    if value.trim().is_empty() {
+        return Err(anyhow!("allowed {kind}s cannot be empty"));
+    }
+    if value.matches('*').count() > 1 {
+        return Err(anyhow!("allowed {kind} \"{value}\" can contain at most one \"*\""));
+    }
+    Ok(())
+}
@@ -0,0 +1,516 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use anyhow::{anyhow, Result};
+use base64::engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD};
+use base64::Engine;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng, Payload};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::models::{StorageTarget, TargetCredentials};
+
+/// User metadata marking an object written with client-side encryption,
+/// holding the format version.
+pub const CSE_METADATA_KEY: &str = "mahzen-cse";
+const CSE_VERSION: &str = "xchacha20poly1305-v1";
+/// The object's data key, wrapped with the target's key.
+const WRAPPED_KEY_METADATA_KEY: &str = "mahzen-cse-key";
+const NONCE_PREFIX_METADATA_KEY: &str = "mahzen-cse-nonce";
+/// Size of the plaintext, since the stored object is larger.
+const PLAINTEXT_SIZE_METADATA_KEY: &str = "mahzen-cse-size";
+
+/// Plaintext bytes per encrypted chunk. Each chunk grows by its tag.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+const TAG_SIZE: usize = 16;
+const ENCRYPTED_CHUNK_SIZE: usize = CHUNK_SIZE + TAG_SIZE;
+// Chunk nonces are this random prefix, the chunk index and a last-chunk flag
+const NONCE_PREFIX_SIZE: usize = 19;
+const NONCE_SIZE: usize = 24;
+const KEY_SIZE: usize = 32;
+
+/// A target's client-side (envelope) encryption. Every object gets a random
+/// data key, wrapped with the target's key and stored in the object's user
+/// metadata. The body is encrypted with XChaCha20-Poly1305 in chunks, so
+/// it can be streamed and truncation is detected.
+///
+/// Objects without the metadata marker are read as-is, so buckets holding
+/// both encrypted and plain objects keep working.
+#[derive(Clone)]
+pub struct ClientEncryption {
+    key: Option<[u8; KEY_SIZE]>,
+    encrypt_objects: bool,
+    encrypt_names: bool,
+}
+
+impl ClientEncryption {
+    /// The settings of `target`. A stored key is used for reading even while
+    /// encryption of new objects is turned off.
+    pub fn for_target(target: &StorageTarget, credentials: &TargetCredentials) -> Result<Self> {
+        let key = credentials
+            .client_encryption_key
+            .as_deref()
+            .filter(|k| !k.trim().is_empty())
+            .map(parse_key)
+            .transpose()?;
+        Ok(Self {
+            key,
+            encrypt_objects: target.client_encryption,
+            encrypt_names: target.client_encryption && target.encrypt_key_names,
+        })
+    }
+
+    fn key(&self) -> Result<&[u8; KEY_SIZE]> {
+        self.key
+            .as_ref()
+            .ok_or_else(|| anyhow!("This target has no client-side encryption key"))
+    }
+
+    /// An encryptor for a new object of `plaintext_size` bytes, with the user
+    /// metadata to store alongside it, or `None` if the target does not
+    /// encrypt new objects.
+    pub fn encryptor(&self, plaintext_size: u64) -> Result<Option<(ObjectEncryptor, HashMap<String, String>)>> {
+        if !self.encrypt_objects {
+            return Ok(None);
+        }
+        let wrapping = XChaCha20Poly1305::new_from_slice(self.key()?)
+            .map_err(|e| anyhow!("Invalid client-side encryption key: {e}"))?;
+
+        let mut data_key = [0u8; KEY_SIZE];
+        OsRng.fill_bytes(&mut data_key);
+        let mut wrap_nonce = [0u8; NONCE_SIZE];
+        OsRng.fill_bytes(&mut wrap_nonce);
+        let mut prefix = [0u8; NONCE_PREFIX_SIZE];
+        OsRng.fill_bytes(&mut prefix);
+
+        let wrapped = wrapping
+            .encrypt(
+                XNonce::from_slice(&wrap_nonce),
+                Payload { msg: &data_key, aad: CSE_VERSION.as_bytes() },
+            )
+            .map_err(|_| anyhow!("Failed to wrap the object's data key"))?;
+        let mut wrapped_key = wrap_nonce.to_vec();
+        wrapped_key.extend_from_slice(&wrapped);
+
+        let metadata = HashMap::from([
+            (CSE_METADATA_KEY.to_string(), CSE_VERSION.to_string()),
+            (WRAPPED_KEY_METADATA_KEY.to_string(), BASE64.encode(wrapped_key)),
+            (NONCE_PREFIX_METADATA_KEY.to_string(), BASE64.encode(prefix)),
+            (PLAINTEXT_SIZE_METADATA_KEY.to_string(), plaintext_size.to_string()),
+        ]);
+        let encryptor = ObjectEncryptor {
+            cipher: XChaCha20Poly1305::new_from_slice(&data_key)
+                .map_err(|e| anyhow!("Invalid data key: {e}"))?,
+            prefix,
+            index: 0,
+        };
+        Ok(Some((encryptor, metadata)))
+    }
+
+    /// A decryptor for an object with the given user metadata, or `None` if
+    /// it was not written with client-side encryption.
+    pub fn decryptor(&self, metadata: Option<&HashMap<String, String>>) -> Result<Option<ObjectDecryptor>> {
+        let Some(metadata) = metadata.filter(|m| m.contains_key(CSE_METADATA_KEY)) else {
+            return Ok(None);
+        };
+        let version = &metadata[CSE_METADATA_KEY];
+        if version != CSE_VERSION {
+            return Err(anyhow!("Unsupported client-side encryption format: {version}"));
+        }
+        let field = |name: &str| {
+            metadata
+                .get(name)
+                .and_then(|v| BASE64.decode(v).ok())
+                .ok_or_else(|| anyhow!("Encrypted object is missing its {name} metadata"))
+        };
+        let wrapped_key = field(WRAPPED_KEY_METADATA_KEY)?;
+        let prefix: [u8; NONCE_PREFIX_SIZE] = field(NONCE_PREFIX_METADATA_KEY)?
+            .try_into()
+            .map_err(|_| anyhow!("Encrypted object has a malformed nonce"))?;
+        if wrapped_key.len() <= NONCE_SIZE {
+            return Err(anyhow!("Encrypted object has a malformed data key"));
+        }
+
+        let wrapping = XChaCha20Poly1305::new_from_slice(self.key()?)
+            .map_err(|e| anyhow!("Invalid client-side encryption key: {e}"))?;
+        let (wrap_nonce, wrapped) = wrapped_key.split_at(NONCE_SIZE);
+        let data_key = wrapping
+            .decrypt(
+                XNonce::from_slice(wrap_nonce),
+                Payload { msg: wrapped, aad: CSE_VERSION.as_bytes() },
+            )
+            .map_err(|_| anyhow!("The object was encrypted with a different key"))?;
+
+        Ok(Some(ObjectDecryptor {
+            cipher: XChaCha20Poly1305::new_from_slice(&data_key)
+                .map_err(|e| anyhow!("Invalid data key: {e}"))?,
+            prefix,
+            index: 0,
+            buffer: Vec::new(),
+        }))
+    }
+
+    pub fn encrypts_names(&self) -> bool {
+        self.key.is_some() && self.encrypt_names
+    }
+
+    /// The stored form of an object key. With name encryption on, each
+    /// `/`-separated segment is encrypted deterministically, so folders and
+    /// prefix listings keep working.
+    pub fn encode_key(&self, key: &str) -> String {
+        match (&self.key, self.encrypt_names) {
+            (Some(user_key), true) => {
+                let names = NameCipher::new(user_key);
+                key.split('/')
+                    .map(|segment| names.encrypt(segment))
+                    .collect::<Vec<_>>()
+                    .join("/")
+            }
+            _ => key.to_string(),
+        }
+    }
+
+    /// The readable form of a stored key. Segments that are not encrypted
+    /// names are left as they are.
+    pub fn decode_key(&self, key: &str) -> String {
+        match &self.key {
+            Some(user_key) => {
+                let names = NameCipher::new(user_key);
+                key.split('/')
+                    .map(|segment| names.decrypt(segment).unwrap_or_else(|| segment.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("/")
+            }
+            None => key.to_string(),
+        }
+    }
+}
+
+/// Size of the readable object, from its metadata, if it is encrypted.
+pub fn plaintext_size(metadata: Option<&HashMap<String, String>>) -> Option<i64> {
+    metadata?.get(PLAINTEXT_SIZE_METADATA_KEY)?.parse().ok()
+}
+
+/// The client-side encryption entries of `metadata`, which must survive a
+/// copy that replaces an object's metadata.
+pub fn envelope_metadata(metadata: Option<&HashMap<String, String>>) -> HashMap<String, String> {
+    metadata
+        .map(|m| {
+            m.iter()
+                .filter(|(k, _)| k.starts_with(CSE_METADATA_KEY))
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A new random key for a target, base64 encoded.
+pub fn generate_key() -> String {
+    let mut key = [0u8; KEY_SIZE];
+    OsRng.fill_bytes(&mut key);
+    BASE64.encode(key)
+}
+
+/// Check that `key` is a base64 encoded 256-bit key.
+pub fn parse_key(key: &str) -> Result<[u8; KEY_SIZE]> {
+    BASE64
+        .decode(key.trim())
+        .ok()
+        .and_then(|raw| raw.try_into().ok())
+        .ok_or_else(|| anyhow!("The client-side encryption key must be 256 bits, base64 encoded"))
+}
+
+fn chunk_nonce(prefix: &[u8; NONCE_PREFIX_SIZE], index: u32, last: bool) -> [u8; NONCE_SIZE] {
+    let mut nonce = [0u8; NONCE_SIZE];
+    nonce[..NONCE_PREFIX_SIZE].copy_from_slice(prefix);
+    nonce[NONCE_PREFIX_SIZE..NONCE_SIZE - 1].copy_from_slice(&index.to_be_bytes());
+    nonce[NONCE_SIZE - 1] = last as u8;
+    nonce
+}
+
+/// Encrypts one object's body, chunk by chunk.
+pub struct ObjectEncryptor {
+    cipher: XChaCha20Poly1305,
+    prefix: [u8; NONCE_PREFIX_SIZE],
+    index: u32,
+}
+
+impl ObjectEncryptor {
+    /// Encrypt the next part of the body. Every part but the `last` one must
+    /// be a whole number of chunks.
+    pub fn encrypt(&mut self, plaintext: &[u8], last: bool) -> Result<Vec<u8>> {
+        if !last && plaintext.len() % CHUNK_SIZE != 0 {
+            return Err(anyhow!("Encrypted parts must be a multiple of {CHUNK_SIZE} bytes"));
+        }
+
+        let mut chunks: Vec<&[u8]> = plaintext.chunks(CHUNK_SIZE).collect();
+        if last && chunks.is_empty() {
+            chunks.push(&[]);
+        }
+
+        let mut out = Vec::with_capacity(plaintext.len() + chunks.len() * TAG_SIZE);
+        let count = chunks.len();
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            let nonce = chunk_nonce(&self.prefix, self.index, last && i + 1 == count);
+            let sealed = self
+                .cipher
+                .encrypt(XNonce::from_slice(&nonce), chunk)
+                .map_err(|_| anyhow!("Failed to encrypt chunk {}", self.index))?;
+            out.extend_from_slice(&sealed);
+            self.index = self
+                .index
+                .checked_add(1)
+                .ok_or_else(|| anyhow!("Object is too large to encrypt"))?;
+        }
+        Ok(out)
+    }
+}
+
+/// Part size for an encrypted multipart upload: `part_size` rounded up to a
+/// whole number of chunks.
+pub fn encrypted_part_size(part_size: i64) -> i64 {
+    let chunk = CHUNK_SIZE as i64;
+    (part_size + chunk - 1) / chunk * chunk
+}
+
+/// Decrypts one object's body as it streams in.
+pub struct ObjectDecryptor {
+    cipher: XChaCha20Poly1305,
+    prefix: [u8; NONCE_PREFIX_SIZE],
+    index: u32,
+    buffer: Vec<u8>,
+}
+
+impl ObjectDecryptor {
+    /// Feed the next bytes of the stored object, returning whatever
+    /// plaintext is complete. The final chunk is held back until `finish`.
+    pub fn push(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        self.buffer.extend_from_slice(data);
+        let mut out = Vec::new();
+        let mut consumed = 0;
+        while self.buffer.len() - consumed > ENCRYPTED_CHUNK_SIZE {
+            let chunk = &self.buffer[consumed..consumed + ENCRYPTED_CHUNK_SIZE];
+            out.extend_from_slice(&self.open(chunk, false)?);
+            self.index = self.index.wrapping_add(1);
+            consumed += ENCRYPTED_CHUNK_SIZE;
+        }
+        self.buffer.drain(..consumed);
+        Ok(out)
+    }
+
+    /// Decrypt the final chunk. Fails if the object was cut short.
+    pub fn finish(self) -> Result<Vec<u8>> {
+        self.open(&self.buffer, true)
+    }
+
+    fn open(&self, chunk: &[u8], last: bool) -> Result<Vec<u8>> {
+        let nonce = chunk_nonce(&self.prefix, self.index, last);
+        self.cipher
+            .decrypt(XNonce::from_slice(&nonce), chunk)
+            .map_err(|_| anyhow!("Encrypted object is corrupt or truncated at chunk {}", self.index))
+    }
+}
+
+/// Turns the stored bytes of an object on one target into its stored bytes
+/// on another, decrypting and/or encrypting along the way.
+pub struct Reencoder {
+    decryptor: Option<ObjectDecryptor>,
+    encryptor: Option<ObjectEncryptor>,
+    pending: Vec<u8>,
+}
+
+impl Reencoder {
+    pub fn new(decryptor: Option<ObjectDecryptor>, encryptor: Option<ObjectEncryptor>) -> Self {
+        Self { decryptor, encryptor, pending: Vec::new() }
+    }
+
+    /// Feed the next source bytes, returning the destination bytes that are
+    /// ready.
+    pub fn push(&mut self, data: &[u8]) -> Result<Vec<u8>> {
+        let plain = match self.decryptor.as_mut() {
+            Some(decryptor) => decryptor.push(data)?,
+            None => data.to_vec(),
+        };
+        let Some(encryptor) = self.encryptor.as_mut() else {
+            return Ok(plain);
+        };
+        self.pending.extend_from_slice(&plain);
+        let whole = self.pending.len() / CHUNK_SIZE * CHUNK_SIZE;
+        let out = encryptor.encrypt(&self.pending[..whole], false)?;
+        self.pending.drain(..whole);
+        Ok(out)
+    }
+
+    /// The remaining destination bytes once the whole source has been fed.
+    pub fn finish(mut self) -> Result<Vec<u8>> {
+        let mut plain = std::mem::take(&mut self.pending);
+        if let Some(decryptor) = self.decryptor.take() {
+            plain.extend_from_slice(&decryptor.finish()?);
+        }
+        match self.encryptor.as_mut() {
+            Some(encryptor) => encryptor.encrypt(&plain, true),
+            None => Ok(plain),
+        }
+    }
+}
+
+/// Decrypt the stored object at `source` into `dest`.
+pub fn decrypt_file(mut decryptor: ObjectDecryptor, source: &Path, dest: &Path) -> Result<()> {
+    let mut input = std::fs::File::open(source)
+        .map_err(|e| anyhow!("Failed to read downloaded file: {e}"))?;
+    let mut output = std::io::BufWriter::new(
+        std::fs::File::create(dest).map_err(|e| anyhow!("Failed to create {}: {e}", dest.display()))?,
+    );
+
+    let mut buf = vec![0u8; 1024 * 1024];
+    loop {
+        let n = input.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        output.write_all(&decryptor.push(&buf[..n])?)?;
+    }
+    output.write_all(&decryptor.finish()?)?;
+    output.flush()?;
+    Ok(())
+}
+
+/// Deterministic encryption of key segments. The nonce is derived from the
+/// segment itself, so the same name always maps to the same stored name.
+struct NameCipher {
+    cipher: XChaCha20Poly1305,
+    nonce_key: [u8; KEY_SIZE],
+}
+
+impl NameCipher {
+    fn new(user_key: &[u8; KEY_SIZE]) -> Self {
+        let cipher_key = derive(user_key, b"mahzen-cse name key");
+        Self {
+            cipher: XChaCha20Poly1305::new_from_slice(&cipher_key).expect("derived keys are 256 bits"),
+            nonce_key: derive(user_key, b"mahzen-cse name nonce"),
+        }
+    }
+
+    fn encrypt(&self, segment: &str) -> String {
+        if segment.is_empty() {
+            return String::new();
+        }
+        let nonce = &derive(&self.nonce_key, segment.as_bytes())[..NONCE_SIZE];
+        let Ok(sealed) = self.cipher.encrypt(XNonce::from_slice(nonce), segment.as_bytes()) else {
+            return segment.to_string();
+        };
+        let mut raw = nonce.to_vec();
+        raw.extend_from_slice(&sealed);
+        URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    fn decrypt(&self, segment: &str) -> Option<String> {
+        let raw = URL_SAFE_NO_PAD.decode(segment).ok()?;
+        if raw.len() < NONCE_SIZE + TAG_SIZE {
+            return None;
+        }
+        let (nonce, sealed) = raw.split_at(NONCE_SIZE);
+        let plain = self.cipher.decrypt(XNonce::from_slice(nonce), sealed).ok()?;
+        String::from_utf8(plain).ok()
+    }
+}
+
+fn derive(key: &[u8], label: &[u8]) -> [u8; KEY_SIZE] {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC takes keys of any size");
+    mac.update(label);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encryption(encrypt_names: bool) -> ClientEncryption {
+        ClientEncryption {
+            key: Some([7u8; KEY_SIZE]),
+            encrypt_objects: true,
+            encrypt_names,
+        }
+    }
+
+    #[test]
+    fn part_sizes_round_up_to_whole_chunks() {
+        let chunk = CHUNK_SIZE as i64;
+        assert_eq!(encrypted_part_size(1), chunk);
+        assert_eq!(encrypted_part_size(chunk), chunk);
+        assert_eq!(encrypted_part_size(chunk + 1), 2 * chunk);
+        assert_eq!(encrypted_part_size(16 * 1024 * 1024), 16 * 1024 * 1024);
+    }
+
+    #[test]
+    fn each_chunk_grows_by_its_tag() {
+        let (mut encryptor, _) = encryption(false).encryptor(0).unwrap().unwrap();
+        let body = vec![1u8; CHUNK_SIZE * 2 + 10];
+        assert!(encryptor.encrypt(&body[..10], false).is_err());
+        assert_eq!(encryptor.encrypt(&body, true).unwrap().len(), body.len() + 3 * TAG_SIZE);
+
+        let (mut encryptor, _) = encryption(false).encryptor(0).unwrap().unwrap();
+        assert_eq!(encryptor.encrypt(&[], true).unwrap().len(), TAG_SIZE);
+    }
+
+    #[test]
+    fn bodies_round_trip_in_any_pieces() {
+        let cse = encryption(false);
+        let body: Vec<u8> = (0..CHUNK_SIZE * 3 + 123).map(|i| i as u8).collect();
+        let (mut encryptor, metadata) = cse.encryptor(body.len() as u64).unwrap().unwrap();
+        let mut stored = encryptor.encrypt(&body[..CHUNK_SIZE * 2], false).unwrap();
+        stored.extend(encryptor.encrypt(&body[CHUNK_SIZE * 2..], true).unwrap());
+        assert_eq!(plaintext_size(Some(&metadata)), Some(body.len() as i64));
+
+        let mut decryptor = cse.decryptor(Some(&metadata)).unwrap().unwrap();
+        let mut plain = Vec::new();
+        for piece in stored.chunks(50_000) {
+            plain.extend(decryptor.push(piece).unwrap());
+        }
+        plain.extend(decryptor.finish().unwrap());
+        assert_eq!(plain, body);
+    }
+
+    #[test]
+    fn truncated_bodies_fail_to_decrypt() {
+        let cse = encryption(false);
+        let body = vec![9u8; CHUNK_SIZE * 2];
+        let (mut encryptor, metadata) = cse.encryptor(body.len() as u64).unwrap().unwrap();
+        let stored = encryptor.encrypt(&body, true).unwrap();
+
+        let mut decryptor = cse.decryptor(Some(&metadata)).unwrap().unwrap();
+        decryptor.push(&stored[..CHUNK_SIZE + TAG_SIZE]).unwrap();
+        assert!(decryptor.finish().is_err());
+    }
+
+    #[test]
+    fn keys_round_trip_segment_by_segment() {
+        let cse = encryption(true);
+        for key in ["a.txt", "photos/2024/a.jpg", "photos/", "a//b", "ünïcode/名前.txt"] {
+            let stored = cse.encode_key(key);
+            assert_ne!(stored, key);
+            assert_eq!(stored.matches('/').count(), key.matches('/').count());
+            assert_eq!(cse.encode_key(key), stored);
+            assert_eq!(cse.decode_key(&stored), key);
+        }
+        let folder = cse.encode_key("photos/");
+        assert!(cse.encode_key("photos/a.jpg").starts_with(&folder));
+    }
+
+    #[test]
+    fn plain_keys_are_left_alone() {
+        let cse = encryption(false);
+        assert_eq!(cse.encode_key("photos/a.jpg"), "photos/a.jpg");
+        assert_eq!(encryption(true).decode_key("photos/a.jpg"), "photos/a.jpg");
+        let no_key = ClientEncryption {
+            key: None,
+            encrypt_objects: false,
+            encrypt_names: true,
+        };
+        assert_eq!(no_key.encode_key("photos/a.jpg"), "photos/a.jpg");
+        assert!(!no_key.encrypts_names());
+    }
+}
@@ -0,0 +1,467 @@
+use std::collections::BTreeSet;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::Client;
+use serde::{Deserialize, Serialize};
+use tokio::fs::OpenOptions;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+
+use super::checksum;
+use super::cse::{self, ObjectDecryptor};
+use super::Encryption;
+use crate::core::bandwidth;
+
+/// Objects at least this large are downloaded as concurrent ranged GETs.
+pub const PARALLEL_DOWNLOAD_THRESHOLD: u64 = 64 * 1024 * 1024;
+const DOWNLOAD_PART_SIZE: u64 = 16 * 1024 * 1024;
+const DOWNLOAD_CONCURRENCY: usize = 6;
+
+/// Sidecar manifest (`<file>.part`) describing a partially downloaded file.
+/// Ranged downloads record which parts of the pre-allocated file have been
+/// written; sequential downloads use a single part spanning the whole object
+/// and resume from the current file length.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PartManifest {
+    etag: Option<String>,
+    size: u64,
+    part_size: u64,
+    completed_parts: BTreeSet<u64>,
+}
+
+impl PartManifest {
+    fn part_count(&self) -> u64 {
+        self.size.div_ceil(self.part_size)
+    }
+
+    fn part_range(&self, index: u64) -> (u64, u64) {
+        let start = index * self.part_size;
+        let end = std::cmp::min(start + self.part_size, self.size) - 1;
+        (start, end)
+    }
+}
+
+enum PartMessage {
+    Bytes(u64),
+    Done(u64),
+    Failed(u64, anyhow::Error),
+}
+
+/// Path of the temporary file a download is written to before it is
+/// renamed to its final destination.
+pub fn partial_path(dest: &Path) -> PathBuf {
+    let mut name = dest.as_os_str().to_owned();
+    name.push(".mahzen-partial");
+    PathBuf::from(name)
+}
+
+fn manifest_path(file_path: &Path) -> PathBuf {
+    let mut name = file_path.as_os_str().to_owned();
+    name.push(".part");
+    PathBuf::from(name)
+}
+
+async fn load_manifest(path: &Path) -> Option<PartManifest> {
+    let raw = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice(&raw).ok()
+}
+
+async fn save_manifest(path: &Path, manifest: &PartManifest) -> Result<()> {
+    let raw = serde_json::to_vec(manifest)?;
+    tokio::fs::write(path, raw)
+        .await
+        .map_err(|e| anyhow!("Failed to write download manifest: {e}"))
+}
+
+/// Check a downloaded file against the object's MD5. A mismatching file is
+/// kept as `partial` for the user to look at, but its manifest is dropped
+/// so the next attempt downloads it afresh.
+fn verify_md5(path: &Path, partial: &Path, expected_md5: Option<&str>) -> Result<()> {
+    let Some(expected) = expected_md5 else {
+        return Ok(());
+    };
+    let actual = checksum::file_md5(&path.to_string_lossy())?;
+    if actual != expected {
+        if path != partial {
+            let _ = std::fs::rename(path, partial);
+        }
+        let _ = std::fs::remove_file(manifest_path(partial));
+        return Err(anyhow!(
+            "Downloaded file does not match the object's MD5 (got {actual}, expected {expected}); it was kept as {}",
+            partial.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Verify a completed partial download, by size and by `expected_md5` if
+/// known, and move it to `dest`.
+pub fn finalize(partial: &Path, dest: &Path, size: u64, expected_md5: Option<&str>) -> Result<()> {
+    let written = std::fs::metadata(partial)
+        .map_err(|e| anyhow!("Failed to read downloaded file: {e}"))?
+        .len();
+    if written != size {
+        return Err(anyhow!(
+            "Downloaded file is incomplete: expected {size} bytes, got {written}"
+        ));
+    }
+    verify_md5(partial, partial, expected_md5)?;
+
+    std::fs::rename(partial, dest)
+        .map_err(|e| anyhow!("Failed to move download into place: {e}"))?;
+    let _ = std::fs::remove_file(manifest_path(partial));
+    Ok(())
+}
+
+/// Like `finalize`, for a client-side encrypted object: the verified
+/// ciphertext is decrypted into `dest` and then removed. `expected_md5` is
+/// of the plaintext.
+pub fn finalize_decrypted(
+    partial: &Path,
+    dest: &Path,
+    size: u64,
+    expected_md5: Option<&str>,
+    decryptor: ObjectDecryptor,
+) -> Result<()> {
+    let written = std::fs::metadata(partial)
+        .map_err(|e| anyhow!("Failed to read downloaded file: {e}"))?
+        .len();
+    if written != size {
+        return Err(anyhow!(
+            "Downloaded file is incomplete: expected {size} bytes, got {written}"
+        ));
+    }
+
+    let decrypted = partial.with_extension("mahzen-decrypting");
+    if let Err(e) = cse::decrypt_file(decryptor, partial, &decrypted) {
+        let _ = std::fs::remove_file(&decrypted);
+        return Err(e);
+    }
+    verify_md5(&decrypted, partial, expected_md5)?;
+    std::fs::rename(&decrypted, dest)
+        .map_err(|e| anyhow!("Failed to move download into place: {e}"))?;
+    let _ = std::fs::remove_file(partial);
+    let _ = std::fs::remove_file(manifest_path(partial));
+    Ok(())
+}
+
+/// Stream `key` into `file_path` with a single GET. If a previous attempt
+/// left a partial file for the same ETag, only the missing tail is requested.
+pub async fn download_sequential(
+    client: &Client,
+    target_id: &str,
+    bucket: &str,
+    key: &str,
+    version_id: Option<&str>,
+    encryption: &Encryption,
+    size: u64,
+    etag: Option<&str>,
+    file_path: &Path,
+    on_progress: &impl Fn(u64, u64),
+) -> Result<()> {
+    let manifest_file = manifest_path(file_path);
+
+    // Resuming is only safe when we can prove the object has not changed
+    let manifest = match etag {
+        Some(_) => load_manifest(&manifest_file)
+            .await
+            .filter(|m| m.size == size && m.part_size == size && m.etag.as_deref() == etag),
+        None => None,
+    };
+    let resume_from = match manifest {
+        Some(_) => tokio::fs::metadata(file_path)
+            .await
+            .ok()
+            .map(|md| md.len())
+            .filter(|&len| len <= size),
+        None => None,
+    };
+
+    let (mut file, mut downloaded) = match resume_from {
+        Some(offset) => {
+            let mut file = OpenOptions::new().write(true).open(file_path).await?;
+            file.seek(SeekFrom::Start(offset)).await?;
+            (file, offset)
+        }
+        None => {
+            let file = tokio::fs::File::create(file_path).await?;
+            save_manifest(
+                &manifest_file,
+                &PartManifest {
+                    etag: etag.map(|s| s.to_string()),
+                    size,
+                    part_size: size,
+                    completed_parts: BTreeSet::new(),
+                },
+            )
+            .await?;
+            (file, 0)
+        }
+    };
+
+    on_progress(downloaded, size);
+    if downloaded == size {
+        return Ok(());
+    }
+
+    let mut req = encryption
+        .apply_get(client.get_object())
+        .bucket(bucket)
+        .key(key)
+        .set_version_id(version_id.map(|v| v.to_string()));
+    if downloaded > 0 {
+        req = req.range(format!("bytes={downloaded}-"));
+    }
+    if let Some(etag) = etag {
+        req = req.if_match(etag);
+    }
+
+    let output = req
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 get object failed: {e}"))?;
+    // A server that ignores the range sends the whole object again
+    if downloaded > 0 && !is_range_response(output.content_range(), downloaded) {
+        return Err(anyhow!("The server did not return the requested range"));
+    }
+
+    let mut reader = output.body.into_async_read();
+    let mut buf = vec![0u8; 256 * 1024]; // 256 KB chunks
+    let mut last_emit = Instant::now();
+
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .await
+            .map_err(|e| anyhow!("Failed to read S3 stream: {e}"))?;
+        if n == 0 {
+            break;
+        }
+        bandwidth::throttle(&[target_id], n as u64).await;
+        file.write_all(&buf[..n]).await?;
+        downloaded += n as u64;
+
+        if last_emit.elapsed().as_millis() >= 50 || downloaded == size {
+            on_progress(downloaded, size);
+            last_emit = Instant::now();
+        }
+    }
+
+    file.flush().await?;
+
+    // Ensure final progress
+    on_progress(downloaded, size);
+    Ok(())
+}
+
+/// Download `key` into `file_path` using concurrent ranged GETs written in
+/// place into a pre-allocated file. Progress is checkpointed to a sidecar
+/// manifest so an interrupted download resumes with only the missing parts,
+/// provided the object's ETag and size are unchanged.
+pub async fn download_ranged(
+    client: &Client,
+    target_id: &str,
+    bucket: &str,
+    key: &str,
+    version_id: Option<&str>,
+    encryption: &Encryption,
+    size: u64,
+    etag: Option<&str>,
+    file_path: &Path,
+    on_progress: &impl Fn(u64, u64),
+) -> Result<()> {
+    let manifest_file = manifest_path(file_path);
+
+    let file_len = tokio::fs::metadata(file_path).await.map(|md| md.len()).ok();
+    let resumable = load_manifest(&manifest_file).await.filter(|m| {
+        etag.is_some()
+            && m.size == size
+            && m.part_size == DOWNLOAD_PART_SIZE
+            && m.etag.as_deref() == etag
+            && file_len == Some(size)
+    });
+
+    let mut manifest = match resumable {
+        Some(manifest) => manifest,
+        None => {
+            let file = tokio::fs::File::create(file_path).await?;
+            file.set_len(size).await?;
+            let manifest = PartManifest {
+                etag: etag.map(|s| s.to_string()),
+                size,
+                part_size: DOWNLOAD_PART_SIZE,
+                completed_parts: BTreeSet::new(),
+            };
+            save_manifest(&manifest_file, &manifest).await?;
+            manifest
+        }
+    };
+
+    let mut pending: Vec<u64> = (0..manifest.part_count())
+        .filter(|i| !manifest.completed_parts.contains(i))
+        .rev()
+        .collect();
+
+    let mut downloaded: u64 = manifest
+        .completed_parts
+        .iter()
+        .map(|&i| {
+            let (start, end) = manifest.part_range(i);
+            end - start + 1
+        })
+        .sum();
+    on_progress(downloaded, size);
+
+    let (tx, mut rx) = mpsc::unbounded_channel::<PartMessage>();
+    let mut workers = JoinSet::new();
+    let mut in_flight = 0usize;
+    let mut last_emit = Instant::now();
+
+    loop {
+        while in_flight < DOWNLOAD_CONCURRENCY {
+            let Some(index) = pending.pop() else {
+                break;
+            };
+            let (start, end) = manifest.part_range(index);
+            let client = client.clone();
+            let target_id = target_id.to_string();
+            let bucket = bucket.to_string();
+            let key = key.to_string();
+            let version_id = version_id.map(|v| v.to_string());
+            let encryption = encryption.clone();
+            let etag = manifest.etag.clone();
+            let path = file_path.to_path_buf();
+            let tx = tx.clone();
+
+            workers.spawn(async move {
+                let result = download_part(
+                    &client,
+                    &target_id,
+                    &bucket,
+                    &key,
+                    version_id.as_deref(),
+                    &encryption,
+                    etag.as_deref(),
+                    (start, end),
+                    &path,
+                    &tx,
+                )
+                .await;
+                let _ = match result {
+                    Ok(()) => tx.send(PartMessage::Done(index)),
+                    Err(e) => tx.send(PartMessage::Failed(index, e)),
+                };
+            });
+            in_flight += 1;
+        }
+
+        if in_flight == 0 {
+            break;
+        }
+
+        match rx.recv().await {
+            Some(PartMessage::Bytes(n)) => {
+                downloaded += n;
+                if last_emit.elapsed().as_millis() >= 50 {
+                    on_progress(downloaded, size);
+                    last_emit = Instant::now();
+                }
+            }
+            Some(PartMessage::Done(index)) => {
+                in_flight -= 1;
+                manifest.completed_parts.insert(index);
+                save_manifest(&manifest_file, &manifest).await?;
+            }
+            Some(PartMessage::Failed(index, e)) => {
+                workers.abort_all();
+                return Err(anyhow!("Ranged download of part {index} failed: {e}"));
+            }
+            None => break,
+        }
+    }
+
+    if manifest.completed_parts.len() as u64 != manifest.part_count() {
+        return Err(anyhow!("Ranged download of {key} ended with missing parts"));
+    }
+
+    on_progress(size, size);
+    Ok(())
+}
+
+async fn download_part(
+    client: &Client,
+    target_id: &str,
+    bucket: &str,
+    key: &str,
+    version_id: Option<&str>,
+    encryption: &Encryption,
+    etag: Option<&str>,
+    (start, end): (u64, u64),
+    file_path: &Path,
+    tx: &mpsc::UnboundedSender<PartMessage>,
+) -> Result<()> {
+    let mut req = encryption
+        .apply_get(client.get_object())
+        .bucket(bucket)
+        .key(key)
+        .set_version_id(version_id.map(|v| v.to_string()))
+        .range(format!("bytes={start}-{end}"));
+    if let Some(etag) = etag {
+        req = req.if_match(etag);
+    }
+
+    let output = req
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 ranged get failed: {e}"))?;
+    if !is_range_response(output.content_range(), start) {
+        return Err(anyhow!("The server did not return the requested range"));
+    }
+
+    let mut file = OpenOptions::new().write(true).open(file_path).await?;
+    file.seek(SeekFrom::Start(start)).await?;
+
+    let expected = end - start + 1;
+    let mut written: u64 = 0;
+    let mut reader = output.body.into_async_read();
+    let mut buf = vec![0u8; 256 * 1024];
+
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .await
+            .map_err(|e| anyhow!("Failed to read S3 stream: {e}"))?;
+        if n == 0 {
+            break;
+        }
+        if written + n as u64 > expected {
+            return Err(anyhow!("Server returned more data than requested"));
+        }
+        bandwidth::throttle(&[target_id], n as u64).await;
+        file.write_all(&buf[..n]).await?;
+        written += n as u64;
+        let _ = tx.send(PartMessage::Bytes(n as u64));
+    }
+
+    if written != expected {
+        return Err(anyhow!("Short read: expected {expected} bytes, got {written}"));
+    }
+
+    // The manifest marks the part done once this returns, so it has to be
+    // on disk by then
+    file.sync_data()
+        .await
+        .map_err(|e| anyhow!("Failed to flush downloaded part: {e}"))?;
+    Ok(())
+}
+
+/// Whether a GET answered with a partial response (206) starting at `start`,
+/// going by its Content-Range.
+fn is_range_response(content_range: Option<&str>, start: u64) -> bool {
+    content_range.is_some_and(|range| range.starts_with(&format!("bytes {start}-")))
+}
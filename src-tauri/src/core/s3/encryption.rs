@@ -0,0 +1,253 @@
+use std::future::Future;
+
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::config::http::HttpResponse;
+use aws_sdk_s3::error::SdkError;
+use aws_sdk_s3::operation::copy_object::builders::CopyObjectFluentBuilder;
+use aws_sdk_s3::operation::create_multipart_upload::builders::CreateMultipartUploadFluentBuilder;
+use aws_sdk_s3::operation::get_object::builders::GetObjectFluentBuilder;
+use aws_sdk_s3::operation::head_object::builders::HeadObjectFluentBuilder;
+use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use aws_sdk_s3::operation::put_object::builders::PutObjectFluentBuilder;
+use aws_sdk_s3::operation::upload_part::builders::UploadPartFluentBuilder;
+use aws_sdk_s3::operation::upload_part_copy::builders::UploadPartCopyFluentBuilder;
+use aws_sdk_s3::types::ServerSideEncryption as SseAlgorithm;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use md5::{Digest, Md5};
+
+use crate::models::{ServerSideEncryption, StorageTarget, TargetCredentials};
+
+/// Server-side encryption for requests to one target, resolved from the
+/// target's default and an optional per-operation override.
+///
+/// Writes send the full set of encryption headers. Reads (GET, HEAD,
+/// presign) only send the SSE-C key, since S3 needs it to decrypt, and only
+/// for objects written with SSE-C; see `send_read`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Encryption {
+    /// No encryption headers; the bucket's default encryption applies.
+    #[default]
+    None,
+    /// SSE-S3, with keys managed by S3.
+    S3,
+    /// SSE-KMS with the given key, or the account's AWS managed key.
+    Kms { key_id: Option<String> },
+    /// SSE-C with a customer-provided 256-bit key, base64 encoded.
+    Customer { key: String, key_md5: String },
+}
+
+impl Encryption {
+    /// `sse` if given, otherwise the target's default. An SSE-C override
+    /// without a key falls back to the key stored with the credentials.
+    pub fn resolve(
+        target: &StorageTarget,
+        credentials: &TargetCredentials,
+        sse: Option<&ServerSideEncryption>,
+    ) -> Result<Self> {
+        match sse {
+            Some(sse) => Self::from_settings(
+                &sse.mode,
+                sse.kms_key_id.as_deref(),
+                sse.customer_key.as_deref().or(credentials.sse_customer_key.as_deref()),
+            ),
+            None => Self::from_settings(
+                target.encryption_mode.as_deref().unwrap_or("none"),
+                target.encryption_kms_key_id.as_deref(),
+                credentials.sse_customer_key.as_deref(),
+            ),
+        }
+    }
+
+    /// Parse an encryption mode ("none", "sseS3", "sseKms" or "sseC") and
+    /// its key settings.
+    pub fn from_settings(mode: &str, kms_key_id: Option<&str>, customer_key: Option<&str>) -> Result<Self> {
+        match mode {
+            "none" => Ok(Self::None),
+            "sseS3" => Ok(Self::S3),
+            "sseKms" => Ok(Self::Kms {
+                key_id: kms_key_id.map(str::trim).filter(|k| !k.is_empty()).map(str::to_string),
+            }),
+            "sseC" => {
+                let key = customer_key
+                    .map(str::trim)
+                    .filter(|k| !k.is_empty())
+                    .ok_or_else(|| anyhow!("SSE-C needs a customer key"))?;
+                let raw = BASE64
+                    .decode(key)
+                    .map_err(|_| anyhow!("The SSE-C key must be base64 encoded"))?;
+                if raw.len() != 32 {
+                    return Err(anyhow!("The SSE-C key must be 256 bits (32 bytes), got {} bytes", raw.len()));
+                }
+                Ok(Self::Customer {
+                    key: key.to_string(),
+                    key_md5: BASE64.encode(Md5::digest(&raw)),
+                })
+            }
+            other => Err(anyhow!("Unknown encryption mode: {other}")),
+        }
+    }
+
+    /// The encryption an existing object was written with, so copying it
+    /// onto itself keeps it. SSE-C objects keep `customer`'s key, the only
+    /// one that can have been used to read them.
+    pub fn from_head(head: &HeadObjectOutput, customer: &Encryption) -> Self {
+        if head.sse_customer_algorithm().is_some() {
+            return match customer {
+                Self::Customer { .. } => customer.clone(),
+                _ => Self::None,
+            };
+        }
+        match head.server_side_encryption() {
+            Some(SseAlgorithm::AwsKms | SseAlgorithm::AwsKmsDsse) => Self::Kms {
+                key_id: head.ssekms_key_id().map(|k| k.to_string()),
+            },
+            Some(SseAlgorithm::Aes256) => Self::S3,
+            _ => Self::None,
+        }
+    }
+
+    pub fn is_customer(&self) -> bool {
+        matches!(self, Self::Customer { .. })
+    }
+
+    fn algorithm(&self) -> Option<SseAlgorithm> {
+        match self {
+            Self::S3 => Some(SseAlgorithm::Aes256),
+            Self::Kms { .. } => Some(SseAlgorithm::AwsKms),
+            Self::None | Self::Customer { .. } => None,
+        }
+    }
+
+    fn kms_key_id(&self) -> Option<String> {
+        match self {
+            Self::Kms { key_id } => key_id.clone(),
+            _ => None,
+        }
+    }
+
+    /// Algorithm, key and key MD5 for the SSE-C headers.
+    fn customer(&self) -> (Option<String>, Option<String>, Option<String>) {
+        match self {
+            Self::Customer { key, key_md5 } => {
+                (Some("AES256".to_string()), Some(key.clone()), Some(key_md5.clone()))
+            }
+            _ => (None, None, None),
+        }
+    }
+
+    /// Form fields, and matching policy conditions, for a POST upload.
+    /// SSE-C can't be used, since the key would be handed out with the form.
+    pub fn post_fields(&self) -> Result<Vec<(&'static str, String)>> {
+        if let Self::Customer { .. } = self {
+            return Err(anyhow!("POST uploads can't use SSE-C"));
+        }
+        let mut fields = Vec::new();
+        if let Some(algorithm) = self.algorithm() {
+            fields.push(("x-amz-server-side-encryption", algorithm.as_str().to_string()));
+        }
+        if let Some(key_id) = self.kms_key_id() {
+            fields.push(("x-amz-server-side-encryption-aws-kms-key-id", key_id));
+        }
+        Ok(fields)
+    }
+
+    pub fn apply_put(&self, req: PutObjectFluentBuilder) -> PutObjectFluentBuilder {
+        let (algorithm, key, key_md5) = self.customer();
+        req.set_server_side_encryption(self.algorithm())
+            .set_ssekms_key_id(self.kms_key_id())
+            .set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(key_md5)
+    }
+
+    pub fn apply_create_multipart(
+        &self,
+        req: CreateMultipartUploadFluentBuilder,
+    ) -> CreateMultipartUploadFluentBuilder {
+        let (algorithm, key, key_md5) = self.customer();
+        req.set_server_side_encryption(self.algorithm())
+            .set_ssekms_key_id(self.kms_key_id())
+            .set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(key_md5)
+    }
+
+    /// Parts of an SSE-C upload must repeat the key given on creation.
+    pub fn apply_upload_part(&self, req: UploadPartFluentBuilder) -> UploadPartFluentBuilder {
+        let (algorithm, key, key_md5) = self.customer();
+        req.set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(key_md5)
+    }
+
+    /// Encryption for the copy; `source` supplies the key to read an SSE-C
+    /// source with.
+    pub fn apply_copy(&self, source: &Encryption, req: CopyObjectFluentBuilder) -> CopyObjectFluentBuilder {
+        let (algorithm, key, key_md5) = self.customer();
+        let (source_algorithm, source_key, source_key_md5) = source.customer();
+        req.set_server_side_encryption(self.algorithm())
+            .set_ssekms_key_id(self.kms_key_id())
+            .set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(key_md5)
+            .set_copy_source_sse_customer_algorithm(source_algorithm)
+            .set_copy_source_sse_customer_key(source_key)
+            .set_copy_source_sse_customer_key_md5(source_key_md5)
+    }
+
+    pub fn apply_upload_part_copy(
+        &self,
+        source: &Encryption,
+        req: UploadPartCopyFluentBuilder,
+    ) -> UploadPartCopyFluentBuilder {
+        let (algorithm, key, key_md5) = self.customer();
+        let (source_algorithm, source_key, source_key_md5) = source.customer();
+        req.set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(key_md5)
+            .set_copy_source_sse_customer_algorithm(source_algorithm)
+            .set_copy_source_sse_customer_key(source_key)
+            .set_copy_source_sse_customer_key_md5(source_key_md5)
+    }
+
+    pub fn apply_get(&self, req: GetObjectFluentBuilder) -> GetObjectFluentBuilder {
+        let (algorithm, key, key_md5) = self.customer();
+        req.set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(key_md5)
+    }
+
+    pub fn apply_head(&self, req: HeadObjectFluentBuilder) -> HeadObjectFluentBuilder {
+        let (algorithm, key, key_md5) = self.customer();
+        req.set_sse_customer_algorithm(algorithm)
+            .set_sse_customer_key(key)
+            .set_sse_customer_key_md5(key_md5)
+    }
+}
+
+/// Send a request that reads an existing object, with `encryption`'s SSE-C
+/// key only if the object needs it. S3 rejects the key for objects that
+/// weren't written with SSE-C and answers 400 without it for those that
+/// were, so the request first goes out without the key. Returns the
+/// response and the encryption that read the object, for further reads.
+pub async fn send_read<T, E, F, Fut>(
+    encryption: &Encryption,
+    send: F,
+) -> std::result::Result<(T, Encryption), SdkError<E, HttpResponse>>
+where
+    F: Fn(&Encryption) -> Fut,
+    Fut: Future<Output = std::result::Result<T, SdkError<E, HttpResponse>>>,
+{
+    match send(&Encryption::None).await {
+        Err(e) if encryption.is_customer() && is_bad_request(&e) => {
+            let output = send(encryption).await?;
+            Ok((output, encryption.clone()))
+        }
+        result => result.map(|output| (output, Encryption::None)),
+    }
+}
+
+fn is_bad_request<E>(error: &SdkError<E, HttpResponse>) -> bool {
+    error.raw_response().is_some_and(|r| r.status().as_u16() == 400)
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use aws_sdk_s3::operation::copy_object::builders::CopyObjectFluentBuilder;
+use aws_sdk_s3::operation::create_multipart_upload::builders::CreateMultipartUploadFluentBuilder;
+use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+use aws_sdk_s3::operation::put_object::builders::PutObjectFluentBuilder;
+use aws_sdk_s3::primitives::{DateTime, DateTimeFormat};
+
+/// User metadata key holding a file's modification time (unix seconds).
+/// Sent on the wire as `x-amz-meta-mtime`.
+pub const MTIME_METADATA_KEY: &str = "mtime";
+
+/// User metadata key holding the hex MD5 of an object's readable content,
+/// for objects whose ETag is not a plain MD5 of it.
+pub const MD5_METADATA_KEY: &str = "mahzen-md5";
+
+/// Content headers and user metadata that travel with an object when it is
+/// written, copied between targets or has its metadata replaced.
+#[derive(Debug, Clone, Default)]
+pub struct ObjectHeaders {
+    pub content_type: Option<String>,
+    pub cache_control: Option<String>,
+    pub content_disposition: Option<String>,
+    pub content_encoding: Option<String>,
+    pub content_language: Option<String>,
+    pub expires: Option<DateTime>,
+    pub website_redirect_location: Option<String>,
+    pub metadata: HashMap<String, String>,
+}
+
+impl ObjectHeaders {
+    pub fn from_head(head: &HeadObjectOutput) -> Self {
+        Self {
+            content_type: head.content_type().map(|s| s.to_string()),
+            cache_control: head.cache_control().map(|s| s.to_string()),
+            content_disposition: head.content_disposition().map(|s| s.to_string()),
+            content_encoding: head.content_encoding().map(|s| s.to_string()),
+            content_language: head.content_language().map(|s| s.to_string()),
+            expires: head
+                .expires_string()
+                .and_then(|s| DateTime::from_str(s, DateTimeFormat::HttpDate).ok()),
+            website_redirect_location: head.website_redirect_location().map(|s| s.to_string()),
+            metadata: head.metadata().cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Take the headers that can't be edited, and the client-side
+    /// encryption envelope, from the object being replaced.
+    pub fn keep_unedited(&mut self, head: &HeadObjectOutput) {
+        let existing = Self::from_head(head);
+        self.content_language = existing.content_language;
+        self.expires = existing.expires;
+        self.website_redirect_location = existing.website_redirect_location;
+        self.metadata.extend(super::cse::envelope_metadata(head.metadata()));
+    }
+
+    pub fn apply_put(&self, req: PutObjectFluentBuilder) -> PutObjectFluentBuilder {
+        req.set_content_type(self.content_type.clone())
+            .set_cache_control(self.cache_control.clone())
+            .set_content_disposition(self.content_disposition.clone())
+            .set_content_encoding(self.content_encoding.clone())
+            .set_content_language(self.content_language.clone())
+            .set_expires(self.expires)
+            .set_website_redirect_location(self.website_redirect_location.clone())
+            .set_metadata(self.metadata_opt())
+    }
+
+    pub fn apply_create_multipart(
+        &self,
+        req: CreateMultipartUploadFluentBuilder,
+    ) -> CreateMultipartUploadFluentBuilder {
+        req.set_content_type(self.content_type.clone())
+            .set_cache_control(self.cache_control.clone())
+            .set_content_disposition(self.content_disposition.clone())
+            .set_content_encoding(self.content_encoding.clone())
+            .set_content_language(self.content_language.clone())
+            .set_expires(self.expires)
+            .set_website_redirect_location(self.website_redirect_location.clone())
+            .set_metadata(self.metadata_opt())
+    }
+
+    /// Only takes effect together with `MetadataDirective::Replace`.
+    pub fn apply_copy(&self, req: CopyObjectFluentBuilder) -> CopyObjectFluentBuilder {
+        req.set_content_type(self.content_type.clone())
+            .set_cache_control(self.cache_control.clone())
+            .set_content_disposition(self.content_disposition.clone())
+            .set_content_encoding(self.content_encoding.clone())
+            .set_content_language(self.content_language.clone())
+            .set_expires(self.expires)
+            .set_website_redirect_location(self.website_redirect_location.clone())
+            .set_metadata(self.metadata_opt())
+    }
+
+    fn metadata_opt(&self) -> Option<HashMap<String, String>> {
+        if self.metadata.is_empty() {
+            None
+        } else {
+            Some(self.metadata.clone())
+        }
+    }
+}
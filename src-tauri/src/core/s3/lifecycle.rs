@@ -0,0 +1,677 @@
+use std::collections::HashSet;
+
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::error::ProvideErrorMetadata;
+use aws_sdk_s3::primitives::DateTime;
+use aws_sdk_s3::types::{
+    AbortIncompleteMultipartUpload, BucketLifecycleConfiguration, ExpirationStatus,
+    LifecycleExpiration, LifecycleRule as SdkLifecycleRule, LifecycleRuleAndOperator,
+    LifecycleRuleFilter, NoncurrentVersionExpiration, NoncurrentVersionTransition, Tag,
+    Transition, TransitionStorageClass,
+};
+use chrono::{NaiveDate, Utc};
+
+use super::{build_client, ClientEncryption};
+use crate::models::{LifecycleRule, LifecycleTransition, ObjectTag, StorageTarget, TargetCredentials};
+
+const MAX_RULES: usize = 1000;
+
+/// The bucket's lifecycle rules, or an empty list if it has none. Prefixes
+/// are returned readable.
+pub async fn get_bucket_lifecycle(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<Vec<LifecycleRule>> {
+    let cse = ClientEncryption::for_target(target, credentials)?;
+    let client = build_client(target, credentials).await?;
+    let output = match client
+        .get_bucket_lifecycle_configuration()
+        .bucket(bucket)
+        .send()
+        .await
+    {
+        Ok(output) => output,
+        Err(e) if e.code() == Some("NoSuchLifecycleConfiguration") => return Ok(Vec::new()),
+        Err(e) => return Err(anyhow!("S3 get bucket lifecycle failed: {e}")),
+    };
+
+    Ok(output
+        .rules()
+        .iter()
+        .map(from_sdk_rule)
+        .map(|rule| LifecycleRule {
+            prefix: cse.decode_key(&rule.prefix),
+            ..rule
+        })
+        .collect())
+}
+
+/// Replace the bucket's lifecycle configuration with `rules` after
+/// validating them. An empty list removes the configuration.
+pub async fn put_bucket_lifecycle(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    rules: &[LifecycleRule],
+) -> Result<()> {
+    if rules.is_empty() {
+        return delete_bucket_lifecycle(target, credentials, bucket).await;
+    }
+    validate_lifecycle_rules(rules)?;
+
+    let cse = ClientEncryption::for_target(target, credentials)?;
+    let sdk_rules = rules
+        .iter()
+        .map(|rule| {
+            to_sdk_rule(&LifecycleRule {
+                prefix: stored_rule_prefix(&cse, &rule.prefix)?,
+                ..rule.clone()
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let configuration = BucketLifecycleConfiguration::builder()
+        .set_rules(Some(sdk_rules))
+        .build()
+        .map_err(|e| anyhow!("Failed to build lifecycle configuration: {e}"))?;
+
+    let client = build_client(target, credentials).await?;
+    client
+        .put_bucket_lifecycle_configuration()
+        .bucket(bucket)
+        .lifecycle_configuration(configuration)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 put bucket lifecycle failed: {e}"))?;
+    Ok(())
+}
+
+pub async fn delete_bucket_lifecycle(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<()> {
+    let client = build_client(target, credentials).await?;
+    client
+        .delete_bucket_lifecycle()
+        .bucket(bucket)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 delete bucket lifecycle failed: {e}"))?;
+    Ok(())
+}
+
+/// The stored form of a rule's prefix. S3 matches it against stored keys,
+/// and encrypted names only line up segment by segment, so with name
+/// encryption on the prefix has to be a whole folder.
+pub fn stored_rule_prefix(cse: &ClientEncryption, prefix: &str) -> Result<String> {
+    if cse.encrypts_names() && !prefix.is_empty() && !prefix.ends_with('/') {
+        return Err(anyhow!(
+            "This target encrypts object names, so lifecycle prefixes must be folders ending in '/'"
+        ));
+    }
+    Ok(cse.encode_key(prefix))
+}
+
+/// Check rules against the limits S3 enforces, so mistakes are reported
+/// per rule instead of as one opaque MalformedXML error.
+pub fn validate_lifecycle_rules(rules: &[LifecycleRule]) -> Result<()> {
+    if rules.len() > MAX_RULES {
+        return Err(anyhow!("A bucket can have at most {MAX_RULES} lifecycle rules"));
+    }
+
+    let mut ids = HashSet::new();
+    for (index, rule) in rules.iter().enumerate() {
+        let label = match rule.id.as_deref().filter(|id| !id.is_empty()) {
+            Some(id) => {
+                if !ids.insert(id) {
+                    return Err(anyhow!("Duplicate lifecycle rule ID \"{id}\""));
+                }
+                format!("Rule \"{id}\"")
+            }
+            None => format!("Rule {}", index + 1),
+        };
+        validate_rule(rule).map_err(|e| anyhow!("{label}: {e}"))?;
+    }
+    Ok(())
+}
+
+fn validate_rule(rule: &LifecycleRule) -> Result<()> {
+    if rule.id.as_ref().is_some_and(|id| id.len() > 255) {
+        return Err(anyhow!("ID must be at most 255 characters"));
+    }
+
+    let has_action = rule.expiration_days.is_some()
+        || rule.expiration_date.is_some()
+        || rule.expired_object_delete_marker
+        || !rule.transitions.is_empty()
+        || rule.noncurrent_expiration_days.is_some()
+        || !rule.noncurrent_transitions.is_empty()
+        || rule.abort_incomplete_upload_days.is_some();
+    if !has_action {
+        return Err(anyhow!("needs at least one action"));
+    }
+
+    // Filter
+    if !rule.tags.is_empty() {
+        super::validate_tags(&rule.tags)?;
+    }
+    if rule.object_size_greater_than.is_some_and(|s| s < 0)
+        || rule.object_size_less_than.is_some_and(|s| s < 1)
+    {
+        return Err(anyhow!("object size bounds must be positive"));
+    }
+    if let (Some(min), Some(max)) = (rule.object_size_greater_than, rule.object_size_less_than) {
+        if min >= max {
+            return Err(anyhow!("minimum object size must be below the maximum"));
+        }
+    }
+    let filters_on_object = !rule.tags.is_empty()
+        || rule.object_size_greater_than.is_some()
+        || rule.object_size_less_than.is_some();
+
+    // Expiration
+    if rule.expiration_days.is_some() && rule.expiration_date.is_some() {
+        return Err(anyhow!("expiration takes either days or a date, not both"));
+    }
+    if rule.expiration_days.is_some_and(|d| d < 1) {
+        return Err(anyhow!("expiration days must be at least 1"));
+    }
+    if let Some(date) = &rule.expiration_date {
+        parse_date(date)?;
+    }
+    if rule.expired_object_delete_marker {
+        if rule.expiration_days.is_some() || rule.expiration_date.is_some() {
+            return Err(anyhow!(
+                "removing expired delete markers cannot be combined with expiration days or date"
+            ));
+        }
+        if filters_on_object {
+            return Err(anyhow!(
+                "removing expired delete markers cannot be used with tag or size filters"
+            ));
+        }
+    }
+
+    // Transitions
+    let last_transition = validate_transitions(&rule.transitions, false)?;
+    if let (Some(days), Some(last)) = (rule.expiration_days, last_transition) {
+        if days <= last {
+            return Err(anyhow!("expiration must come after every transition"));
+        }
+    }
+    if (rule.expiration_date.is_some() && rule.transitions.iter().any(|t| t.days.is_some()))
+        || (rule.expiration_days.is_some() && rule.transitions.iter().any(|t| t.date.is_some()))
+    {
+        return Err(anyhow!("expiration and transitions must all use days or all use dates"));
+    }
+
+    // Noncurrent versions
+    if rule.noncurrent_expiration_days.is_some_and(|d| d < 1) {
+        return Err(anyhow!("noncurrent expiration days must be at least 1"));
+    }
+    if let Some(newer) = rule.noncurrent_newer_versions {
+        if !(1..=100).contains(&newer) {
+            return Err(anyhow!("noncurrent versions to keep must be between 1 and 100"));
+        }
+        if rule.noncurrent_expiration_days.is_none() {
+            return Err(anyhow!("noncurrent versions to keep requires noncurrent expiration days"));
+        }
+    }
+    let last_noncurrent = validate_transitions(&rule.noncurrent_transitions, true)?;
+    if let (Some(days), Some(last)) = (rule.noncurrent_expiration_days, last_noncurrent) {
+        if days <= last {
+            return Err(anyhow!("noncurrent expiration must come after every noncurrent transition"));
+        }
+    }
+
+    // Incomplete multipart uploads
+    if let Some(days) = rule.abort_incomplete_upload_days {
+        if days < 1 {
+            return Err(anyhow!("days before aborting incomplete uploads must be at least 1"));
+        }
+        if filters_on_object {
+            return Err(anyhow!("aborting incomplete uploads cannot be used with tag or size filters"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate transitions and return the largest transition day, if they are
+/// day-based. Storage classes must be unique and move to colder tiers as
+/// the days increase.
+fn validate_transitions(transitions: &[LifecycleTransition], noncurrent: bool) -> Result<Option<i32>> {
+    let kind = if noncurrent { "noncurrent transition" } else { "transition" };
+    if transitions.iter().any(|t| t.days.is_some()) && transitions.iter().any(|t| t.date.is_some()) {
+        return Err(anyhow!("{kind}s must all use days or all use dates"));
+    }
+
+    let mut classes = HashSet::new();
+    let mut steps: Vec<(u8, i64)> = Vec::new();
+
+    for transition in transitions {
+        let class = transition.storage_class.as_str();
+        let tier = storage_class_tier(class)
+            .filter(|t| *t > 0)
+            .ok_or_else(|| anyhow!("unsupported {kind} storage class {class}"))?;
+        if !classes.insert(class) {
+            return Err(anyhow!("more than one {kind} to {class}"));
+        }
+
+        let point = match (transition.days, transition.date.as_deref()) {
+            (Some(days), None) => {
+                if days < 0 {
+                    return Err(anyhow!("{kind} days cannot be negative"));
+                }
+                if days < 30 && matches!(class, "STANDARD_IA" | "ONEZONE_IA") {
+                    return Err(anyhow!("{kind} to {class} needs at least 30 days"));
+                }
+                if noncurrent && days < 1 {
+                    return Err(anyhow!("{kind} days must be at least 1"));
+                }
+                days as i64
+            }
+            (None, Some(_)) if noncurrent => {
+                return Err(anyhow!("{kind}s take days, not dates"));
+            }
+            (None, Some(date)) => parse_date(date)?.secs(),
+            _ => return Err(anyhow!("{kind} to {class} takes either days or a date")),
+        };
+        steps.push((tier, point));
+    }
+
+    steps.sort();
+    if steps.windows(2).any(|w| w[0].1 >= w[1].1) {
+        return Err(anyhow!("each {kind} must come later than the ones to warmer storage classes"));
+    }
+
+    Ok(transitions.iter().filter_map(|t| t.days).max())
+}
+
+/// How cold a storage class is, for ordering transitions. `None` for
+/// classes lifecycle rules cannot transition to or from.
+fn storage_class_tier(class: &str) -> Option<u8> {
+    match class {
+        "STANDARD" | "REDUCED_REDUNDANCY" => Some(0),
+        "STANDARD_IA" | "ONEZONE_IA" | "INTELLIGENT_TIERING" => Some(1),
+        "GLACIER_IR" => Some(2),
+        "GLACIER" => Some(3),
+        "DEEP_ARCHIVE" => Some(4),
+        _ => None,
+    }
+}
+
+/// Every storage class `storage_class_tier` knows.
+const TIERED_STORAGE_CLASSES: [&str; 8] = [
+    "STANDARD",
+    "REDUCED_REDUNDANCY",
+    "STANDARD_IA",
+    "ONEZONE_IA",
+    "INTELLIGENT_TIERING",
+    "GLACIER_IR",
+    "GLACIER",
+    "DEEP_ARCHIVE",
+];
+
+/// When a lifecycle action falls due for a current object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LifecycleDue {
+    Never,
+    Always,
+    /// For objects last modified at or before this Unix time.
+    ModifiedBy(i64),
+}
+
+impl LifecycleDue {
+    fn applies(self, modified: Option<i64>) -> bool {
+        match self {
+            LifecycleDue::Never => false,
+            LifecycleDue::Always => true,
+            LifecycleDue::ModifiedBy(cutoff) => modified.is_some_and(|m| m <= cutoff),
+        }
+    }
+}
+
+pub struct ScheduledTransition {
+    pub storage_class: String,
+    pub due: LifecycleDue,
+    /// Classes already as cold as `storage_class` or colder, which the
+    /// transition leaves alone.
+    pub settled_classes: Vec<&'static str>,
+    tier: u8,
+}
+
+/// When each of a rule's current-version actions falls due as of a given
+/// moment, so that many objects can be matched against the rule at once.
+pub struct LifecycleSchedule {
+    pub object_size_greater_than: Option<i64>,
+    pub object_size_less_than: Option<i64>,
+    pub expiration: LifecycleDue,
+    pub transitions: Vec<ScheduledTransition>,
+}
+
+pub fn lifecycle_schedule(rule: &LifecycleRule, now: chrono::DateTime<Utc>) -> LifecycleSchedule {
+    let due = |days: Option<i32>, date: Option<&str>| match (days, date) {
+        (Some(days), _) => LifecycleDue::ModifiedBy(now.timestamp() - days as i64 * 86_400),
+        (None, Some(date)) if parse_date(date).is_ok_and(|d| now.timestamp() >= d.secs()) => {
+            LifecycleDue::Always
+        }
+        _ => LifecycleDue::Never,
+    };
+
+    LifecycleSchedule {
+        object_size_greater_than: rule.object_size_greater_than,
+        object_size_less_than: rule.object_size_less_than,
+        expiration: due(rule.expiration_days, rule.expiration_date.as_deref()),
+        transitions: rule
+            .transitions
+            .iter()
+            .filter_map(|t| {
+                let tier = storage_class_tier(&t.storage_class).filter(|tier| *tier > 0)?;
+                Some(ScheduledTransition {
+                    storage_class: t.storage_class.clone(),
+                    due: due(t.days, t.date.as_deref()),
+                    settled_classes: TIERED_STORAGE_CLASSES
+                        .into_iter()
+                        .filter(|class| storage_class_tier(class).is_some_and(|c| c >= tier))
+                        .collect(),
+                    tier,
+                })
+            })
+            .collect(),
+    }
+}
+
+/// What `rule` would currently do to a current object: "expire", or
+/// "transition" with the storage class it would move to. Only the size
+/// bounds of the filter are checked here; prefix and tags are the caller's.
+pub fn lifecycle_action(
+    rule: &LifecycleRule,
+    size: i64,
+    last_modified: Option<&str>,
+    storage_class: Option<&str>,
+    now: chrono::DateTime<Utc>,
+) -> Option<(&'static str, Option<String>)> {
+    if rule.object_size_greater_than.is_some_and(|min| size <= min)
+        || rule.object_size_less_than.is_some_and(|max| size >= max)
+    {
+        return None;
+    }
+
+    let schedule = lifecycle_schedule(rule, now);
+    let modified = last_modified
+        .and_then(|lm| chrono::DateTime::parse_from_rfc3339(lm).ok())
+        .map(|created| created.timestamp());
+    if schedule.expiration.applies(modified) {
+        return Some(("expire", None));
+    }
+
+    let current = storage_class.unwrap_or("STANDARD");
+    schedule
+        .transitions
+        .into_iter()
+        .filter(|t| t.due.applies(modified) && !t.settled_classes.contains(&current))
+        .max_by_key(|t| t.tier)
+        .map(|t| ("transition", Some(t.storage_class)))
+}
+
+fn parse_date(date: &str) -> Result<DateTime> {
+    let day = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| anyhow!("invalid date \"{date}\", expected YYYY-MM-DD"))?;
+    let midnight = day
+        .and_hms_opt(0, 0, 0)
+        .ok_or_else(|| anyhow!("invalid date \"{date}\""))?;
+    Ok(DateTime::from_secs(midnight.and_utc().timestamp()))
+}
+
+fn format_date(date: &DateTime) -> Option<String> {
+    chrono::DateTime::from_timestamp(date.secs(), 0).map(|d| d.format("%Y-%m-%d").to_string())
+}
+
+fn to_sdk_tag(tag: &ObjectTag) -> Result<Tag> {
+    Tag::builder()
+        .key(&tag.key)
+        .value(&tag.value)
+        .build()
+        .map_err(|e| anyhow!("Failed to build tag: {e}"))
+}
+
+fn to_sdk_rule(rule: &LifecycleRule) -> Result<SdkLifecycleRule> {
+    let conditions = rule.tags.len()
+        + usize::from(!rule.prefix.is_empty())
+        + usize::from(rule.object_size_greater_than.is_some())
+        + usize::from(rule.object_size_less_than.is_some());
+
+    let filter = if conditions > 1 {
+        let tags = rule.tags.iter().map(to_sdk_tag).collect::<Result<Vec<_>>>()?;
+        LifecycleRuleFilter::builder()
+            .and(
+                LifecycleRuleAndOperator::builder()
+                    .set_prefix(Some(rule.prefix.clone()).filter(|p| !p.is_empty()))
+                    .set_tags(Some(tags).filter(|t| !t.is_empty()))
+                    .set_object_size_greater_than(rule.object_size_greater_than)
+                    .set_object_size_less_than(rule.object_size_less_than)
+                    .build(),
+            )
+            .build()
+    } else if let Some(tag) = rule.tags.first() {
+        LifecycleRuleFilter::builder().tag(to_sdk_tag(tag)?).build()
+    } else {
+        LifecycleRuleFilter::builder()
+            .set_object_size_greater_than(rule.object_size_greater_than)
+            .set_object_size_less_than(rule.object_size_less_than)
+            .set_prefix(if conditions == 0 || !rule.prefix.is_empty() {
+                Some(rule.prefix.clone())
+            } else {
+                None
+            })
+            .build()
+    };
+
+    let expiration = if rule.expiration_days.is_some()
+        || rule.expiration_date.is_some()
+        || rule.expired_object_delete_marker
+    {
+        Some(
+            LifecycleExpiration::builder()
+                .set_days(rule.expiration_days)
+                .set_date(rule.expiration_date.as_deref().map(parse_date).transpose()?)
+                .set_expired_object_delete_marker(rule.expired_object_delete_marker.then_some(true))
+                .build(),
+        )
+    } else {
+        None
+    };
+
+    let transitions = rule
+        .transitions
+        .iter()
+        .map(|t| {
+            Ok(Transition::builder()
+                .set_days(t.days)
+                .set_date(t.date.as_deref().map(parse_date).transpose()?)
+                .storage_class(TransitionStorageClass::from(t.storage_class.as_str()))
+                .build())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let noncurrent_transitions: Vec<NoncurrentVersionTransition> = rule
+        .noncurrent_transitions
+        .iter()
+        .map(|t| {
+            NoncurrentVersionTransition::builder()
+                .set_noncurrent_days(t.days)
+                .storage_class(TransitionStorageClass::from(t.storage_class.as_str()))
+                .build()
+        })
+        .collect();
+
+    let noncurrent_expiration = rule.noncurrent_expiration_days.map(|days| {
+        NoncurrentVersionExpiration::builder()
+            .noncurrent_days(days)
+            .set_newer_noncurrent_versions(rule.noncurrent_newer_versions)
+            .build()
+    });
+
+    let abort = rule.abort_incomplete_upload_days.map(|days| {
+        AbortIncompleteMultipartUpload::builder()
+            .days_after_initiation(days)
+            .build()
+    });
+
+    SdkLifecycleRule::builder()
+        .set_id(rule.id.clone().filter(|id| !id.is_empty()))
+        .status(if rule.enabled {
+            ExpirationStatus::Enabled
+        } else {
+            ExpirationStatus::Disabled
+        })
+        .filter(filter)
+        .set_expiration(expiration)
+        .set_transitions(Some(transitions).filter(|t| !t.is_empty()))
+        .set_noncurrent_version_transitions(
+            Some(noncurrent_transitions).filter(|t| !t.is_empty()),
+        )
+        .set_noncurrent_version_expiration(noncurrent_expiration)
+        .set_abort_incomplete_multipart_upload(abort)
+        .build()
+        .map_err(|e| anyhow!("Failed to build lifecycle rule: {e}"))
+}
+
+fn from_sdk_tag(tag: &Tag) -> ObjectTag {
+    ObjectTag {
+        key: tag.key().to_string(),
+        value: tag.value().to_string(),
+    }
+}
+
+fn from_sdk_rule(rule: &SdkLifecycleRule) -> LifecycleRule {
+    let filter = rule.filter();
+    let and = filter.and_then(|f| f.and());
+
+    // Rules written before filters existed carry the prefix on the rule itself
+    #[allow(deprecated)]
+    let legacy_prefix = rule.prefix();
+    let prefix = and
+        .and_then(|a| a.prefix())
+        .or_else(|| filter.and_then(|f| f.prefix()))
+        .or(legacy_prefix)
+        .unwrap_or_default()
+        .to_string();
+    let tags = match and {
+        Some(a) => a.tags().iter().map(from_sdk_tag).collect(),
+        None => filter
+            .and_then(|f| f.tag())
+            .map(|t| vec![from_sdk_tag(t)])
+            .unwrap_or_default(),
+    };
+    let object_size_greater_than = and
+        .and_then(|a| a.object_size_greater_than())
+        .or_else(|| filter.and_then(|f| f.object_size_greater_than()));
+    let object_size_less_than = and
+        .and_then(|a| a.object_size_less_than())
+        .or_else(|| filter.and_then(|f| f.object_size_less_than()));
+
+    let expiration = rule.expiration();
+    LifecycleRule {
+        id: rule.id().map(|s| s.to_string()),
+        enabled: rule.status() == &ExpirationStatus::Enabled,
+        prefix,
+        tags,
+        object_size_greater_than,
+        object_size_less_than,
+        expiration_days: expiration.and_then(|e| e.days()),
+        expiration_date: expiration.and_then(|e| e.date()).and_then(format_date),
+        expired_object_delete_marker: expiration
+            .and_then(|e| e.expired_object_delete_marker())
+            .unwrap_or(false),
+        transitions: rule
+            .transitions()
+            .iter()
+            .map(|t| LifecycleTransition {
+                days: t.days(),
+                date: t.date().and_then(format_date),
+                storage_class: t.storage_class().map(|c| c.to_string()).unwrap_or_default(),
+            })
+            .collect(),
+        noncurrent_expiration_days: rule
+            .noncurrent_version_expiration()
+            .and_then(|e| e.noncurrent_days()),
+        noncurrent_newer_versions: rule
+            .noncurrent_version_expiration()
+            .and_then(|e| e.newer_noncurrent_versions()),
+        noncurrent_transitions: rule
+            .noncurrent_version_transitions()
+            .iter()
+            .map(|t| LifecycleTransition {
+                days: t.noncurrent_days(),
+                date: None,
+                storage_class: t.storage_class().map(|c| c.to_string()).unwrap_or_default(),
+            })
+            .collect(),
+        abort_incomplete_upload_days: rule
+            .abort_incomplete_multipart_upload()
+            .and_then(|a| a.days_after_initiation()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn by_days(days: i32, storage_class: &str) -> LifecycleTransition {
+        LifecycleTransition {
+            days: Some(days),
+            date: None,
+            storage_class: storage_class.to_string(),
+        }
+    }
+
+    fn by_date(date: &str, storage_class: &str) -> LifecycleTransition {
+        LifecycleTransition {
+            days: None,
+            date: Some(date.to_string()),
+            storage_class: storage_class.to_string(),
+        }
+    }
+
+    #[test]
+    fn transitions_return_the_last_day() {
+        let transitions = [by_days(30, "STANDARD_IA"), by_days(90, "GLACIER"), by_days(180, "DEEP_ARCHIVE")];
+        assert_eq!(validate_transitions(&transitions, false).unwrap(), Some(180));
+        assert_eq!(validate_transitions(&[], false).unwrap(), None);
+        assert_eq!(validate_transitions(&[by_date("2030-01-01", "GLACIER")], false).unwrap(), None);
+    }
+
+    #[test]
+    fn transitions_must_get_colder_over_time() {
+        assert!(validate_transitions(&[by_days(90, "STANDARD_IA"), by_days(30, "GLACIER")], false).is_err());
+        assert!(validate_transitions(&[by_days(90, "STANDARD_IA"), by_days(90, "GLACIER")], false).is_err());
+        let dates = [by_date("2030-06-01", "STANDARD_IA"), by_date("2030-01-01", "GLACIER")];
+        assert!(validate_transitions(&dates, false).is_err());
+    }
+
+    #[test]
+    fn transitions_reject_invalid_entries() {
+        assert!(validate_transitions(&[by_days(30, "GLACIER"), by_days(60, "GLACIER")], false).is_err());
+        assert!(validate_transitions(&[by_days(30, "STANDARD")], false).is_err());
+        assert!(validate_transitions(&[by_days(10, "STANDARD_IA")], false).is_err());
+        assert!(validate_transitions(&[by_days(-1, "GLACIER")], false).is_err());
+        assert!(validate_transitions(&[by_days(30, "STANDARD_IA"), by_date("2030-01-01", "GLACIER")], false).is_err());
+        assert!(validate_transitions(&[by_date("2030-13-01", "GLACIER")], false).is_err());
+        let neither = LifecycleTransition {
+            days: None,
+            date: None,
+            storage_class: "GLACIER".to_string(),
+        };
+        assert!(validate_transitions(&[neither], false).is_err());
+    }
+
+    #[test]
+    fn noncurrent_transitions_take_at_least_one_day() {
+        assert!(validate_transitions(&[by_days(0, "GLACIER")], true).is_err());
+        assert!(validate_transitions(&[by_date("2030-01-01", "GLACIER")], true).is_err());
+        assert_eq!(validate_transitions(&[by_days(1, "GLACIER")], true).unwrap(), Some(1));
+    }
+}
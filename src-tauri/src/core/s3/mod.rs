@@ -2,14 +2,64 @@ use anyhow::{anyhow, Result};
 use aws_config::BehaviorVersion;
 use aws_credential_types::Credentials;
 use aws_sdk_s3::config::Region;
-use aws_sdk_s3::presigning::PresigningConfig;
 use aws_sdk_s3::primitives::ByteStream;
-use aws_sdk_s3::types::{Delete, ObjectIdentifier};
+use aws_sdk_s3::types::{ChecksumMode, Delete, MetadataDirective, ObjectIdentifier, StorageClass};
 use aws_sdk_s3::Client;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::time::Duration;
 
-use crate::models::{BucketStats, S3BucketSummary, S3ObjectEntry, S3ObjectListPage, StorageTarget, TargetCredentials};
+use crate::core::bandwidth;
+mod access;
+mod buckets;
+mod checksum;
+mod cors;
+mod cse;
+mod download;
+mod encryption;
+mod headers;
+mod lifecycle;
+mod multipart;
+mod presign;
+mod restore;
+mod tagging;
+mod versions;
+
+pub use access::{
+    delete_bucket_policy, delete_public_access_block, get_bucket_access_summary,
+    get_bucket_policy, get_public_access_block, put_bucket_policy, put_public_access_block,
+    validate_bucket_policy,
+};
+pub use buckets::{create_bucket, delete_bucket};
+pub use checksum::{file_md5, file_multipart_etag, object_fingerprint, stored_md5, unquote_etag};
+pub use cors::{delete_bucket_cors, get_bucket_cors, put_bucket_cors, validate_cors_rules};
+pub use cse::{generate_key as generate_client_encryption_key, parse_key as parse_client_encryption_key, ClientEncryption};
+pub use encryption::Encryption;
+use encryption::send_read;
+pub use headers::ObjectHeaders;
+pub use lifecycle::{
+    delete_bucket_lifecycle, get_bucket_lifecycle, lifecycle_action, lifecycle_schedule,
+    put_bucket_lifecycle, stored_rule_prefix, validate_lifecycle_rules, LifecycleDue,
+    LifecycleSchedule,
+};
+pub use multipart::{
+    abort_multipart_upload, abort_multipart_uploads, abort_stale_multipart_uploads,
+    list_multipart_uploads, part_size_for as multipart_part_size,
+};
+pub use presign::{presign_object, presign_post, presign_put_object, ObjectPresigner};
+pub use restore::{
+    is_archived_class, parse_restore_header, restore_object, restore_status, validate_restore_request,
+};
+pub use tagging::{
+    delete_object_tagging, get_object_tagging, get_tags, put_object_tagging, put_tags,
+    validate_tags,
+};
+pub use versions::{
+    delete_object_version, get_bucket_versioning, list_object_versions, restore_object_version,
+    undelete_object,
+};
+
+use crate::models::{BucketStats, DeleteObjectsResult, ObjectDeleteError, ObjectMetadata, S3BucketSummary, S3ObjectEntry, S3ObjectListPage, ServerSideEncryption, StorageTarget, TargetCredentials};
 
 fn default_region(provider: &str) -> String {
     if provider.eq_ignore_ascii_case("Cloudflare R2") {
@@ -19,17 +69,21 @@ fn default_region(provider: &str) -> String {
     }
 }
 
+fn target_region(target: &StorageTarget) -> String {
+    target
+        .region
+        .as_ref()
+        .map(|r| r.trim().to_string())
+        .filter(|r| !r.is_empty())
+        .unwrap_or_else(|| default_region(&target.provider))
+}
+
 pub async fn build_client(target: &StorageTarget, credentials: &TargetCredentials) -> Result<Client> {
     if credentials.access_key_id.trim().is_empty() || credentials.secret_access_key.trim().is_empty() {
         return Err(anyhow!("Missing access key credentials for target."));
     }
 
-    let region = target
-        .region
-        .as_ref()
-        .map(|r| r.trim().to_string())
-        .filter(|r| !r.is_empty())
-        .unwrap_or_else(|| default_region(&target.provider));
+    let region = target_region(target);
 
     let shared = aws_config::defaults(BehaviorVersion::latest())
         .region(Region::new(region))
@@ -84,6 +138,8 @@ pub async fn list_objects(
     prefix: &str,
 ) -> Result<Vec<S3ObjectEntry>> {
     let client = build_client(target, credentials).await?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
+    let stored_prefix = cse.encode_key(prefix);
     let mut entries = Vec::new();
     let mut continuation_token: Option<String> = None;
 
@@ -91,7 +147,7 @@ pub async fn list_objects(
         let mut req = client
             .list_objects_v2()
             .bucket(bucket)
-            .prefix(prefix)
+            .prefix(&stored_prefix)
             .delimiter("/");
 
         if let Some(token) = &continuation_token {
@@ -106,7 +162,7 @@ pub async fn list_objects(
         // Common prefixes → folders
         if let Some(prefixes) = output.common_prefixes {
             for cp in prefixes {
-                if let Some(p) = cp.prefix {
+                if let Some(p) = cp.prefix.map(|p| cse.decode_key(&p)) {
                     if p == prefix {
                         continue;
                     }
@@ -135,7 +191,7 @@ pub async fn list_objects(
         // Contents → files
         if let Some(contents) = output.contents {
             for obj in contents {
-                let key = obj.key.unwrap_or_default();
+                let key = cse.decode_key(&obj.key.unwrap_or_default());
                 if key == prefix || key.is_empty() {
                     continue;
                 }
@@ -196,12 +252,13 @@ pub async fn list_objects_page(
     continuation_token: Option<String>,
 ) -> Result<S3ObjectListPage> {
     let client = build_client(target, credentials).await?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
     let mut entries = Vec::new();
 
     let mut req = client
         .list_objects_v2()
         .bucket(bucket)
-        .prefix(prefix)
+        .prefix(cse.encode_key(prefix))
         .delimiter("/")
         .max_keys(max_keys);
 
@@ -217,7 +274,7 @@ pub async fn list_objects_page(
     // Common prefixes → folders (include from every page)
     if let Some(prefixes) = output.common_prefixes {
         for cp in prefixes {
-            if let Some(p) = cp.prefix {
+            if let Some(p) = cp.prefix.map(|p| cse.decode_key(&p)) {
                 if p == prefix {
                     continue;
                 }
@@ -246,7 +303,7 @@ pub async fn list_objects_page(
     // Contents → files
     if let Some(contents) = output.contents {
         for obj in contents {
-            let key = obj.key.unwrap_or_default();
+            let key = cse.decode_key(&obj.key.unwrap_or_default());
             if key == prefix || key.is_empty() {
                 continue;
             }
@@ -300,124 +357,356 @@ pub async fn list_objects_page(
     })
 }
 
+/// Upload a local file. The file is read here rather than streamed by the
+/// SDK, so that bandwidth caps apply to each chunk; files larger than one
+/// part are therefore sent as a multipart upload, which keeps the memory
+/// held per upload to one part and avoids the 5 GB single-PUT limit. With
+/// `preserve_timestamps` the file's mtime is stored as user metadata.
+/// `sse` overrides the target's default server-side encryption. On targets
+/// with client-side encryption the body is encrypted before it leaves this
+/// machine. The content's MD5 is stored as metadata unless the object's
+/// ETag will already be derived from it.
 pub async fn put_object(
     target: &StorageTarget,
     credentials: &TargetCredentials,
     bucket: &str,
     key: &str,
     source_path: &str,
+    preserve_timestamps: bool,
+    sse: Option<&ServerSideEncryption>,
 ) -> Result<()> {
+    let encryption = Encryption::resolve(target, credentials, sse)?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
     let client = build_client(target, credentials).await?;
-    let body = ByteStream::from_path(Path::new(source_path))
+    let mut file = tokio::fs::File::open(source_path)
+        .await
+        .map_err(|e| anyhow!("Failed to read file {source_path}: {e}"))?;
+    let file_metadata = file
+        .metadata()
         .await
         .map_err(|e| anyhow!("Failed to read file {source_path}: {e}"))?;
+    let size = file_metadata.len() as i64;
 
-    let content_type = guess_content_type(key);
+    let mut headers = ObjectHeaders {
+        content_type: Some(guess_content_type(key)),
+        ..Default::default()
+    };
+    if preserve_timestamps {
+        if let Some(mtime) = file_metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        {
+            headers
+                .metadata
+                .insert(headers::MTIME_METADATA_KEY.to_string(), mtime.as_secs().to_string());
+        }
+    }
 
-    client
-        .put_object()
-        .bucket(bucket)
-        .key(key)
-        .body(body)
-        .content_type(content_type)
-        .send()
-        .await
-        .map_err(|e| anyhow!("S3 put object failed: {e}"))?;
+    let key = cse.encode_key(key);
+    let mut encryptor = match cse.encryptor(size as u64)? {
+        Some((encryptor, metadata)) => {
+            headers.metadata.extend(metadata);
+            Some(encryptor)
+        }
+        None => None,
+    };
 
-    Ok(())
+    if size <= multipart::PART_SIZE {
+        let mut body = read_file_chunk(&mut file, size as usize, &target.id).await?;
+        headers
+            .metadata
+            .insert(headers::MD5_METADATA_KEY.to_string(), checksum::md5_hex(&body));
+        if let Some(encryptor) = encryptor.as_mut() {
+            body = encryptor.encrypt(&body, true)?;
+        }
+        let req = encryption.apply_put(client.put_object().bucket(bucket).key(&key));
+        headers
+            .apply_put(req)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 put object failed: {e}"))?;
+        return Ok(());
+    }
+
+    // The ETag of an encrypted object says nothing about its content, so
+    // the MD5 is worked out up front for later comparisons
+    if encryptor.is_some() {
+        let path = source_path.to_string();
+        let md5 = tokio::task::spawn_blocking(move || checksum::file_md5(&path))
+            .await
+            .map_err(|e| anyhow!("Checksum task failed: {e}"))??;
+        headers.metadata.insert(headers::MD5_METADATA_KEY.to_string(), md5);
+    }
+
+    // Encrypted parts must hold whole chunks
+    let part_size = match encryptor {
+        Some(_) => cse::encrypted_part_size(multipart::part_size_for(size)),
+        None => multipart::part_size_for(size),
+    };
+    let mut upload =
+        multipart::MultipartUpload::create(&client, bucket, &key, &headers, &encryption).await?;
+
+    let mut offset: i64 = 0;
+    while offset < size {
+        let len = std::cmp::min(part_size, size - offset);
+        let last = offset + len >= size;
+        let result = match read_file_chunk(&mut file, len as usize, &target.id).await {
+            Ok(data) => match encryptor.as_mut() {
+                Some(encryptor) => match encryptor.encrypt(&data, last) {
+                    Ok(data) => upload.upload_part(data).await,
+                    Err(e) => Err(e),
+                },
+                None => upload.upload_part(data).await,
+            },
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            upload.abort().await;
+            return Err(e);
+        }
+        offset += len;
+    }
+
+    upload.complete().await
+}
+
+/// Read the next `len` bytes of a local file, honoring bandwidth caps.
+async fn read_file_chunk(file: &mut tokio::fs::File, len: usize, target_id: &str) -> Result<Vec<u8>> {
+    use tokio::io::AsyncReadExt;
+
+    let mut data = vec![0u8; len];
+    let mut filled = 0;
+    while filled < len {
+        let end = std::cmp::min(filled + 256 * 1024, len);
+        let n = file
+            .read(&mut data[filled..end])
+            .await
+            .map_err(|e| anyhow!("Failed to read local file: {e}"))?;
+        if n == 0 {
+            return Err(anyhow!("Local file changed size during upload"));
+        }
+        bandwidth::throttle(&[target_id], n as u64).await;
+        filled += n;
+    }
+    Ok(data)
 }
 
+/// Download an object to `dest_path`. Data is written to a
+/// `<dest>.mahzen-partial` file first and only renamed into place once the
+/// whole object has arrived, so an interrupted download can be resumed by
+/// calling this again with the same destination. With `preserve_timestamps`
+/// the file's mtime is set from the object's `mtime` metadata, falling back
+/// to its Last-Modified time. `version_id` selects a specific version in a
+/// versioned bucket, and `sse` supplies an SSE-C key other than the
+/// target's. Client-side encrypted objects are decrypted once the download
+/// is complete.
 pub async fn get_object(
     target: &StorageTarget,
     credentials: &TargetCredentials,
     bucket: &str,
     key: &str,
+    version_id: Option<&str>,
     dest_path: &str,
+    preserve_timestamps: bool,
+    sse: Option<&ServerSideEncryption>,
     on_progress: impl Fn(u64, u64),
 ) -> Result<()> {
-    use std::io::Write;
-    use std::time::Instant;
-    use tokio::io::AsyncReadExt;
-
+    let encryption = Encryption::resolve(target, credentials, sse)?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
     let client = build_client(target, credentials).await?;
-    let output = client
-        .get_object()
-        .bucket(bucket)
-        .key(key)
-        .send()
-        .await
-        .map_err(|e| anyhow!("S3 get object failed: {e}"))?;
-
-    let total = output.content_length().map(|v| v.max(0) as u64).unwrap_or(0);
+    let key = &cse.encode_key(key);
 
     let dest = Path::new(dest_path);
     if let Some(parent) = dest.parent() {
-        std::fs::create_dir_all(parent)?;
+        tokio::fs::create_dir_all(parent).await?;
     }
 
-    let mut file = std::fs::File::create(dest)?;
-    let mut reader = output.body.into_async_read();
-    let mut downloaded: u64 = 0;
-    let mut buf = vec![0u8; 256 * 1024]; // 256 KB chunks
-    let mut last_emit = Instant::now();
+    let (head, encryption) = send_read(&encryption, |encryption| {
+        encryption
+            .apply_head(client.head_object())
+            .bucket(bucket)
+            .key(key)
+            .set_version_id(version_id.map(|v| v.to_string()))
+            .send()
+    })
+    .await
+    .map_err(|e| anyhow!("S3 head object failed: {e}"))?;
+    let size = head.content_length().map(|v| v.max(0) as u64).unwrap_or(0);
+    let etag = head.e_tag();
+    let expected_md5 = if target.skip_download_verification {
+        None
+    } else {
+        checksum::content_md5(&head)
+    };
+    let decryptor = cse.decryptor(head.metadata())?;
+
+    let partial = download::partial_path(dest);
+
+    // Large objects are fetched as concurrent ranged GETs
+    if size >= download::PARALLEL_DOWNLOAD_THRESHOLD {
+        download::download_ranged(
+            &client,
+            &target.id,
+            bucket,
+            key,
+            version_id,
+            &encryption,
+            size,
+            etag,
+            &partial,
+            &on_progress,
+        )
+        .await?;
+    } else {
+        download::download_sequential(
+            &client,
+            &target.id,
+            bucket,
+            key,
+            version_id,
+            &encryption,
+            size,
+            etag,
+            &partial,
+            &on_progress,
+        )
+        .await?;
+    }
 
-    loop {
-        let n = reader
-            .read(&mut buf)
-            .await
-            .map_err(|e| anyhow!("Failed to read S3 stream: {e}"))?;
-        if n == 0 {
-            break;
+    // Verifying and decrypting read the whole file
+    let final_path = dest.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let expected_md5 = expected_md5.as_deref();
+        match decryptor {
+            Some(decryptor) => download::finalize_decrypted(&partial, &final_path, size, expected_md5, decryptor),
+            None => download::finalize(&partial, &final_path, size, expected_md5),
         }
-        file.write_all(&buf[..n])?;
-        downloaded += n as u64;
-
-        if last_emit.elapsed().as_millis() >= 50 || downloaded == total {
-            on_progress(downloaded, total);
-            last_emit = Instant::now();
+    })
+    .await
+    .map_err(|e| anyhow!("Download finalize task failed: {e}"))??;
+
+    if preserve_timestamps {
+        let mtime = head
+            .metadata()
+            .and_then(|m| m.get(headers::MTIME_METADATA_KEY))
+            .and_then(|v| v.parse::<i64>().ok())
+            .or_else(|| head.last_modified().map(|dt| dt.secs()));
+        if let Some(secs) = mtime.filter(|&secs| secs >= 0) {
+            let modified = std::time::UNIX_EPOCH + Duration::from_secs(secs as u64);
+            std::fs::OpenOptions::new()
+                .write(true)
+                .open(dest)
+                .and_then(|f| f.set_modified(modified))
+                .map_err(|e| anyhow!("Failed to set file modification time: {e}"))?;
         }
     }
 
-    // Ensure final progress
-    on_progress(downloaded, if total > 0 { total } else { downloaded });
     Ok(())
 }
 
+/// Delete `keys` in DeleteObjects requests of up to 1000 keys. Keys S3
+/// refuses, or whose whole request fails, are returned as failed rather
+/// than failing the call.
 pub async fn delete_objects(
     target: &StorageTarget,
     credentials: &TargetCredentials,
     bucket: &str,
     keys: Vec<String>,
-) -> Result<()> {
+) -> Result<DeleteObjectsResult> {
+    let mut result = DeleteObjectsResult::default();
     if keys.is_empty() {
-        return Ok(());
+        return Ok(result);
     }
 
     let client = build_client(target, credentials).await?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
 
-    // S3 delete_objects supports up to 1000 keys per request
     for chunk in keys.chunks(1000) {
-        let objects: Vec<ObjectIdentifier> = chunk
-            .iter()
-            .map(|k| ObjectIdentifier::builder().key(k).build())
-            .collect::<std::result::Result<Vec<_>, _>>()
-            .map_err(|e| anyhow!("Failed to build object identifier: {e}"))?;
+        // Errors name stored keys, which map back to what the caller passed
+        let stored: HashMap<String, &String> = chunk.iter().map(|k| (cse.encode_key(k), k)).collect();
+        let objects: Vec<(String, Option<String>)> = stored.keys().map(|k| (k.clone(), None)).collect();
+
+        match delete_object_batch(&client, bucket, &objects, false).await {
+            Ok(errors) => {
+                let mut failed = HashSet::new();
+                for error in errors {
+                    // Already gone is as good as deleted
+                    if matches!(error.code.as_deref(), Some("NoSuchKey")) {
+                        continue;
+                    }
+                    let key = stored.get(&error.key).map(|k| k.to_string()).unwrap_or(error.key);
+                    failed.insert(key.clone());
+                    result.failed.push(ObjectDeleteError { key, ..error });
+                }
+                result
+                    .deleted
+                    .extend(chunk.iter().filter(|k| !failed.contains(*k)).cloned());
+            }
+            Err(e) => {
+                let message = e.to_string();
+                result.failed.extend(chunk.iter().map(|key| ObjectDeleteError {
+                    key: key.clone(),
+                    version_id: None,
+                    code: None,
+                    message: Some(message.clone()),
+                }));
+            }
+        }
+    }
 
-        let delete = Delete::builder()
-            .set_objects(Some(objects))
-            .build()
-            .map_err(|e| anyhow!("Failed to build delete request: {e}"))?;
+    Ok(result)
+}
 
-        client
-            .delete_objects()
-            .bucket(bucket)
-            .delete(delete)
-            .send()
-            .await
-            .map_err(|e| anyhow!("S3 delete objects failed: {e}"))?;
+/// Delete up to 1000 objects, or specific versions of them, in one
+/// DeleteObjects request. Returns the entries S3 reported as not deleted.
+pub async fn delete_object_batch(
+    client: &Client,
+    bucket: &str,
+    objects: &[(String, Option<String>)],
+    bypass_governance: bool,
+) -> Result<Vec<ObjectDeleteError>> {
+    if objects.is_empty() {
+        return Ok(Vec::new());
     }
 
-    Ok(())
+    let identifiers: Vec<ObjectIdentifier> = objects
+        .iter()
+        .map(|(key, version_id)| {
+            ObjectIdentifier::builder()
+                .key(key)
+                .set_version_id(version_id.clone())
+                .build()
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| anyhow!("Failed to build object identifier: {e}"))?;
+
+    let delete = Delete::builder()
+        .set_objects(Some(identifiers))
+        .quiet(true)
+        .build()
+        .map_err(|e| anyhow!("Failed to build delete request: {e}"))?;
+
+    let mut req = client.delete_objects().bucket(bucket).delete(delete);
+    if bypass_governance {
+        req = req.bypass_governance_retention(true);
+    }
+    let output = req
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 delete objects failed: {e}"))?;
+
+    Ok(output
+        .errors()
+        .iter()
+        .map(|e| ObjectDeleteError {
+            key: e.key().unwrap_or_default().to_string(),
+            version_id: e.version_id().map(|s| s.to_string()),
+            code: e.code().map(|s| s.to_string()),
+            message: e.message().map(|s| s.to_string()),
+        })
+        .collect())
 }
 
 pub async fn create_folder(
@@ -425,16 +714,19 @@ pub async fn create_folder(
     credentials: &TargetCredentials,
     bucket: &str,
     key: &str,
+    sse: Option<&ServerSideEncryption>,
 ) -> Result<()> {
+    let encryption = Encryption::resolve(target, credentials, sse)?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
     let client = build_client(target, credentials).await?;
     let folder_key = if key.ends_with('/') {
-        key.to_string()
+        cse.encode_key(key)
     } else {
-        format!("{key}/")
+        cse.encode_key(&format!("{key}/"))
     };
 
-    client
-        .put_object()
+    encryption
+        .apply_put(client.put_object())
         .bucket(bucket)
         .key(&folder_key)
         .body(ByteStream::from_static(b""))
@@ -490,31 +782,6 @@ pub async fn bucket_stats(
     })
 }
 
-pub async fn presign_object(
-    target: &StorageTarget,
-    credentials: &TargetCredentials,
-    bucket: &str,
-    key: &str,
-    expires_in_secs: u64,
-) -> Result<String> {
-    let client = build_client(target, credentials).await?;
-
-    let presigning_config = PresigningConfig::builder()
-        .expires_in(Duration::from_secs(expires_in_secs))
-        .build()
-        .map_err(|e| anyhow!("Failed to build presigning config: {e}"))?;
-
-    let presigned = client
-        .get_object()
-        .bucket(bucket)
-        .key(key)
-        .presigned(presigning_config)
-        .await
-        .map_err(|e| anyhow!("S3 presign failed: {e}"))?;
-
-    Ok(presigned.uri().to_string())
-}
-
 pub async fn list_objects_recursive(
     target: &StorageTarget,
     credentials: &TargetCredentials,
@@ -522,6 +789,8 @@ pub async fn list_objects_recursive(
     prefix: &str,
 ) -> Result<Vec<S3ObjectEntry>> {
     let client = build_client(target, credentials).await?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
+    let stored_prefix = cse.encode_key(prefix);
     let mut entries = Vec::new();
     let mut continuation_token: Option<String> = None;
 
@@ -529,7 +798,7 @@ pub async fn list_objects_recursive(
         let mut req = client
             .list_objects_v2()
             .bucket(bucket)
-            .prefix(prefix);
+            .prefix(&stored_prefix);
 
         if let Some(token) = &continuation_token {
             req = req.continuation_token(token);
@@ -542,7 +811,7 @@ pub async fn list_objects_recursive(
 
         if let Some(contents) = output.contents {
             for obj in contents {
-                let key = obj.key.unwrap_or_default();
+                let key = cse.decode_key(&obj.key.unwrap_or_default());
                 if key.is_empty() || key.ends_with('/') {
                     continue;
                 }
@@ -597,6 +866,8 @@ pub async fn download_objects_as_zip(
     use zip::write::SimpleFileOptions;
     use zip::ZipWriter;
 
+    let encryption = Encryption::resolve(target, credentials, None)?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
     let client = build_client(target, credentials).await?;
 
     let file = std::fs::File::create(dest_path)
@@ -615,13 +886,13 @@ pub async fn download_objects_as_zip(
             continue;
         }
 
-        let output = client
-            .get_object()
-            .bucket(bucket)
-            .key(key)
-            .send()
-            .await
-            .map_err(|e| anyhow!("S3 get object failed for {key}: {e}"))?;
+        let stored_key = cse.encode_key(key);
+        let (output, _) = send_read(&encryption, |encryption| {
+            encryption.apply_get(client.get_object()).bucket(bucket).key(&stored_key).send()
+        })
+        .await
+        .map_err(|e| anyhow!("S3 get object failed for {key}: {e}"))?;
+        let mut decryptor = cse.decryptor(output.metadata())?;
 
         zip.start_file(entry_name, options)
             .map_err(|e| anyhow!("Failed to start ZIP entry {entry_name}: {e}"))?;
@@ -637,7 +908,12 @@ pub async fn download_objects_as_zip(
             if n == 0 {
                 break;
             }
-            zip.write_all(&buf[..n])
+            bandwidth::throttle(&[&target.id], n as u64).await;
+            let data = match decryptor.as_mut() {
+                Some(decryptor) => decryptor.push(&buf[..n])?,
+                None => buf[..n].to_vec(),
+            };
+            zip.write_all(&data)
                 .map_err(|e| anyhow!("Failed to write ZIP entry {entry_name}: {e}"))?;
             cumulative += n as u64;
 
@@ -646,6 +922,10 @@ pub async fn download_objects_as_zip(
                 last_emit = Instant::now();
             }
         }
+        if let Some(decryptor) = decryptor {
+            zip.write_all(&decryptor.finish()?)
+                .map_err(|e| anyhow!("Failed to write ZIP entry {entry_name}: {e}"))?;
+        }
     }
 
     // Final progress
@@ -669,9 +949,15 @@ pub async fn head_object(
     bucket: &str,
     key: &str,
 ) -> Result<Option<(i64, Option<String>)>> {
+    let encryption = Encryption::resolve(target, credentials, None)?;
+    let key = ClientEncryption::for_target(target, credentials)?.encode_key(key);
     let client = build_client(target, credentials).await?;
-    match client.head_object().bucket(bucket).key(key).send().await {
-        Ok(output) => {
+    let head = send_read(&encryption, |encryption| {
+        encryption.apply_head(client.head_object()).bucket(bucket).key(&key).send()
+    })
+    .await;
+    match head {
+        Ok((output, _)) => {
             let size = output.content_length().unwrap_or(0);
             let last_modified = output.last_modified().map(|dt| dt.to_string());
             Ok(Some((size, last_modified)))
@@ -687,6 +973,130 @@ pub async fn head_object(
     }
 }
 
+/// Full HeadObject response for the metadata inspector. Checksums are only
+/// returned when requested and when the object was uploaded with one. The
+/// size of a client-side encrypted object is its plaintext size.
+pub async fn head_object_details(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+    sse: Option<&ServerSideEncryption>,
+) -> Result<ObjectMetadata> {
+    let encryption = Encryption::resolve(target, credentials, sse)?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
+    let client = build_client(target, credentials).await?;
+    let stored_key = cse.encode_key(key);
+    let (head, _) = send_read(&encryption, |encryption| {
+        encryption
+            .apply_head(client.head_object())
+            .bucket(bucket)
+            .key(&stored_key)
+            .checksum_mode(ChecksumMode::Enabled)
+            .send()
+    })
+    .await
+    .map_err(|e| anyhow!("S3 head object failed: {e}"))?;
+
+    Ok(ObjectMetadata {
+        key: key.to_string(),
+        size: cse::plaintext_size(head.metadata()).unwrap_or_else(|| head.content_length().unwrap_or(0)),
+        last_modified: head.last_modified().map(|dt| dt.to_string()),
+        etag: head.e_tag().map(|s| s.to_string()),
+        version_id: head.version_id().map(|s| s.to_string()),
+        storage_class: head.storage_class().map(|sc| sc.to_string()),
+        content_type: head.content_type().map(|s| s.to_string()),
+        cache_control: head.cache_control().map(|s| s.to_string()),
+        content_disposition: head.content_disposition().map(|s| s.to_string()),
+        content_encoding: head.content_encoding().map(|s| s.to_string()),
+        content_language: head.content_language().map(|s| s.to_string()),
+        expires: head.expires_string().map(|s| s.to_string()),
+        metadata: head.metadata().cloned().unwrap_or_default(),
+        server_side_encryption: head.server_side_encryption().map(|s| s.to_string()),
+        sse_kms_key_id: head.ssekms_key_id().map(|s| s.to_string()),
+        sse_customer_algorithm: head.sse_customer_algorithm().map(|s| s.to_string()),
+        bucket_key_enabled: head.bucket_key_enabled(),
+        checksum_crc32: head.checksum_crc32().map(|s| s.to_string()),
+        checksum_crc32c: head.checksum_crc32_c().map(|s| s.to_string()),
+        checksum_crc64nvme: head.checksum_crc64_nvme().map(|s| s.to_string()),
+        checksum_sha1: head.checksum_sha1().map(|s| s.to_string()),
+        checksum_sha256: head.checksum_sha256().map(|s| s.to_string()),
+        parts_count: head.parts_count(),
+        restore: head.restore().map(|s| s.to_string()),
+        archive_status: head.archive_status().map(|s| s.to_string()),
+        replication_status: head.replication_status().map(|s| s.to_string()),
+        object_lock_mode: head.object_lock_mode().map(|s| s.to_string()),
+        object_lock_retain_until: head.object_lock_retain_until_date().map(|dt| dt.to_string()),
+        object_lock_legal_hold: head.object_lock_legal_hold_status().map(|s| s.to_string()),
+    })
+}
+
+/// Replace an object's headers and user metadata in place by copying it onto
+/// itself. Headers that aren't edited here, the storage class, the
+/// encryption and the tags are kept; objects over 5 GB go through a
+/// multipart copy.
+pub async fn update_object_metadata(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+    headers: &ObjectHeaders,
+) -> Result<()> {
+    let default_encryption = Encryption::resolve(target, credentials, None)?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
+    let client = build_client(target, credentials).await?;
+    let stored_key = cse.encode_key(key);
+    let (head, source_encryption) = send_read(&default_encryption, |encryption| {
+        encryption.apply_head(client.head_object()).bucket(bucket).key(&stored_key).send()
+    })
+    .await
+    .map_err(|e| anyhow!("S3 head object failed: {e}"))?;
+
+    // A client-side encrypted object can't be read without its envelope
+    let mut headers = headers.clone();
+    headers.keep_unedited(&head);
+
+    let size = head.content_length().unwrap_or(0);
+    let tags = if copy_keeps_tags(size) {
+        None
+    } else {
+        Some(get_tags(&client, bucket, &stored_key).await?)
+    };
+
+    // A copy without a storage class lands in STANDARD
+    let encryption = Encryption::from_head(&head, &default_encryption);
+    let options = CopyOptions {
+        headers: Some(&headers),
+        storage_class: head.storage_class().cloned(),
+        encryption: Some(&encryption),
+        source_encryption: Some(&source_encryption),
+        ..Default::default()
+    };
+    copy_with_client(&client, bucket, &stored_key, bucket, &stored_key, size, &options).await?;
+
+    if let Some(tags) = tags.filter(|t| !t.is_empty()) {
+        put_tags(&client, bucket, &stored_key, &tags).await?;
+    }
+    Ok(())
+}
+
+/// Overrides for `copy_object`. By default the copy carries over the source's
+/// headers and user metadata, gets the bucket's default storage class and is
+/// encrypted with the target's default encryption.
+#[derive(Debug, Default)]
+pub struct CopyOptions<'a> {
+    /// Replace headers and user metadata instead of copying them.
+    pub headers: Option<&'a ObjectHeaders>,
+    pub storage_class: Option<StorageClass>,
+    /// Copy this version of the source rather than its current one.
+    pub source_version_id: Option<&'a str>,
+    /// Encryption for the copy.
+    pub encryption: Option<&'a Encryption>,
+    /// Supplies the SSE-C key of an encrypted source. By default the
+    /// target's key is used if the source turns out to need it.
+    pub source_encryption: Option<&'a Encryption>,
+}
+
 pub async fn copy_object(
     target: &StorageTarget,
     credentials: &TargetCredentials,
@@ -695,27 +1105,131 @@ pub async fn copy_object(
     dest_bucket: &str,
     dest_key: &str,
     source_size: i64,
+    options: &CopyOptions<'_>,
 ) -> Result<()> {
+    let default_encryption = Encryption::resolve(target, credentials, None)?;
+    let cse = ClientEncryption::for_target(target, credentials)?;
     let client = build_client(target, credentials).await?;
-    let copy_source = format!("{}/{}", source_bucket, source_key);
+    let source_key = cse.encode_key(source_key);
+    let source_encryption = match options.source_encryption {
+        Some(encryption) => encryption.clone(),
+        None => {
+            read_encryption(&client, source_bucket, &source_key, options.source_version_id, &default_encryption)
+                .await?
+        }
+    };
+    let options = CopyOptions {
+        storage_class: options.storage_class.clone(),
+        encryption: options.encryption.or(Some(&default_encryption)),
+        source_encryption: Some(&source_encryption),
+        ..*options
+    };
+    copy_with_client(
+        &client,
+        source_bucket,
+        &source_key,
+        dest_bucket,
+        &cse.encode_key(dest_key),
+        source_size,
+        &options,
+    )
+    .await
+}
+
+/// The encryption to read an existing object with: `encryption` if the
+/// object was written with SSE-C, otherwise none.
+async fn read_encryption(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    version_id: Option<&str>,
+    encryption: &Encryption,
+) -> Result<Encryption> {
+    if !encryption.is_customer() {
+        return Ok(Encryption::None);
+    }
+    let (_, encryption) = send_read(encryption, |encryption| {
+        encryption
+            .apply_head(client.head_object())
+            .bucket(bucket)
+            .key(key)
+            .set_version_id(version_id.map(|v| v.to_string()))
+            .send()
+    })
+    .await
+    .map_err(|e| anyhow!("S3 head object failed for {key}: {e}"))?;
+    Ok(encryption)
+}
+
+/// Largest object a single CopyObject request can copy; bigger ones are
+/// copied part by part, which does not carry over object tags.
+pub const MAX_SINGLE_COPY_SIZE: i64 = 5 * 1024 * 1024 * 1024;
+
+/// Whether copying an object of `size` bytes carries its tags over; when it
+/// doesn't, they have to be read before the copy and written back after.
+pub fn copy_keeps_tags(size: i64) -> bool {
+    size <= MAX_SINGLE_COPY_SIZE
+}
+
+/// `copy_object` on an existing client. Without encryption options no
+/// encryption headers are sent.
+pub async fn copy_with_client(
+    client: &Client,
+    source_bucket: &str,
+    source_key: &str,
+    dest_bucket: &str,
+    dest_key: &str,
+    source_size: i64,
+    options: &CopyOptions<'_>,
+) -> Result<()> {
+    let copy_source = copy_source(source_bucket, source_key, options.source_version_id);
+    let no_encryption = Encryption::default();
+    let encryption = options.encryption.unwrap_or(&no_encryption);
+    let source_encryption = options.source_encryption.unwrap_or(&no_encryption);
 
-    const FIVE_GB: i64 = 5 * 1024 * 1024 * 1024;
     const PART_SIZE: i64 = 100 * 1024 * 1024;
 
-    if source_size <= FIVE_GB {
-        client
-            .copy_object()
+    if source_size <= MAX_SINGLE_COPY_SIZE {
+        let mut req = encryption
+            .apply_copy(source_encryption, client.copy_object())
             .copy_source(&copy_source)
             .bucket(dest_bucket)
             .key(dest_key)
-            .send()
+            .set_storage_class(options.storage_class.clone());
+        if let Some(headers) = options.headers {
+            req = headers.apply_copy(req.metadata_directive(MetadataDirective::Replace));
+        }
+        req.send()
             .await
             .map_err(|e| anyhow!("S3 copy object failed: {e}"))?;
     } else {
-        let create = client
-            .create_multipart_upload()
-            .bucket(dest_bucket)
-            .key(dest_key)
+        // Unlike CopyObject, a multipart copy does not carry the source's
+        // headers and user metadata over on its own
+        let source_headers;
+        let headers = match options.headers {
+            Some(headers) => headers,
+            None => {
+                let head = source_encryption
+                    .apply_head(client.head_object())
+                    .bucket(source_bucket)
+                    .key(source_key)
+                    .set_version_id(options.source_version_id.map(|v| v.to_string()))
+                    .send()
+                    .await
+                    .map_err(|e| anyhow!("S3 head object failed for {source_key}: {e}"))?;
+                source_headers = ObjectHeaders::from_head(&head);
+                &source_headers
+            }
+        };
+
+        let create = headers
+            .apply_create_multipart(
+                encryption
+                    .apply_create_multipart(client.create_multipart_upload())
+                    .bucket(dest_bucket)
+                    .key(dest_key)
+                    .set_storage_class(options.storage_class.clone()),
+            )
             .send()
             .await
             .map_err(|e| anyhow!("Failed to create multipart upload: {e}"))?;
@@ -733,8 +1247,8 @@ pub async fn copy_object(
             let end = std::cmp::min(offset + PART_SIZE - 1, source_size - 1);
             let range = format!("bytes={}-{}", offset, end);
 
-            let part_result = client
-                .upload_part_copy()
+            let part_result = encryption
+                .apply_upload_part_copy(source_encryption, client.upload_part_copy())
                 .copy_source(&copy_source)
                 .copy_source_range(&range)
                 .bucket(dest_bucket)
@@ -792,6 +1306,11 @@ pub async fn copy_object(
     Ok(())
 }
 
+/// Copy an object between two targets by piping ranged GETs from the source
+/// straight into a (multipart) upload on the destination. No temp files are
+/// written; at most one part is held in memory at a time. Client-side
+/// encrypted sources are decrypted, and the copy is encrypted if the
+/// destination uses client-side encryption.
 pub async fn cross_target_copy(
     source_target: &StorageTarget,
     source_credentials: &TargetCredentials,
@@ -801,34 +1320,233 @@ pub async fn cross_target_copy(
     dest_credentials: &TargetCredentials,
     dest_bucket: &str,
     dest_key: &str,
-    temp_dir: &Path,
     on_progress: impl Fn(u64, u64),
 ) -> Result<()> {
-    let temp_file = temp_dir.join(format!("clone_{}", uuid::Uuid::now_v7()));
-    let temp_path = temp_file
-        .to_str()
-        .ok_or_else(|| anyhow!("Invalid temp path"))?;
-
-    let download_result = get_object(
-        source_target,
-        source_credentials,
-        source_bucket,
-        source_key,
-        temp_path,
-        &on_progress,
-    )
-    .await;
+    use std::time::Instant;
+
+    let source_encryption = Encryption::resolve(source_target, source_credentials, None)?;
+    let dest_encryption = Encryption::resolve(dest_target, dest_credentials, None)?;
+    let source_cse = ClientEncryption::for_target(source_target, source_credentials)?;
+    let dest_cse = ClientEncryption::for_target(dest_target, dest_credentials)?;
+    let source = build_client(source_target, source_credentials).await?;
+    let dest = build_client(dest_target, dest_credentials).await?;
+    let throttle_ids = [source_target.id.as_str(), dest_target.id.as_str()];
+    let source_key = &source_cse.encode_key(source_key);
+    let content_type = guess_content_type(dest_key);
+    let dest_key = &dest_cse.encode_key(dest_key);
+
+    let (head, source_encryption) = send_read(&source_encryption, |encryption| {
+        encryption.apply_head(source.head_object()).bucket(source_bucket).key(source_key).send()
+    })
+    .await
+    .map_err(|e| anyhow!("S3 head object failed for {source_key}: {e}"))?;
+
+    let total = head.content_length().unwrap_or(0).max(0);
+    let etag = head.e_tag().map(|s| s.to_string());
+    let mut headers = ObjectHeaders::from_head(&head);
+    if headers.content_type.is_none() {
+        headers.content_type = Some(content_type);
+    }
+
+    // The source's envelope is no use on the destination
+    let decryptor = source_cse.decryptor(head.metadata())?;
+    headers.metadata.retain(|k, _| !k.starts_with(cse::CSE_METADATA_KEY));
+    let plaintext_size = cse::plaintext_size(head.metadata()).unwrap_or(total);
+    let encryptor = match dest_cse.encryptor(plaintext_size.max(0) as u64)? {
+        Some((encryptor, metadata)) => {
+            headers.metadata.extend(metadata);
+            Some(encryptor)
+        }
+        None => None,
+    };
+    let mut reencoder = match (decryptor, encryptor) {
+        (None, None) => None,
+        (decryptor, encryptor) => Some(cse::Reencoder::new(decryptor, encryptor)),
+    };
+
+    let mut transferred: u64 = 0;
+    let mut last_emit = Instant::now();
+    let mut report = |n: usize| {
+        transferred += n as u64;
+        if last_emit.elapsed().as_millis() >= 50 {
+            on_progress(transferred, total as u64);
+            last_emit = Instant::now();
+        }
+    };
+
+    if total <= multipart::PART_SIZE {
+        let body = read_range(
+            &source,
+            source_bucket,
+            source_key,
+            &source_encryption,
+            None,
+            etag.as_deref(),
+            &throttle_ids,
+            &mut report,
+        )
+        .await?;
+        let body = match reencoder {
+            Some(mut reencoder) => {
+                let mut out = reencoder.push(&body)?;
+                out.extend(reencoder.finish()?);
+                out
+            }
+            None => body,
+        };
+
+        let req = dest_encryption.apply_put(dest.put_object().bucket(dest_bucket).key(dest_key));
+        headers
+            .apply_put(req)
+            .body(ByteStream::from(body))
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 put object failed: {e}"))?;
+
+        on_progress(total as u64, total as u64);
+        return Ok(());
+    }
+
+    let part_size = multipart::part_size_for(total);
+    let mut upload =
+        multipart::MultipartUpload::create(&dest, dest_bucket, dest_key, &headers, &dest_encryption)
+            .await?;
+
+    // Re-encoded bytes are gathered until they fill a part
+    let mut pending: Vec<u8> = Vec::new();
+    let mut offset: i64 = 0;
+    while offset < total {
+        let end = std::cmp::min(offset + part_size - 1, total - 1);
+        let result = match read_range(
+            &source,
+            source_bucket,
+            source_key,
+            &source_encryption,
+            Some((offset, end)),
+            etag.as_deref(),
+            &throttle_ids,
+            &mut report,
+        )
+        .await
+        {
+            Ok(data) => match reencoder.as_mut() {
+                Some(reencoder) => match reencoder.push(&data) {
+                    Ok(out) => {
+                        pending.extend(out);
+                        if pending.len() as i64 >= part_size {
+                            upload.upload_part(std::mem::take(&mut pending)).await
+                        } else {
+                            Ok(())
+                        }
+                    }
+                    Err(e) => Err(e),
+                },
+                None => upload.upload_part(data).await,
+            },
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            upload.abort().await;
+            return Err(e);
+        }
+        offset = end + 1;
+    }
+
+    if let Some(reencoder) = reencoder {
+        let result = match reencoder.finish() {
+            Ok(out) => {
+                pending.extend(out);
+                if pending.is_empty() {
+                    Ok(())
+                } else {
+                    upload.upload_part(pending).await
+                }
+            }
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            upload.abort().await;
+            return Err(e);
+        }
+    }
+
+    upload.complete().await?;
+    on_progress(total as u64, total as u64);
+    Ok(())
+}
+
+/// Read an object (or an inclusive byte range of it) fully into memory.
+/// When `etag` is given the request is conditional, so a source that changes
+/// mid-copy fails instead of producing a corrupt destination object.
+async fn read_range(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    encryption: &Encryption,
+    range: Option<(i64, i64)>,
+    etag: Option<&str>,
+    throttle_ids: &[&str],
+    on_chunk: &mut impl FnMut(usize),
+) -> Result<Vec<u8>> {
+    use tokio::io::AsyncReadExt;
 
-    if let Err(e) = download_result {
-        let _ = std::fs::remove_file(&temp_file);
-        return Err(e);
+    let mut req = encryption.apply_get(client.get_object()).bucket(bucket).key(key);
+    if let Some((start, end)) = range {
+        req = req.range(format!("bytes={start}-{end}"));
+    }
+    if let Some(etag) = etag {
+        req = req.if_match(etag);
     }
 
-    let upload_result =
-        put_object(dest_target, dest_credentials, dest_bucket, dest_key, temp_path).await;
+    let output = req
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 get object failed for {key}: {e}"))?;
+
+    let expected = output.content_length().unwrap_or(0).max(0) as usize;
+    let mut data = Vec::with_capacity(expected);
+    let mut reader = output.body.into_async_read();
+    let mut buf = vec![0u8; 256 * 1024];
 
-    let _ = std::fs::remove_file(&temp_file);
-    upload_result
+    loop {
+        let n = reader
+            .read(&mut buf)
+            .await
+            .map_err(|e| anyhow!("Failed to read S3 stream for {key}: {e}"))?;
+        if n == 0 {
+            break;
+        }
+        bandwidth::throttle(throttle_ids, n as u64).await;
+        data.extend_from_slice(&buf[..n]);
+        on_chunk(n);
+    }
+
+    if data.len() != expected {
+        return Err(anyhow!(
+            "Short read for {key}: expected {expected} bytes, got {}",
+            data.len()
+        ));
+    }
+
+    Ok(data)
+}
+
+/// `CopySource` value for `bucket/key`, with the key URL-encoded as S3
+/// requires.
+fn copy_source(bucket: &str, key: &str, version_id: Option<&str>) -> String {
+    let mut encoded = String::with_capacity(key.len());
+    for byte in key.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    match version_id {
+        Some(version_id) => format!("{bucket}/{encoded}?versionId={version_id}"),
+        None => format!("{bucket}/{encoded}"),
+    }
 }
 
 fn guess_content_type(key: &str) -> String {
@@ -887,3 +1605,40 @@ fn guess_content_type(key: &str) -> String {
     .to_string()
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_s3::operation::head_object::HeadObjectOutput;
+
+    #[test]
+    fn metadata_edit_keeps_unedited_headers_and_tags() {
+        let head = HeadObjectOutput::builder()
+            .content_length(6 * 1024 * 1024 * 1024)
+            .content_type("text/plain")
+            .content_language("de")
+            .expires_string("Wed, 21 Oct 2026 07:28:00 GMT")
+            .website_redirect_location("/elsewhere")
+            .metadata("owner", "old")
+            .metadata(format!("{}-key", cse::CSE_METADATA_KEY), "envelope")
+            .build();
+
+        let mut headers = ObjectHeaders {
+            content_type: Some("text/html".to_string()),
+            metadata: [("owner".to_string(), "new".to_string())].into(),
+            ..Default::default()
+        };
+        headers.keep_unedited(&head);
+
+        assert_eq!(headers.content_type.as_deref(), Some("text/html"));
+        assert_eq!(headers.content_language.as_deref(), Some("de"));
+        assert_eq!(headers.website_redirect_location.as_deref(), Some("/elsewhere"));
+        assert_eq!(headers.expires.map(|dt| dt.secs()), Some(1792567680));
+        assert_eq!(headers.metadata["owner"], "new");
+        assert_eq!(headers.metadata[&format!("{}-key", cse::CSE_METADATA_KEY)], "envelope");
+
+        // Tags are only carried over by a single CopyObject
+        assert!(!copy_keeps_tags(head.content_length().unwrap()));
+        assert!(copy_keeps_tags(MAX_SINGLE_COPY_SIZE));
+    }
+}
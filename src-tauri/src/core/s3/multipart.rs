@@ -0,0 +1,321 @@
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::error::ProvideErrorMetadata;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+use aws_sdk_s3::Client;
+use tokio::sync::Semaphore;
+
+use super::{Encryption, ObjectHeaders};
+use crate::models::{MultipartAbortResult, MultipartUploadRef, MultipartUploadSummary};
+
+/// Part size for multipart uploads. Parts are buffered in memory before they
+/// are sent, so this bounds memory use per upload.
+pub const PART_SIZE: i64 = 16 * 1024 * 1024;
+const MAX_PARTS: i64 = 10_000;
+/// Uploads whose parts are listed at once.
+const LIST_PARTS_CONCURRENCY: usize = 8;
+
+/// Part size for an object of `total` bytes, grown as needed to stay within
+/// S3's part count limit.
+pub fn part_size_for(total: i64) -> i64 {
+    std::cmp::max(PART_SIZE, (total + MAX_PARTS - 1) / MAX_PARTS)
+}
+
+/// An in-progress multipart upload. Parts are uploaded in order; callers
+/// must either `complete` or `abort` it.
+pub struct MultipartUpload<'a> {
+    client: &'a Client,
+    bucket: &'a str,
+    key: &'a str,
+    encryption: &'a Encryption,
+    upload_id: String,
+    parts: Vec<CompletedPart>,
+}
+
+impl<'a> MultipartUpload<'a> {
+    pub async fn create(
+        client: &'a Client,
+        bucket: &'a str,
+        key: &'a str,
+        headers: &ObjectHeaders,
+        encryption: &'a Encryption,
+    ) -> Result<Self> {
+        let create = headers
+            .apply_create_multipart(
+                encryption.apply_create_multipart(client.create_multipart_upload().bucket(bucket).key(key)),
+            )
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to create multipart upload: {e}"))?;
+
+        let upload_id = create
+            .upload_id()
+            .ok_or_else(|| anyhow!("No upload_id returned"))?
+            .to_string();
+
+        Ok(Self {
+            client,
+            bucket,
+            key,
+            encryption,
+            upload_id,
+            parts: Vec::new(),
+        })
+    }
+
+    pub async fn upload_part(&mut self, data: Vec<u8>) -> Result<()> {
+        let part_number = self.parts.len() as i32 + 1;
+        let part = self
+            .encryption
+            .apply_upload_part(self.client.upload_part())
+            .bucket(self.bucket)
+            .key(self.key)
+            .upload_id(&self.upload_id)
+            .part_number(part_number)
+            .body(ByteStream::from(data))
+            .send()
+            .await
+            .map_err(|e| anyhow!("UploadPart failed for part {part_number}: {e}"))?;
+
+        let etag = part
+            .e_tag()
+            .ok_or_else(|| anyhow!("No ETag for part {part_number}"))?;
+
+        self.parts.push(
+            CompletedPart::builder()
+                .e_tag(etag)
+                .part_number(part_number)
+                .build(),
+        );
+        Ok(())
+    }
+
+    pub async fn complete(self) -> Result<()> {
+        let completed = CompletedMultipartUpload::builder()
+            .set_parts(Some(self.parts))
+            .build();
+
+        self.client
+            .complete_multipart_upload()
+            .bucket(self.bucket)
+            .key(self.key)
+            .upload_id(&self.upload_id)
+            .multipart_upload(completed)
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to complete multipart upload: {e}"))?;
+        Ok(())
+    }
+
+    pub async fn abort(self) {
+        let _ = self
+            .client
+            .abort_multipart_upload()
+            .bucket(self.bucket)
+            .key(self.key)
+            .upload_id(&self.upload_id)
+            .send()
+            .await;
+    }
+}
+
+/// Incomplete multipart uploads in `bucket` under `prefix`, oldest first.
+/// Keys are returned as stored. Their parts are counted and summed with
+/// ListParts, one or more requests per upload, a few uploads at a time.
+pub async fn list_multipart_uploads(
+    client: &Client,
+    bucket: &str,
+    prefix: &str,
+) -> Result<Vec<MultipartUploadSummary>> {
+    let mut uploads = Vec::new();
+    let mut key_marker: Option<String> = None;
+    let mut upload_id_marker: Option<String> = None;
+
+    loop {
+        let output = client
+            .list_multipart_uploads()
+            .bucket(bucket)
+            .prefix(prefix)
+            .set_key_marker(key_marker.take())
+            .set_upload_id_marker(upload_id_marker.take())
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 list multipart uploads failed: {e}"))?;
+
+        for upload in output.uploads() {
+            let (Some(key), Some(upload_id)) = (upload.key(), upload.upload_id()) else {
+                continue;
+            };
+            uploads.push(MultipartUploadSummary {
+                key: key.to_string(),
+                upload_id: upload_id.to_string(),
+                initiated: upload.initiated().map(|dt| dt.secs()),
+                storage_class: upload.storage_class().map(|sc| sc.to_string()),
+                part_count: 0,
+                size: 0,
+            });
+        }
+
+        if output.is_truncated() != Some(true) {
+            break;
+        }
+        key_marker = output.next_key_marker().map(|s| s.to_string());
+        upload_id_marker = output.next_upload_id_marker().map(|s| s.to_string());
+        if key_marker.is_none() && upload_id_marker.is_none() {
+            break;
+        }
+    }
+
+    let semaphore = Arc::new(Semaphore::new(LIST_PARTS_CONCURRENCY));
+    let mut handles = Vec::new();
+    for upload in &uploads {
+        let permit = semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| anyhow!("Semaphore error: {e}"))?;
+        let client = client.clone();
+        let bucket = bucket.to_string();
+        let key = upload.key.clone();
+        let upload_id = upload.upload_id.clone();
+        handles.push(tokio::spawn(async move {
+            let result = count_parts(&client, &bucket, &key, &upload_id).await;
+            drop(permit);
+            result
+        }));
+    }
+    for (upload, handle) in uploads.iter_mut().zip(handles) {
+        (upload.part_count, upload.size) = handle.await.map_err(|e| anyhow!("List parts task failed: {e}"))??;
+    }
+
+    uploads.sort_by_key(|u| u.initiated.unwrap_or(i64::MAX));
+    Ok(uploads)
+}
+
+/// Number of parts stored for an upload and their total size. An upload
+/// that went away meanwhile counts as empty.
+async fn count_parts(client: &Client, bucket: &str, key: &str, upload_id: &str) -> Result<(i64, i64)> {
+    let mut part_count = 0;
+    let mut size = 0;
+    let mut marker: Option<String> = None;
+
+    loop {
+        let output = match client
+            .list_parts()
+            .bucket(bucket)
+            .key(key)
+            .upload_id(upload_id)
+            .set_part_number_marker(marker.take())
+            .send()
+            .await
+        {
+            Ok(output) => output,
+            Err(e) if e.code() == Some("NoSuchUpload") => return Ok((0, 0)),
+            Err(e) => return Err(anyhow!("S3 list parts failed for {key}: {e}")),
+        };
+
+        for part in output.parts() {
+            part_count += 1;
+            size += part.size().unwrap_or(0);
+        }
+
+        if output.is_truncated() != Some(true) {
+            break;
+        }
+        marker = output.next_part_number_marker().map(|s| s.to_string());
+        if marker.is_none() {
+            break;
+        }
+    }
+
+    Ok((part_count, size))
+}
+
+/// Abort an incomplete upload and free its parts. Returns `false` if the
+/// upload no longer exists.
+pub async fn abort_multipart_upload(client: &Client, bucket: &str, key: &str, upload_id: &str) -> Result<bool> {
+    match client
+        .abort_multipart_upload()
+        .bucket(bucket)
+        .key(key)
+        .upload_id(upload_id)
+        .send()
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(e) if e.code() == Some("NoSuchUpload") => Ok(false),
+        Err(e) => Err(anyhow!("S3 abort multipart upload failed for {key}: {e}")),
+    }
+}
+
+/// Abort each of `uploads`, carrying on past failures.
+pub async fn abort_multipart_uploads(
+    client: &Client,
+    bucket: &str,
+    uploads: &[MultipartUploadRef],
+) -> MultipartAbortResult {
+    let mut result = MultipartAbortResult::default();
+    for upload in uploads {
+        match abort_multipart_upload(client, bucket, &upload.key, &upload.upload_id).await {
+            Ok(true) => result.aborted += 1,
+            Ok(false) => {}
+            Err(e) => result.errors.push(e.to_string()),
+        }
+    }
+    result
+}
+
+/// Abort every upload in `bucket` started at least `older_than_days` ago.
+/// Uploads without an initiated time are left alone.
+pub async fn abort_stale_multipart_uploads(
+    client: &Client,
+    bucket: &str,
+    older_than_days: i64,
+) -> Result<MultipartAbortResult> {
+    if older_than_days < 0 {
+        return Err(anyhow!("Age must not be negative"));
+    }
+    let cutoff = chrono::Utc::now().timestamp() - older_than_days * 86400;
+    let mut stale = Vec::new();
+    let mut key_marker: Option<String> = None;
+    let mut upload_id_marker: Option<String> = None;
+
+    // Part counts aren't needed here, so list without ListParts
+    loop {
+        let output = client
+            .list_multipart_uploads()
+            .bucket(bucket)
+            .set_key_marker(key_marker.take())
+            .set_upload_id_marker(upload_id_marker.take())
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 list multipart uploads failed: {e}"))?;
+
+        for upload in output.uploads() {
+            let (Some(key), Some(upload_id), Some(initiated)) =
+                (upload.key(), upload.upload_id(), upload.initiated())
+            else {
+                continue;
+            };
+            if initiated.secs() <= cutoff {
+                stale.push(MultipartUploadRef {
+                    key: key.to_string(),
+                    upload_id: upload_id.to_string(),
+                });
+            }
+        }
+
+        if output.is_truncated() != Some(true) {
+            break;
+        }
+        key_marker = output.next_key_marker().map(|s| s.to_string());
+        upload_id_marker = output.next_upload_id_marker().map(|s| s.to_string());
+        if key_marker.is_none() && upload_id_marker.is_none() {
+            break;
+        }
+    }
+
+    Ok(abort_multipart_uploads(client, bucket, &stale).await)
+}
@@ -0,0 +1,284 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::presigning::PresigningConfig;
+use aws_sdk_s3::Client;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::Sha256;
+
+use super::{build_client, read_encryption, target_region, ClientEncryption, Encryption};
+use crate::models::{
+    PresignResponseOverrides, PresignedPost, PresignedRequest, ServerSideEncryption, StorageTarget,
+    TargetCredentials,
+};
+
+/// SigV4 signatures can't outlive a week.
+const MAX_EXPIRY_SECS: u64 = 7 * 24 * 3600;
+
+fn presigning_config(expires_in_secs: u64) -> Result<PresigningConfig> {
+    PresigningConfig::builder()
+        .expires_in(Duration::from_secs(expires_in_secs))
+        .build()
+        .map_err(|e| anyhow!("Failed to build presigning config: {e}"))
+}
+
+/// Presigned GET URL for `key`. For SSE-C objects the key headers are part
+/// of the signature, so whoever uses the URL must send them as well; other
+/// objects are signed without them.
+/// Client-side encrypted objects are served as stored, still encrypted.
+/// `response` sets headers S3 answers with, e.g. to force a download name.
+pub async fn presign_object(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+    version_id: Option<&str>,
+    expires_in_secs: u64,
+    sse: Option<&ServerSideEncryption>,
+    response: Option<&PresignResponseOverrides>,
+) -> Result<String> {
+    ObjectPresigner::new(target, credentials, sse)
+        .await?
+        .presign(bucket, key, version_id, expires_in_secs, response)
+        .await
+}
+
+/// Presigns GET URLs for any number of objects on one target, reusing its
+/// client. See `presign_object`.
+pub struct ObjectPresigner {
+    client: Client,
+    cse: ClientEncryption,
+    encryption: Encryption,
+}
+
+impl ObjectPresigner {
+    pub async fn new(
+        target: &StorageTarget,
+        credentials: &TargetCredentials,
+        sse: Option<&ServerSideEncryption>,
+    ) -> Result<Self> {
+        Ok(Self {
+            encryption: Encryption::resolve(target, credentials, sse)?,
+            cse: ClientEncryption::for_target(target, credentials)?,
+            client: build_client(target, credentials).await?,
+        })
+    }
+
+    pub async fn presign(
+        &self,
+        bucket: &str,
+        key: &str,
+        version_id: Option<&str>,
+        expires_in_secs: u64,
+        response: Option<&PresignResponseOverrides>,
+    ) -> Result<String> {
+        let client = &self.client;
+        let key = self.cse.encode_key(key);
+        let encryption = read_encryption(client, bucket, &key, version_id, &self.encryption).await?;
+        let response = response.cloned().unwrap_or_default();
+        let content_disposition = response
+            .content_disposition
+            .filter(|v| !v.trim().is_empty())
+            .or_else(|| {
+                response
+                    .download_filename
+                    .filter(|v| !v.trim().is_empty())
+                    .map(|name| attachment_disposition(name.trim()))
+            });
+
+        let presigned = encryption
+            .apply_get(client.get_object())
+            .bucket(bucket)
+            .key(key)
+            .set_version_id(version_id.map(|v| v.to_string()))
+            .set_response_content_type(response.content_type.filter(|v| !v.trim().is_empty()))
+            .set_response_content_disposition(content_disposition)
+            .set_response_cache_control(response.cache_control.filter(|v| !v.trim().is_empty()))
+            .set_response_content_language(response.content_language.filter(|v| !v.trim().is_empty()))
+            .set_response_content_encoding(response.content_encoding.filter(|v| !v.trim().is_empty()))
+            .presigned(presigning_config(expires_in_secs)?)
+            .await
+            .map_err(|e| anyhow!("S3 presign failed: {e}"))?;
+
+        Ok(presigned.uri().to_string())
+    }
+}
+
+/// Presigned PUT for uploading to `key`. The content type and length, when
+/// given, are signed, so an upload with different values is rejected. The
+/// returned headers must be sent with the upload. For a size limit rather
+/// than an exact size, use a POST policy. SSE-C can't be used, since the
+/// key would be handed out with the link.
+pub async fn presign_put_object(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+    expires_in_secs: u64,
+    content_type: Option<&str>,
+    content_length: Option<i64>,
+    sse: Option<&ServerSideEncryption>,
+) -> Result<PresignedRequest> {
+    if content_length.is_some_and(|len| len < 0) {
+        return Err(anyhow!("Content length can't be negative"));
+    }
+    let encryption = Encryption::resolve(target, credentials, sse)?;
+    if let Encryption::Customer { .. } = encryption {
+        return Err(anyhow!("Upload links can't use SSE-C"));
+    }
+    let client = build_client(target, credentials).await?;
+
+    let presigned = encryption
+        .apply_put(client.put_object())
+        .bucket(bucket)
+        .key(ClientEncryption::for_target(target, credentials)?.encode_key(key))
+        .set_content_type(content_type.map(str::trim).filter(|v| !v.is_empty()).map(str::to_string))
+        .set_content_length(content_length)
+        .presigned(presigning_config(expires_in_secs)?)
+        .await
+        .map_err(|e| anyhow!("S3 presign failed: {e}"))?;
+
+    let mut headers: HashMap<String, String> = presigned
+        .headers()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    if let Some(len) = content_length {
+        headers
+            .entry("content-length".to_string())
+            .or_insert_with(|| len.to_string());
+    }
+
+    Ok(PresignedRequest {
+        url: presigned.uri().to_string(),
+        method: presigned.method().to_string(),
+        headers,
+    })
+}
+
+/// An S3 POST policy for browser uploads under `key_prefix`. The form uploads
+/// to `<key_prefix><file name>`, and S3 rejects files larger than `max_size`
+/// or, with `content_type_prefix`, of other types. The form must then carry
+/// a matching Content-Type field. The target's default encryption applies.
+pub fn presign_post(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key_prefix: &str,
+    expires_in_secs: u64,
+    max_size: Option<i64>,
+    content_type_prefix: Option<&str>,
+) -> Result<PresignedPost> {
+    if expires_in_secs == 0 || expires_in_secs > MAX_EXPIRY_SECS {
+        return Err(anyhow!("Expiry must be between 1 second and 7 days"));
+    }
+    if max_size.is_some_and(|max| max <= 0) {
+        return Err(anyhow!("Maximum size must be positive"));
+    }
+    if credentials.access_key_id.trim().is_empty() || credentials.secret_access_key.trim().is_empty() {
+        return Err(anyhow!("Missing access key credentials for target."));
+    }
+    let encryption = Encryption::resolve(target, credentials, None)?;
+    let key_prefix = ClientEncryption::for_target(target, credentials)?.encode_key(key_prefix);
+
+    let region = target_region(target);
+    let now = chrono::Utc::now();
+    let date = now.format("%Y%m%d").to_string();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let expires_at = (now + chrono::Duration::seconds(expires_in_secs as i64))
+        .format("%Y-%m-%dT%H:%M:%S%.3fZ")
+        .to_string();
+    let credential = format!(
+        "{}/{date}/{region}/s3/aws4_request",
+        credentials.access_key_id.trim()
+    );
+
+    let mut fields = BTreeMap::new();
+    fields.insert("key".to_string(), format!("{key_prefix}${{filename}}"));
+    fields.insert("x-amz-algorithm".to_string(), "AWS4-HMAC-SHA256".to_string());
+    fields.insert("x-amz-credential".to_string(), credential);
+    fields.insert("x-amz-date".to_string(), amz_date);
+    if let Some(token) = credentials.session_token.as_deref().filter(|t| !t.is_empty()) {
+        fields.insert("x-amz-security-token".to_string(), token.to_string());
+    }
+    for (name, value) in encryption.post_fields()? {
+        fields.insert(name.to_string(), value);
+    }
+
+    let mut conditions = vec![
+        json!({ "bucket": bucket }),
+        json!(["starts-with", "$key", key_prefix]),
+    ];
+    for (name, value) in &fields {
+        if name != "key" {
+            conditions.push(json!({ name: value }));
+        }
+    }
+    if let Some(max) = max_size {
+        conditions.push(json!(["content-length-range", 0, max]));
+    }
+    if let Some(prefix) = content_type_prefix.map(str::trim).filter(|p| !p.is_empty()) {
+        conditions.push(json!(["starts-with", "$Content-Type", prefix]));
+    }
+
+    let policy = BASE64.encode(
+        json!({ "expiration": expires_at, "conditions": conditions }).to_string(),
+    );
+    let signing_key = [date.as_str(), region.as_str(), "s3", "aws4_request"].iter().fold(
+        format!("AWS4{}", credentials.secret_access_key.trim()).into_bytes(),
+        |key, part| hmac_sha256(&key, part.as_bytes()),
+    );
+    let signature = hmac_sha256(&signing_key, policy.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+    fields.insert("policy".to_string(), policy);
+    fields.insert("x-amz-signature".to_string(), signature);
+
+    Ok(PresignedPost {
+        url: bucket_url(target, bucket, &region),
+        fields,
+        expires_at,
+    })
+}
+
+/// URL of `bucket` itself, which POST uploads go to.
+fn bucket_url(target: &StorageTarget, bucket: &str, region: &str) -> String {
+    let endpoint = target.endpoint.trim().trim_end_matches('/');
+    if endpoint.is_empty() {
+        return format!("https://{bucket}.s3.{region}.amazonaws.com");
+    }
+    if target.force_path_style {
+        return format!("{endpoint}/{bucket}");
+    }
+    match endpoint.split_once("://") {
+        Some((scheme, host)) => format!("{scheme}://{bucket}.{host}"),
+        None => format!("https://{bucket}.{endpoint}"),
+    }
+}
+
+/// `attachment` Content-Disposition for `filename`, with an ASCII fallback
+/// and the exact name in RFC 5987 form.
+fn attachment_disposition(filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| if (c.is_ascii_graphic() || c == ' ') && c != '"' && c != '\\' { c } else { '_' })
+        .collect();
+    let mut encoded = String::with_capacity(filename.len());
+    for byte in filename.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    format!("attachment; filename=\"{fallback}\"; filename*=UTF-8''{encoded}")
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).expect("HMAC takes keys of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
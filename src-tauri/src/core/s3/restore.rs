@@ -0,0 +1,140 @@
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::error::{ProvideErrorMetadata, SdkError};
+use aws_sdk_s3::types::{GlacierJobParameters, RestoreRequest, Tier};
+use aws_sdk_s3::Client;
+
+/// Where an archived object stands, from its HEAD response.
+pub struct RestoreStatus {
+    pub storage_class: Option<String>,
+    /// `Some(true)` while a restore is running, `Some(false)` once a
+    /// restored copy is readable, `None` if no restore was requested.
+    pub ongoing: Option<bool>,
+    /// When the restored copy is removed again, as sent by S3.
+    pub expiry_date: Option<String>,
+}
+
+impl RestoreStatus {
+    /// The object can be downloaded right now.
+    pub fn is_readable(&self) -> bool {
+        self.ongoing == Some(false) || !is_archived_class(self.storage_class.as_deref())
+    }
+}
+
+/// Storage classes whose objects must be restored before they can be read.
+pub fn is_archived_class(storage_class: Option<&str>) -> bool {
+    matches!(storage_class, Some("GLACIER" | "DEEP_ARCHIVE"))
+}
+
+pub fn validate_restore_request(days: i32, tier: &str) -> Result<()> {
+    if days < 1 {
+        return Err(anyhow!("Restored copies must be kept for at least 1 day"));
+    }
+    if !matches!(tier, "Standard" | "Bulk" | "Expedited") {
+        return Err(anyhow!("Restore tier must be Standard, Bulk or Expedited"));
+    }
+    Ok(())
+}
+
+/// Ask S3 to make a temporary copy of an archived object readable for
+/// `days`. Returns `false` if a restore was already in progress.
+pub async fn restore_object(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    version_id: Option<&str>,
+    days: i32,
+    tier: &str,
+) -> Result<bool> {
+    validate_restore_request(days, tier)?;
+
+    let parameters = GlacierJobParameters::builder()
+        .tier(Tier::from(tier))
+        .build()
+        .map_err(|e| anyhow!("Failed to build restore request: {e}"))?;
+    let request = RestoreRequest::builder()
+        .days(days)
+        .glacier_job_parameters(parameters)
+        .build();
+
+    match client
+        .restore_object()
+        .bucket(bucket)
+        .key(key)
+        .set_version_id(version_id.map(|v| v.to_string()))
+        .restore_request(request)
+        .send()
+        .await
+    {
+        Ok(_) => Ok(true),
+        Err(e) if e.code() == Some("RestoreAlreadyInProgress") => Ok(false),
+        Err(e) => Err(anyhow!("S3 restore object failed for {key}: {e}")),
+    }
+}
+
+/// Where the restore of `key` stands, or `None` if the object no longer
+/// exists.
+pub async fn restore_status(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    version_id: Option<&str>,
+) -> Result<Option<RestoreStatus>> {
+    let head = match client
+        .head_object()
+        .bucket(bucket)
+        .key(key)
+        .set_version_id(version_id.map(|v| v.to_string()))
+        .send()
+        .await
+    {
+        Ok(head) => head,
+        Err(SdkError::ServiceError(e)) if e.err().is_not_found() => return Ok(None),
+        Err(e) => return Err(anyhow!("S3 head object failed for {key}: {e}")),
+    };
+
+    let (ongoing, expiry_date) = match head.restore() {
+        Some(header) => parse_restore_header(header),
+        None => (None, None),
+    };
+    Ok(Some(RestoreStatus {
+        storage_class: head.storage_class().map(|c| c.to_string()),
+        ongoing,
+        expiry_date,
+    }))
+}
+
+/// Parse an `x-amz-restore` header such as
+/// `ongoing-request="false", expiry-date="Fri, 21 Dec 2012 00:00:00 GMT"`.
+pub fn parse_restore_header(header: &str) -> (Option<bool>, Option<String>) {
+    let value = |name: &str| {
+        let start = header.find(&format!("{name}=\""))? + name.len() + 2;
+        let end = header[start..].find('"')? + start;
+        Some(header[start..end].to_string())
+    };
+    let ongoing = value("ongoing-request").map(|v| v == "true");
+    (ongoing, value("expiry-date"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_restore_header;
+
+    #[test]
+    fn restore_header_of_a_finished_restore() {
+        assert_eq!(
+            parse_restore_header(r#"ongoing-request="false", expiry-date="Fri, 21 Dec 2012 00:00:00 GMT""#),
+            (Some(false), Some("Fri, 21 Dec 2012 00:00:00 GMT".to_string()))
+        );
+    }
+
+    #[test]
+    fn restore_header_of_a_running_restore() {
+        assert_eq!(parse_restore_header(r#"ongoing-request="true""#), (Some(true), None));
+    }
+
+    #[test]
+    fn restore_header_without_known_fields() {
+        assert_eq!(parse_restore_header(""), (None, None));
+        assert_eq!(parse_restore_header(r#"ongoing-request="false"#), (None, None));
+    }
+}
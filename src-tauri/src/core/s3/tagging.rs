@@ -0,0 +1,136 @@
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::types::{Tag, Tagging};
+use aws_sdk_s3::Client;
+
+use super::{build_client, ClientEncryption};
+use crate::models::{ObjectTag, StorageTarget, TargetCredentials};
+
+const MAX_TAGS: usize = 10;
+const MAX_TAG_KEY_LEN: usize = 128;
+const MAX_TAG_VALUE_LEN: usize = 256;
+
+pub async fn get_object_tagging(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+) -> Result<Vec<ObjectTag>> {
+    let key = ClientEncryption::for_target(target, credentials)?.encode_key(key);
+    let client = build_client(target, credentials).await?;
+    get_tags(&client, bucket, &key).await
+}
+
+pub async fn put_object_tagging(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+    tags: &[ObjectTag],
+) -> Result<()> {
+    let key = ClientEncryption::for_target(target, credentials)?.encode_key(key);
+    let client = build_client(target, credentials).await?;
+    put_tags(&client, bucket, &key, tags).await
+}
+
+pub async fn delete_object_tagging(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+) -> Result<()> {
+    let key = ClientEncryption::for_target(target, credentials)?.encode_key(key);
+    let client = build_client(target, credentials).await?;
+    client
+        .delete_object_tagging()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 delete object tagging failed: {e}"))?;
+    Ok(())
+}
+
+pub async fn get_tags(client: &Client, bucket: &str, key: &str) -> Result<Vec<ObjectTag>> {
+    let output = client
+        .get_object_tagging()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 get object tagging failed for {key}: {e}"))?;
+
+    Ok(output
+        .tag_set()
+        .iter()
+        .map(|tag| ObjectTag {
+            key: tag.key().to_string(),
+            value: tag.value().to_string(),
+        })
+        .collect())
+}
+
+/// Replace the full tag set of `key`. An empty set removes all tags.
+pub async fn put_tags(client: &Client, bucket: &str, key: &str, tags: &[ObjectTag]) -> Result<()> {
+    validate_tags(tags)?;
+
+    if tags.is_empty() {
+        client
+            .delete_object_tagging()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 delete object tagging failed for {key}: {e}"))?;
+        return Ok(());
+    }
+
+    let mut tag_set = Vec::with_capacity(tags.len());
+    for tag in tags {
+        tag_set.push(
+            Tag::builder()
+                .key(&tag.key)
+                .value(&tag.value)
+                .build()
+                .map_err(|e| anyhow!("Invalid tag {}: {e}", tag.key))?,
+        );
+    }
+    let tagging = Tagging::builder()
+        .set_tag_set(Some(tag_set))
+        .build()
+        .map_err(|e| anyhow!("Invalid tag set: {e}"))?;
+
+    client
+        .put_object_tagging()
+        .bucket(bucket)
+        .key(key)
+        .tagging(tagging)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 put object tagging failed for {key}: {e}"))?;
+    Ok(())
+}
+
+/// Check a tag set against S3's limits before sending it.
+pub fn validate_tags(tags: &[ObjectTag]) -> Result<()> {
+    if tags.len() > MAX_TAGS {
+        return Err(anyhow!("Objects can have at most {MAX_TAGS} tags"));
+    }
+    for (i, tag) in tags.iter().enumerate() {
+        if tag.key.is_empty() {
+            return Err(anyhow!("Tag keys cannot be empty"));
+        }
+        if tag.key.chars().count() > MAX_TAG_KEY_LEN {
+            return Err(anyhow!("Tag key {} is longer than {MAX_TAG_KEY_LEN} characters", tag.key));
+        }
+        if tag.value.chars().count() > MAX_TAG_VALUE_LEN {
+            return Err(anyhow!(
+                "Value of tag {} is longer than {MAX_TAG_VALUE_LEN} characters",
+                tag.key
+            ));
+        }
+        if tags[..i].iter().any(|t| t.key == tag.key) {
+            return Err(anyhow!("Duplicate tag key {}", tag.key));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,253 @@
+use anyhow::{anyhow, Result};
+use aws_sdk_s3::primitives::DateTime;
+
+use super::{build_client, copy_object, send_read, ClientEncryption, CopyOptions, Encryption};
+use crate::models::{ObjectVersion, ObjectVersionPage, StorageTarget, TargetCredentials};
+
+/// The bucket's versioning status: "Enabled", "Suspended", or `None` if
+/// versioning has never been turned on.
+pub async fn get_bucket_versioning(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<Option<String>> {
+    let client = build_client(target, credentials).await?;
+    let output = client
+        .get_bucket_versioning()
+        .bucket(bucket)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 get bucket versioning failed: {e}"))?;
+    Ok(output.status().map(|s| s.to_string()))
+}
+
+/// One page of versions and delete markers directly under `prefix`, grouped
+/// by key with the newest version first. Deeper prefixes are returned as
+/// folders. Key markers are stored keys, to be passed back as they are.
+pub async fn list_object_versions(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    prefix: &str,
+    max_keys: i32,
+    key_marker: Option<String>,
+    version_id_marker: Option<String>,
+) -> Result<ObjectVersionPage> {
+    let cse = ClientEncryption::for_target(target, credentials)?;
+    let client = build_client(target, credentials).await?;
+    let stored_prefix = cse.encode_key(prefix);
+    let output = client
+        .list_object_versions()
+        .bucket(bucket)
+        .prefix(&stored_prefix)
+        .delimiter("/")
+        .max_keys(max_keys)
+        .set_key_marker(key_marker)
+        .set_version_id_marker(version_id_marker)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 list object versions failed: {e}"))?;
+
+    let mut entries: Vec<(Option<DateTime>, ObjectVersion)> = Vec::new();
+
+    for v in output.versions() {
+        let Some(key) = v.key().filter(|k| !k.is_empty()) else {
+            continue;
+        };
+        entries.push((
+            v.last_modified().cloned(),
+            ObjectVersion {
+                key: cse.decode_key(key),
+                version_id: v.version_id().unwrap_or("null").to_string(),
+                is_latest: v.is_latest().unwrap_or(false),
+                is_delete_marker: false,
+                size: v.size().unwrap_or(0),
+                last_modified: v.last_modified().map(|dt| dt.to_string()),
+                etag: v.e_tag().map(|s| s.to_string()),
+                storage_class: v.storage_class().map(|sc| sc.to_string()),
+            },
+        ));
+    }
+
+    for m in output.delete_markers() {
+        let Some(key) = m.key().filter(|k| !k.is_empty()) else {
+            continue;
+        };
+        entries.push((
+            m.last_modified().cloned(),
+            ObjectVersion {
+                key: cse.decode_key(key),
+                version_id: m.version_id().unwrap_or("null").to_string(),
+                is_latest: m.is_latest().unwrap_or(false),
+                is_delete_marker: true,
+                size: 0,
+                last_modified: m.last_modified().map(|dt| dt.to_string()),
+                etag: None,
+                storage_class: None,
+            },
+        ));
+    }
+
+    // Versions and delete markers come back as separate lists
+    entries.sort_by(|(a_time, a), (b_time, b)| {
+        a.key
+            .cmp(&b.key)
+            .then(b.is_latest.cmp(&a.is_latest))
+            .then_with(|| {
+                let a_secs = a_time.as_ref().map(|dt| (dt.secs(), dt.subsec_nanos()));
+                let b_secs = b_time.as_ref().map(|dt| (dt.secs(), dt.subsec_nanos()));
+                b_secs.cmp(&a_secs)
+            })
+    });
+
+    let folders = output
+        .common_prefixes()
+        .iter()
+        .filter_map(|cp| cp.prefix())
+        .filter(|p| *p != stored_prefix)
+        .map(|p| cse.decode_key(p))
+        .collect();
+
+    let is_truncated = output.is_truncated() == Some(true);
+    Ok(ObjectVersionPage {
+        versions: entries.into_iter().map(|(_, v)| v).collect(),
+        folders,
+        next_key_marker: if is_truncated {
+            output.next_key_marker().map(|s| s.to_string())
+        } else {
+            None
+        },
+        next_version_id_marker: if is_truncated {
+            output.next_version_id_marker().map(|s| s.to_string())
+        } else {
+            None
+        },
+        is_truncated,
+    })
+}
+
+/// Make an older version current again by copying it over the key. The
+/// version itself is kept, and so is every newer version.
+pub async fn restore_object_version(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+    version_id: &str,
+) -> Result<()> {
+    let default_encryption = Encryption::resolve(target, credentials, None)?;
+    let stored_key = ClientEncryption::for_target(target, credentials)?.encode_key(key);
+    let client = build_client(target, credentials).await?;
+    let (head, source_encryption) = send_read(&default_encryption, |encryption| {
+        encryption
+            .apply_head(client.head_object())
+            .bucket(bucket)
+            .key(&stored_key)
+            .version_id(version_id)
+            .send()
+    })
+    .await
+    .map_err(|e| anyhow!("S3 head object failed for version {version_id}: {e}"))?;
+
+    let encryption = Encryption::from_head(&head, &default_encryption);
+    let options = CopyOptions {
+        storage_class: head.storage_class().cloned(),
+        source_version_id: Some(version_id),
+        encryption: Some(&encryption),
+        source_encryption: Some(&source_encryption),
+        ..Default::default()
+    };
+    copy_object(
+        target,
+        credentials,
+        bucket,
+        key,
+        bucket,
+        key,
+        head.content_length().unwrap_or(0),
+        &options,
+    )
+    .await
+}
+
+/// Permanently delete one version (or delete marker) of `key`.
+pub async fn delete_object_version(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+    version_id: &str,
+) -> Result<()> {
+    let key = ClientEncryption::for_target(target, credentials)?.encode_key(key);
+    let client = build_client(target, credentials).await?;
+    client
+        .delete_object()
+        .bucket(bucket)
+        .key(key)
+        .version_id(version_id)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 delete object version failed: {e}"))?;
+    Ok(())
+}
+
+/// Undelete `key` by removing the delete marker that hides it. Fails if the
+/// key's current version is not a delete marker.
+pub async fn undelete_object(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    key: &str,
+) -> Result<()> {
+    let readable_key = key;
+    let key = &ClientEncryption::for_target(target, credentials)?.encode_key(key);
+    let client = build_client(target, credentials).await?;
+    let mut key_marker: Option<String> = None;
+    let mut version_id_marker: Option<String> = None;
+
+    // Listings are ordered by key, so keys sharing this prefix may come first
+    let marker_version = loop {
+        let output = client
+            .list_object_versions()
+            .bucket(bucket)
+            .prefix(key)
+            .set_key_marker(key_marker)
+            .set_version_id_marker(version_id_marker)
+            .send()
+            .await
+            .map_err(|e| anyhow!("S3 list object versions failed: {e}"))?;
+
+        let latest_marker = output
+            .delete_markers()
+            .iter()
+            .find(|m| m.key() == Some(key.as_str()) && m.is_latest() == Some(true))
+            .and_then(|m| m.version_id().map(|v| v.to_string()));
+        if latest_marker.is_some() {
+            break latest_marker;
+        }
+
+        let passed_key = output
+            .versions()
+            .iter()
+            .filter_map(|v| v.key())
+            .chain(output.delete_markers().iter().filter_map(|m| m.key()))
+            .any(|k| k > key.as_str());
+        if passed_key || output.is_truncated() != Some(true) {
+            break None;
+        }
+        key_marker = output.next_key_marker().map(|s| s.to_string());
+        version_id_marker = output.next_version_id_marker().map(|s| s.to_string());
+    };
+
+    let version_id =
+        marker_version.ok_or_else(|| anyhow!("{readable_key} is not deleted, nothing to restore"))?;
+    client
+        .delete_object()
+        .bucket(bucket)
+        .key(key)
+        .version_id(&version_id)
+        .send()
+        .await
+        .map_err(|e| anyhow!("S3 delete marker removal failed: {e}"))?;
+    Ok(())
+}
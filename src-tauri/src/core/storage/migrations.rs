@@ -212,5 +212,209 @@ pub fn run_migrations(conn: &Connection) -> Result<()> {
         conn.execute_batch("ALTER TABLE targets ADD COLUMN scoped_bucket TEXT;")?;
     }
 
+    // v3: bandwidth caps
+    add_column_if_missing(conn, "targets", "bandwidth_limit_bytes_per_sec", "INTEGER")?;
+    add_column_if_missing(
+        conn,
+        "app_settings",
+        "bandwidth_limit_bytes_per_sec",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(
+        conn,
+        "app_settings",
+        "bandwidth_schedule_enabled",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(
+        conn,
+        "app_settings",
+        "bandwidth_schedule_start_minute",
+        "INTEGER NOT NULL DEFAULT 540",
+    )?;
+    add_column_if_missing(
+        conn,
+        "app_settings",
+        "bandwidth_schedule_end_minute",
+        "INTEGER NOT NULL DEFAULT 1080",
+    )?;
+
+    // v4: object tags in the bucket index and batch jobs
+    add_column_if_missing(conn, "bucket_index_state", "include_tags", "INTEGER NOT NULL DEFAULT 0")?;
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS bucket_index_tags (
+          target_id TEXT NOT NULL,
+          bucket TEXT NOT NULL,
+          key TEXT NOT NULL,
+          tag_key TEXT NOT NULL,
+          tag_value TEXT NOT NULL,
+          PRIMARY KEY (target_id, bucket, key, tag_key)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_bit_tag
+          ON bucket_index_tags(target_id, bucket, tag_key, tag_value);
+
+        CREATE TABLE IF NOT EXISTS batch_jobs (
+          id TEXT PRIMARY KEY,
+          kind TEXT NOT NULL,
+          status TEXT NOT NULL DEFAULT 'pending',
+          target_id TEXT NOT NULL,
+          bucket TEXT NOT NULL,
+          prefix TEXT NOT NULL DEFAULT '',
+          params_json TEXT NOT NULL DEFAULT '{}',
+          enumeration_token TEXT,
+          enumeration_complete INTEGER NOT NULL DEFAULT 0,
+          total_items INTEGER NOT NULL DEFAULT 0,
+          completed_items INTEGER NOT NULL DEFAULT 0,
+          failed_items INTEGER NOT NULL DEFAULT 0,
+          skipped_items INTEGER NOT NULL DEFAULT 0,
+          created_at INTEGER NOT NULL,
+          updated_at INTEGER NOT NULL,
+          completed_at INTEGER,
+          FOREIGN KEY(target_id) REFERENCES targets(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_batch_jobs_status ON batch_jobs(status);
+
+        CREATE TABLE IF NOT EXISTS batch_job_items (
+          id TEXT PRIMARY KEY,
+          job_id TEXT NOT NULL,
+          key TEXT NOT NULL,
+          version_id TEXT,
+          size INTEGER NOT NULL DEFAULT 0,
+          status TEXT NOT NULL DEFAULT 'pending',
+          error_message TEXT,
+          created_at INTEGER NOT NULL,
+          updated_at INTEGER NOT NULL,
+          FOREIGN KEY(job_id) REFERENCES batch_jobs(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_batch_job_items_status ON batch_job_items(job_id, status);
+        "#,
+    )?;
+
+    // v5: multipart uploads as batch job items
+    add_column_if_missing(conn, "batch_job_items", "upload_id", "TEXT")?;
+
+    // v6: archive restore tracking
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS object_restores (
+          id TEXT PRIMARY KEY,
+          target_id TEXT NOT NULL,
+          bucket TEXT NOT NULL,
+          key TEXT NOT NULL,
+          version_id TEXT,
+          tier TEXT NOT NULL,
+          days INTEGER NOT NULL,
+          status TEXT NOT NULL DEFAULT 'pending',
+          expiry_date TEXT,
+          error_message TEXT,
+          requested_at INTEGER NOT NULL,
+          last_checked_at INTEGER,
+          completed_at INTEGER,
+          FOREIGN KEY(target_id) REFERENCES targets(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_object_restores_status ON object_restores(status);
+        "#,
+    )?;
+
+    // v7: default server-side encryption
+    add_column_if_missing(conn, "targets", "encryption_mode", "TEXT")?;
+    add_column_if_missing(conn, "targets", "encryption_kms_key_id", "TEXT")?;
+    add_column_if_missing(conn, "target_credentials", "sse_customer_key", "TEXT")?;
+
+    // v8: client-side encryption
+    add_column_if_missing(conn, "targets", "client_encryption", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "targets", "encrypt_key_names", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "target_credentials", "client_encryption_key", "TEXT")?;
+
+    // v9: share links
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS share_links (
+          id TEXT PRIMARY KEY,
+          target_id TEXT NOT NULL,
+          bucket TEXT NOT NULL,
+          key TEXT NOT NULL,
+          version_id TEXT,
+          url TEXT NOT NULL,
+          expires_in_secs INTEGER NOT NULL,
+          response_json TEXT,
+          signing_key_id TEXT NOT NULL,
+          note TEXT,
+          created_at INTEGER NOT NULL,
+          expires_at INTEGER NOT NULL,
+          FOREIGN KEY(target_id) REFERENCES targets(id) ON DELETE CASCADE
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_share_links_expires ON share_links(expires_at);
+        "#,
+    )?;
+
+    // v10: background sweep of incomplete multipart uploads
+    add_column_if_missing(conn, "app_settings", "multipart_sweep_enabled", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "app_settings", "multipart_sweep_days", "INTEGER NOT NULL DEFAULT 7")?;
+
+    // v11: move jobs, clone jobs that delete their source
+    add_column_if_missing(conn, "clone_jobs", "delete_source", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // v12: trash mode
+    add_column_if_missing(conn, "targets", "trash_enabled", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "targets", "trash_bucket", "TEXT")?;
+    add_column_if_missing(conn, "targets", "trash_retention_days", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // v13: read-only targets and protected prefixes
+    add_column_if_missing(conn, "targets", "read_only", "INTEGER NOT NULL DEFAULT 0")?;
+    add_column_if_missing(conn, "targets", "protected_prefixes_json", "TEXT NOT NULL DEFAULT '[]'")?;
+
+    // v14: audit log. Entries outlive the targets they mention.
+    conn.execute_batch(
+        r#"
+        CREATE TABLE IF NOT EXISTS audit_log (
+          id TEXT PRIMARY KEY,
+          timestamp INTEGER NOT NULL,
+          initiator TEXT NOT NULL,
+          operation TEXT NOT NULL,
+          target_id TEXT NOT NULL,
+          bucket TEXT,
+          keys_json TEXT NOT NULL DEFAULT '[]',
+          prefix TEXT,
+          result TEXT NOT NULL,
+          error TEXT
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp ON audit_log(timestamp);
+        CREATE INDEX IF NOT EXISTS idx_audit_log_target ON audit_log(target_id, bucket);
+        "#,
+    )?;
+
+    // v15: the multipart sweep is opt-in per target
+    add_column_if_missing(conn, "targets", "multipart_sweep", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // v16: download MD5 verification can be turned off per target
+    add_column_if_missing(conn, "targets", "skip_download_verification", "INTEGER NOT NULL DEFAULT 0")?;
+
+    // v17: jobs remember what started them, for the audit log
+    add_column_if_missing(conn, "batch_jobs", "initiator", "TEXT NOT NULL DEFAULT 'manual'")?;
+    add_column_if_missing(conn, "clone_jobs", "initiator", "TEXT NOT NULL DEFAULT 'clone'")?;
+
+    Ok(())
+}
+
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let exists: bool = conn
+        .prepare(&format!(
+            "SELECT COUNT(*) FROM pragma_table_info('{table}') WHERE name = ?1"
+        ))?
+        .query_row([column], |row| row.get::<_, i64>(0))
+        .map(|count| count > 0)?;
+
+    if !exists {
+        conn.execute_batch(&format!("ALTER TABLE {table} ADD COLUMN {column} {definition};"))?;
+    }
+
     Ok(())
 }
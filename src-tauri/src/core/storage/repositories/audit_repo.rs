@@ -0,0 +1,82 @@
+use anyhow::Result;
+use rusqlite::{params, Row};
+
+use crate::core::storage::sqlite::SqliteStorage;
+use crate::models::{AuditEntry, AuditLogFilter};
+
+const COLUMNS: &str = r#"
+  id, timestamp, initiator, operation, target_id, bucket, keys_json, prefix, result, error
+"#;
+
+fn from_row(row: &Row) -> rusqlite::Result<AuditEntry> {
+    let keys_json: String = row.get(6)?;
+    Ok(AuditEntry {
+        id: row.get(0)?,
+        timestamp: row.get(1)?,
+        initiator: row.get(2)?,
+        operation: row.get(3)?,
+        target_id: row.get(4)?,
+        bucket: row.get(5)?,
+        keys: serde_json::from_str(&keys_json).unwrap_or_default(),
+        prefix: row.get(7)?,
+        result: row.get(8)?,
+        error: row.get(9)?,
+    })
+}
+
+pub fn insert(storage: &SqliteStorage, entry: &AuditEntry) -> Result<()> {
+    let conn = storage.connection()?;
+    conn.execute(
+        &format!(
+            r#"INSERT INTO audit_log ({COLUMNS})
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)"#
+        ),
+        params![
+            entry.id,
+            entry.timestamp,
+            entry.initiator,
+            entry.operation,
+            entry.target_id,
+            entry.bucket,
+            serde_json::to_string(&entry.keys)?,
+            entry.prefix,
+            entry.result,
+            entry.error,
+        ],
+    )?;
+    Ok(())
+}
+
+/// Entries matching `filter`, newest first. Without a limit, all of them.
+pub fn list(storage: &SqliteStorage, filter: &AuditLogFilter) -> Result<Vec<AuditEntry>> {
+    let conn = storage.connection()?;
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {COLUMNS} FROM audit_log
+           WHERE (?1 IS NULL OR target_id = ?1)
+             AND (?2 IS NULL OR bucket = ?2)
+             AND (?3 IS NULL OR operation = ?3)
+             AND (?4 IS NULL OR initiator = ?4)
+             AND (?5 IS NULL OR result = ?5)
+             AND (?6 IS NULL OR instr(keys_json, ?6) > 0 OR instr(prefix, ?6) > 0)
+             AND (?7 IS NULL OR timestamp >= ?7)
+             AND (?8 IS NULL OR timestamp < ?8)
+           ORDER BY timestamp DESC, id DESC
+           LIMIT ?9 OFFSET ?10"#
+    ))?;
+    let rows = stmt.query_map(
+        params![
+            filter.target_id,
+            filter.bucket,
+            filter.operation,
+            filter.initiator,
+            filter.result,
+            filter.key.as_deref().filter(|k| !k.is_empty()),
+            filter.since,
+            filter.until,
+            filter.limit.unwrap_or(-1),
+            filter.offset.unwrap_or(0),
+        ],
+        from_row,
+    )?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
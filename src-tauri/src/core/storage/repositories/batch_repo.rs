@@ -0,0 +1,382 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use rusqlite::{params, Row, Transaction};
+
+use crate::core::storage::sqlite::SqliteStorage;
+use crate::models::{BatchJob, BatchJobItem};
+
+const JOB_COLUMNS: &str = r#"
+  id, kind, status, target_id, bucket, prefix, params_json,
+  enumeration_token, enumeration_complete,
+  total_items, completed_items, failed_items, skipped_items,
+  created_at, updated_at, completed_at, initiator
+"#;
+
+const ITEM_COLUMNS: &str = r#"
+  id, job_id, key, version_id, size, status, error_message, created_at, updated_at, upload_id
+"#;
+
+fn job_from_row(row: &Row) -> rusqlite::Result<BatchJob> {
+    let params_json: String = row.get(6)?;
+    Ok(BatchJob {
+        id: row.get(0)?,
+        kind: row.get(1)?,
+        status: row.get(2)?,
+        target_id: row.get(3)?,
+        bucket: row.get(4)?,
+        prefix: row.get(5)?,
+        params: serde_json::from_str(&params_json).unwrap_or(serde_json::Value::Null),
+        enumeration_token: row.get(7)?,
+        enumeration_complete: row.get::<_, i64>(8)? != 0,
+        total_items: row.get(9)?,
+        completed_items: row.get(10)?,
+        failed_items: row.get(11)?,
+        skipped_items: row.get(12)?,
+        created_at: row.get(13)?,
+        updated_at: row.get(14)?,
+        completed_at: row.get(15)?,
+        initiator: row.get(16)?,
+    })
+}
+
+fn item_from_row(row: &Row) -> rusqlite::Result<BatchJobItem> {
+    Ok(BatchJobItem {
+        id: row.get(0)?,
+        job_id: row.get(1)?,
+        key: row.get(2)?,
+        version_id: row.get(3)?,
+        size: row.get(4)?,
+        status: row.get(5)?,
+        error_message: row.get(6)?,
+        created_at: row.get(7)?,
+        updated_at: row.get(8)?,
+        upload_id: row.get(9)?,
+    })
+}
+
+pub fn list_jobs(storage: &SqliteStorage) -> Result<Vec<BatchJob>> {
+    let conn = storage.connection()?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {JOB_COLUMNS} FROM batch_jobs ORDER BY created_at DESC"
+    ))?;
+    let rows = stmt.query_map([], job_from_row)?;
+    Ok(rows.filter_map(|row| row.ok()).collect())
+}
+
+pub fn get_job(storage: &SqliteStorage, id: &str) -> Result<Option<BatchJob>> {
+    let conn = storage.connection()?;
+    let mut stmt = conn.prepare(&format!("SELECT {JOB_COLUMNS} FROM batch_jobs WHERE id = ?1"))?;
+    let mut rows = stmt.query_map(params![id], job_from_row)?;
+    Ok(rows.next().and_then(|r| r.ok()))
+}
+
+pub fn find_jobs_by_status(storage: &SqliteStorage, statuses: &[&str]) -> Result<Vec<BatchJob>> {
+    let conn = storage.connection()?;
+    let placeholders: Vec<String> = statuses.iter().enumerate().map(|(i, _)| format!("?{}", i + 1)).collect();
+    let sql = format!(
+        "SELECT {JOB_COLUMNS} FROM batch_jobs WHERE status IN ({})",
+        placeholders.join(", ")
+    );
+
+    let mut stmt = conn.prepare(&sql)?;
+    let params: Vec<&dyn rusqlite::types::ToSql> = statuses.iter().map(|s| s as &dyn rusqlite::types::ToSql).collect();
+    let rows = stmt.query_map(params.as_slice(), job_from_row)?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+pub fn insert_job(storage: &SqliteStorage, job: &BatchJob) -> Result<()> {
+    let conn = storage.connection()?;
+    conn.execute(
+        &format!(
+            "INSERT INTO batch_jobs ({JOB_COLUMNS}) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17)"
+        ),
+        params![
+            job.id,
+            job.kind,
+            job.status,
+            job.target_id,
+            job.bucket,
+            job.prefix,
+            job.params.to_string(),
+            job.enumeration_token,
+            job.enumeration_complete as i64,
+            job.total_items,
+            job.completed_items,
+            job.failed_items,
+            job.skipped_items,
+            job.created_at,
+            job.updated_at,
+            job.completed_at,
+            job.initiator,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn update_job_status(storage: &SqliteStorage, id: &str, status: &str) -> Result<()> {
+    let conn = storage.connection()?;
+    let now = now_epoch();
+    conn.execute(
+        "UPDATE batch_jobs SET status = ?1, updated_at = ?2 WHERE id = ?3",
+        params![status, now, id],
+    )?;
+    Ok(())
+}
+
+pub fn update_job_progress(
+    storage: &SqliteStorage,
+    id: &str,
+    completed_items: i64,
+    failed_items: i64,
+    skipped_items: i64,
+) -> Result<()> {
+    let conn = storage.connection()?;
+    let now = now_epoch();
+    conn.execute(
+        r#"UPDATE batch_jobs SET
+            completed_items = ?1, failed_items = ?2, skipped_items = ?3, updated_at = ?4
+           WHERE id = ?5"#,
+        params![completed_items, failed_items, skipped_items, now, id],
+    )?;
+    Ok(())
+}
+
+pub fn complete_job(storage: &SqliteStorage, id: &str, status: &str) -> Result<()> {
+    let conn = storage.connection()?;
+    let now = now_epoch();
+    conn.execute(
+        "UPDATE batch_jobs SET status = ?1, completed_at = ?2, updated_at = ?2 WHERE id = ?3",
+        params![status, now, id],
+    )?;
+    Ok(())
+}
+
+pub fn save_enumeration_state(
+    storage: &SqliteStorage,
+    id: &str,
+    token: Option<&str>,
+    total_items: i64,
+    complete: bool,
+) -> Result<()> {
+    let conn = storage.connection()?;
+    let now = now_epoch();
+    conn.execute(
+        r#"UPDATE batch_jobs SET
+            enumeration_token = ?1, total_items = ?2,
+            enumeration_complete = ?3, updated_at = ?4
+           WHERE id = ?5"#,
+        params![token, total_items, complete as i64, now, id],
+    )?;
+    Ok(())
+}
+
+pub fn delete_job(storage: &SqliteStorage, id: &str) -> Result<()> {
+    let conn = storage.connection()?;
+    conn.execute("DELETE FROM batch_jobs WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+// --- Batch Job Items ---
+
+/// Drop every item of a job, for an enumeration that has to start over.
+pub fn delete_items(storage: &SqliteStorage, job_id: &str) -> Result<()> {
+    let conn = storage.connection()?;
+    conn.execute("DELETE FROM batch_job_items WHERE job_id = ?1", params![job_id])?;
+    Ok(())
+}
+
+pub fn insert_items_batch(storage: &SqliteStorage, items: &[BatchJobItem]) -> Result<()> {
+    let mut conn = storage.connection()?;
+    let tx = conn.transaction()?;
+    insert_items(&tx, items)?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Add a page of enumerated items and checkpoint the enumeration in one
+/// transaction, so a job cut short never lists the same page twice.
+pub fn insert_enumerated_items(
+    storage: &SqliteStorage,
+    id: &str,
+    items: &[BatchJobItem],
+    token: Option<&str>,
+    total_items: i64,
+    complete: bool,
+) -> Result<()> {
+    let mut conn = storage.connection()?;
+    let tx = conn.transaction()?;
+    insert_items(&tx, items)?;
+    tx.execute(
+        r#"UPDATE batch_jobs SET
+            enumeration_token = ?1, total_items = ?2,
+            enumeration_complete = ?3, updated_at = ?4
+           WHERE id = ?5"#,
+        params![token, total_items, complete as i64, now_epoch(), id],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+fn insert_items(tx: &Transaction, items: &[BatchJobItem]) -> Result<()> {
+    let mut stmt = tx.prepare(&format!(
+        "INSERT OR IGNORE INTO batch_job_items ({ITEM_COLUMNS}) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10)"
+    ))?;
+    for item in items {
+        stmt.execute(params![
+            item.id,
+            item.job_id,
+            item.key,
+            item.version_id,
+            item.size,
+            item.status,
+            item.error_message,
+            item.created_at,
+            item.updated_at,
+            item.upload_id,
+        ])?;
+    }
+    Ok(())
+}
+
+pub fn list_pending_items(
+    storage: &SqliteStorage,
+    job_id: &str,
+    limit: i64,
+) -> Result<Vec<BatchJobItem>> {
+    let conn = storage.connection()?;
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT {ITEM_COLUMNS}
+        FROM batch_job_items
+        WHERE job_id = ?1 AND status = 'pending'
+        ORDER BY created_at ASC, id ASC
+        LIMIT ?2
+        "#
+    ))?;
+    let rows = stmt.query_map(params![job_id, limit], item_from_row)?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+pub fn list_items(
+    storage: &SqliteStorage,
+    job_id: &str,
+    status_filter: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<BatchJobItem>> {
+    let conn = storage.connection()?;
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT {ITEM_COLUMNS}
+        FROM batch_job_items
+        WHERE job_id = ?1 AND (?2 IS NULL OR status = ?2)
+        ORDER BY created_at ASC, id ASC
+        LIMIT ?3 OFFSET ?4
+        "#
+    ))?;
+    let rows = stmt.query_map(params![job_id, status_filter, limit, offset], item_from_row)?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+pub fn update_item_status(
+    storage: &SqliteStorage,
+    item_id: &str,
+    status: &str,
+    error_message: Option<&str>,
+) -> Result<()> {
+    let conn = storage.connection()?;
+    let now = now_epoch();
+    conn.execute(
+        r#"UPDATE batch_job_items
+           SET status = ?1, error_message = ?2, updated_at = ?3
+           WHERE id = ?4"#,
+        params![status, error_message, now, item_id],
+    )?;
+    Ok(())
+}
+
+pub fn reset_active_items(storage: &SqliteStorage, job_id: &str) -> Result<i64> {
+    let conn = storage.connection()?;
+    let now = now_epoch();
+    let count = conn.execute(
+        "UPDATE batch_job_items SET status = 'pending', updated_at = ?1 WHERE job_id = ?2 AND status = 'active'",
+        params![now, job_id],
+    )?;
+    Ok(count as i64)
+}
+
+pub fn reset_failed_items(storage: &SqliteStorage, job_id: &str) -> Result<i64> {
+    let conn = storage.connection()?;
+    let now = now_epoch();
+    let count = conn.execute(
+        "UPDATE batch_job_items SET status = 'pending', error_message = NULL, updated_at = ?1 WHERE job_id = ?2 AND status = 'failed'",
+        params![now, job_id],
+    )?;
+    Ok(count as i64)
+}
+
+/// Those of `keys` that still have pending or failed items in the job.
+pub fn keys_with_unfinished_items(
+    storage: &SqliteStorage,
+    job_id: &str,
+    keys: &[&str],
+) -> Result<HashSet<String>> {
+    if keys.is_empty() {
+        return Ok(HashSet::new());
+    }
+    let conn = storage.connection()?;
+    let placeholders = (2..keys.len() + 2).map(|i| format!("?{i}")).collect::<Vec<_>>().join(", ");
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT DISTINCT key
+        FROM batch_job_items
+        WHERE job_id = ?1 AND status IN ('pending', 'failed') AND key IN ({placeholders})
+        "#
+    ))?;
+    let mut params_vec: Vec<&dyn rusqlite::types::ToSql> = vec![&job_id];
+    params_vec.extend(keys.iter().map(|k| k as &dyn rusqlite::types::ToSql));
+    let rows = stmt.query_map(params_vec.as_slice(), |row| row.get::<_, String>(0))?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+pub struct ItemStatusCounts {
+    pub completed: i64,
+    pub failed: i64,
+    pub skipped: i64,
+    pub pending: i64,
+}
+
+pub fn count_items_by_status(storage: &SqliteStorage, job_id: &str) -> Result<ItemStatusCounts> {
+    let conn = storage.connection()?;
+    let mut stmt = conn.prepare(
+        r#"
+        SELECT
+          COALESCE(SUM(CASE WHEN status = 'completed' THEN 1 ELSE 0 END), 0),
+          COALESCE(SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END), 0),
+          COALESCE(SUM(CASE WHEN status = 'skipped' THEN 1 ELSE 0 END), 0),
+          COALESCE(SUM(CASE WHEN status IN ('pending', 'active') THEN 1 ELSE 0 END), 0)
+        FROM batch_job_items
+        WHERE job_id = ?1
+        "#,
+    )?;
+
+    let counts = stmt.query_row(params![job_id], |row| {
+        Ok(ItemStatusCounts {
+            completed: row.get(0)?,
+            failed: row.get(1)?,
+            skipped: row.get(2)?,
+            pending: row.get(3)?,
+        })
+    })?;
+
+    Ok(counts)
+}
+
+fn now_epoch() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
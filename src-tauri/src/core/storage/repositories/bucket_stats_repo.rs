@@ -52,6 +52,15 @@ pub fn upsert(
     Ok(())
 }
 
+pub fn delete(storage: &SqliteStorage, target_id: &str, bucket: &str) -> Result<()> {
+    let conn = storage.connection()?;
+    conn.execute(
+        "DELETE FROM bucket_stats_cache WHERE target_id = ?1 AND bucket = ?2",
+        params![target_id, bucket],
+    )?;
+    Ok(())
+}
+
 fn now_epoch() -> i64 {
     use std::time::{SystemTime, UNIX_EPOCH};
     SystemTime::now()
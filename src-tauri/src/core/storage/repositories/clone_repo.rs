@@ -13,7 +13,8 @@ pub fn list_jobs(storage: &SqliteStorage) -> Result<Vec<CloneJob>> {
           dest_target_id, dest_bucket, dest_prefix, conflict_policy,
           is_same_target, enumeration_token, enumeration_complete,
           total_items, completed_items, failed_items, skipped_items,
-          total_bytes, transferred_bytes, created_at, updated_at, completed_at
+          total_bytes, transferred_bytes, created_at, updated_at, completed_at,
+          delete_source, initiator
         FROM clone_jobs
         ORDER BY created_at DESC
         "#,
@@ -42,6 +43,8 @@ pub fn list_jobs(storage: &SqliteStorage) -> Result<Vec<CloneJob>> {
             created_at: row.get(18)?,
             updated_at: row.get(19)?,
             completed_at: row.get(20)?,
+            delete_source: row.get::<_, i64>(21)? != 0,
+            initiator: row.get(22)?,
         })
     })?;
 
@@ -57,7 +60,8 @@ pub fn get_job(storage: &SqliteStorage, id: &str) -> Result<Option<CloneJob>> {
           dest_target_id, dest_bucket, dest_prefix, conflict_policy,
           is_same_target, enumeration_token, enumeration_complete,
           total_items, completed_items, failed_items, skipped_items,
-          total_bytes, transferred_bytes, created_at, updated_at, completed_at
+          total_bytes, transferred_bytes, created_at, updated_at, completed_at,
+          delete_source, initiator
         FROM clone_jobs
         WHERE id = ?1
         "#,
@@ -86,6 +90,8 @@ pub fn get_job(storage: &SqliteStorage, id: &str) -> Result<Option<CloneJob>> {
             created_at: row.get(18)?,
             updated_at: row.get(19)?,
             completed_at: row.get(20)?,
+            delete_source: row.get::<_, i64>(21)? != 0,
+            initiator: row.get(22)?,
         })
     })?;
 
@@ -101,9 +107,10 @@ pub fn insert_job(storage: &SqliteStorage, job: &CloneJob) -> Result<()> {
           dest_target_id, dest_bucket, dest_prefix, conflict_policy,
           is_same_target, enumeration_token, enumeration_complete,
           total_items, completed_items, failed_items, skipped_items,
-          total_bytes, transferred_bytes, created_at, updated_at, completed_at
+          total_bytes, transferred_bytes, created_at, updated_at, completed_at,
+          delete_source, initiator
         )
-        VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19,?20,?21)
+        VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19,?20,?21,?22,?23)
         "#,
         params![
             job.id,
@@ -127,6 +134,8 @@ pub fn insert_job(storage: &SqliteStorage, job: &CloneJob) -> Result<()> {
             job.created_at,
             job.updated_at,
             job.completed_at,
+            job.delete_source as i64,
+            job.initiator,
         ],
     )?;
     Ok(())
@@ -232,6 +241,25 @@ pub fn list_pending_items(
     storage: &SqliteStorage,
     job_id: &str,
     limit: i64,
+) -> Result<Vec<CloneJobItem>> {
+    list_items_with_status(storage, job_id, "pending", limit)
+}
+
+/// Items of a move job that were copied and verified, but whose source
+/// object still has to be deleted.
+pub fn list_copied_items(
+    storage: &SqliteStorage,
+    job_id: &str,
+    limit: i64,
+) -> Result<Vec<CloneJobItem>> {
+    list_items_with_status(storage, job_id, "copied", limit)
+}
+
+fn list_items_with_status(
+    storage: &SqliteStorage,
+    job_id: &str,
+    status: &str,
+    limit: i64,
 ) -> Result<Vec<CloneJobItem>> {
     let conn = storage.connection()?;
     let mut stmt = conn.prepare(
@@ -240,13 +268,13 @@ pub fn list_pending_items(
                source_last_modified, status, error_message, retry_count,
                created_at, updated_at
         FROM clone_job_items
-        WHERE job_id = ?1 AND status = 'pending'
+        WHERE job_id = ?1 AND status = ?2
         ORDER BY created_at ASC
-        LIMIT ?2
+        LIMIT ?3
         "#,
     )?;
 
-    let rows = stmt.query_map(params![job_id, limit], |row| {
+    let rows = stmt.query_map(params![job_id, status, limit], |row| {
         Ok(CloneJobItem {
             id: row.get(0)?,
             job_id: row.get(1)?,
@@ -377,6 +405,7 @@ pub struct ItemStatusCounts {
     pub failed: i64,
     pub skipped: i64,
     pub pending: i64,
+    pub copied: i64,
     pub _active: i64,
     pub total_transferred_bytes: i64,
 }
@@ -390,8 +419,9 @@ pub fn count_items_by_status(storage: &SqliteStorage, job_id: &str) -> Result<It
           COALESCE(SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END), 0),
           COALESCE(SUM(CASE WHEN status = 'skipped' THEN 1 ELSE 0 END), 0),
           COALESCE(SUM(CASE WHEN status = 'pending' THEN 1 ELSE 0 END), 0),
+          COALESCE(SUM(CASE WHEN status = 'copied' THEN 1 ELSE 0 END), 0),
           COALESCE(SUM(CASE WHEN status = 'active' THEN 1 ELSE 0 END), 0),
-          COALESCE(SUM(CASE WHEN status = 'completed' THEN size ELSE 0 END), 0)
+          COALESCE(SUM(CASE WHEN status IN ('completed', 'copied') THEN size ELSE 0 END), 0)
         FROM clone_job_items
         WHERE job_id = ?1
         "#,
@@ -403,8 +433,9 @@ pub fn count_items_by_status(storage: &SqliteStorage, job_id: &str) -> Result<It
             failed: row.get(1)?,
             skipped: row.get(2)?,
             pending: row.get(3)?,
-            _active: row.get(4)?,
-            total_transferred_bytes: row.get(5)?,
+            copied: row.get(4)?,
+            _active: row.get(5)?,
+            total_transferred_bytes: row.get(6)?,
         })
     })?;
 
@@ -421,7 +452,8 @@ pub fn find_jobs_by_status(storage: &SqliteStorage, statuses: &[&str]) -> Result
           dest_target_id, dest_bucket, dest_prefix, conflict_policy,
           is_same_target, enumeration_token, enumeration_complete,
           total_items, completed_items, failed_items, skipped_items,
-          total_bytes, transferred_bytes, created_at, updated_at, completed_at
+          total_bytes, transferred_bytes, created_at, updated_at, completed_at,
+          delete_source, initiator
         FROM clone_jobs
         WHERE status IN ({})
         "#,
@@ -453,6 +485,8 @@ pub fn find_jobs_by_status(storage: &SqliteStorage, statuses: &[&str]) -> Result
             created_at: row.get(18)?,
             updated_at: row.get(19)?,
             completed_at: row.get(20)?,
+            delete_source: row.get::<_, i64>(21)? != 0,
+            initiator: row.get(22)?,
         })
     })?;
 
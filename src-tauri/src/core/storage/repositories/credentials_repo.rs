@@ -10,13 +10,16 @@ pub fn upsert(storage: &SqliteStorage, target_id: &str, credentials: &TargetCred
     conn.execute(
         r#"
         INSERT INTO target_credentials(
-          target_id, access_key_id, secret_access_key, session_token, created_at, updated_at
+          target_id, access_key_id, secret_access_key, session_token, sse_customer_key,
+          client_encryption_key, created_at, updated_at
         )
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
         ON CONFLICT(target_id) DO UPDATE SET
           access_key_id = excluded.access_key_id,
           secret_access_key = excluded.secret_access_key,
           session_token = excluded.session_token,
+          sse_customer_key = excluded.sse_customer_key,
+          client_encryption_key = excluded.client_encryption_key,
           updated_at = excluded.updated_at
         "#,
         params![
@@ -24,6 +27,8 @@ pub fn upsert(storage: &SqliteStorage, target_id: &str, credentials: &TargetCred
             credentials.access_key_id,
             credentials.secret_access_key,
             credentials.session_token,
+            credentials.sse_customer_key,
+            credentials.client_encryption_key,
             now,
             now
         ],
@@ -35,7 +40,7 @@ pub fn get(storage: &SqliteStorage, target_id: &str) -> Result<Option<TargetCred
     let conn = storage.connection()?;
     let mut stmt = conn.prepare(
         r#"
-        SELECT access_key_id, secret_access_key, session_token
+        SELECT access_key_id, secret_access_key, session_token, sse_customer_key, client_encryption_key
         FROM target_credentials
         WHERE target_id = ?1
         LIMIT 1
@@ -48,6 +53,8 @@ pub fn get(storage: &SqliteStorage, target_id: &str) -> Result<Option<TargetCred
             access_key_id: row.get(0)?,
             secret_access_key: row.get(1)?,
             session_token: row.get(2)?,
+            sse_customer_key: row.get(3)?,
+            client_encryption_key: row.get(4)?,
         }));
     }
 
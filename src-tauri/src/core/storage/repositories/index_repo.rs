@@ -1,8 +1,11 @@
 use anyhow::Result;
 use rusqlite::params;
 
+use crate::core::s3::{LifecycleDue, LifecycleSchedule};
 use crate::core::storage::sqlite::SqliteStorage;
-use crate::models::{BucketIndexObject, BucketIndexState, S3ObjectEntry, S3ObjectListPage};
+use crate::models::{
+    BucketIndexObject, BucketIndexState, ObjectTag, S3ObjectEntry, S3ObjectListPage, TagFilter,
+};
 
 fn now_epoch() -> i64 {
     use std::time::{SystemTime, UNIX_EPOCH};
@@ -23,7 +26,7 @@ pub fn get_index_state(
     let mut stmt = conn.prepare(
         r#"
         SELECT target_id, bucket, status, total_objects, indexed_objects, total_size,
-               continuation_token, last_indexed_at, created_at, updated_at
+               continuation_token, last_indexed_at, created_at, updated_at, include_tags
         FROM bucket_index_state
         WHERE target_id = ?1 AND bucket = ?2
         "#,
@@ -39,6 +42,7 @@ pub fn get_index_state(
             total_size: row.get(5)?,
             continuation_token: row.get(6)?,
             last_indexed_at: row.get(7)?,
+            include_tags: row.get::<_, i64>(10)? != 0,
             created_at: row.get(8)?,
             updated_at: row.get(9)?,
         })
@@ -56,7 +60,7 @@ pub fn list_index_states(storage: &SqliteStorage) -> Result<Vec<BucketIndexState
     let mut stmt = conn.prepare(
         r#"
         SELECT target_id, bucket, status, total_objects, indexed_objects, total_size,
-               continuation_token, last_indexed_at, created_at, updated_at
+               continuation_token, last_indexed_at, created_at, updated_at, include_tags
         FROM bucket_index_state
         ORDER BY updated_at DESC
         "#,
@@ -72,6 +76,7 @@ pub fn list_index_states(storage: &SqliteStorage) -> Result<Vec<BucketIndexState
             total_size: row.get(5)?,
             continuation_token: row.get(6)?,
             last_indexed_at: row.get(7)?,
+            include_tags: row.get::<_, i64>(10)? != 0,
             created_at: row.get(8)?,
             updated_at: row.get(9)?,
         })
@@ -105,6 +110,21 @@ pub fn upsert_index_state(
     Ok(())
 }
 
+/// Whether the index run for this bucket also fetches each object's tags.
+pub fn set_include_tags(
+    storage: &SqliteStorage,
+    target_id: &str,
+    bucket: &str,
+    include_tags: bool,
+) -> Result<()> {
+    let conn = storage.connection()?;
+    conn.execute(
+        "UPDATE bucket_index_state SET include_tags = ?3 WHERE target_id = ?1 AND bucket = ?2",
+        params![target_id, bucket, include_tags as i64],
+    )?;
+    Ok(())
+}
+
 pub fn update_index_progress(
     storage: &SqliteStorage,
     target_id: &str,
@@ -155,6 +175,10 @@ pub fn delete_index(storage: &SqliteStorage, target_id: &str, bucket: &str) -> R
         "DELETE FROM bucket_index_objects WHERE target_id = ?1 AND bucket = ?2",
         params![target_id, bucket],
     )?;
+    conn.execute(
+        "DELETE FROM bucket_index_tags WHERE target_id = ?1 AND bucket = ?2",
+        params![target_id, bucket],
+    )?;
     conn.execute(
         "DELETE FROM bucket_index_state WHERE target_id = ?1 AND bucket = ?2",
         params![target_id, bucket],
@@ -170,6 +194,10 @@ pub fn clear_objects(storage: &SqliteStorage, target_id: &str, bucket: &str) ->
         "DELETE FROM bucket_index_objects WHERE target_id = ?1 AND bucket = ?2",
         params![target_id, bucket],
     )?;
+    conn.execute(
+        "DELETE FROM bucket_index_tags WHERE target_id = ?1 AND bucket = ?2",
+        params![target_id, bucket],
+    )?;
     Ok(())
 }
 
@@ -279,29 +307,43 @@ pub fn browse(
 
 /// Search objects by name or key pattern within an indexed bucket.
 /// Matches against both the file name and the full key (path), so queries
-/// like "image.png" and "assets/images/image.png" both work.
+/// like "image.png" and "assets/images/image.png" both work. Every tag
+/// filter must match; an empty query with tag filters lists all tagged
+/// matches.
 pub fn search(
     storage: &SqliteStorage,
     target_id: &str,
     bucket: &str,
     query: &str,
+    tag_filters: &[TagFilter],
     limit: i64,
 ) -> Result<Vec<S3ObjectEntry>> {
     let conn = storage.connection()?;
     let pattern = format!("%{query}%");
-    let mut stmt = conn.prepare(
+
+    let mut params_vec: Vec<Box<dyn rusqlite::types::ToSql>> = vec![
+        Box::new(target_id.to_string()),
+        Box::new(bucket.to_string()),
+        Box::new(pattern),
+        Box::new(limit),
+    ];
+    let tag_clause = tag_filter_clause(tag_filters, &mut params_vec);
+
+    let sql = format!(
         r#"
-        SELECT key, name, is_folder, size, last_modified, etag, storage_class
-        FROM bucket_index_objects
-        WHERE target_id = ?1 AND bucket = ?2
-          AND (name LIKE ?3 OR key LIKE ?3)
-          AND is_folder = 0
-        ORDER BY name ASC
+        SELECT o.key, o.name, o.is_folder, o.size, o.last_modified, o.etag, o.storage_class
+        FROM bucket_index_objects o
+        WHERE o.target_id = ?1 AND o.bucket = ?2
+          AND (o.name LIKE ?3 OR o.key LIKE ?3)
+          AND o.is_folder = 0{tag_clause}
+        ORDER BY o.name ASC
         LIMIT ?4
-        "#,
-    )?;
+        "#
+    );
 
-    let rows = stmt.query_map(params![target_id, bucket, pattern, limit], |row| {
+    let mut stmt = conn.prepare(&sql)?;
+    let param_refs: Vec<&dyn rusqlite::types::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+    let rows = stmt.query_map(param_refs.as_slice(), |row| {
         let is_folder: i64 = row.get(2)?;
         Ok(S3ObjectEntry {
             key: row.get(0)?,
@@ -322,6 +364,156 @@ pub fn search(
     Ok(result)
 }
 
+/// Indexed objects under a prefix that a lifecycle rule was checked against.
+pub struct LifecycleMatches {
+    /// Objects (not folders) under the prefix that match all tag filters.
+    pub matched: i64,
+    /// Those the schedule currently acts on, and their total size.
+    pub affected: i64,
+    pub affected_size: i64,
+    /// The first `limit` affected objects, in key order.
+    pub sample: Vec<BucketIndexObject>,
+}
+
+/// Counts the indexed objects under `prefix` matching all tag filters and
+/// those `schedule` currently acts on, and returns up to `limit` of the
+/// latter.
+pub fn lifecycle_matches(
+    storage: &SqliteStorage,
+    target_id: &str,
+    bucket: &str,
+    prefix: &str,
+    tag_filters: &[TagFilter],
+    schedule: &LifecycleSchedule,
+    limit: usize,
+) -> Result<LifecycleMatches> {
+    let conn = storage.connection()?;
+    let mut params_vec: Vec<Box<dyn rusqlite::types::ToSql>> = vec![
+        Box::new(target_id.to_string()),
+        Box::new(bucket.to_string()),
+        Box::new(prefix.to_string()),
+    ];
+    let tag_clause = tag_filter_clause(tag_filters, &mut params_vec);
+    let affected = lifecycle_clause(schedule, &mut params_vec);
+    let from = format!(
+        r#"
+        FROM bucket_index_objects o
+        WHERE o.target_id = ?1 AND o.bucket = ?2
+          AND substr(o.key, 1, length(?3)) = ?3
+          AND o.is_folder = 0{tag_clause}
+        "#
+    );
+    let param_refs: Vec<&dyn rusqlite::types::ToSql> = params_vec.iter().map(|p| p.as_ref()).collect();
+
+    let (matched, affected_count, affected_size) = conn.query_row(
+        &format!(
+            "SELECT COUNT(*), COALESCE(SUM({affected}), 0), COALESCE(SUM(CASE WHEN {affected} THEN o.size ELSE 0 END), 0) {from}"
+        ),
+        param_refs.as_slice(),
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT o.key, o.parent_prefix, o.name, o.size, o.last_modified, o.etag, o.storage_class {from} AND {affected} ORDER BY o.key ASC LIMIT {limit}"
+    ))?;
+    let rows = stmt.query_map(param_refs.as_slice(), |row| {
+        Ok(BucketIndexObject {
+            target_id: target_id.to_string(),
+            bucket: bucket.to_string(),
+            key: row.get(0)?,
+            parent_prefix: row.get(1)?,
+            name: row.get(2)?,
+            is_folder: false,
+            size: row.get(3)?,
+            last_modified: row.get(4)?,
+            etag: row.get(5)?,
+            storage_class: row.get(6)?,
+        })
+    })?;
+
+    let mut sample = Vec::new();
+    for row in rows {
+        sample.push(row?);
+    }
+    Ok(LifecycleMatches {
+        matched,
+        affected: affected_count,
+        affected_size,
+        sample,
+    })
+}
+
+/// A condition on an outer query aliased `o` that holds for the objects
+/// `schedule` acts on. Binds its values after `params_vec`.
+fn lifecycle_clause(
+    schedule: &LifecycleSchedule,
+    params_vec: &mut Vec<Box<dyn rusqlite::types::ToSql>>,
+) -> String {
+    let mut actions = vec![due_clause(schedule.expiration, params_vec)];
+    for transition in &schedule.transitions {
+        let due = due_clause(transition.due, params_vec);
+        let mut settled = Vec::new();
+        for class in &transition.settled_classes {
+            params_vec.push(Box::new(class.to_string()));
+            settled.push(format!("?{}", params_vec.len()));
+        }
+        actions.push(format!(
+            "({due} AND COALESCE(o.storage_class, 'STANDARD') NOT IN ({}))",
+            settled.join(", ")
+        ));
+    }
+
+    let mut clause = format!("({})", actions.join(" OR "));
+    if let Some(min) = schedule.object_size_greater_than {
+        params_vec.push(Box::new(min));
+        clause.push_str(&format!(" AND o.size > ?{}", params_vec.len()));
+    }
+    if let Some(max) = schedule.object_size_less_than {
+        params_vec.push(Box::new(max));
+        clause.push_str(&format!(" AND o.size < ?{}", params_vec.len()));
+    }
+    format!("({clause})")
+}
+
+fn due_clause(due: LifecycleDue, params_vec: &mut Vec<Box<dyn rusqlite::types::ToSql>>) -> String {
+    match due {
+        LifecycleDue::Never => "0".to_string(),
+        LifecycleDue::Always => "1".to_string(),
+        LifecycleDue::ModifiedBy(cutoff) => {
+            params_vec.push(Box::new(cutoff));
+            format!("CAST(strftime('%s', o.last_modified) AS INTEGER) <= ?{}", params_vec.len())
+        }
+    }
+}
+
+/// `AND EXISTS (...)` conditions on `bucket_index_tags` for an outer query
+/// aliased `o`, one per filter. Binds its values after `params_vec`.
+fn tag_filter_clause(
+    tag_filters: &[TagFilter],
+    params_vec: &mut Vec<Box<dyn rusqlite::types::ToSql>>,
+) -> String {
+    let mut tag_clause = String::new();
+    for filter in tag_filters {
+        params_vec.push(Box::new(filter.key.clone()));
+        let key_param = params_vec.len();
+        match &filter.value {
+            Some(value) => {
+                params_vec.push(Box::new(value.clone()));
+                tag_clause.push_str(&format!(
+                    " AND EXISTS (SELECT 1 FROM bucket_index_tags t WHERE t.target_id = o.target_id AND t.bucket = o.bucket AND t.key = o.key AND t.tag_key = ?{key_param} AND t.tag_value = ?{})",
+                    params_vec.len()
+                ));
+            }
+            None => {
+                tag_clause.push_str(&format!(
+                    " AND EXISTS (SELECT 1 FROM bucket_index_tags t WHERE t.target_id = o.target_id AND t.bucket = o.bucket AND t.key = o.key AND t.tag_key = ?{key_param})"
+                ));
+            }
+        }
+    }
+    tag_clause
+}
+
 /// Add a single object to the index (for live updates after upload/delete).
 pub fn upsert_object(storage: &SqliteStorage, obj: &BucketIndexObject) -> Result<()> {
     let conn = storage.connection()?;
@@ -360,6 +552,63 @@ pub fn remove_objects(
             "DELETE FROM bucket_index_objects WHERE target_id = ?1 AND bucket = ?2 AND key = ?3",
             params![target_id, bucket, key],
         )?;
+        conn.execute(
+            "DELETE FROM bucket_index_tags WHERE target_id = ?1 AND bucket = ?2 AND key = ?3",
+            params![target_id, bucket, key],
+        )?;
+    }
+    Ok(())
+}
+
+/// Update the indexed storage class of one object after it was changed in place.
+pub fn set_storage_class(
+    storage: &SqliteStorage,
+    target_id: &str,
+    bucket: &str,
+    key: &str,
+    storage_class: &str,
+) -> Result<()> {
+    let conn = storage.connection()?;
+    conn.execute(
+        "UPDATE bucket_index_objects SET storage_class = ?1 WHERE target_id = ?2 AND bucket = ?3 AND key = ?4",
+        params![storage_class, target_id, bucket, key],
+    )?;
+    Ok(())
+}
+
+// --- Index Tags ---
+
+/// Replace the indexed tags of each `(key, tags)` pair. Keys that are not in
+/// the index are ignored, so tag edits on unindexed buckets leave no rows.
+pub fn replace_object_tags(
+    storage: &SqliteStorage,
+    target_id: &str,
+    bucket: &str,
+    objects: &[(String, Vec<ObjectTag>)],
+) -> Result<()> {
+    let conn = storage.connection()?;
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut delete = tx.prepare(
+            "DELETE FROM bucket_index_tags WHERE target_id = ?1 AND bucket = ?2 AND key = ?3",
+        )?;
+        let mut insert = tx.prepare(
+            r#"
+            INSERT OR REPLACE INTO bucket_index_tags (target_id, bucket, key, tag_key, tag_value)
+            SELECT ?1, ?2, ?3, ?4, ?5
+            WHERE EXISTS (
+              SELECT 1 FROM bucket_index_objects
+              WHERE target_id = ?1 AND bucket = ?2 AND key = ?3
+            )
+            "#,
+        )?;
+        for (key, tags) in objects {
+            delete.execute(params![target_id, bucket, key])?;
+            for tag in tags {
+                insert.execute(params![target_id, bucket, key, tag.key, tag.value])?;
+            }
+        }
     }
+    tx.commit()?;
     Ok(())
 }
@@ -1,8 +1,12 @@
+pub mod audit_repo;
+pub mod batch_repo;
 pub mod bucket_stats_repo;
 pub mod clone_repo;
 pub mod credentials_repo;
 pub mod index_repo;
+pub mod restore_repo;
 pub mod settings_repo;
+pub mod share_links_repo;
 pub mod sync_profiles_repo;
 pub mod targets_repo;
 pub mod transfer_repo;
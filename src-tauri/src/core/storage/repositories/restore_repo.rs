@@ -0,0 +1,115 @@
+use anyhow::Result;
+use rusqlite::{params, Row};
+
+use crate::core::storage::sqlite::SqliteStorage;
+use crate::models::ObjectRestore;
+
+const COLUMNS: &str = r#"
+  id, target_id, bucket, key, version_id, tier, days, status, expiry_date,
+  error_message, requested_at, last_checked_at, completed_at
+"#;
+
+fn from_row(row: &Row) -> rusqlite::Result<ObjectRestore> {
+    Ok(ObjectRestore {
+        id: row.get(0)?,
+        target_id: row.get(1)?,
+        bucket: row.get(2)?,
+        key: row.get(3)?,
+        version_id: row.get(4)?,
+        tier: row.get(5)?,
+        days: row.get(6)?,
+        status: row.get(7)?,
+        expiry_date: row.get(8)?,
+        error_message: row.get(9)?,
+        requested_at: row.get(10)?,
+        last_checked_at: row.get(11)?,
+        completed_at: row.get(12)?,
+    })
+}
+
+pub fn list(storage: &SqliteStorage) -> Result<Vec<ObjectRestore>> {
+    let conn = storage.connection()?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS} FROM object_restores ORDER BY requested_at DESC"
+    ))?;
+    let rows = stmt.query_map([], from_row)?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+pub fn list_pending(storage: &SqliteStorage) -> Result<Vec<ObjectRestore>> {
+    let conn = storage.connection()?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {COLUMNS} FROM object_restores WHERE status = 'pending' ORDER BY requested_at ASC"
+    ))?;
+    let rows = stmt.query_map([], from_row)?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+/// Track a new restore request, replacing any earlier one for the same
+/// object version.
+pub fn record_request(storage: &SqliteStorage, restore: &ObjectRestore) -> Result<()> {
+    let mut conn = storage.connection()?;
+    let tx = conn.transaction()?;
+    tx.execute(
+        r#"DELETE FROM object_restores
+           WHERE target_id = ?1 AND bucket = ?2 AND key = ?3 AND version_id IS ?4"#,
+        params![restore.target_id, restore.bucket, restore.key, restore.version_id],
+    )?;
+    tx.execute(
+        &format!(
+            "INSERT INTO object_restores ({COLUMNS}) VALUES (?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13)"
+        ),
+        params![
+            restore.id,
+            restore.target_id,
+            restore.bucket,
+            restore.key,
+            restore.version_id,
+            restore.tier,
+            restore.days,
+            restore.status,
+            restore.expiry_date,
+            restore.error_message,
+            restore.requested_at,
+            restore.last_checked_at,
+            restore.completed_at,
+        ],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Record the outcome of a status check. A status other than "pending"
+/// also sets `completed_at`.
+pub fn update_status(
+    storage: &SqliteStorage,
+    id: &str,
+    status: &str,
+    expiry_date: Option<&str>,
+    error_message: Option<&str>,
+) -> Result<()> {
+    let conn = storage.connection()?;
+    let now = now_epoch();
+    conn.execute(
+        r#"UPDATE object_restores SET
+             status = ?1, expiry_date = ?2, error_message = ?3, last_checked_at = ?4,
+             completed_at = CASE WHEN ?1 = 'pending' THEN NULL ELSE ?4 END
+           WHERE id = ?5"#,
+        params![status, expiry_date, error_message, now, id],
+    )?;
+    Ok(())
+}
+
+pub fn delete(storage: &SqliteStorage, id: &str) -> Result<()> {
+    let conn = storage.connection()?;
+    conn.execute("DELETE FROM object_restores WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+fn now_epoch() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
@@ -14,7 +14,10 @@ pub fn get(storage: &SqliteStorage) -> Result<AppSettings> {
           double_click_nav, show_hidden, remember_path, auto_refresh,
           confirm_delete, concurrent_uploads, concurrent_downloads,
           multipart_threshold_mb, part_size_mb, auto_retry, retry_count,
-          preserve_timestamps, verify_checksum
+          preserve_timestamps, verify_checksum,
+          bandwidth_limit_bytes_per_sec, bandwidth_schedule_enabled,
+          bandwidth_schedule_start_minute, bandwidth_schedule_end_minute,
+          multipart_sweep_enabled, multipart_sweep_days
         FROM app_settings
         WHERE id = 'default'
         LIMIT 1
@@ -44,6 +47,12 @@ pub fn get(storage: &SqliteStorage) -> Result<AppSettings> {
             retry_count: row.get(17)?,
             preserve_timestamps: row.get::<_, i64>(18)? == 1,
             verify_checksum: row.get::<_, i64>(19)? == 1,
+            bandwidth_limit_bytes_per_sec: row.get(20)?,
+            bandwidth_schedule_enabled: row.get::<_, i64>(21)? == 1,
+            bandwidth_schedule_start_minute: row.get(22)?,
+            bandwidth_schedule_end_minute: row.get(23)?,
+            multipart_sweep_enabled: row.get::<_, i64>(24)? == 1,
+            multipart_sweep_days: row.get(25)?,
         })
     } else {
         Ok(AppSettings::default())
@@ -62,7 +71,11 @@ pub fn upsert(storage: &SqliteStorage, settings: &AppSettings) -> Result<AppSett
           double_click_nav, show_hidden, remember_path, auto_refresh,
           confirm_delete, concurrent_uploads, concurrent_downloads,
           multipart_threshold_mb, part_size_mb, auto_retry, retry_count,
-          preserve_timestamps, verify_checksum, created_at, updated_at
+          preserve_timestamps, verify_checksum,
+          bandwidth_limit_bytes_per_sec, bandwidth_schedule_enabled,
+          bandwidth_schedule_start_minute, bandwidth_schedule_end_minute,
+          multipart_sweep_enabled, multipart_sweep_days,
+          created_at, updated_at
         )
         VALUES (
           'default', ?1, ?2, ?3, ?4,
@@ -70,7 +83,11 @@ pub fn upsert(storage: &SqliteStorage, settings: &AppSettings) -> Result<AppSett
           ?8, ?9, ?10, ?11,
           ?12, ?13, ?14,
           ?15, ?16, ?17, ?18,
-          ?19, ?20, ?21, ?22
+          ?19, ?20,
+          ?21, ?22,
+          ?23, ?24,
+          ?25, ?26,
+          ?27, ?28
         )
         ON CONFLICT(id) DO UPDATE SET
           theme = excluded.theme,
@@ -93,6 +110,12 @@ pub fn upsert(storage: &SqliteStorage, settings: &AppSettings) -> Result<AppSett
           retry_count = excluded.retry_count,
           preserve_timestamps = excluded.preserve_timestamps,
           verify_checksum = excluded.verify_checksum,
+          bandwidth_limit_bytes_per_sec = excluded.bandwidth_limit_bytes_per_sec,
+          bandwidth_schedule_enabled = excluded.bandwidth_schedule_enabled,
+          bandwidth_schedule_start_minute = excluded.bandwidth_schedule_start_minute,
+          bandwidth_schedule_end_minute = excluded.bandwidth_schedule_end_minute,
+          multipart_sweep_enabled = excluded.multipart_sweep_enabled,
+          multipart_sweep_days = excluded.multipart_sweep_days,
           updated_at = excluded.updated_at
         "#,
         params![
@@ -116,6 +139,12 @@ pub fn upsert(storage: &SqliteStorage, settings: &AppSettings) -> Result<AppSett
             settings.retry_count,
             if settings.preserve_timestamps { 1 } else { 0 },
             if settings.verify_checksum { 1 } else { 0 },
+            settings.bandwidth_limit_bytes_per_sec,
+            if settings.bandwidth_schedule_enabled { 1 } else { 0 },
+            settings.bandwidth_schedule_start_minute,
+            settings.bandwidth_schedule_end_minute,
+            if settings.multipart_sweep_enabled { 1 } else { 0 },
+            settings.multipart_sweep_days,
             now,
             now
         ],
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use rusqlite::{params, Connection, Row};
+
+use crate::core::storage::sqlite::SqliteStorage;
+use crate::models::ShareLink;
+
+const COLUMNS: &str = r#"
+  id, target_id, bucket, key, version_id, url, expires_in_secs, response_json,
+  signing_key_id, note, created_at, expires_at
+"#;
+
+fn from_row(row: &Row) -> rusqlite::Result<ShareLink> {
+    let response_json: Option<String> = row.get(7)?;
+    Ok(ShareLink {
+        id: row.get(0)?,
+        target_id: row.get(1)?,
+        bucket: row.get(2)?,
+        key: row.get(3)?,
+        version_id: row.get(4)?,
+        url: row.get(5)?,
+        expires_in_secs: row.get(6)?,
+        response: response_json.and_then(|json| serde_json::from_str(&json).ok()),
+        signing_key_id: row.get(8)?,
+        note: row.get(9)?,
+        created_at: row.get(10)?,
+        expires_at: row.get(11)?,
+    })
+}
+
+/// Share links, newest first. `expired` picks links past (`true`) or before
+/// (`false`) their expiry; `None` returns both.
+pub fn list(storage: &SqliteStorage, target_id: Option<&str>, expired: Option<bool>) -> Result<Vec<ShareLink>> {
+    let conn = storage.connection()?;
+    let mut stmt = conn.prepare(&format!(
+        r#"SELECT {COLUMNS} FROM share_links
+           WHERE (?1 IS NULL OR target_id = ?1)
+             AND (?2 IS NULL OR (expires_at <= ?3) = ?2)
+           ORDER BY created_at DESC"#
+    ))?;
+    let rows = stmt.query_map(params![target_id, expired, now_epoch()], from_row)?;
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
+
+pub fn get(storage: &SqliteStorage, id: &str) -> Result<Option<ShareLink>> {
+    let conn = storage.connection()?;
+    let mut stmt = conn.prepare(&format!("SELECT {COLUMNS} FROM share_links WHERE id = ?1"))?;
+    let mut rows = stmt.query(params![id])?;
+    match rows.next()? {
+        Some(row) => Ok(Some(from_row(row)?)),
+        None => Ok(None),
+    }
+}
+
+/// Insert a link, or replace it when regenerated under the same id.
+pub fn upsert(storage: &SqliteStorage, link: &ShareLink) -> Result<()> {
+    let conn = storage.connection()?;
+    upsert_with(&conn, link)
+}
+
+/// `upsert` for many links in one transaction.
+pub fn upsert_many(storage: &SqliteStorage, links: &[ShareLink]) -> Result<()> {
+    let mut conn = storage.connection()?;
+    let tx = conn.transaction()?;
+    for link in links {
+        upsert_with(&tx, link)?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+fn upsert_with(conn: &Connection, link: &ShareLink) -> Result<()> {
+    let response_json = link.response.as_ref().map(serde_json::to_string).transpose()?;
+    conn.execute(
+        &format!(
+            r#"INSERT OR REPLACE INTO share_links ({COLUMNS})
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)"#
+        ),
+        params![
+            link.id,
+            link.target_id,
+            link.bucket,
+            link.key,
+            link.version_id,
+            link.url,
+            link.expires_in_secs,
+            response_json,
+            link.signing_key_id,
+            link.note,
+            link.created_at,
+            link.expires_at,
+        ],
+    )?;
+    Ok(())
+}
+
+pub fn delete_many(storage: &SqliteStorage, ids: &[String]) -> Result<()> {
+    let mut conn = storage.connection()?;
+    let tx = conn.transaction()?;
+    for id in ids {
+        tx.execute("DELETE FROM share_links WHERE id = ?1", params![id])?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Forget links that expired before `before`.
+pub fn delete_expired(storage: &SqliteStorage, before: i64) -> Result<usize> {
+    let conn = storage.connection()?;
+    Ok(conn.execute("DELETE FROM share_links WHERE expires_at <= ?1", params![before])?)
+}
+
+fn now_epoch() -> i64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
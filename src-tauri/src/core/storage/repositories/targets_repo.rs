@@ -14,7 +14,9 @@ pub fn list(storage: &SqliteStorage) -> Result<Vec<StorageTarget>> {
           force_path_style, default_bucket, pinned_buckets_json,
           skip_destructive_confirmations, updated_at,
           EXISTS(SELECT 1 FROM target_credentials c WHERE c.target_id = targets.id) AS has_credentials,
-          scoped_bucket
+          scoped_bucket, bandwidth_limit_bytes_per_sec, encryption_mode, encryption_kms_key_id,
+          client_encryption, encrypt_key_names, trash_enabled, trash_bucket, trash_retention_days,
+          read_only, protected_prefixes_json, multipart_sweep, skip_download_verification
         FROM targets
         ORDER BY name COLLATE NOCASE ASC
         "#,
@@ -23,6 +25,8 @@ pub fn list(storage: &SqliteStorage) -> Result<Vec<StorageTarget>> {
     let rows = stmt.query_map([], |row| {
         let pinned_buckets_json: String = row.get(7)?;
         let pinned_buckets = serde_json::from_str::<Vec<String>>(&pinned_buckets_json).unwrap_or_default();
+        let protected_prefixes_json: String = row.get(21)?;
+        let protected_prefixes = serde_json::from_str::<Vec<String>>(&protected_prefixes_json).unwrap_or_default();
 
         Ok(StorageTarget {
             id: row.get(0)?,
@@ -35,6 +39,18 @@ pub fn list(storage: &SqliteStorage) -> Result<Vec<StorageTarget>> {
             scoped_bucket: row.get(11)?,
             pinned_buckets,
             skip_destructive_confirmations: row.get::<_, i64>(8)? == 1,
+            bandwidth_limit_bytes_per_sec: row.get(12)?,
+            encryption_mode: row.get(13)?,
+            encryption_kms_key_id: row.get(14)?,
+            client_encryption: row.get::<_, i64>(15)? == 1,
+            encrypt_key_names: row.get::<_, i64>(16)? == 1,
+            trash_enabled: row.get::<_, i64>(17)? == 1,
+            trash_bucket: row.get(18)?,
+            trash_retention_days: row.get(19)?,
+            read_only: row.get::<_, i64>(20)? == 1,
+            protected_prefixes,
+            multipart_sweep: row.get::<_, i64>(22)? == 1,
+            skip_download_verification: row.get::<_, i64>(23)? == 1,
             has_credentials: row.get::<_, i64>(10)? == 1,
             updated_at: row.get(9)?,
         })
@@ -47,14 +63,18 @@ pub fn upsert(storage: &SqliteStorage, target: StorageTarget) -> Result<StorageT
     let conn = storage.connection()?;
     let now = now_epoch();
     let pinned_buckets_json: Value = serde_json::to_value(&target.pinned_buckets)?;
+    let protected_prefixes_json: Value = serde_json::to_value(&target.protected_prefixes)?;
 
     conn.execute(
         r#"
         INSERT INTO targets (
           id, name, provider, endpoint, region, force_path_style, default_bucket,
-          scoped_bucket, pinned_buckets_json, skip_destructive_confirmations, created_at, updated_at
+          scoped_bucket, pinned_buckets_json, skip_destructive_confirmations,
+          bandwidth_limit_bytes_per_sec, encryption_mode, encryption_kms_key_id,
+          client_encryption, encrypt_key_names, trash_enabled, trash_bucket, trash_retention_days,
+          read_only, protected_prefixes_json, multipart_sweep, skip_download_verification, created_at, updated_at
         )
-        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21, ?22, ?23, ?24)
         ON CONFLICT(id) DO UPDATE SET
           name = excluded.name,
           provider = excluded.provider,
@@ -65,6 +85,18 @@ pub fn upsert(storage: &SqliteStorage, target: StorageTarget) -> Result<StorageT
           scoped_bucket = excluded.scoped_bucket,
           pinned_buckets_json = excluded.pinned_buckets_json,
           skip_destructive_confirmations = excluded.skip_destructive_confirmations,
+          bandwidth_limit_bytes_per_sec = excluded.bandwidth_limit_bytes_per_sec,
+          encryption_mode = excluded.encryption_mode,
+          encryption_kms_key_id = excluded.encryption_kms_key_id,
+          client_encryption = excluded.client_encryption,
+          encrypt_key_names = excluded.encrypt_key_names,
+          trash_enabled = excluded.trash_enabled,
+          trash_bucket = excluded.trash_bucket,
+          trash_retention_days = excluded.trash_retention_days,
+          read_only = excluded.read_only,
+          protected_prefixes_json = excluded.protected_prefixes_json,
+          multipart_sweep = excluded.multipart_sweep,
+          skip_download_verification = excluded.skip_download_verification,
           updated_at = excluded.updated_at
         "#,
         params![
@@ -78,6 +110,18 @@ pub fn upsert(storage: &SqliteStorage, target: StorageTarget) -> Result<StorageT
             target.scoped_bucket,
             pinned_buckets_json.to_string(),
             if target.skip_destructive_confirmations { 1 } else { 0 },
+            target.bandwidth_limit_bytes_per_sec,
+            target.encryption_mode,
+            target.encryption_kms_key_id,
+            if target.client_encryption { 1 } else { 0 },
+            if target.encrypt_key_names { 1 } else { 0 },
+            if target.trash_enabled { 1 } else { 0 },
+            target.trash_bucket,
+            target.trash_retention_days,
+            if target.read_only { 1 } else { 0 },
+            protected_prefixes_json.to_string(),
+            if target.multipart_sweep { 1 } else { 0 },
+            if target.skip_download_verification { 1 } else { 0 },
             now,
             now
         ],
@@ -99,7 +143,9 @@ pub fn find_by_id(storage: &SqliteStorage, id: &str) -> Result<Option<StorageTar
           force_path_style, default_bucket, pinned_buckets_json,
           skip_destructive_confirmations, updated_at,
           EXISTS(SELECT 1 FROM target_credentials c WHERE c.target_id = targets.id) AS has_credentials,
-          scoped_bucket
+          scoped_bucket, bandwidth_limit_bytes_per_sec, encryption_mode, encryption_kms_key_id,
+          client_encryption, encrypt_key_names, trash_enabled, trash_bucket, trash_retention_days,
+          read_only, protected_prefixes_json, multipart_sweep, skip_download_verification
         FROM targets
         WHERE id = ?1
         LIMIT 1
@@ -110,6 +156,8 @@ pub fn find_by_id(storage: &SqliteStorage, id: &str) -> Result<Option<StorageTar
     if let Some(row) = rows.next()? {
         let pinned_buckets_json: String = row.get(7)?;
         let pinned_buckets = serde_json::from_str::<Vec<String>>(&pinned_buckets_json).unwrap_or_default();
+        let protected_prefixes_json: String = row.get(21)?;
+        let protected_prefixes = serde_json::from_str::<Vec<String>>(&protected_prefixes_json).unwrap_or_default();
 
         return Ok(Some(StorageTarget {
             id: row.get(0)?,
@@ -122,6 +170,18 @@ pub fn find_by_id(storage: &SqliteStorage, id: &str) -> Result<Option<StorageTar
             scoped_bucket: row.get(11)?,
             pinned_buckets,
             skip_destructive_confirmations: row.get::<_, i64>(8)? == 1,
+            bandwidth_limit_bytes_per_sec: row.get(12)?,
+            encryption_mode: row.get(13)?,
+            encryption_kms_key_id: row.get(14)?,
+            client_encryption: row.get::<_, i64>(15)? == 1,
+            encrypt_key_names: row.get::<_, i64>(16)? == 1,
+            trash_enabled: row.get::<_, i64>(17)? == 1,
+            trash_bucket: row.get(18)?,
+            trash_retention_days: row.get(19)?,
+            read_only: row.get::<_, i64>(20)? == 1,
+            protected_prefixes,
+            multipart_sweep: row.get::<_, i64>(22)? == 1,
+            skip_download_verification: row.get::<_, i64>(23)? == 1,
             updated_at: row.get(9)?,
             has_credentials: row.get::<_, i64>(10)? == 1,
         }));
@@ -0,0 +1,349 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use chrono::{NaiveDateTime, Utc};
+use tokio::sync::Semaphore;
+
+use crate::core::audit;
+use crate::core::s3;
+use crate::core::storage::repositories::{credentials_repo, targets_repo};
+use crate::core::storage::sqlite::SqliteStorage;
+use crate::models::{
+    DeleteObjectsResult, ObjectDeleteError, StorageTarget, TargetCredentials, TrashEntry,
+    TrashRestoreResult,
+};
+
+pub const TRASH_ROOT: &str = ".mahzen-trash/";
+
+// Millisecond folders keep two deletes in a row from landing in one folder
+const FOLDER_FORMAT: &str = "%Y%m%dT%H%M%S%.3fZ";
+const CONCURRENCY: usize = 16;
+const PURGE_INTERVAL: Duration = Duration::from_secs(6 * 3600);
+
+/// Where the trash of one bucket lives. Each delete gets its own
+/// timestamped folder under `prefix`, holding the objects at their
+/// original keys.
+pub struct TrashLocation {
+    pub bucket: String,
+    pub prefix: String,
+}
+
+impl TrashLocation {
+    /// A new folder for a delete happening now.
+    pub fn new_folder(&self) -> String {
+        format!("{}{}/", self.prefix, Utc::now().format(FOLDER_FORMAT))
+    }
+
+    /// When `trash_key` was deleted and the key it was deleted from.
+    pub fn parse(&self, trash_key: &str) -> Option<(i64, String)> {
+        let (folder, original_key) = trash_key.strip_prefix(&self.prefix)?.split_once('/')?;
+        let deleted_at = NaiveDateTime::parse_from_str(folder, FOLDER_FORMAT).ok()?.and_utc().timestamp();
+        (!original_key.is_empty()).then(|| (deleted_at, original_key.to_string()))
+    }
+}
+
+/// The trash for `bucket`: the target's trash bucket, with a folder per
+/// source bucket, or `.mahzen-trash/` in the bucket itself.
+pub fn location(target: &StorageTarget, bucket: &str) -> TrashLocation {
+    match target.trash_bucket.as_deref().filter(|b| !b.is_empty() && *b != bucket) {
+        Some(trash_bucket) => TrashLocation {
+            bucket: trash_bucket.to_string(),
+            prefix: format!("{TRASH_ROOT}{bucket}/"),
+        },
+        None => TrashLocation {
+            bucket: bucket.to_string(),
+            prefix: TRASH_ROOT.to_string(),
+        },
+    }
+}
+
+/// Whether `key` is already in a trash, so deleting it is for good.
+pub fn is_trashed(key: &str) -> bool {
+    key.starts_with(TRASH_ROOT)
+}
+
+/// Move `keys` into the trash: each object is copied into a new trash
+/// folder and deleted once its copy exists. Keys that are already in the
+/// trash are deleted outright.
+pub async fn move_to_trash(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    keys: Vec<String>,
+) -> Result<DeleteObjectsResult> {
+    let (trashed, keys): (Vec<_>, Vec<_>) = keys.into_iter().partition(|k| is_trashed(k));
+    let mut result = s3::delete_objects(target, credentials, bucket, trashed).await?;
+    if keys.is_empty() {
+        return Ok(result);
+    }
+
+    let location = location(target, bucket);
+    let folder = location.new_folder();
+    let semaphore = Arc::new(Semaphore::new(CONCURRENCY));
+    let mut handles = Vec::new();
+
+    for key in keys {
+        let permit = semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| anyhow!("Semaphore error: {e}"))?;
+
+        let target = target.clone();
+        let credentials = credentials.clone();
+        let bucket = bucket.to_string();
+        let trash_bucket = location.bucket.clone();
+        let trash_key = format!("{folder}{key}");
+
+        handles.push(tokio::spawn(async move {
+            let result = copy_existing(&target, &credentials, &bucket, &key, &trash_bucket, &trash_key).await;
+            drop(permit);
+            (key, result)
+        }));
+    }
+
+    let mut copied = Vec::new();
+    for handle in handles {
+        match handle.await {
+            Ok((key, Ok(()))) => copied.push(key),
+            Ok((key, Err(e))) => result.failed.push(ObjectDeleteError {
+                key,
+                version_id: None,
+                code: None,
+                message: Some(format!("Could not move to the trash: {e}")),
+            }),
+            Err(e) => log::error!("Trash task join error: {e}"),
+        }
+    }
+
+    // Only objects whose copy exists are deleted
+    let deleted = s3::delete_objects(target, credentials, bucket, copied).await?;
+    result.deleted.extend(deleted.deleted);
+    result.failed.extend(deleted.failed);
+    Ok(result)
+}
+
+/// Everything in the trash of `bucket`, most recently deleted first.
+pub async fn list(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+) -> Result<Vec<TrashEntry>> {
+    let location = location(target, bucket);
+    let objects = s3::list_objects_recursive(target, credentials, &location.bucket, &location.prefix).await?;
+
+    let mut entries: Vec<TrashEntry> = objects
+        .into_iter()
+        .filter_map(|obj| {
+            let (deleted_at, original_key) = location.parse(&obj.key)?;
+            Some(TrashEntry {
+                trash_key: obj.key,
+                original_key,
+                deleted_at,
+                size: obj.size,
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.deleted_at
+            .cmp(&a.deleted_at)
+            .then_with(|| a.original_key.cmp(&b.original_key))
+    });
+    Ok(entries)
+}
+
+/// Put trashed objects back at their original keys. Unless `overwrite` is
+/// set, an object that exists there again is left in the trash.
+pub async fn restore(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    trash_keys: Vec<String>,
+    overwrite: bool,
+) -> Result<TrashRestoreResult> {
+    let location = location(target, bucket);
+    let mut result = TrashRestoreResult::default();
+    let mut restored_trash_keys = Vec::new();
+
+    for trash_key in trash_keys {
+        let Some((_, original_key)) = location.parse(&trash_key) else {
+            result.errors.push(format!("{trash_key}: not in the trash of {bucket}"));
+            continue;
+        };
+
+        if !overwrite {
+            match s3::head_object(target, credentials, bucket, &original_key).await {
+                Ok(Some(_)) => {
+                    result.skipped.push(original_key);
+                    continue;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    result.errors.push(format!("{original_key}: {e}"));
+                    continue;
+                }
+            }
+        }
+
+        match copy_existing(target, credentials, &location.bucket, &trash_key, bucket, &original_key).await {
+            Ok(()) => {
+                result.restored.push(original_key);
+                restored_trash_keys.push(trash_key);
+            }
+            Err(e) => result.errors.push(format!("{original_key}: {e}")),
+        }
+    }
+
+    let removed = s3::delete_objects(target, credentials, &location.bucket, restored_trash_keys).await?;
+    for failed in removed.failed {
+        let message = failed.code.or(failed.message).unwrap_or_else(|| "Delete failed".to_string());
+        result
+            .errors
+            .push(format!("{}: restored, but still in the trash ({message})", failed.key));
+    }
+    Ok(result)
+}
+
+/// Delete trashed objects for good: those deleted before `before`, or all
+/// of them.
+pub async fn purge(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    before: Option<i64>,
+) -> Result<DeleteObjectsResult> {
+    let location = location(target, bucket);
+    let keys: Vec<String> = list(target, credentials, bucket)
+        .await?
+        .into_iter()
+        .filter(|entry| before.is_none_or(|before| entry.deleted_at < before))
+        .map(|entry| entry.trash_key)
+        .collect();
+    s3::delete_objects(target, credentials, &location.bucket, keys).await
+}
+
+/// Purge expired trash every few hours for the lifetime of the app, for
+/// every target with a trash retention period.
+pub async fn run(storage: Arc<SqliteStorage>) {
+    loop {
+        match sweep(&storage).await {
+            Ok(0) => {}
+            Ok(purged) => log::info!("Trash purge removed {purged} object(s)"),
+            Err(e) => log::error!("Trash purge failed: {e}"),
+        }
+        tokio::time::sleep(PURGE_INTERVAL).await;
+    }
+}
+
+/// Purge objects that have been in the trash longer than their target's
+/// retention period. Returns how many were deleted.
+pub async fn sweep(storage: &SqliteStorage) -> Result<usize> {
+    let now = Utc::now().timestamp();
+    let mut purged = 0;
+
+    for target in targets_repo::list(storage)? {
+        if !target.trash_enabled || target.trash_retention_days < 1 || target.read_only {
+            continue;
+        }
+        let Some(credentials) = credentials_repo::get(storage, &target.id)? else {
+            continue;
+        };
+
+        let buckets = match target.scoped_bucket.as_deref().filter(|b| !b.is_empty()) {
+            Some(bucket) => vec![bucket.to_string()],
+            None => match s3::list_buckets(&target, &credentials).await {
+                Ok(buckets) => buckets.into_iter().map(|b| b.name).collect(),
+                Err(e) => {
+                    log::warn!("Skipping trash purge for target {}: {e}", target.id);
+                    continue;
+                }
+            },
+        };
+
+        let before = now - target.trash_retention_days * 86400;
+        for bucket in buckets {
+            match purge(&target, &credentials, &bucket, Some(before)).await {
+                Ok(result) => {
+                    let trash_bucket = location(&target, &bucket).bucket;
+                    audit::record_deletes(storage, audit::SCHEDULE, "purgeTrash", &target.id, &trash_bucket, &result);
+                    purged += result.deleted.len();
+                    if let Some(failed) = result.failed.first() {
+                        log::warn!("Trash purge in {bucket} left {} object(s), e.g. {}", result.failed.len(), failed.key);
+                    }
+                }
+                Err(e) => log::warn!("Trash purge failed for {bucket}: {e}"),
+            }
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Copy an object at its stored size. An object that is already gone
+/// counts as copied.
+async fn copy_existing(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    source_bucket: &str,
+    source_key: &str,
+    dest_bucket: &str,
+    dest_key: &str,
+) -> Result<()> {
+    let Some((size, _)) = s3::head_object(target, credentials, source_bucket, source_key).await? else {
+        return Ok(());
+    };
+    s3::copy_object(
+        target,
+        credentials,
+        source_bucket,
+        source_key,
+        dest_bucket,
+        dest_key,
+        size,
+        &s3::CopyOptions::default(),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn own_bucket() -> TrashLocation {
+        TrashLocation {
+            bucket: "photos".to_string(),
+            prefix: TRASH_ROOT.to_string(),
+        }
+    }
+
+    #[test]
+    fn parse_reads_the_folder_time_and_original_key() {
+        let location = own_bucket();
+        assert_eq!(
+            location.parse(".mahzen-trash/20240102T030405.678Z/albums/a.jpg"),
+            Some((1_704_164_645, "albums/a.jpg".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_round_trips_new_folders() {
+        let location = TrashLocation {
+            bucket: "trash".to_string(),
+            prefix: format!("{TRASH_ROOT}photos/"),
+        };
+        let key = format!("{}a/b.txt", location.new_folder());
+        let (deleted_at, original_key) = location.parse(&key).unwrap();
+        assert_eq!(original_key, "a/b.txt");
+        assert!((Utc::now().timestamp() - deleted_at).abs() < 5);
+    }
+
+    #[test]
+    fn parse_rejects_keys_outside_the_trash() {
+        let location = own_bucket();
+        assert_eq!(location.parse("albums/a.jpg"), None);
+        assert_eq!(location.parse(".mahzen-trash/not-a-time/a.jpg"), None);
+        assert_eq!(location.parse(".mahzen-trash/20240102T030405.678Z/"), None);
+        assert_eq!(location.parse(".mahzen-trash/20240102T030405.678Z"), None);
+    }
+}
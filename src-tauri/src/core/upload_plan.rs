@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use tokio::sync::Semaphore;
+
+use crate::core::s3;
+use crate::models::{
+    S3ObjectEntry, StorageTarget, TargetCredentials, UploadPlan, UploadPlanEntry, UploadPlanFile,
+};
+
+pub const NEW: &str = "new";
+pub const CHANGED: &str = "changed";
+pub const UNCHANGED: &str = "unchanged";
+
+const CONCURRENCY: usize = 16;
+// Past this many files one listing is cheaper than a HEAD for each
+const LIST_THRESHOLD: usize = 100;
+
+/// Compare local files with the objects at their keys, so identical ones
+/// can be left out of an upload.
+pub async fn plan(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    files: Vec<UploadPlanFile>,
+) -> Result<UploadPlan> {
+    let listed = if files.len() > LIST_THRESHOLD {
+        let prefix = common_prefix(files.iter().map(|f| f.key.as_str()));
+        let entries = s3::list_objects_recursive(target, credentials, bucket, &prefix).await?;
+        Some(entries.into_iter().map(|e| (e.key.clone(), e)).collect::<HashMap<_, _>>())
+    } else {
+        None
+    };
+
+    let semaphore = Arc::new(Semaphore::new(CONCURRENCY));
+    let mut handles = Vec::new();
+
+    for file in files {
+        let size = local_size(&file.local_path)?;
+        let entry = match &listed {
+            Some(listed) => match listed.get(&file.key) {
+                Some(entry) => Some(entry.clone()),
+                None => {
+                    handles.push(tokio::spawn(async move { Ok(new_entry(file, size, NEW)) }));
+                    continue;
+                }
+            },
+            None => None,
+        };
+
+        let permit = semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|e| anyhow!("Semaphore error: {e}"))?;
+
+        let target = target.clone();
+        let credentials = credentials.clone();
+        let bucket = bucket.to_string();
+
+        handles.push(tokio::spawn(async move {
+            let status = file_status(&target, &credentials, &bucket, &file, size, entry.as_ref()).await;
+            drop(permit);
+            status.map(|status| new_entry(file, size, status))
+        }));
+    }
+
+    let mut plan = UploadPlan::default();
+    for handle in handles {
+        let entry = handle.await.map_err(|e| anyhow!("Upload plan task failed: {e}"))??;
+        match entry.status.as_str() {
+            NEW => plan.new_count += 1,
+            CHANGED => plan.changed_count += 1,
+            _ => plan.unchanged_count += 1,
+        }
+        if entry.status != UNCHANGED {
+            plan.upload_bytes += entry.size;
+        }
+        plan.entries.push(entry);
+    }
+    Ok(plan)
+}
+
+/// Whether the object at `file.key` is missing, differs from the local
+/// file, or holds the same content. `listed` is the object's entry from a
+/// listing, which settles most plain objects without a HEAD request.
+/// Objects are only reported unchanged when a checksum matches; anything
+/// that cannot be compared counts as changed.
+pub async fn file_status(
+    target: &StorageTarget,
+    credentials: &TargetCredentials,
+    bucket: &str,
+    file: &UploadPlanFile,
+    size: i64,
+    listed: Option<&S3ObjectEntry>,
+) -> Result<&'static str> {
+    let mut local_md5 = None;
+
+    if let Some(entry) = listed {
+        // Listings show the ciphertext size of encrypted objects
+        if !target.client_encryption && entry.size != size {
+            return Ok(CHANGED);
+        }
+        if let Some(etag) = entry.etag.as_deref().map(s3::unquote_etag).filter(|e| !e.contains('-')) {
+            let md5 = md5_of(&file.local_path).await?;
+            if etag == md5 {
+                return Ok(UNCHANGED);
+            }
+            local_md5 = Some(md5);
+        }
+    }
+
+    let Some(remote) = s3::object_fingerprint(target, credentials, bucket, &file.key).await? else {
+        return Ok(NEW);
+    };
+    if remote.size != size {
+        return Ok(CHANGED);
+    }
+
+    let remote_md5 = match (remote.md5, remote.etag) {
+        (Some(md5), _) => md5,
+        (None, _) if remote.client_encrypted => return Ok(CHANGED),
+        (None, None) => return Ok(CHANGED),
+        (None, Some(etag)) if etag.contains('-') => {
+            // Only multipart uploads made with our own part size can be matched
+            let path = file.local_path.clone();
+            let part_size = s3::multipart_part_size(size);
+            let local = tokio::task::spawn_blocking(move || s3::file_multipart_etag(&path, part_size))
+                .await
+                .map_err(|e| anyhow!("Checksum task failed: {e}"))??;
+            return Ok(if local == etag { UNCHANGED } else { CHANGED });
+        }
+        (None, Some(etag)) => etag,
+    };
+
+    let local_md5 = match local_md5 {
+        Some(md5) => md5,
+        None => md5_of(&file.local_path).await?,
+    };
+    Ok(if local_md5 == remote_md5 { UNCHANGED } else { CHANGED })
+}
+
+async fn md5_of(path: &str) -> Result<String> {
+    let path = path.to_string();
+    tokio::task::spawn_blocking(move || s3::file_md5(&path))
+        .await
+        .map_err(|e| anyhow!("Checksum task failed: {e}"))?
+}
+
+fn local_size(path: &str) -> Result<i64> {
+    std::fs::metadata(path)
+        .map(|m| m.len() as i64)
+        .map_err(|e| anyhow!("Failed to read file {path}: {e}"))
+}
+
+fn new_entry(file: UploadPlanFile, size: i64, status: &str) -> UploadPlanEntry {
+    UploadPlanEntry {
+        local_path: file.local_path,
+        key: file.key,
+        size,
+        status: status.to_string(),
+    }
+}
+
+/// The longest folder prefix shared by all of `keys`.
+fn common_prefix<'a>(mut keys: impl Iterator<Item = &'a str>) -> String {
+    let Some(first) = keys.next() else {
+        return String::new();
+    };
+    let mut prefix = &first[..first.rfind('/').map(|i| i + 1).unwrap_or(0)];
+    for key in keys {
+        while !key.starts_with(prefix) {
+            prefix = &prefix[..prefix[..prefix.len() - 1].rfind('/').map(|i| i + 1).unwrap_or(0)];
+        }
+    }
+    prefix.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::common_prefix;
+
+    fn prefix_of(keys: &[&str]) -> String {
+        common_prefix(keys.iter().copied())
+    }
+
+    #[test]
+    fn common_prefix_stops_at_folder_boundaries() {
+        assert_eq!(prefix_of(&["a/b/c.txt", "a/b/d.txt"]), "a/b/");
+        assert_eq!(prefix_of(&["a/b/c.txt", "a/bc/d.txt"]), "a/");
+        assert_eq!(prefix_of(&["a/b/c.txt", "a/b/sub/d.txt"]), "a/b/");
+        assert_eq!(prefix_of(&["a/b/c.txt", "x/y.txt"]), "");
+    }
+
+    #[test]
+    fn common_prefix_of_one_key_is_its_folder() {
+        assert_eq!(prefix_of(&["a/b/c.txt"]), "a/b/");
+        assert_eq!(prefix_of(&["c.txt"]), "");
+        assert_eq!(prefix_of(&[]), "");
+    }
+}
@@ -22,7 +22,7 @@ pub fn run() {
             // Crash recovery: reset interrupted clone jobs to paused
             if let Ok(crashed) = core::storage::repositories::clone_repo::find_jobs_by_status(
                 &state.storage,
-                &["running", "enumerating"],
+                &["running", "enumerating", "deleting"],
             ) {
                 for job in &crashed {
                     let _ = core::storage::repositories::clone_repo::update_job_status(
@@ -37,6 +37,24 @@ pub fn run() {
                 }
             }
 
+            // Crash recovery: reset interrupted batch jobs to paused
+            if let Ok(crashed) = core::storage::repositories::batch_repo::find_jobs_by_status(
+                &state.storage,
+                &["running", "enumerating"],
+            ) {
+                for job in &crashed {
+                    let _ = core::storage::repositories::batch_repo::update_job_status(
+                        &state.storage,
+                        &job.id,
+                        "paused",
+                    );
+                    let _ = core::storage::repositories::batch_repo::reset_active_items(
+                        &state.storage,
+                        &job.id,
+                    );
+                }
+            }
+
             // Crash recovery: reset interrupted index jobs to idle
             if let Ok(indexes) = core::storage::repositories::index_repo::list_index_states(&state.storage) {
                 for idx in &indexes {
@@ -51,8 +69,26 @@ pub fn run() {
                 }
             }
 
+            // Apply persisted bandwidth caps before any transfer can start
+            if let Ok(settings) = core::storage::repositories::settings_repo::get(&state.storage) {
+                core::bandwidth::apply_settings(&settings);
+            }
+            if let Ok(targets) = core::storage::repositories::targets_repo::list(&state.storage) {
+                for target in &targets {
+                    core::bandwidth::set_target_limit(&target.id, target.bandwidth_limit_bytes_per_sec);
+                }
+            }
+
+            let storage = state.storage.clone();
             app.manage(state);
 
+            // Watch archive restores requested in this or earlier sessions
+            tauri::async_runtime::spawn(core::restore_poller::run(app.handle().clone(), storage.clone()));
+            // Abort abandoned multipart uploads when the sweep is turned on
+            tauri::async_runtime::spawn(core::multipart_janitor::run(app.handle().clone(), storage.clone()));
+            // Purge trashed objects once their target's retention is up
+            tauri::async_runtime::spawn(core::trash::run(storage));
+
             let open_item = MenuItem::with_id(app, "open-main", "Open Mahzen", true, None::<&str>)?;
             let quit_item = MenuItem::with_id(app, "quit-app", "Quit", true, None::<&str>)?;
             let menu = Menu::with_items(app, &[&open_item, &quit_item])?;
@@ -114,11 +150,15 @@ pub fn run() {
             commands::targets::targets_delete,
             commands::targets::target_credentials_get,
             commands::targets::target_credentials_upsert,
+            commands::targets::target_client_encryption_key_generate,
             commands::targets::target_buckets_list,
+            commands::targets::target_bucket_create,
+            commands::targets::target_bucket_delete,
             commands::targets::target_connection_test,
             commands::objects::target_objects_list,
             commands::objects::target_objects_list_page,
             commands::objects::target_object_upload,
+            commands::objects::target_upload_plan,
             commands::objects::target_object_download,
             commands::objects::target_objects_delete,
             commands::objects::target_folder_create,
@@ -126,9 +166,53 @@ pub fn run() {
             commands::objects::target_objects_list_recursive,
             commands::objects::target_objects_download_zip,
             commands::objects::target_object_presign,
+            commands::objects::target_object_presign_put,
+            commands::objects::target_presign_post,
+            commands::shares::share_link_create,
+            commands::shares::share_links_list,
+            commands::shares::share_link_regenerate,
+            commands::shares::share_links_delete,
+            commands::shares::share_links_purge_expired,
+            commands::shares::share_links_generate_prefix,
+            commands::objects::target_object_metadata_get,
+            commands::objects::target_object_metadata_update,
+            commands::objects::target_object_tags_get,
+            commands::objects::target_object_tags_put,
+            commands::objects::target_object_tags_delete,
+            commands::objects::target_bucket_versioning_get,
+            commands::buckets::target_bucket_lifecycle_get,
+            commands::buckets::target_bucket_lifecycle_put,
+            commands::buckets::target_bucket_lifecycle_delete,
+            commands::buckets::target_bucket_lifecycle_validate,
+            commands::buckets::target_bucket_lifecycle_preview,
+            commands::buckets::target_bucket_policy_get,
+            commands::buckets::target_bucket_policy_put,
+            commands::buckets::target_bucket_policy_delete,
+            commands::buckets::target_bucket_policy_validate,
+            commands::buckets::target_bucket_cors_get,
+            commands::buckets::target_bucket_cors_put,
+            commands::buckets::target_bucket_cors_delete,
+            commands::buckets::target_bucket_cors_validate,
+            commands::buckets::target_bucket_public_access_block_get,
+            commands::buckets::target_bucket_public_access_block_put,
+            commands::buckets::target_bucket_public_access_block_delete,
+            commands::buckets::target_bucket_access_summary,
+            commands::buckets::target_bucket_multipart_uploads_list,
+            commands::buckets::target_bucket_multipart_uploads_abort,
+            commands::buckets::target_bucket_multipart_uploads_abort_stale,
+            commands::buckets::multipart_sweep_run,
+            commands::objects::target_object_versions_list,
+            commands::objects::target_object_version_restore,
+            commands::objects::target_object_undelete,
+            commands::objects::target_object_restore,
+            commands::objects::object_restores_list,
+            commands::objects::object_restore_delete,
+            commands::objects::object_restores_check,
+            commands::objects::target_object_version_delete,
             commands::objects::bucket_stats_cache_list,
             commands::objects::bucket_stats_cache_upsert,
             commands::files::list_directory_files,
+            commands::files::path_is_directory,
             commands::settings::settings_get,
             commands::settings::settings_upsert,
             commands::sync::sync_profiles_list,
@@ -139,6 +223,7 @@ pub fn run() {
             commands::transfers::transfer_queue_delete,
             commands::transfers::transfer_queue_clear_terminal,
             commands::clone::clone_start,
+            commands::clone::move_start,
             commands::clone::clone_pause,
             commands::clone::clone_resume,
             commands::clone::clone_cancel,
@@ -147,6 +232,26 @@ pub fn run() {
             commands::clone::clone_job_delete,
             commands::clone::clone_retry_failed,
             commands::clone::clone_job_items_list,
+            commands::batch::batch_tag_start,
+            commands::batch::batch_empty_bucket_start,
+            commands::batch::batch_delete_prefix_start,
+            commands::batch::batch_upload_directory_start,
+            commands::batch::batch_storage_class_start,
+            commands::batch::batch_restore_start,
+            commands::batch::batch_pause,
+            commands::batch::batch_resume,
+            commands::batch::batch_cancel,
+            commands::batch::batch_retry_failed,
+            commands::batch::batch_job_list,
+            commands::batch::batch_job_get,
+            commands::batch::batch_job_delete,
+            commands::batch::batch_job_items_list,
+            commands::trash::trash_list,
+            commands::trash::trash_restore,
+            commands::trash::trash_empty,
+            commands::trash::trash_prefix_start,
+            commands::audit::audit_log_list,
+            commands::audit::audit_log_export,
             commands::indexing::index_start,
             commands::indexing::index_cancel,
             commands::indexing::index_delete,
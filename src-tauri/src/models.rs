@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +23,94 @@ pub struct S3ObjectListPage {
     pub is_truncated: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectMetadata {
+    pub key: String,
+    pub size: i64,
+    pub last_modified: Option<String>,
+    pub etag: Option<String>,
+    pub version_id: Option<String>,
+    pub storage_class: Option<String>,
+    pub content_type: Option<String>,
+    pub cache_control: Option<String>,
+    pub content_disposition: Option<String>,
+    pub content_encoding: Option<String>,
+    pub content_language: Option<String>,
+    pub expires: Option<String>,
+    pub metadata: HashMap<String, String>,
+    pub server_side_encryption: Option<String>,
+    pub sse_kms_key_id: Option<String>,
+    pub sse_customer_algorithm: Option<String>,
+    pub bucket_key_enabled: Option<bool>,
+    pub checksum_crc32: Option<String>,
+    pub checksum_crc32c: Option<String>,
+    pub checksum_crc64nvme: Option<String>,
+    pub checksum_sha1: Option<String>,
+    pub checksum_sha256: Option<String>,
+    pub parts_count: Option<i32>,
+    pub restore: Option<String>,
+    pub archive_status: Option<String>,
+    pub replication_status: Option<String>,
+    pub object_lock_mode: Option<String>,
+    pub object_lock_retain_until: Option<String>,
+    pub object_lock_legal_hold: Option<String>,
+}
+
+/// Replacement headers and user metadata for an in-place metadata edit.
+/// Fields left empty are removed from the object.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectMetadataUpdate {
+    pub content_type: Option<String>,
+    pub cache_control: Option<String>,
+    pub content_disposition: Option<String>,
+    pub content_encoding: Option<String>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+/// One entry of a `ListObjectVersions` listing: either a stored version or
+/// a delete marker.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectVersion {
+    pub key: String,
+    pub version_id: String,
+    pub is_latest: bool,
+    pub is_delete_marker: bool,
+    pub size: i64,
+    pub last_modified: Option<String>,
+    pub etag: Option<String>,
+    pub storage_class: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectVersionPage {
+    pub versions: Vec<ObjectVersion>,
+    pub folders: Vec<String>,
+    pub next_key_marker: Option<String>,
+    pub next_version_id_marker: Option<String>,
+    pub is_truncated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectTag {
+    pub key: String,
+    pub value: String,
+}
+
+/// Index search filter matching objects that carry `key`, optionally with
+/// exactly `value`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagFilter {
+    pub key: String,
+    pub value: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StorageTarget {
@@ -35,6 +125,45 @@ pub struct StorageTarget {
     pub pinned_buckets: Vec<String>,
     pub skip_destructive_confirmations: bool,
     #[serde(default)]
+    pub bandwidth_limit_bytes_per_sec: Option<i64>,
+    /// Default server-side encryption for writes: "none", "sseS3", "sseKms"
+    /// or "sseC". The SSE-C key lives with the credentials.
+    #[serde(default)]
+    pub encryption_mode: Option<String>,
+    #[serde(default)]
+    pub encryption_kms_key_id: Option<String>,
+    /// Encrypt new objects on this machine before upload. The key lives with
+    /// the credentials.
+    #[serde(default)]
+    pub client_encryption: bool,
+    /// Also encrypt object key names when client-side encryption is on.
+    #[serde(default)]
+    pub encrypt_key_names: bool,
+    /// Move deleted objects to the trash instead of removing them.
+    #[serde(default)]
+    pub trash_enabled: bool,
+    /// Bucket the trash lives in. Without one, each bucket keeps its own
+    /// trash under `.mahzen-trash/`.
+    #[serde(default)]
+    pub trash_bucket: Option<String>,
+    /// Purge trashed objects after this many days; 0 keeps them.
+    #[serde(default)]
+    pub trash_retention_days: i64,
+    /// Reject every write, delete and configuration change.
+    #[serde(default)]
+    pub read_only: bool,
+    /// `bucket/key` prefixes, optionally with `*` wildcards, whose objects
+    /// can't be deleted, overwritten or moved.
+    #[serde(default)]
+    pub protected_prefixes: Vec<String>,
+    /// Let the background sweep abort stale multipart uploads here.
+    #[serde(default)]
+    pub multipart_sweep: bool,
+    /// Don't check downloads against the object's MD5, for providers whose
+    /// ETags look like MD5s but aren't.
+    #[serde(default)]
+    pub skip_download_verification: bool,
+    #[serde(default)]
     pub has_credentials: bool,
     pub updated_at: i64,
 }
@@ -45,6 +174,62 @@ pub struct TargetCredentials {
     pub access_key_id: String,
     pub secret_access_key: String,
     pub session_token: Option<String>,
+    /// Base64 256-bit key for SSE-C reads and writes.
+    #[serde(default)]
+    pub sse_customer_key: Option<String>,
+    /// Base64 256-bit key for client-side encryption.
+    #[serde(default)]
+    pub client_encryption_key: Option<String>,
+}
+
+/// Per-operation override of a target's default server-side encryption.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerSideEncryption {
+    /// "none", "sseS3", "sseKms" or "sseC".
+    pub mode: String,
+    pub kms_key_id: Option<String>,
+    /// Falls back to the target's stored SSE-C key when omitted.
+    pub customer_key: Option<String>,
+}
+
+/// Response headers S3 should send when a presigned GET URL is used.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresignResponseOverrides {
+    #[serde(default)]
+    pub content_type: Option<String>,
+    #[serde(default)]
+    pub content_disposition: Option<String>,
+    /// Shorthand for an `attachment` Content-Disposition with this filename.
+    #[serde(default)]
+    pub download_filename: Option<String>,
+    #[serde(default)]
+    pub cache_control: Option<String>,
+    #[serde(default)]
+    pub content_language: Option<String>,
+    #[serde(default)]
+    pub content_encoding: Option<String>,
+}
+
+/// A presigned request, with the headers that were signed along with it.
+/// They must be sent exactly as given.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresignedRequest {
+    pub url: String,
+    pub method: String,
+    pub headers: HashMap<String, String>,
+}
+
+/// An S3 POST policy: a browser form posting `fields`, plus a `file` field,
+/// to `url` uploads within the policy's conditions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresignedPost {
+    pub url: String,
+    pub fields: std::collections::BTreeMap<String, String>,
+    pub expires_at: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -148,6 +333,20 @@ pub struct AppSettings {
     pub retry_count: i64,
     pub preserve_timestamps: bool,
     pub verify_checksum: bool,
+    #[serde(default)]
+    pub bandwidth_limit_bytes_per_sec: i64,
+    #[serde(default)]
+    pub bandwidth_schedule_enabled: bool,
+    #[serde(default)]
+    pub bandwidth_schedule_start_minute: i64,
+    #[serde(default)]
+    pub bandwidth_schedule_end_minute: i64,
+    /// Abort incomplete multipart uploads in the background.
+    #[serde(default)]
+    pub multipart_sweep_enabled: bool,
+    /// Age in days after which the background sweep aborts an upload.
+    #[serde(default)]
+    pub multipart_sweep_days: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -174,6 +373,11 @@ pub struct CloneJob {
     pub created_at: i64,
     pub updated_at: i64,
     pub completed_at: Option<i64>,
+    /// A move: each source object is deleted once its copy is verified.
+    #[serde(default)]
+    pub delete_source: bool,
+    /// What started the job; its writes are audited under this.
+    pub initiator: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -207,6 +411,434 @@ pub struct CloneProgressEvent {
     pub current_key: Option<String>,
 }
 
+/// A resumable bulk operation over the objects of one bucket, either an
+/// explicit selection of keys or everything under `prefix`. `kind` selects
+/// what is done to each item and `params` holds its kind-specific options.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchJob {
+    pub id: String,
+    pub kind: String,
+    pub status: String,
+    pub target_id: String,
+    pub bucket: String,
+    pub prefix: String,
+    pub params: serde_json::Value,
+    pub enumeration_token: Option<String>,
+    pub enumeration_complete: bool,
+    pub total_items: i64,
+    pub completed_items: i64,
+    pub failed_items: i64,
+    pub skipped_items: i64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub completed_at: Option<i64>,
+    /// What started the job; its writes are audited under this.
+    pub initiator: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchJobItem {
+    pub id: String,
+    pub job_id: String,
+    pub key: String,
+    pub version_id: Option<String>,
+    pub upload_id: Option<String>,
+    pub size: i64,
+    pub status: String,
+    pub error_message: Option<String>,
+    pub created_at: i64,
+    pub updated_at: i64,
+}
+
+/// Parameters of a `tag` batch job. `mode` is "merge" (add or overwrite the
+/// given tags), "replace" (set exactly the given tags) or "remove" (drop the
+/// given tag keys).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TagJobParams {
+    pub mode: String,
+    pub tags: Vec<ObjectTag>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageClassJobParams {
+    pub storage_class: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreJobParams {
+    pub days: i32,
+    /// "Standard", "Bulk" or "Expedited".
+    pub tier: String,
+}
+
+/// A restore request for an archived object, tracked until the restored
+/// copy is readable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectRestore {
+    pub id: String,
+    pub target_id: String,
+    pub bucket: String,
+    pub key: String,
+    pub version_id: Option<String>,
+    pub tier: String,
+    pub days: i32,
+    /// "pending", "restored", "expired" or "failed".
+    pub status: String,
+    pub expiry_date: Option<String>,
+    pub error_message: Option<String>,
+    pub requested_at: i64,
+    pub last_checked_at: Option<i64>,
+    pub completed_at: Option<i64>,
+}
+
+/// A presigned GET URL handed out for sharing. The URL can't be revoked on
+/// its own: it stays valid until it expires or the access key that signed
+/// it (`signing_key_id`) is deactivated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShareLink {
+    pub id: String,
+    pub target_id: String,
+    pub bucket: String,
+    pub key: String,
+    pub version_id: Option<String>,
+    pub url: String,
+    pub expires_in_secs: i64,
+    pub response: Option<PresignResponseOverrides>,
+    pub signing_key_id: String,
+    pub note: Option<String>,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+/// An incomplete multipart upload. Its parts are stored, and billed, until
+/// the upload is completed or aborted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipartUploadSummary {
+    /// The key as stored, encrypted if the target encrypts key names.
+    pub key: String,
+    pub upload_id: String,
+    pub initiated: Option<i64>,
+    pub storage_class: Option<String>,
+    pub part_count: i64,
+    pub size: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipartUploadRef {
+    pub key: String,
+    pub upload_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultipartAbortResult {
+    pub aborted: i64,
+    pub errors: Vec<String>,
+}
+
+/// Parameters of an `emptyBucket` batch job, which deletes every object
+/// version and delete marker and aborts incomplete multipart uploads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmptyBucketParams {
+    /// Delete the bucket itself once it is empty.
+    pub delete_bucket: bool,
+    /// Also delete versions under object lock governance mode.
+    #[serde(default)]
+    pub bypass_governance: bool,
+}
+
+/// Parameters of a `deletePrefix` batch job, which deletes everything
+/// under the job's prefix, folder markers included.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletePrefixParams {
+    /// Delete every version and delete marker rather than only the current
+    /// objects.
+    pub all_versions: bool,
+    /// Also delete versions under object lock governance mode.
+    #[serde(default)]
+    pub bypass_governance: bool,
+}
+
+/// Parameters of an `uploadDirectory` batch job, which uploads the files
+/// under a local folder. Each item's file sits at the item's key, less
+/// `dest_prefix`, under `root`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadDirectoryParams {
+    pub root: String,
+    /// Readable form of the job's prefix, empty or ending in `/`.
+    pub dest_prefix: String,
+    /// Gitignore-style globs; when any are given only matching files are
+    /// uploaded. Matches win over ignore files and hidden-file filtering.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// Gitignore-style globs of files and folders to leave out.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+    #[serde(default)]
+    pub show_hidden: bool,
+    /// Leave out what `.gitignore` and `.ignore` files in the folder list.
+    pub respect_ignore_files: bool,
+    /// "skip" leaves symlinks out; "follow" uploads what they point to.
+    pub symlinks: String,
+    /// Skip files whose content is already at their key.
+    #[serde(default)]
+    pub skip_identical: bool,
+}
+
+/// An entry S3 refused to delete in a DeleteObjects request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectDeleteError {
+    pub key: String,
+    pub version_id: Option<String>,
+    pub code: Option<String>,
+    pub message: Option<String>,
+}
+
+/// What a delete of a list of keys did: the keys that are gone, and the
+/// ones S3 refused with its error code.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteObjectsResult {
+    pub deleted: Vec<String>,
+    pub failed: Vec<ObjectDeleteError>,
+}
+
+/// An object in a bucket's trash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    /// Where the object sits in the trash bucket.
+    pub trash_key: String,
+    /// Where it will be restored to.
+    pub original_key: String,
+    pub deleted_at: i64,
+    pub size: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashRestoreResult {
+    pub restored: Vec<String>,
+    /// Keys left in the trash because something already exists there.
+    pub skipped: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// A local file and the key it would be uploaded to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadPlanFile {
+    pub local_path: String,
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadPlanEntry {
+    pub local_path: String,
+    pub key: String,
+    pub size: i64,
+    /// "new", "changed" or "unchanged".
+    pub status: String,
+}
+
+/// How a set of local files compares with what is already in a bucket.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadPlan {
+    pub entries: Vec<UploadPlanEntry>,
+    pub new_count: usize,
+    pub changed_count: usize,
+    pub unchanged_count: usize,
+    /// Bytes to send for the new and changed files.
+    pub upload_bytes: i64,
+}
+
+/// One write Mahzen made, or tried to make.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub id: String,
+    pub timestamp: i64,
+    /// What started the write: "manual", "clone" or "schedule".
+    pub initiator: String,
+    pub operation: String,
+    pub target_id: String,
+    /// `None` for changes to the target itself or its credentials.
+    pub bucket: Option<String>,
+    pub keys: Vec<String>,
+    pub prefix: Option<String>,
+    /// "success" or "failure".
+    pub result: String,
+    pub error: Option<String>,
+}
+
+/// Narrows an audit log query. Every field is optional; `since` and
+/// `until` are epoch seconds, `key` matches part of a key or prefix.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AuditLogFilter {
+    pub target_id: Option<String>,
+    pub bucket: Option<String>,
+    pub operation: Option<String>,
+    pub initiator: Option<String>,
+    pub result: Option<String>,
+    pub key: Option<String>,
+    pub since: Option<i64>,
+    pub until: Option<i64>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchProgressEvent {
+    pub job_id: String,
+    pub kind: String,
+    pub status: String,
+    pub total_items: i64,
+    pub completed_items: i64,
+    pub failed_items: i64,
+    pub skipped_items: i64,
+}
+
+/// One rule of a bucket lifecycle configuration. The filter is the
+/// prefix, tags and size bounds combined; every action is optional but a
+/// rule needs at least one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecycleRule {
+    pub id: Option<String>,
+    pub enabled: bool,
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default)]
+    pub tags: Vec<ObjectTag>,
+    pub object_size_greater_than: Option<i64>,
+    pub object_size_less_than: Option<i64>,
+    pub expiration_days: Option<i32>,
+    /// Expire on this date (`YYYY-MM-DD`, midnight UTC).
+    pub expiration_date: Option<String>,
+    #[serde(default)]
+    pub expired_object_delete_marker: bool,
+    #[serde(default)]
+    pub transitions: Vec<LifecycleTransition>,
+    pub noncurrent_expiration_days: Option<i32>,
+    /// Noncurrent versions to keep regardless of age.
+    pub noncurrent_newer_versions: Option<i32>,
+    #[serde(default)]
+    pub noncurrent_transitions: Vec<LifecycleTransition>,
+    pub abort_incomplete_upload_days: Option<i32>,
+}
+
+/// Move objects to `storage_class` after `days` (counted from creation, or
+/// from becoming noncurrent for noncurrent transitions) or on `date`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecycleTransition {
+    pub days: Option<i32>,
+    pub date: Option<String>,
+    pub storage_class: String,
+}
+
+/// What a lifecycle rule would do right now to the current objects in the
+/// local bucket index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecyclePreview {
+    pub matched_objects: i64,
+    pub affected_objects: i64,
+    pub affected_size: i64,
+    pub objects: Vec<LifecyclePreviewObject>,
+    pub truncated: bool,
+    pub last_indexed_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LifecyclePreviewObject {
+    pub key: String,
+    pub size: i64,
+    pub last_modified: Option<String>,
+    pub storage_class: Option<String>,
+    /// "expire" or "transition".
+    pub action: String,
+    pub transition_storage_class: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CorsRule {
+    pub id: Option<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_origins: Vec<String>,
+    #[serde(default)]
+    pub allowed_headers: Vec<String>,
+    #[serde(default)]
+    pub expose_headers: Vec<String>,
+    pub max_age_seconds: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublicAccessBlock {
+    pub block_public_acls: bool,
+    pub ignore_public_acls: bool,
+    pub block_public_policy: bool,
+    pub restrict_public_buckets: bool,
+}
+
+/// A principal granted read or list access to a bucket, by its policy or
+/// its ACL.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketReader {
+    /// "*" for anyone, otherwise an ARN, account, service or ACL grantee.
+    pub principal: String,
+    /// "policy" or "acl".
+    pub source: String,
+    /// "read" (GetObject) and/or "list" (ListBucket).
+    pub permissions: Vec<String>,
+    /// Resources the grant covers; for ACL grants, the whole bucket.
+    pub resources: Vec<String>,
+    /// The policy statement only applies under conditions.
+    pub conditional: bool,
+    pub is_public: bool,
+    /// Public access block settings make the grant ineffective.
+    pub blocked: bool,
+}
+
+/// Who can read a bucket, derived from its policy, ACL and public access
+/// block. Deny statements are reported but not evaluated.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BucketAccessSummary {
+    pub readers: Vec<BucketReader>,
+    /// Anyone can read objects, unconditionally and not blocked.
+    pub public_read: bool,
+    /// Anyone can list objects, unconditionally and not blocked.
+    pub public_list: bool,
+    /// The provider's own evaluation of the policy, where supported.
+    pub policy_is_public: Option<bool>,
+    pub public_access_block: Option<PublicAccessBlock>,
+    pub has_deny_statements: bool,
+    /// Parts of the configuration that could not be read.
+    pub warnings: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BucketIndexState {
@@ -218,6 +850,8 @@ pub struct BucketIndexState {
     pub total_size: i64,
     pub continuation_token: Option<String>,
     pub last_indexed_at: Option<i64>,
+    #[serde(default)]
+    pub include_tags: bool,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -270,6 +904,12 @@ impl Default for AppSettings {
             retry_count: 3,
             preserve_timestamps: true,
             verify_checksum: true,
+            bandwidth_limit_bytes_per_sec: 0,
+            bandwidth_schedule_enabled: false,
+            bandwidth_schedule_start_minute: 9 * 60,
+            bandwidth_schedule_end_minute: 18 * 60,
+            multipart_sweep_enabled: false,
+            multipart_sweep_days: 7,
         }
     }
 }
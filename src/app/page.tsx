@@ -17,14 +17,21 @@ import { UploadDialog, type StagedFile } from '@/components/upload-dialog'
 import { NewFolderDialog } from '@/components/new-folder-dialog'
 import { DeleteConfirmDialog } from '@/components/delete-confirm-dialog'
 import { PresignDialog } from '@/components/presign-dialog'
+import { UploadLinkDialog } from '@/components/upload-link-dialog'
+import { ShareLinksDialog } from '@/components/share-links-dialog'
+import { TrashDialog } from '@/components/trash-dialog'
+import { LifecycleDialog } from '@/components/lifecycle-dialog'
+import { MetadataDialog } from '@/components/metadata-dialog'
+import { VersionsDialog } from '@/components/versions-dialog'
+import { MoveDialog } from '@/components/move-dialog'
 import { TransfersPanel } from '@/components/transfers-panel'
 import { CloneDialog } from '@/components/clone/clone-dialog'
 import { SettingsDialog } from '@/components/settings-dialog'
 import { TitleBar } from '@/components/title-bar'
 import { useActiveTransferCount } from '@/lib/transfer-store'
 import { transferStore } from '@/lib/transfer-store'
-import { targetsList, targetBucketsList, targetObjectsListPage, targetObjectsListRecursive, targetObjectsDelete, targetBucketStats, isTauriRuntime, settingsGet, listDirectoryFiles, bucketStatsCacheList, bucketStatsCacheUpsert, indexStart, indexStateGet, indexBrowse, indexSearch } from '@/lib/tauri'
-import type { AppSettings, BucketIndexState, SidebarBucket } from '@/lib/types'
+import { targetsList, targetBucketsList, targetObjectsListPage, targetObjectsListRecursive, targetObjectsDelete, batchDeletePrefixStart, trashPrefixStart, targetBucketStats, isTauriRuntime, settingsGet, pathIsDirectory, bucketStatsCacheList, bucketStatsCacheUpsert, indexStart, indexStateGet, indexBrowse, indexSearch } from '@/lib/tauri'
+import type { AppSettings, BatchProgressEvent, BucketIndexState, MultipartAbortResult, ObjectRestore, SidebarBucket } from '@/lib/types'
 import { toast } from 'sonner'
 
 const DEFAULT_SETTINGS: AppSettings = {
@@ -48,6 +55,12 @@ const DEFAULT_SETTINGS: AppSettings = {
   retryCount: 3,
   preserveTimestamps: true,
   verifyChecksum: true,
+  bandwidthLimitBytesPerSec: 0,
+  bandwidthScheduleEnabled: false,
+  bandwidthScheduleStartMinute: 540,
+  bandwidthScheduleEndMinute: 1080,
+  multipartSweepEnabled: false,
+  multipartSweepDays: 7,
 }
 
 export default function Page() {
@@ -100,9 +113,19 @@ export default function Page() {
   const [transfersExpanded, setTransfersExpanded] = useState(false)
   const [pendingDeleteKeys, setPendingDeleteKeys] = useState<string[]>([])
   const [deleteConfirmOpen, setDeleteConfirmOpen] = useState(false)
+  const folderDeleteJobs = useRef<Set<string>>(new Set())
+  const folderUploadJobs = useRef<Set<string>>(new Set())
   const [settingsOpen, setSettingsOpen] = useState(false)
   const [presignOpen, setPresignOpen] = useState(false)
   const [presignObject, setPresignObject] = useState<S3Object | null>(null)
+  const [metadataObject, setMetadataObject] = useState<S3Object | null>(null)
+  const [moveObject, setMoveObject] = useState<S3Object | null>(null)
+  const [uploadLinkOpen, setUploadLinkOpen] = useState(false)
+  const [shareLinksOpen, setShareLinksOpen] = useState(false)
+  const [trashOpen, setTrashOpen] = useState(false)
+  const [lifecycleOpen, setLifecycleOpen] = useState(false)
+  const [versionsOpen, setVersionsOpen] = useState(false)
+  const [versionsObject, setVersionsObject] = useState<S3Object | null>(null)
   const [cloneOpen, setCloneOpen] = useState(false)
   const [cloneSourceOverride, setCloneSourceOverride] = useState<SidebarBucket | null>(null)
 
@@ -174,6 +197,7 @@ export default function Page() {
               region: target.region || 'auto',
               targetId: target.id,
               targetName: target.name,
+              trashEnabled: target.trashEnabled,
               provider: target.provider,
               objectCount: cached?.objectCount ?? null,
               totalSize: cached?.totalSize ?? null,
@@ -314,27 +338,22 @@ export default function Page() {
             return
           }
 
-          // Convert dropped paths to StagedFile[] and expand directories
+          // Convert dropped paths to StagedFile[]; folders are walked by their upload job
           const processDroppedPaths = async () => {
             const staged: StagedFile[] = []
 
             for (const p of paths) {
               try {
-                // Try expanding as directory first
-                const files = await listDirectoryFiles(p)
-                if (files.length > 0) {
+                if (await pathIsDirectory(p)) {
                   const folderName = p.split('/').pop() || p.split('\\').pop() || p
-                  for (const f of files) {
-                    const relativePath = `${folderName}/${f.relativePath.replace(/\\/g, '/')}`
-                    const name = f.relativePath.split('/').pop() || f.relativePath.split('\\').pop() || f.relativePath
-                    staged.push({
-                      id: `${Date.now()}-${Math.random().toString(36).slice(2, 7)}`,
-                      name,
-                      path: f.absolutePath,
-                      relativePath,
-                      size: f.size,
-                    })
-                  }
+                  staged.push({
+                    id: `${Date.now()}-${Math.random().toString(36).slice(2, 7)}`,
+                    name: folderName,
+                    path: p,
+                    relativePath: `${folderName}/`,
+                    size: 0,
+                    isFolder: true,
+                  })
                 } else {
                   // Treat as single file
                   const name = p.split('/').pop() || p.split('\\').pop() || p
@@ -434,6 +453,44 @@ export default function Page() {
     }
   }, [selectedBucket, currentPath, loadObjects])
 
+  // Announce archived objects that have become readable
+  useEffect(() => {
+    if (!isTauriRuntime()) return
+    let unlisten: (() => void) | undefined
+
+    import('@tauri-apps/api/event').then(({ listen }) => {
+      listen<ObjectRestore>('object-restore-complete', (event) => {
+        const r = event.payload
+        toast.success('Restore complete', {
+          description: r.expiryDate
+            ? `${r.bucket}/${r.key} can be downloaded until ${r.expiryDate}`
+            : `${r.bucket}/${r.key} can be downloaded`,
+        })
+      }).then(fn => { unlisten = fn })
+    })
+
+    return () => { unlisten?.() }
+  }, [])
+
+  // Report what the background multipart sweep cleaned up
+  useEffect(() => {
+    if (!isTauriRuntime()) return
+    let unlisten: (() => void) | undefined
+
+    import('@tauri-apps/api/event').then(({ listen }) => {
+      listen<MultipartAbortResult>('multipart-sweep-complete', (event) => {
+        const { aborted, errors } = event.payload
+        if (errors.length > 0) {
+          toast.error('Some abandoned uploads could not be aborted', { description: errors[0] })
+        } else {
+          toast.success(`Aborted ${aborted} abandoned upload${aborted === 1 ? '' : 's'}`)
+        }
+      }).then(fn => { unlisten = fn })
+    })
+
+    return () => { unlisten?.() }
+  }, [])
+
   // Load objects when bucket or path changes
   useEffect(() => {
     if (!selectedBucket || !isTauriRuntime()) {
@@ -558,6 +615,52 @@ export default function Page() {
     })
   }, [selectedBucket, currentPath, loadObjects, isIndexed])
 
+  const handleRefreshRef = useRef(handleRefresh)
+  useEffect(() => {
+    handleRefreshRef.current = handleRefresh
+  }, [handleRefresh])
+
+  // Report folder deletes and uploads that finished in the background
+  useEffect(() => {
+    if (!isTauriRuntime()) return
+    let unlisten: (() => void) | undefined
+
+    import('@tauri-apps/api/event').then(({ listen }) => {
+      listen<BatchProgressEvent>('batch-progress', (event) => {
+        const { jobId, status, completedItems, failedItems, skippedItems } = event.payload
+        if (status !== 'completed' && status !== 'failed') return
+
+        if (folderUploadJobs.current.has(jobId)) {
+          folderUploadJobs.current.delete(jobId)
+          const skipped = skippedItems > 0 ? `, ${skippedItems} unchanged` : ''
+          if (failedItems > 0) {
+            toast.error(`${failedItems} file${failedItems === 1 ? '' : 's'} could not be uploaded`, {
+              description: `${completedItems} uploaded${skipped}`,
+            })
+          } else {
+            toast.success(`Uploaded ${completedItems} file${completedItems === 1 ? '' : 's'}${skipped}`)
+          }
+          handleRefreshRef.current()
+          return
+        }
+
+        if (!folderDeleteJobs.current.has(jobId)) return
+        folderDeleteJobs.current.delete(jobId)
+
+        if (failedItems > 0) {
+          toast.error(`${failedItems} item${failedItems === 1 ? '' : 's'} could not be deleted`, {
+            description: `${completedItems} deleted`,
+          })
+        } else {
+          toast.success(`Deleted ${completedItems} item${completedItems === 1 ? '' : 's'}`)
+        }
+        handleRefreshRef.current()
+      }).then(fn => { unlisten = fn })
+    })
+
+    return () => { unlisten?.() }
+  }, [])
+
   const handleIndexBucket = useCallback(async (bucket: SidebarBucket) => {
     try {
       const state = await indexStart(bucket.targetId, bucket.name, true)
@@ -585,40 +688,69 @@ export default function Page() {
     toast.info('Bucket deletion not yet implemented')
   }, [])
 
+  // Folders are deleted by a background job, so their contents never have
+  // to be listed here first
+  const deleteKeys = useCallback(async (keys: string[]) => {
+    if (!selectedBucket) return
+    const folders = keys.filter((k) => k.endsWith('/'))
+    const files = keys.filter((k) => !k.endsWith('/'))
+    try {
+      const toTrash = selectedBucket.trashEnabled
+      let failedKeys: string[] = []
+      if (files.length > 0) {
+        const { deleted, failed } = await targetObjectsDelete(selectedBucket.targetId, selectedBucket.name, files)
+        failedKeys = failed.map((f) => f.key)
+        if (failed.length > 0) {
+          const first = failed[0]
+          toast.error(`${failed.length} item${failed.length > 1 ? 's' : ''} could not be deleted`, {
+            description: `${first.key}: ${first.code ?? first.message ?? 'Delete failed'}`,
+          })
+        }
+        if (deleted.length > 0) {
+          const what = `${deleted.length} item${deleted.length > 1 ? 's' : ''}`
+          toast.success(toTrash ? `Moved ${what} to the trash` : `Deleted ${what}`)
+        }
+      }
+      // Folders in trash mode are moved by a move job, which shows in the transfers panel
+      for (const prefix of folders) {
+        if (toTrash && !prefix.startsWith('.mahzen-trash/')) {
+          await trashPrefixStart(selectedBucket.targetId, selectedBucket.name, prefix)
+        } else {
+          const job = await batchDeletePrefixStart(selectedBucket.targetId, selectedBucket.name, prefix, false, false, toTrash)
+          folderDeleteJobs.current.add(job.id)
+        }
+      }
+      if (folders.length > 0) {
+        const what = `${folders.length} folder${folders.length > 1 ? 's' : ''}`
+        toast.info(toTrash ? `Moving ${what} to the trash` : `Deleting ${what} in the background`)
+        if (toTrash) setTransfersExpanded(true)
+      }
+      // Leave what is still there selected so it can be retried
+      setSelectedKeys(new Set(failedKeys))
+      setDetailObject(null)
+      handleRefresh()
+    } catch (err) {
+      const msg = err instanceof Error ? err.message : String(err)
+      toast.error('Failed to delete', { description: msg })
+    }
+  }, [selectedBucket, handleRefresh])
+
   const handleDeleteObjects = useCallback(async (keys: string[]) => {
     if (!selectedBucket || keys.length === 0) return
     if (!settings.confirmDelete) {
       // Skip confirmation dialog — delete directly
-      try {
-        await targetObjectsDelete(selectedBucket.targetId, selectedBucket.name, keys)
-        toast.success(`Deleted ${keys.length} item${keys.length > 1 ? 's' : ''}`)
-        setSelectedKeys(new Set())
-        setDetailObject(null)
-        handleRefresh()
-      } catch (err) {
-        const msg = err instanceof Error ? err.message : String(err)
-        toast.error('Failed to delete', { description: msg })
-      }
+      await deleteKeys(keys)
       return
     }
     setPendingDeleteKeys(keys)
     setDeleteConfirmOpen(true)
-  }, [selectedBucket, settings.confirmDelete, handleRefresh])
+  }, [selectedBucket, settings.confirmDelete, deleteKeys])
 
   const confirmDeleteObjects = useCallback(async () => {
-    if (!selectedBucket || pendingDeleteKeys.length === 0) return
-    try {
-      await targetObjectsDelete(selectedBucket.targetId, selectedBucket.name, pendingDeleteKeys)
-      toast.success(`Deleted ${pendingDeleteKeys.length} item${pendingDeleteKeys.length > 1 ? 's' : ''}`)
-      setSelectedKeys(new Set())
-      setDetailObject(null)
-      setPendingDeleteKeys([])
-      handleRefresh()
-    } catch (err) {
-      const msg = err instanceof Error ? err.message : String(err)
-      toast.error('Failed to delete', { description: msg })
-    }
-  }, [selectedBucket, pendingDeleteKeys, handleRefresh])
+    if (pendingDeleteKeys.length === 0) return
+    await deleteKeys(pendingDeleteKeys)
+    setPendingDeleteKeys([])
+  }, [pendingDeleteKeys, deleteKeys])
 
   const handleDownloadObject = useCallback(async (obj: S3Object) => {
     if (!selectedBucket || obj.type === 'folder') return
@@ -880,6 +1012,7 @@ export default function Page() {
         open={deleteConfirmOpen}
         onOpenChange={setDeleteConfirmOpen}
         items={pendingDeleteKeys}
+        toTrash={selectedBucket?.trashEnabled ?? false}
         onConfirm={confirmDeleteObjects}
       />
       {selectedBucket && (
@@ -895,6 +1028,8 @@ export default function Page() {
             currentPath={currentPath}
             onUploadComplete={handleRefresh}
             initialFiles={uploadInitialFiles}
+            showHidden={settings.showHidden}
+            onFolderJobStarted={(jobId) => folderUploadJobs.current.add(jobId)}
           />
           <NewFolderDialog
             open={newFolderOpen}
@@ -914,6 +1049,71 @@ export default function Page() {
               objectName={presignObject.name}
             />
           )}
+          {metadataObject && (
+            <MetadataDialog
+              open={!!metadataObject}
+              onOpenChange={(open) => {
+                if (!open) setMetadataObject(null)
+              }}
+              targetId={selectedBucket.targetId}
+              bucketName={selectedBucket.name}
+              objectKey={metadataObject.key}
+              onUpdated={handleRefresh}
+            />
+          )}
+          {moveObject && (
+            <MoveDialog
+              open={!!moveObject}
+              onOpenChange={(open) => {
+                if (!open) setMoveObject(null)
+              }}
+              targetId={selectedBucket.targetId}
+              bucketName={selectedBucket.name}
+              objectKey={moveObject.key}
+              isFolder={moveObject.type === 'folder'}
+              onMoveStarted={() => setTransfersExpanded(true)}
+            />
+          )}
+          <UploadLinkDialog
+            open={uploadLinkOpen}
+            onOpenChange={setUploadLinkOpen}
+            targetId={selectedBucket.targetId}
+            bucketName={selectedBucket.name}
+            currentPath={currentPath}
+          />
+          <ShareLinksDialog
+            open={shareLinksOpen}
+            onOpenChange={setShareLinksOpen}
+            targetId={selectedBucket.targetId}
+            bucketName={selectedBucket.name}
+            currentPath={currentPath}
+          />
+          <TrashDialog
+            open={trashOpen}
+            onOpenChange={setTrashOpen}
+            targetId={selectedBucket.targetId}
+            bucketName={selectedBucket.name}
+            onRestored={handleRefresh}
+          />
+          <LifecycleDialog
+            open={lifecycleOpen}
+            onOpenChange={setLifecycleOpen}
+            targetId={selectedBucket.targetId}
+            bucketName={selectedBucket.name}
+            currentPath={currentPath}
+          />
+          <VersionsDialog
+            open={versionsOpen}
+            onOpenChange={(open) => {
+              setVersionsOpen(open)
+              if (!open) setVersionsObject(null)
+            }}
+            targetId={selectedBucket.targetId}
+            bucketName={selectedBucket.name}
+            currentPath={currentPath}
+            objectKey={versionsObject?.key}
+            onChanged={handleRefresh}
+          />
         </>
       )}
 
@@ -944,6 +1144,11 @@ export default function Page() {
               onDeleteSelected={handleDeleteSelected}
               onDownloadSelected={handleDownloadSelected}
               onClone={() => setCloneOpen(true)}
+              onUploadLink={() => setUploadLinkOpen(true)}
+              onShareLinks={() => setShareLinksOpen(true)}
+              onTrash={selectedBucket.trashEnabled ? () => setTrashOpen(true) : undefined}
+              onLifecycle={() => setLifecycleOpen(true)}
+              onVersions={() => setVersionsOpen(true)}
               indexStatus={bucketIndexState?.status ?? null}
               indexedAt={bucketIndexState?.lastIndexedAt ?? null}
               indexProgress={bucketIndexState?.indexedObjects}
@@ -970,6 +1175,7 @@ export default function Page() {
                     onDelete={handleDeleteObjects}
                     onDownload={handleDownloadObject}
                     onPresign={handlePresignObject}
+                    onMove={setMoveObject}
                     doubleClickNav={settings.doubleClickNav}
                     showFileIcons={settings.showFileIcons}
                     dateFormat={settings.dateFormat as 'relative' | 'absolute' | 'iso'}
@@ -993,6 +1199,11 @@ export default function Page() {
                     onClose={handleCloseDetails}
                     onDownload={handleDownloadObject}
                     onPresign={handlePresignObject}
+                    onEditMetadata={setMetadataObject}
+                    onShowVersions={(obj) => {
+                      setVersionsObject(obj)
+                      setVersionsOpen(true)
+                    }}
                     onNavigate={navigateTo}
                     sizeFormat={settings.sizeFormat as 'binary' | 'decimal'}
                     dateFormat={settings.dateFormat as 'relative' | 'absolute' | 'iso'}
@@ -211,6 +211,18 @@ export function AddSourceDialog({ open, onOpenChange, onSourceAdded }: AddSource
       scopedBucket,
       pinnedBuckets: [],
       skipDestructiveConfirmations: false,
+      bandwidthLimitBytesPerSec: null,
+      encryptionMode: null,
+      encryptionKmsKeyId: null,
+      clientEncryption: false,
+      encryptKeyNames: false,
+      trashEnabled: false,
+      trashBucket: null,
+      trashRetentionDays: 0,
+      readOnly: false,
+      protectedPrefixes: [],
+      multipartSweep: false,
+      skipDownloadVerification: false,
       hasCredentials: true,
       updatedAt: Math.floor(Date.now() / 1000),
     }
@@ -220,6 +232,8 @@ export function AddSourceDialog({ open, onOpenChange, onSourceAdded }: AddSource
     accessKeyId: form.accessKeyId,
     secretAccessKey: form.secretAccessKey,
     sessionToken: null,
+    sseCustomerKey: null,
+    clientEncryptionKey: null,
   })
 
   const saveTargetAndCredentials = async (id: string) => {
@@ -15,6 +15,11 @@ import {
   Trash2,
   Copy,
   Database,
+  Link2,
+  Share2,
+  ArchiveRestore,
+  CalendarClock,
+  History,
   Loader2,
 } from 'lucide-react'
 import { cn } from '@/lib/utils'
@@ -49,6 +54,11 @@ interface BrowserToolbarProps {
   onDeleteSelected: () => void
   onDownloadSelected: () => void
   onClone?: () => void
+  onUploadLink?: () => void
+  onShareLinks?: () => void
+  onTrash?: () => void
+  onLifecycle?: () => void
+  onVersions?: () => void
   indexStatus?: 'idle' | 'indexing' | 'error' | null
   indexedAt?: number | null
   indexProgress?: number
@@ -78,6 +88,11 @@ export function BrowserToolbar({
   onDeleteSelected,
   onDownloadSelected,
   onClone,
+  onUploadLink,
+  onShareLinks,
+  onTrash,
+  onLifecycle,
+  onVersions,
   indexStatus,
   indexedAt,
   indexProgress,
@@ -277,6 +292,86 @@ export function BrowserToolbar({
                 <TooltipContent side="bottom" className="text-xs">Clone bucket or prefix</TooltipContent>
               </Tooltip>
             )}
+            {onUploadLink && (
+              <Tooltip>
+                <TooltipTrigger asChild>
+                  <button
+                    type="button"
+                    onClick={onUploadLink}
+                    className="flex items-center gap-1.5 rounded-md px-2 py-1 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+                    aria-label="Create upload link"
+                  >
+                    <Link2 className="h-3.5 w-3.5" />
+                    <span>Upload Link</span>
+                  </button>
+                </TooltipTrigger>
+                <TooltipContent side="bottom" className="text-xs">Let others upload to this folder</TooltipContent>
+              </Tooltip>
+            )}
+            {onShareLinks && (
+              <Tooltip>
+                <TooltipTrigger asChild>
+                  <button
+                    type="button"
+                    onClick={onShareLinks}
+                    className="flex items-center gap-1.5 rounded-md px-2 py-1 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+                    aria-label="Manage share links"
+                  >
+                    <Share2 className="h-3.5 w-3.5" />
+                    <span>Shared</span>
+                  </button>
+                </TooltipTrigger>
+                <TooltipContent side="bottom" className="text-xs">Share links and their expiry</TooltipContent>
+              </Tooltip>
+            )}
+            {onTrash && (
+              <Tooltip>
+                <TooltipTrigger asChild>
+                  <button
+                    type="button"
+                    onClick={onTrash}
+                    className="flex items-center gap-1.5 rounded-md px-2 py-1 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+                    aria-label="Open trash"
+                  >
+                    <ArchiveRestore className="h-3.5 w-3.5" />
+                    <span>Trash</span>
+                  </button>
+                </TooltipTrigger>
+                <TooltipContent side="bottom" className="text-xs">Restore deleted objects</TooltipContent>
+              </Tooltip>
+            )}
+            {onLifecycle && (
+              <Tooltip>
+                <TooltipTrigger asChild>
+                  <button
+                    type="button"
+                    onClick={onLifecycle}
+                    className="flex items-center gap-1.5 rounded-md px-2 py-1 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+                    aria-label="Edit lifecycle rules"
+                  >
+                    <CalendarClock className="h-3.5 w-3.5" />
+                    <span>Lifecycle</span>
+                  </button>
+                </TooltipTrigger>
+                <TooltipContent side="bottom" className="text-xs">Expire or archive objects automatically</TooltipContent>
+              </Tooltip>
+            )}
+            {onVersions && (
+              <Tooltip>
+                <TooltipTrigger asChild>
+                  <button
+                    type="button"
+                    onClick={onVersions}
+                    className="flex items-center gap-1.5 rounded-md px-2 py-1 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+                    aria-label="Browse versions"
+                  >
+                    <History className="h-3.5 w-3.5" />
+                    <span>Versions</span>
+                  </button>
+                </TooltipTrigger>
+                <TooltipContent side="bottom" className="text-xs">Older versions and deleted objects in this folder</TooltipContent>
+              </Tooltip>
+            )}
 
             {selectedCount > 0 && (
               <>
@@ -20,7 +20,7 @@ import {
   SelectTrigger,
   SelectValue,
 } from "@/components/ui/select";
-import { cloneStart, isTauriRuntime, targetBucketsList, targetsList } from "@/lib/tauri";
+import { cloneStart, isTauriRuntime, moveStart, targetBucketsList, targetsList } from "@/lib/tauri";
 import { cloneConflictPolicies } from "@/lib/constants";
 import type { CloneJob, S3BucketSummary, StorageTarget } from "@/lib/types";
 
@@ -54,6 +54,7 @@ export function CloneDialog({
   const [destPrefix, setDestPrefix] = useState("");
 
   const [conflictPolicy, setConflictPolicy] = useState("skip");
+  const [deleteSource, setDeleteSource] = useState(false);
   const [isSubmitting, setIsSubmitting] = useState(false);
 
   const isSameTarget = sourceTargetId === destTargetId && sourceTargetId !== "";
@@ -111,7 +112,7 @@ export function CloneDialog({
     if (!isValid) return;
     setIsSubmitting(true);
     try {
-      const job = await cloneStart(
+      const job = await (deleteSource ? moveStart : cloneStart)(
         sourceTargetId,
         sourceBucket,
         sourcePrefix,
@@ -120,7 +121,7 @@ export function CloneDialog({
         destPrefix,
         conflictPolicy,
       );
-      toast.success("Clone job started");
+      toast.success(deleteSource ? "Move job started" : "Clone job started");
       onCloneStarted?.(job);
       onOpenChange(false);
     } catch (err) {
@@ -137,6 +138,7 @@ export function CloneDialog({
     destBucket,
     destPrefix,
     conflictPolicy,
+    deleteSource,
     onCloneStarted,
     onOpenChange,
   ]);
@@ -151,6 +153,7 @@ export function CloneDialog({
       setDestBucket("");
       setDestPrefix("");
       setConflictPolicy("skip");
+      setDeleteSource(false);
     }
   }, [open, defaultSourceTargetId, defaultSourceBucket, defaultSourcePrefix]);
 
@@ -266,6 +269,16 @@ export function CloneDialog({
             </Select>
           </div>
 
+          <label className="flex items-center gap-2 text-xs text-muted-foreground">
+            <input
+              type="checkbox"
+              checked={deleteSource}
+              onChange={(e) => setDeleteSource(e.target.checked)}
+              className="h-3.5 w-3.5 rounded border-border"
+            />
+            Move: delete each source object once its copy is verified
+          </label>
+
           {/* Info badges */}
           {isSameTarget && (
             <div className="flex items-center gap-1.5 rounded-md bg-emerald-500/10 px-3 py-1.5 text-[11px] text-emerald-700 dark:text-emerald-400">
@@ -295,7 +308,7 @@ export function CloneDialog({
             ) : (
               <Copy className="h-3 w-3" />
             )}
-            {isSubmitting ? "Starting..." : "Start Clone"}
+            {isSubmitting ? "Starting..." : deleteSource ? "Start Move" : "Start Clone"}
           </Button>
         </div>
       </DialogContent>
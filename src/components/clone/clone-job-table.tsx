@@ -47,7 +47,7 @@ function JobRow({ job, actions }: { job: CloneJob; actions: CloneActions }) {
   const processedItems = completedItems + failedItems + skippedItems;
   const progress = totalItems > 0 ? Math.round((processedItems / totalItems) * 100) : 0;
 
-  const isActive = status === "running" || status === "enumerating";
+  const isActive = status === "running" || status === "enumerating" || status === "deleting";
   const isPaused = status === "paused";
   const isTerminal = status === "completed" || status === "failed" || status === "cancelled";
 
@@ -71,6 +71,9 @@ function JobRow({ job, actions }: { job: CloneJob; actions: CloneActions }) {
       <div className="flex min-w-0 flex-1 flex-col gap-1">
         <div className="flex items-center gap-2">
           <CloneStatusBadge status={status} />
+          {job.deleteSource && (
+            <span className="text-[10px] text-muted-foreground">move</span>
+          )}
           {job.isSameTarget && (
             <span className="text-[10px] text-muted-foreground">server-side</span>
           )}
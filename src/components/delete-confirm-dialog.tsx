@@ -14,10 +14,12 @@ interface DeleteConfirmDialogProps {
   open: boolean
   onOpenChange: (open: boolean) => void
   items: string[]
+  /** Deleted items go to the source's trash rather than being removed. */
+  toTrash?: boolean
   onConfirm: () => void
 }
 
-export function DeleteConfirmDialog({ open, onOpenChange, items, onConfirm }: DeleteConfirmDialogProps) {
+export function DeleteConfirmDialog({ open, onOpenChange, items, toTrash = false, onConfirm }: DeleteConfirmDialogProps) {
   const [isDeleting, setIsDeleting] = useState(false)
 
   const handleConfirm = async () => {
@@ -39,7 +41,9 @@ export function DeleteConfirmDialog({ open, onOpenChange, items, onConfirm }: De
             Confirm Delete
           </DialogTitle>
           <DialogDescription className="mt-0.5 text-xs">
-            This action cannot be undone. The following {items.length === 1 ? 'item' : `${items.length} items`} will be permanently deleted.
+            {toTrash
+              ? `The following ${items.length === 1 ? 'item' : `${items.length} items`} will be moved to the trash.`
+              : `This action cannot be undone. The following ${items.length === 1 ? 'item' : `${items.length} items`} will be permanently deleted.`}
           </DialogDescription>
         </DialogHeader>
 
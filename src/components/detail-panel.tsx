@@ -1,6 +1,6 @@
 'use client'
 
-import { X, Copy, ExternalLink, Tag, Shield, Clock, HardDrive, FileText, Check, Download, Share2 } from 'lucide-react'
+import { X, Copy, ExternalLink, Tag, Shield, Clock, HardDrive, FileText, Check, Download, Share2, History } from 'lucide-react'
 import { useState } from 'react'
 import { motion } from 'motion/react'
 import { toast } from 'sonner'
@@ -19,6 +19,8 @@ interface DetailPanelProps {
   onClose: () => void
   onDownload: (obj: S3Object) => void
   onPresign?: (obj: S3Object) => void
+  onEditMetadata?: (obj: S3Object) => void
+  onShowVersions?: (obj: S3Object) => void
   onNavigate?: (path: string) => void
   sizeFormat?: 'binary' | 'decimal'
   dateFormat?: 'relative' | 'absolute' | 'iso'
@@ -62,7 +64,7 @@ function DetailRow({ label, value, mono, copyable, compact }: { label: string; v
   )
 }
 
-export function DetailPanel({ object, bucketName, targetId, onClose, onDownload, onPresign, onNavigate, sizeFormat = 'binary', dateFormat = 'relative', compactMode = false }: DetailPanelProps) {
+export function DetailPanel({ object, bucketName, targetId, onClose, onDownload, onPresign, onEditMetadata, onShowVersions, onNavigate, sizeFormat = 'binary', dateFormat = 'relative', compactMode = false }: DetailPanelProps) {
   const previewCategory = object.type === 'file' ? getPreviewCategory(object.name) : null
   const s3Uri = `s3://${bucketName}/${object.key}`
   const objectUrl = `https://${bucketName}.s3.amazonaws.com/${object.key}`
@@ -186,8 +188,8 @@ export function DetailPanel({ object, bucketName, targetId, onClose, onDownload,
           <DetailRow compact={compactMode} label="ARN" value={`arn:aws:s3:::${bucketName}/${object.key}`} mono copyable />
         </div>
 
-        {/* ETag section if present */}
-        {object.etag && (
+        {/* Metadata section */}
+        {(object.etag || (object.type === 'file' && onEditMetadata)) && (
           <>
             <div className="px-4">
               <Separator />
@@ -196,8 +198,17 @@ export function DetailPanel({ object, bucketName, targetId, onClose, onDownload,
               <div className="flex items-center gap-2 px-4 py-2">
                 <Tag className="h-3.5 w-3.5 text-muted-foreground" />
                 <span className="text-[11px] font-semibold uppercase tracking-wider text-muted-foreground">Metadata</span>
+                {object.type === 'file' && onEditMetadata && (
+                  <button
+                    type="button"
+                    onClick={() => onEditMetadata(object)}
+                    className="ml-auto rounded px-1.5 py-0.5 text-[11px] text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+                  >
+                    View &amp; edit
+                  </button>
+                )}
               </div>
-              <DetailRow compact={compactMode} label="ETag (Full)" value={object.etag} mono copyable />
+              {object.etag && <DetailRow compact={compactMode} label="ETag (Full)" value={object.etag} mono copyable />}
             </div>
           </>
         )}
@@ -240,6 +251,16 @@ export function DetailPanel({ object, bucketName, targetId, onClose, onDownload,
             Share
           </button>
         )}
+        {object.type === 'file' && onShowVersions && (
+          <button
+            type="button"
+            onClick={() => onShowVersions(object)}
+            className="flex items-center justify-center gap-1.5 rounded-md border border-border bg-secondary px-3 py-2 text-xs font-medium text-secondary-foreground transition-colors hover:bg-secondary/80"
+            aria-label="Versions"
+          >
+            <History className="h-3.5 w-3.5" />
+          </button>
+        )}
         <button
           type="button"
           onClick={() => {
@@ -16,6 +16,7 @@ import {
   Info,
   Tag,
   Share2,
+  FolderInput,
   Loader2,
 } from 'lucide-react'
 import { cn } from '@/lib/utils'
@@ -50,6 +51,7 @@ interface FileTableProps {
   onDelete: (keys: string[]) => void
   onDownload: (obj: S3Object) => void
   onPresign?: (obj: S3Object) => void
+  onMove?: (obj: S3Object) => void
   doubleClickNav?: boolean
   showFileIcons?: boolean
   dateFormat?: 'relative' | 'absolute' | 'iso'
@@ -74,6 +76,7 @@ export function FileTable({
   onDelete,
   onDownload,
   onPresign,
+  onMove,
   doubleClickNav = true,
   showFileIcons = true,
   dateFormat = 'relative',
@@ -296,7 +299,7 @@ export function FileTable({
                   }}
                 >
                   {rowItems.map((obj) => (
-                    <FileContextMenu key={obj.key} obj={obj} bucketName={bucketName} onNavigate={onNavigate} onShowDetails={onShowDetails} onDelete={onDelete} onDownload={onDownload} onPresign={onPresign}>
+                    <FileContextMenu key={obj.key} obj={obj} bucketName={bucketName} onNavigate={onNavigate} onShowDetails={onShowDetails} onDelete={onDelete} onDownload={onDownload} onPresign={onPresign} onMove={onMove}>
                       <div
                         data-file-row
                         role="button"
@@ -419,7 +422,7 @@ export function FileTable({
             const obj = sortedObjects[virtualRow.index]
             const isSelected = selectedKeys.has(obj.key)
             return (
-              <FileContextMenu key={obj.key} obj={obj} bucketName={bucketName} onNavigate={onNavigate} onShowDetails={onShowDetails} onDelete={onDelete} onDownload={onDownload} onPresign={onPresign}>
+              <FileContextMenu key={obj.key} obj={obj} bucketName={bucketName} onNavigate={onNavigate} onShowDetails={onShowDetails} onDelete={onDelete} onDownload={onDownload} onPresign={onPresign} onMove={onMove}>
                 <div
                   data-file-row
                   role="row"
@@ -541,6 +544,7 @@ function FileContextMenu({
   onDelete,
   onDownload,
   onPresign,
+  onMove,
 }: {
   children: React.ReactNode
   obj: S3Object
@@ -550,6 +554,7 @@ function FileContextMenu({
   onDelete: (keys: string[]) => void
   onDownload: (obj: S3Object) => void
   onPresign?: (obj: S3Object) => void
+  onMove?: (obj: S3Object) => void
 }) {
   const s3Uri = `s3://${bucketName}/${obj.key}`
   const objectUrl = `https://${bucketName}.s3.amazonaws.com/${obj.key}`
@@ -583,6 +588,12 @@ function FileContextMenu({
             Get Presigned URL
           </ContextMenuItem>
         )}
+        {onMove && (
+          <ContextMenuItem className="gap-2 text-xs" onSelect={() => onMove(obj)}>
+            <FolderInput className="h-3.5 w-3.5" />
+            Move / Rename
+          </ContextMenuItem>
+        )}
         <ContextMenuSeparator />
         <ContextMenuSub>
           <ContextMenuSubTrigger className="gap-2 text-xs">
@@ -28,6 +28,7 @@ import { Switch } from "@/components/ui/switch";
 import { useApp } from "@/contexts/app-context";
 import { providerOptions } from "@/lib/constants";
 import { nowEpoch } from "@/lib/format";
+import { targetClientEncryptionKeyGenerate } from "@/lib/tauri";
 import { targetFormSchema, normalizeEndpoint, parseEndpointForBucket, type TargetFormValues } from "@/components/forms/target-form-schema";
 import type { StorageTarget, TargetCredentials } from "@/lib/types";
 
@@ -57,6 +58,19 @@ export function TargetFormDialog({ open, onOpenChange, editTarget }: TargetFormD
       accessKeyId: "",
       secretAccessKey: "",
       sessionToken: "",
+      encryptionMode: "none",
+      encryptionKmsKeyId: "",
+      sseCustomerKey: "",
+      clientEncryption: false,
+      encryptKeyNames: false,
+      clientEncryptionKey: "",
+      trashEnabled: false,
+      trashBucket: "",
+      trashRetentionDays: "30",
+      readOnly: false,
+      protectedPrefixes: "",
+      multipartSweep: false,
+      skipDownloadVerification: false,
     },
   });
 
@@ -79,6 +93,19 @@ export function TargetFormDialog({ open, onOpenChange, editTarget }: TargetFormD
         accessKeyId: "",
         secretAccessKey: "",
         sessionToken: "",
+        encryptionMode: editTarget.encryptionMode ?? "none",
+        encryptionKmsKeyId: editTarget.encryptionKmsKeyId ?? "",
+        sseCustomerKey: "",
+        clientEncryption: editTarget.clientEncryption,
+        encryptKeyNames: editTarget.encryptKeyNames,
+        clientEncryptionKey: "",
+        trashEnabled: editTarget.trashEnabled,
+        trashBucket: editTarget.trashBucket ?? "",
+        trashRetentionDays: String(editTarget.trashRetentionDays),
+        readOnly: editTarget.readOnly,
+        protectedPrefixes: editTarget.protectedPrefixes.join(", "),
+        multipartSweep: editTarget.multipartSweep,
+        skipDownloadVerification: editTarget.skipDownloadVerification,
       });
 
       void getTargetCredentials(editTarget.id)
@@ -87,6 +114,8 @@ export function TargetFormDialog({ open, onOpenChange, editTarget }: TargetFormD
             form.setValue("accessKeyId", creds.accessKeyId);
             form.setValue("secretAccessKey", creds.secretAccessKey);
             form.setValue("sessionToken", creds.sessionToken ?? "");
+            form.setValue("sseCustomerKey", creds.sseCustomerKey ?? "");
+            form.setValue("clientEncryptionKey", creds.clientEncryptionKey ?? "");
           }
         })
         .catch(() => toast.error("Failed to load credentials"))
@@ -106,6 +135,19 @@ export function TargetFormDialog({ open, onOpenChange, editTarget }: TargetFormD
         accessKeyId: "",
         secretAccessKey: "",
         sessionToken: "",
+        encryptionMode: "none",
+        encryptionKmsKeyId: "",
+        sseCustomerKey: "",
+        clientEncryption: false,
+        encryptKeyNames: false,
+        clientEncryptionKey: "",
+        trashEnabled: false,
+        trashBucket: "",
+        trashRetentionDays: "30",
+        readOnly: false,
+        protectedPrefixes: "",
+        multipartSweep: false,
+        skipDownloadVerification: false,
       });
       setBusy(false);
     }
@@ -127,6 +169,15 @@ export function TargetFormDialog({ open, onOpenChange, editTarget }: TargetFormD
     }
   };
 
+  const handleGenerateKey = async () => {
+    try {
+      form.setValue("clientEncryptionKey", await targetClientEncryptionKeyGenerate(), { shouldValidate: true });
+      toast.info("Key generated. Keep a copy: encrypted objects can't be read without it.");
+    } catch (err) {
+      toast.error(err instanceof Error ? err.message : "Failed to generate key");
+    }
+  };
+
   const onSubmit = async (values: TargetFormValues) => {
     setBusy(true);
     try {
@@ -156,6 +207,21 @@ export function TargetFormDialog({ open, onOpenChange, editTarget }: TargetFormD
           .map((b) => b.trim())
           .filter(Boolean),
         skipDestructiveConfirmations: values.skipDestructiveConfirmations,
+        bandwidthLimitBytesPerSec: editTarget?.bandwidthLimitBytesPerSec ?? null,
+        encryptionMode: values.encryptionMode === "none" ? null : values.encryptionMode,
+        encryptionKmsKeyId: values.encryptionMode === "sseKms" ? values.encryptionKmsKeyId.trim() || null : null,
+        clientEncryption: values.clientEncryption,
+        encryptKeyNames: values.clientEncryption && values.encryptKeyNames,
+        trashEnabled: values.trashEnabled,
+        trashBucket: values.trashEnabled ? values.trashBucket.trim() || null : null,
+        trashRetentionDays: Number(values.trashRetentionDays),
+        readOnly: values.readOnly,
+        protectedPrefixes: values.protectedPrefixes
+          .split(",")
+          .map((p) => p.trim())
+          .filter(Boolean),
+        multipartSweep: values.multipartSweep,
+        skipDownloadVerification: values.skipDownloadVerification,
         hasCredentials: true,
         updatedAt: nowEpoch(),
       };
@@ -164,6 +230,8 @@ export function TargetFormDialog({ open, onOpenChange, editTarget }: TargetFormD
         accessKeyId: values.accessKeyId,
         secretAccessKey: values.secretAccessKey,
         sessionToken: values.sessionToken?.trim() || null,
+        sseCustomerKey: values.sseCustomerKey.trim() || null,
+        clientEncryptionKey: values.clientEncryptionKey.trim() || null,
       };
 
       if (editTarget) {
@@ -353,6 +421,115 @@ export function TargetFormDialog({ open, onOpenChange, editTarget }: TargetFormD
               )}
             />
 
+            <div className="grid grid-cols-2 gap-3">
+              <FormField
+                control={form.control}
+                name="encryptionMode"
+                render={({ field }) => (
+                  <FormItem>
+                    <FormLabel>Default Encryption</FormLabel>
+                    <Select onValueChange={field.onChange} value={field.value} disabled={busy}>
+                      <FormControl>
+                        <SelectTrigger className="w-full">
+                          <SelectValue />
+                        </SelectTrigger>
+                      </FormControl>
+                      <SelectContent>
+                        <SelectItem value="none">Bucket default</SelectItem>
+                        <SelectItem value="sseS3">SSE-S3</SelectItem>
+                        <SelectItem value="sseKms">SSE-KMS</SelectItem>
+                        <SelectItem value="sseC">SSE-C</SelectItem>
+                      </SelectContent>
+                    </Select>
+                    <FormMessage />
+                  </FormItem>
+                )}
+              />
+              {form.watch("encryptionMode") === "sseKms" && (
+                <FormField
+                  control={form.control}
+                  name="encryptionKmsKeyId"
+                  render={({ field }) => (
+                    <FormItem>
+                      <FormLabel>KMS Key ID</FormLabel>
+                      <FormControl>
+                        <Input {...field} placeholder="arn:aws:kms:... (optional)" disabled={busy} />
+                      </FormControl>
+                      <FormMessage />
+                    </FormItem>
+                  )}
+                />
+              )}
+              {form.watch("encryptionMode") === "sseC" && (
+                <FormField
+                  control={form.control}
+                  name="sseCustomerKey"
+                  render={({ field }) => (
+                    <FormItem>
+                      <FormLabel>SSE-C Key</FormLabel>
+                      <FormControl>
+                        <Input {...field} type="password" placeholder="base64, 256-bit" disabled={busy} />
+                      </FormControl>
+                      <FormMessage />
+                    </FormItem>
+                  )}
+                />
+              )}
+            </div>
+
+            <div className="grid grid-cols-2 gap-3">
+              <FormField
+                control={form.control}
+                name="clientEncryption"
+                render={({ field }) => (
+                  <FormItem className="flex items-center justify-between rounded-lg border px-3 py-2">
+                    <FormLabel className="text-xs font-medium">Client-Side Encryption</FormLabel>
+                    <FormControl>
+                      <Switch checked={field.value} onCheckedChange={field.onChange} disabled={busy} />
+                    </FormControl>
+                  </FormItem>
+                )}
+              />
+              {form.watch("clientEncryption") && (
+                <FormField
+                  control={form.control}
+                  name="encryptKeyNames"
+                  render={({ field }) => (
+                    <FormItem className="flex items-center justify-between rounded-lg border px-3 py-2">
+                      <FormLabel className="text-xs font-medium">Encrypt Key Names</FormLabel>
+                      <FormControl>
+                        <Switch checked={field.value} onCheckedChange={field.onChange} disabled={busy} />
+                      </FormControl>
+                    </FormItem>
+                  )}
+                />
+              )}
+            </div>
+
+            {(form.watch("clientEncryption") || form.watch("clientEncryptionKey")) && (
+              <FormField
+                control={form.control}
+                name="clientEncryptionKey"
+                render={({ field }) => (
+                  <FormItem>
+                    <FormLabel>Client-Side Encryption Key</FormLabel>
+                    <div className="flex gap-2">
+                      <FormControl>
+                        <Input {...field} type="password" placeholder="base64, 256-bit" disabled={busy} />
+                      </FormControl>
+                      <Button type="button" variant="outline" onClick={handleGenerateKey} disabled={busy}>
+                        Generate
+                      </Button>
+                    </div>
+                    <FormDescription>
+                      Objects are encrypted before upload. Without this key they can&apos;t be read.
+                    </FormDescription>
+                    <FormMessage />
+                  </FormItem>
+                )}
+              />
+            )}
+
             <div className="grid grid-cols-2 gap-3">
               <FormField
                 control={form.control}
@@ -380,6 +557,122 @@ export function TargetFormDialog({ open, onOpenChange, editTarget }: TargetFormD
               />
             </div>
 
+            <FormField
+              control={form.control}
+              name="trashEnabled"
+              render={({ field }) => (
+                <FormItem className="flex items-center justify-between rounded-lg border px-3 py-2">
+                  <FormLabel className="text-xs font-medium">Move Deleted Objects to Trash</FormLabel>
+                  <FormControl>
+                    <Switch checked={field.value} onCheckedChange={field.onChange} disabled={busy} />
+                  </FormControl>
+                </FormItem>
+              )}
+            />
+            {form.watch("trashEnabled") && (
+              <div className="grid grid-cols-2 gap-3">
+                <FormField
+                  control={form.control}
+                  name="trashBucket"
+                  render={({ field }) => (
+                    <FormItem>
+                      <FormLabel>Trash Bucket</FormLabel>
+                      <FormControl>
+                        <Input {...field} placeholder="Same bucket (.mahzen-trash/)" disabled={busy} />
+                      </FormControl>
+                      <FormMessage />
+                    </FormItem>
+                  )}
+                />
+                <FormField
+                  control={form.control}
+                  name="trashRetentionDays"
+                  render={({ field }) => (
+                    <FormItem>
+                      <FormLabel>Purge After</FormLabel>
+                      <Select onValueChange={field.onChange} value={field.value} disabled={busy}>
+                        <FormControl>
+                          <SelectTrigger className="w-full">
+                            <SelectValue />
+                          </SelectTrigger>
+                        </FormControl>
+                        <SelectContent>
+                          <SelectItem value="7">7 days</SelectItem>
+                          <SelectItem value="30">30 days</SelectItem>
+                          <SelectItem value="90">90 days</SelectItem>
+                          <SelectItem value="0">Never</SelectItem>
+                        </SelectContent>
+                      </Select>
+                      <FormMessage />
+                    </FormItem>
+                  )}
+                />
+              </div>
+            )}
+
+            <FormField
+              control={form.control}
+              name="readOnly"
+              render={({ field }) => (
+                <FormItem className="flex items-center justify-between rounded-lg border px-3 py-2">
+                  <FormLabel className="text-xs font-medium">Read-Only</FormLabel>
+                  <FormControl>
+                    <Switch checked={field.value} onCheckedChange={field.onChange} disabled={busy} />
+                  </FormControl>
+                </FormItem>
+              )}
+            />
+            <FormField
+              control={form.control}
+              name="protectedPrefixes"
+              render={({ field }) => (
+                <FormItem>
+                  <FormLabel>Protected Prefixes (comma separated)</FormLabel>
+                  <FormControl>
+                    <Input {...field} placeholder="backups/, media/*/originals/" disabled={busy} />
+                  </FormControl>
+                  <FormDescription>
+                    Objects under these bucket/key prefixes can&apos;t be deleted, overwritten or moved. * matches within one folder.
+                  </FormDescription>
+                  <FormMessage />
+                </FormItem>
+              )}
+            />
+            <FormField
+              control={form.control}
+              name="multipartSweep"
+              render={({ field }) => (
+                <FormItem className="flex items-center justify-between rounded-lg border px-3 py-2">
+                  <div className="space-y-0.5">
+                    <FormLabel className="text-xs font-medium">Clean Up Abandoned Uploads</FormLabel>
+                    <FormDescription className="text-[11px]">
+                      Let the background sweep abort stale multipart uploads in this target&apos;s buckets.
+                    </FormDescription>
+                  </div>
+                  <FormControl>
+                    <Switch checked={field.value} onCheckedChange={field.onChange} disabled={busy} />
+                  </FormControl>
+                </FormItem>
+              )}
+            />
+            <FormField
+              control={form.control}
+              name="skipDownloadVerification"
+              render={({ field }) => (
+                <FormItem className="flex items-center justify-between rounded-lg border px-3 py-2">
+                  <div className="space-y-0.5">
+                    <FormLabel className="text-xs font-medium">Skip Download Verification</FormLabel>
+                    <FormDescription className="text-[11px]">
+                      Don&apos;t check downloads against the object&apos;s MD5, for providers whose ETags aren&apos;t MD5s.
+                    </FormDescription>
+                  </div>
+                  <FormControl>
+                    <Switch checked={field.value} onCheckedChange={field.onChange} disabled={busy} />
+                  </FormControl>
+                </FormItem>
+              )}
+            />
+
             <DialogFooter>
               <Button type="button" variant="outline" onClick={() => onOpenChange(false)} disabled={busy}>
                 Cancel
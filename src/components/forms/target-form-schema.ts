@@ -28,6 +28,28 @@ export const targetFormSchema = z.object({
   accessKeyId: z.string().min(1, "Access key is required"),
   secretAccessKey: z.string().min(1, "Secret key is required"),
   sessionToken: z.string(),
+  encryptionMode: z.enum(["none", "sseS3", "sseKms", "sseC"]),
+  encryptionKmsKeyId: z.string(),
+  sseCustomerKey: z.string(),
+  clientEncryption: z.boolean(),
+  encryptKeyNames: z.boolean(),
+  clientEncryptionKey: z.string(),
+  trashEnabled: z.boolean(),
+  trashBucket: z.string(),
+  trashRetentionDays: z.string(),
+  readOnly: z.boolean(),
+  protectedPrefixes: z.string(),
+  multipartSweep: z.boolean(),
+  skipDownloadVerification: z.boolean(),
+}).refine((values) => values.encryptionMode !== "sseC" || values.sseCustomerKey.trim().length > 0, {
+  message: "SSE-C needs a customer key",
+  path: ["sseCustomerKey"],
+}).refine((values) => !values.clientEncryption || values.clientEncryptionKey.trim().length > 0, {
+  message: "Client-side encryption needs a key",
+  path: ["clientEncryptionKey"],
+}).refine((values) => values.protectedPrefixes.split(",").every((p) => !p.trim().startsWith("/")), {
+  message: "Start each prefix with a bucket name",
+  path: ["protectedPrefixes"],
 });
 
 export type TargetFormValues = z.infer<typeof targetFormSchema>;
@@ -0,0 +1,529 @@
+'use client'
+
+import { useCallback, useEffect, useState } from 'react'
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogDescription,
+} from '@/components/ui/dialog'
+import { cn } from '@/lib/utils'
+import { AlertCircle, CalendarClock, Eye, Plus, Trash2, X } from 'lucide-react'
+import { toast } from 'sonner'
+import {
+  targetBucketLifecycleDelete,
+  targetBucketLifecycleGet,
+  targetBucketLifecyclePreview,
+  targetBucketLifecyclePut,
+  targetBucketLifecycleValidate,
+} from '@/lib/tauri'
+import { lifecycleStorageClasses } from '@/lib/constants'
+import { formatBytes, formatDate } from '@/lib/format'
+import type { LifecyclePreview, LifecycleRule, LifecycleTransition } from '@/lib/types'
+
+interface LifecycleDialogProps {
+  open: boolean
+  onOpenChange: (open: boolean) => void
+  targetId: string
+  bucketName: string
+  currentPath: string
+}
+
+const inputClass =
+  'w-full rounded-md border border-border bg-background px-2.5 py-1.5 text-xs text-foreground placeholder:text-muted-foreground focus:border-primary/50 focus:outline-none focus:ring-1 focus:ring-primary/30'
+
+const emptyRule = (prefix: string): LifecycleRule => ({
+  id: null,
+  enabled: true,
+  prefix,
+  tags: [],
+  objectSizeGreaterThan: null,
+  objectSizeLessThan: null,
+  expirationDays: null,
+  expirationDate: null,
+  expiredObjectDeleteMarker: false,
+  transitions: [],
+  noncurrentExpirationDays: null,
+  noncurrentNewerVersions: null,
+  noncurrentTransitions: [],
+  abortIncompleteUploadDays: null,
+})
+
+const toNumber = (value: string) => (value.trim() === '' ? null : Number(value))
+
+const formatTags = (rule: LifecycleRule) => rule.tags.map((t) => `${t.key}=${t.value}`).join(', ')
+
+const parseTags = (value: string) =>
+  value
+    .split(',')
+    .map((part) => part.trim())
+    .filter(Boolean)
+    .map((part) => {
+      const [key, ...rest] = part.split('=')
+      return { key: key.trim(), value: rest.join('=').trim() }
+    })
+
+const ruleLabel = (rule: LifecycleRule, index: number) => rule.id || (rule.prefix ? rule.prefix : `Rule ${index + 1}`)
+
+function NumberField({
+  id,
+  label,
+  value,
+  placeholder,
+  onChange,
+}: {
+  id: string
+  label: string
+  value: number | null
+  placeholder?: string
+  onChange: (value: number | null) => void
+}) {
+  return (
+    <div className="space-y-1">
+      <label htmlFor={id} className="text-[11px] font-medium text-muted-foreground">{label}</label>
+      <input
+        id={id}
+        type="text"
+        inputMode="numeric"
+        placeholder={placeholder ?? 'never'}
+        value={value ?? ''}
+        onChange={(e) => onChange(toNumber(e.target.value.replace(/[^0-9]/g, '')))}
+        className={inputClass}
+      />
+    </div>
+  )
+}
+
+function TransitionsField({
+  label,
+  transitions,
+  onChange,
+}: {
+  label: string
+  transitions: LifecycleTransition[]
+  onChange: (transitions: LifecycleTransition[]) => void
+}) {
+  const update = (index: number, patch: Partial<LifecycleTransition>) =>
+    onChange(transitions.map((t, i) => (i === index ? { ...t, ...patch } : t)))
+
+  return (
+    <div className="space-y-1">
+      <div className="flex items-center justify-between">
+        <span className="text-[11px] font-medium text-muted-foreground">{label}</span>
+        <button
+          type="button"
+          onClick={() => onChange([...transitions, { days: 30, date: null, storageClass: 'STANDARD_IA' }])}
+          className="flex items-center gap-1 text-[11px] text-muted-foreground transition-colors hover:text-foreground"
+        >
+          <Plus className="h-3 w-3" />
+          Add
+        </button>
+      </div>
+      {transitions.length === 0 ? (
+        <p className="text-[11px] text-muted-foreground">None</p>
+      ) : (
+        transitions.map((transition, index) => (
+          <div key={index} className="flex items-center gap-2">
+            <span className="text-[11px] text-muted-foreground">After</span>
+            <input
+              type="text"
+              inputMode="numeric"
+              aria-label="Days"
+              value={transition.days ?? ''}
+              onChange={(e) => update(index, { days: toNumber(e.target.value.replace(/[^0-9]/g, '')), date: null })}
+              className={cn(inputClass, 'w-16')}
+            />
+            <span className="text-[11px] text-muted-foreground">days to</span>
+            <select
+              aria-label="Storage class"
+              value={transition.storageClass}
+              onChange={(e) => update(index, { storageClass: e.target.value })}
+              className={cn(inputClass, 'flex-1')}
+            >
+              {lifecycleStorageClasses.map((sc) => (
+                <option key={sc.value} value={sc.value}>{sc.label}</option>
+              ))}
+            </select>
+            <button
+              type="button"
+              onClick={() => onChange(transitions.filter((_, i) => i !== index))}
+              className="rounded p-1 text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+              aria-label="Remove transition"
+            >
+              <X className="h-3 w-3" />
+            </button>
+          </div>
+        ))
+      )}
+    </div>
+  )
+}
+
+export function LifecycleDialog({ open, onOpenChange, targetId, bucketName, currentPath }: LifecycleDialogProps) {
+  const [rules, setRules] = useState<LifecycleRule[]>([])
+  const [selected, setSelected] = useState(0)
+  const [tagsText, setTagsText] = useState('')
+  const [preview, setPreview] = useState<LifecyclePreview | null>(null)
+  const [error, setError] = useState<string | null>(null)
+  const [isLoading, setIsLoading] = useState(false)
+  const [isWorking, setIsWorking] = useState(false)
+  const [confirmDelete, setConfirmDelete] = useState(false)
+
+  const rule = rules[selected] as LifecycleRule | undefined
+
+  const select = (index: number, list: LifecycleRule[] = rules) => {
+    setSelected(index)
+    setTagsText(list[index] ? formatTags(list[index]) : '')
+    setPreview(null)
+  }
+
+  const load = useCallback(async () => {
+    setIsLoading(true)
+    try {
+      const loaded = await targetBucketLifecycleGet(targetId, bucketName)
+      setRules(loaded)
+      setSelected(0)
+      setTagsText(loaded[0] ? formatTags(loaded[0]) : '')
+      setPreview(null)
+      setError(null)
+    } catch (err) {
+      toast.error('Failed to load lifecycle rules', { description: err instanceof Error ? err.message : String(err) })
+    } finally {
+      setIsLoading(false)
+    }
+  }, [targetId, bucketName])
+
+  useEffect(() => {
+    if (open) {
+      setConfirmDelete(false)
+      void load()
+    }
+  }, [open, load])
+
+  const updateRule = (patch: Partial<LifecycleRule>) => {
+    setRules((prev) => prev.map((r, i) => (i === selected ? { ...r, ...patch } : r)))
+    setPreview(null)
+    setError(null)
+  }
+
+  const handleAdd = () => {
+    const next = [...rules, emptyRule(currentPath)]
+    setRules(next)
+    select(next.length - 1, next)
+  }
+
+  const handleRemove = () => {
+    const next = rules.filter((_, i) => i !== selected)
+    setRules(next)
+    select(Math.max(0, selected - 1), next)
+  }
+
+  const handlePreview = async () => {
+    if (!rule) return
+    setIsWorking(true)
+    setError(null)
+    try {
+      setPreview(await targetBucketLifecyclePreview(targetId, bucketName, rule))
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err))
+    } finally {
+      setIsWorking(false)
+    }
+  }
+
+  const handleSave = async () => {
+    setIsWorking(true)
+    setError(null)
+    try {
+      if (rules.length === 0) {
+        await targetBucketLifecycleDelete(targetId, bucketName)
+        toast.success('Lifecycle rules removed')
+        onOpenChange(false)
+        return
+      }
+      await targetBucketLifecycleValidate(rules)
+      const saved = await targetBucketLifecyclePut(targetId, bucketName, rules)
+      setRules(saved)
+      toast.success(`Saved ${saved.length} lifecycle rule${saved.length === 1 ? '' : 's'}`)
+      onOpenChange(false)
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err))
+    } finally {
+      setIsWorking(false)
+    }
+  }
+
+  const handleDeleteAll = async () => {
+    if (!confirmDelete) {
+      setConfirmDelete(true)
+      return
+    }
+    setIsWorking(true)
+    try {
+      await targetBucketLifecycleDelete(targetId, bucketName)
+      toast.success('Lifecycle rules removed')
+      setConfirmDelete(false)
+      void load()
+    } catch (err) {
+      toast.error('Failed to remove lifecycle rules', { description: err instanceof Error ? err.message : String(err) })
+    } finally {
+      setIsWorking(false)
+    }
+  }
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-3xl gap-0 overflow-hidden p-0">
+        <DialogHeader className="border-b border-border px-6 py-4">
+          <DialogTitle className="flex items-center gap-2 text-sm font-semibold">
+            <CalendarClock className="h-4 w-4 text-primary" />
+            Lifecycle Rules
+          </DialogTitle>
+          <DialogDescription className="mt-0.5 text-xs">
+            Expire or transition objects in {bucketName} automatically
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="flex max-h-[28rem] min-h-64">
+          <div className="flex w-48 flex-shrink-0 flex-col border-r border-border">
+            <ul className="flex-1 overflow-y-auto py-1">
+              {rules.map((r, index) => (
+                <li key={index}>
+                  <button
+                    type="button"
+                    onClick={() => select(index)}
+                    className={cn(
+                      'flex w-full items-center gap-2 px-4 py-1.5 text-left text-xs transition-colors',
+                      index === selected
+                        ? 'bg-primary/10 text-primary'
+                        : 'text-muted-foreground hover:bg-secondary hover:text-foreground',
+                    )}
+                  >
+                    <span className={cn('truncate font-mono', !r.enabled && 'line-through opacity-60')}>
+                      {ruleLabel(r, index)}
+                    </span>
+                  </button>
+                </li>
+              ))}
+            </ul>
+            <button
+              type="button"
+              onClick={handleAdd}
+              disabled={isLoading}
+              className="flex items-center gap-1.5 border-t border-border px-4 py-2 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground disabled:opacity-40"
+            >
+              <Plus className="h-3.5 w-3.5" />
+              Add rule
+            </button>
+          </div>
+
+          <div className="flex-1 space-y-3 overflow-y-auto px-6 py-4">
+            {isLoading ? (
+              <p className="py-6 text-center text-xs text-muted-foreground">Loading...</p>
+            ) : !rule ? (
+              <p className="py-6 text-center text-xs text-muted-foreground">This bucket has no lifecycle rules</p>
+            ) : (
+              <>
+                <div className="grid grid-cols-2 gap-3">
+                  <div className="space-y-1">
+                    <label htmlFor="lifecycle-id" className="text-[11px] font-medium text-muted-foreground">Name</label>
+                    <input
+                      id="lifecycle-id"
+                      type="text"
+                      placeholder="generated"
+                      value={rule.id ?? ''}
+                      onChange={(e) => updateRule({ id: e.target.value || null })}
+                      className={inputClass}
+                    />
+                  </div>
+                  <div className="space-y-1">
+                    <label htmlFor="lifecycle-prefix" className="text-[11px] font-medium text-muted-foreground">Prefix</label>
+                    <input
+                      id="lifecycle-prefix"
+                      type="text"
+                      placeholder="whole bucket"
+                      value={rule.prefix}
+                      onChange={(e) => updateRule({ prefix: e.target.value })}
+                      className={cn(inputClass, 'font-mono')}
+                    />
+                  </div>
+                  <div className="col-span-2 space-y-1">
+                    <label htmlFor="lifecycle-tags" className="text-[11px] font-medium text-muted-foreground">Tags</label>
+                    <input
+                      id="lifecycle-tags"
+                      type="text"
+                      placeholder="key=value, key=value"
+                      value={tagsText}
+                      onChange={(e) => {
+                        setTagsText(e.target.value)
+                        updateRule({ tags: parseTags(e.target.value) })
+                      }}
+                      className={cn(inputClass, 'font-mono')}
+                    />
+                  </div>
+                  <NumberField
+                    id="lifecycle-min-size"
+                    label="Larger than (bytes)"
+                    placeholder="any size"
+                    value={rule.objectSizeGreaterThan}
+                    onChange={(objectSizeGreaterThan) => updateRule({ objectSizeGreaterThan })}
+                  />
+                  <NumberField
+                    id="lifecycle-max-size"
+                    label="Smaller than (bytes)"
+                    placeholder="any size"
+                    value={rule.objectSizeLessThan}
+                    onChange={(objectSizeLessThan) => updateRule({ objectSizeLessThan })}
+                  />
+                </div>
+
+                <TransitionsField
+                  label="Transitions"
+                  transitions={rule.transitions}
+                  onChange={(transitions) => updateRule({ transitions })}
+                />
+
+                <div className="grid grid-cols-2 gap-3">
+                  <NumberField
+                    id="lifecycle-expire"
+                    label="Expire after (days)"
+                    value={rule.expirationDays}
+                    onChange={(expirationDays) => updateRule({ expirationDays, expirationDate: null })}
+                  />
+                  <NumberField
+                    id="lifecycle-abort"
+                    label="Abort incomplete uploads after (days)"
+                    value={rule.abortIncompleteUploadDays}
+                    onChange={(abortIncompleteUploadDays) => updateRule({ abortIncompleteUploadDays })}
+                  />
+                  <NumberField
+                    id="lifecycle-noncurrent-expire"
+                    label="Expire old versions after (days)"
+                    value={rule.noncurrentExpirationDays}
+                    onChange={(noncurrentExpirationDays) => updateRule({ noncurrentExpirationDays })}
+                  />
+                  <NumberField
+                    id="lifecycle-noncurrent-keep"
+                    label="Keep newest old versions"
+                    placeholder="none"
+                    value={rule.noncurrentNewerVersions}
+                    onChange={(noncurrentNewerVersions) => updateRule({ noncurrentNewerVersions })}
+                  />
+                </div>
+
+                <TransitionsField
+                  label="Old version transitions"
+                  transitions={rule.noncurrentTransitions}
+                  onChange={(noncurrentTransitions) => updateRule({ noncurrentTransitions })}
+                />
+
+                <div className="flex items-center gap-4">
+                  <label className="flex items-center gap-2 text-xs text-muted-foreground">
+                    <input
+                      type="checkbox"
+                      checked={rule.enabled}
+                      onChange={(e) => updateRule({ enabled: e.target.checked })}
+                      className="h-3.5 w-3.5 rounded border-border"
+                    />
+                    Enabled
+                  </label>
+                  <label className="flex items-center gap-2 text-xs text-muted-foreground">
+                    <input
+                      type="checkbox"
+                      checked={rule.expiredObjectDeleteMarker}
+                      onChange={(e) => updateRule({ expiredObjectDeleteMarker: e.target.checked })}
+                      className="h-3.5 w-3.5 rounded border-border"
+                    />
+                    Remove expired delete markers
+                  </label>
+                </div>
+
+                <div className="flex items-center gap-2">
+                  <button
+                    type="button"
+                    onClick={handlePreview}
+                    disabled={isWorking}
+                    className="flex items-center gap-1.5 rounded-md border border-border px-2.5 py-1.5 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground disabled:opacity-40"
+                  >
+                    <Eye className="h-3.5 w-3.5" />
+                    Preview
+                  </button>
+                  <button
+                    type="button"
+                    onClick={handleRemove}
+                    className="flex items-center gap-1.5 rounded-md border border-border px-2.5 py-1.5 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-destructive"
+                  >
+                    <Trash2 className="h-3.5 w-3.5" />
+                    Remove rule
+                  </button>
+                </div>
+
+                {preview && (
+                  <div className="space-y-2 rounded-md border border-border bg-secondary/30 px-3 py-2 animate-in fade-in slide-in-from-top-1 duration-150">
+                    <p className="text-[11px] text-muted-foreground">
+                      {preview.affectedObjects} of {preview.matchedObjects} indexed object
+                      {preview.matchedObjects === 1 ? '' : 's'} ({formatBytes(preview.affectedSize)}) would be acted on today.
+                      Index built {formatDate(preview.lastIndexedAt)}.
+                    </p>
+                    {preview.objects.length > 0 && (
+                      <ul className="max-h-40 divide-y divide-border overflow-y-auto">
+                        {preview.objects.map((obj) => (
+                          <li key={obj.key} className="flex items-center justify-between gap-3 py-1">
+                            <span className="truncate font-mono text-[11px] text-foreground">{obj.key}</span>
+                            <span className="flex-shrink-0 text-[11px] text-muted-foreground">
+                              {obj.action === 'expire' ? 'Expire' : `To ${obj.transitionStorageClass}`} · {formatBytes(obj.size)}
+                            </span>
+                          </li>
+                        ))}
+                      </ul>
+                    )}
+                    {preview.truncated && (
+                      <p className="text-[11px] text-muted-foreground">Showing the first {preview.objects.length}</p>
+                    )}
+                  </div>
+                )}
+              </>
+            )}
+
+            {error && (
+              <div className="flex items-center gap-1.5 text-destructive animate-in fade-in slide-in-from-top-1 duration-150">
+                <AlertCircle className="h-3 w-3 flex-shrink-0" />
+                <p className="text-[11px]">{error}</p>
+              </div>
+            )}
+          </div>
+        </div>
+
+        <div className="flex items-center justify-between gap-2 border-t border-border px-6 py-3">
+          <button
+            type="button"
+            onClick={handleDeleteAll}
+            disabled={isWorking || isLoading}
+            className="flex items-center gap-1.5 rounded-md border border-border px-2.5 py-1.5 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-destructive disabled:opacity-40"
+          >
+            <Trash2 className="h-3.5 w-3.5" />
+            {confirmDelete ? 'Click again to remove every rule' : 'Remove all rules'}
+          </button>
+          <div className="flex items-center gap-2">
+            <button
+              type="button"
+              onClick={() => onOpenChange(false)}
+              className="rounded-md border border-border bg-transparent px-3 py-1.5 text-xs font-medium text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+            >
+              Cancel
+            </button>
+            <button
+              type="button"
+              onClick={handleSave}
+              disabled={isWorking || isLoading}
+              className="rounded-md bg-primary px-4 py-1.5 text-xs font-medium text-primary-foreground transition-colors hover:bg-primary/90 disabled:cursor-not-allowed disabled:opacity-40"
+            >
+              Save
+            </button>
+          </div>
+        </div>
+      </DialogContent>
+    </Dialog>
+  )
+}
@@ -0,0 +1,267 @@
+'use client'
+
+import { useCallback, useEffect, useState } from 'react'
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogDescription,
+} from '@/components/ui/dialog'
+import { cn } from '@/lib/utils'
+import { AlertCircle, Plus, Tag, X } from 'lucide-react'
+import { toast } from 'sonner'
+import { targetObjectMetadataGet, targetObjectMetadataUpdate } from '@/lib/tauri'
+import { formatBytes } from '@/lib/format'
+import type { ObjectMetadata } from '@/lib/types'
+
+interface MetadataDialogProps {
+  open: boolean
+  onOpenChange: (open: boolean) => void
+  targetId: string
+  bucketName: string
+  objectKey: string
+  onUpdated?: () => void
+}
+
+// Client-side encryption envelope; kept by the backend on every edit
+const MANAGED_PREFIX = 'mahzen-cse'
+
+const inputClass =
+  'w-full rounded-md border border-border bg-background px-2.5 py-1.5 text-xs text-foreground placeholder:text-muted-foreground focus:border-primary/50 focus:outline-none focus:ring-1 focus:ring-primary/30'
+
+type Headers = {
+  contentType: string
+  cacheControl: string
+  contentDisposition: string
+  contentEncoding: string
+}
+
+const HEADER_FIELDS: { field: keyof Headers; label: string; placeholder: string }[] = [
+  { field: 'contentType', label: 'Content-Type', placeholder: 'application/octet-stream' },
+  { field: 'cacheControl', label: 'Cache-Control', placeholder: 'max-age=3600' },
+  { field: 'contentDisposition', label: 'Content-Disposition', placeholder: 'attachment' },
+  { field: 'contentEncoding', label: 'Content-Encoding', placeholder: 'gzip' },
+]
+
+function InfoRow({ label, value }: { label: string; value: string | null | undefined }) {
+  if (!value) return null
+  return (
+    <div className="flex items-baseline justify-between gap-3 py-1">
+      <span className="flex-shrink-0 text-[11px] text-muted-foreground">{label}</span>
+      <span className="truncate font-mono text-[11px] text-foreground" title={value}>{value}</span>
+    </div>
+  )
+}
+
+export function MetadataDialog({ open, onOpenChange, targetId, bucketName, objectKey, onUpdated }: MetadataDialogProps) {
+  const [details, setDetails] = useState<ObjectMetadata | null>(null)
+  const [headers, setHeaders] = useState<Headers>({ contentType: '', cacheControl: '', contentDisposition: '', contentEncoding: '' })
+  const [entries, setEntries] = useState<{ key: string; value: string }[]>([])
+  const [error, setError] = useState<string | null>(null)
+  const [isLoading, setIsLoading] = useState(false)
+  const [isSaving, setIsSaving] = useState(false)
+
+  const apply = (metadata: ObjectMetadata) => {
+    setDetails(metadata)
+    setHeaders({
+      contentType: metadata.contentType ?? '',
+      cacheControl: metadata.cacheControl ?? '',
+      contentDisposition: metadata.contentDisposition ?? '',
+      contentEncoding: metadata.contentEncoding ?? '',
+    })
+    setEntries(
+      Object.entries(metadata.metadata)
+        .filter(([key]) => !key.startsWith(MANAGED_PREFIX))
+        .sort(([a], [b]) => a.localeCompare(b))
+        .map(([key, value]) => ({ key, value })),
+    )
+  }
+
+  const load = useCallback(async () => {
+    setIsLoading(true)
+    setError(null)
+    try {
+      apply(await targetObjectMetadataGet(targetId, bucketName, objectKey))
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err))
+    } finally {
+      setIsLoading(false)
+    }
+  }, [targetId, bucketName, objectKey])
+
+  useEffect(() => {
+    if (open) void load()
+  }, [open, load])
+
+  const updateEntry = (index: number, patch: Partial<{ key: string; value: string }>) =>
+    setEntries((prev) => prev.map((e, i) => (i === index ? { ...e, ...patch } : e)))
+
+  const handleSave = async () => {
+    const keys = entries.map((e) => e.key.trim().toLowerCase()).filter(Boolean)
+    if (new Set(keys).size !== keys.length) {
+      setError('Metadata keys must be unique')
+      return
+    }
+    setIsSaving(true)
+    setError(null)
+    try {
+      const updated = await targetObjectMetadataUpdate(targetId, bucketName, objectKey, {
+        contentType: headers.contentType || null,
+        cacheControl: headers.cacheControl || null,
+        contentDisposition: headers.contentDisposition || null,
+        contentEncoding: headers.contentEncoding || null,
+        metadata: Object.fromEntries(entries.filter((e) => e.key.trim()).map((e) => [e.key, e.value])),
+      })
+      apply(updated)
+      toast.success('Metadata updated')
+      onUpdated?.()
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err))
+    } finally {
+      setIsSaving(false)
+    }
+  }
+
+  const encryption = details?.sseCustomerAlgorithm
+    ? `SSE-C (${details.sseCustomerAlgorithm})`
+    : details?.serverSideEncryption
+      ? details.sseKmsKeyId
+        ? `${details.serverSideEncryption} (${details.sseKmsKeyId})`
+        : details.serverSideEncryption
+      : null
+  const isClientEncrypted = details ? Object.keys(details.metadata).some((k) => k.startsWith(MANAGED_PREFIX)) : false
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-xl gap-0 overflow-hidden p-0">
+        <DialogHeader className="border-b border-border px-6 py-4">
+          <DialogTitle className="flex items-center gap-2 text-sm font-semibold">
+            <Tag className="h-4 w-4 text-primary" />
+            Object Metadata
+          </DialogTitle>
+          <DialogDescription className="mt-0.5 truncate font-mono text-xs">{objectKey}</DialogDescription>
+        </DialogHeader>
+
+        <div className="max-h-[28rem] space-y-4 overflow-y-auto px-6 py-4">
+          {isLoading ? (
+            <p className="py-6 text-center text-xs text-muted-foreground">Loading...</p>
+          ) : details && (
+            <>
+              <div className="divide-y divide-border rounded-md border border-border bg-secondary/30 px-3 py-1">
+                <InfoRow label="Size" value={formatBytes(details.size)} />
+                <InfoRow label="Last modified" value={details.lastModified} />
+                <InfoRow label="ETag" value={details.etag} />
+                <InfoRow label="Version" value={details.versionId} />
+                <InfoRow label="Storage class" value={details.storageClass ?? 'STANDARD'} />
+                <InfoRow label="Encryption" value={encryption} />
+                <InfoRow label="Client-side encryption" value={isClientEncrypted ? 'Yes' : null} />
+                <InfoRow label="CRC32" value={details.checksumCrc32} />
+                <InfoRow label="CRC32C" value={details.checksumCrc32c} />
+                <InfoRow label="CRC64NVME" value={details.checksumCrc64nvme} />
+                <InfoRow label="SHA-1" value={details.checksumSha1} />
+                <InfoRow label="SHA-256" value={details.checksumSha256} />
+                <InfoRow label="Parts" value={details.partsCount?.toString()} />
+                <InfoRow label="Restore" value={details.restore} />
+                <InfoRow label="Archive status" value={details.archiveStatus} />
+                <InfoRow label="Replication" value={details.replicationStatus} />
+                <InfoRow label="Object lock" value={details.objectLockMode && `${details.objectLockMode} until ${details.objectLockRetainUntil ?? '?'}`} />
+                <InfoRow label="Legal hold" value={details.objectLockLegalHold} />
+              </div>
+
+              <div className="grid grid-cols-2 gap-3">
+                {HEADER_FIELDS.map(({ field, label, placeholder }) => (
+                  <div key={field} className="space-y-1">
+                    <label htmlFor={`metadata-${field}`} className="text-[11px] font-medium text-muted-foreground">{label}</label>
+                    <input
+                      id={`metadata-${field}`}
+                      type="text"
+                      placeholder={placeholder}
+                      value={headers[field]}
+                      onChange={(e) => setHeaders((prev) => ({ ...prev, [field]: e.target.value }))}
+                      className={inputClass}
+                    />
+                  </div>
+                ))}
+              </div>
+
+              <div className="space-y-1.5">
+                <div className="flex items-center justify-between">
+                  <span className="text-[11px] font-medium text-muted-foreground">User metadata (x-amz-meta-*)</span>
+                  <button
+                    type="button"
+                    onClick={() => setEntries((prev) => [...prev, { key: '', value: '' }])}
+                    className="flex items-center gap-1 text-[11px] text-muted-foreground transition-colors hover:text-foreground"
+                  >
+                    <Plus className="h-3 w-3" />
+                    Add
+                  </button>
+                </div>
+                {entries.length === 0 ? (
+                  <p className="text-[11px] text-muted-foreground">None</p>
+                ) : (
+                  entries.map((entry, index) => (
+                    <div key={index} className="flex items-center gap-2">
+                      <input
+                        type="text"
+                        aria-label="Key"
+                        placeholder="key"
+                        value={entry.key}
+                        onChange={(e) => updateEntry(index, { key: e.target.value })}
+                        className={cn(inputClass, 'w-40 font-mono')}
+                      />
+                      <input
+                        type="text"
+                        aria-label="Value"
+                        placeholder="value"
+                        value={entry.value}
+                        onChange={(e) => updateEntry(index, { value: e.target.value })}
+                        className={cn(inputClass, 'flex-1 font-mono')}
+                      />
+                      <button
+                        type="button"
+                        onClick={() => setEntries((prev) => prev.filter((_, i) => i !== index))}
+                        className="rounded p-1 text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+                        aria-label="Remove entry"
+                      >
+                        <X className="h-3 w-3" />
+                      </button>
+                    </div>
+                  ))
+                )}
+              </div>
+            </>
+          )}
+
+          {error && (
+            <div className="flex items-center gap-1.5 text-destructive animate-in fade-in slide-in-from-top-1 duration-150">
+              <AlertCircle className="h-3 w-3 flex-shrink-0" />
+              <p className="text-[11px]">{error}</p>
+            </div>
+          )}
+        </div>
+
+        <div className="flex items-center justify-between gap-2 border-t border-border px-6 py-3">
+          <p className="text-[11px] text-muted-foreground">Saving copies the object onto itself</p>
+          <div className="flex items-center gap-2">
+            <button
+              type="button"
+              onClick={() => onOpenChange(false)}
+              className="rounded-md border border-border bg-transparent px-3 py-1.5 text-xs font-medium text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+            >
+              Close
+            </button>
+            <button
+              type="button"
+              onClick={handleSave}
+              disabled={isSaving || isLoading || !details}
+              className="rounded-md bg-primary px-4 py-1.5 text-xs font-medium text-primary-foreground transition-colors hover:bg-primary/90 disabled:cursor-not-allowed disabled:opacity-40"
+            >
+              Save
+            </button>
+          </div>
+        </div>
+      </DialogContent>
+    </Dialog>
+  )
+}
@@ -0,0 +1,165 @@
+'use client'
+
+import React, { useState, useEffect, useRef } from 'react'
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogDescription,
+} from '@/components/ui/dialog'
+import { cn } from '@/lib/utils'
+import { FolderInput, AlertCircle } from 'lucide-react'
+import { toast } from 'sonner'
+import { moveStart } from '@/lib/tauri'
+import type { CloneJob } from '@/lib/types'
+
+interface MoveDialogProps {
+  open: boolean
+  onOpenChange: (open: boolean) => void
+  targetId: string
+  bucketName: string
+  objectKey: string
+  isFolder: boolean
+  onMoveStarted?: (job: CloneJob) => void
+}
+
+export function MoveDialog({ open, onOpenChange, targetId, bucketName, objectKey, isFolder, onMoveStarted }: MoveDialogProps) {
+  const [destination, setDestination] = useState('')
+  const [overwrite, setOverwrite] = useState(false)
+  const [isStarting, setIsStarting] = useState(false)
+  const inputRef = useRef<HTMLInputElement>(null)
+
+  useEffect(() => {
+    if (open) {
+      setDestination(objectKey)
+      setOverwrite(false)
+      setIsStarting(false)
+      setTimeout(() => inputRef.current?.select(), 100)
+    }
+  }, [open, objectKey])
+
+  // Folders keep their trailing slash, objects must not end with one
+  const destKey = isFolder
+    ? destination.trim().replace(/\/*$/, '/').replace(/^\/$/, '')
+    : destination.trim()
+
+  const validate = (): string | null => {
+    if (!destination.trim()) return null
+    if (destination.startsWith('/')) return 'Path cannot start with a slash'
+    if (destination.includes('//')) return 'Path cannot contain consecutive slashes'
+    if (!isFolder && destKey.endsWith('/')) return 'Object names cannot end with a slash'
+    if (destKey === objectKey) return null
+    if (isFolder && destKey.startsWith(objectKey)) return "A folder can't be moved into itself"
+    return null
+  }
+  const error = validate()
+  const canMove = destKey !== '' && destKey !== objectKey && !error && !isStarting
+
+  const handleMove = async () => {
+    if (!canMove) return
+
+    setIsStarting(true)
+    try {
+      const job = await moveStart(
+        targetId,
+        bucketName,
+        objectKey,
+        targetId,
+        bucketName,
+        destKey,
+        overwrite ? 'overwrite' : 'skip',
+      )
+      toast.success(isFolder ? 'Folder move started' : 'Move started', {
+        description: `${objectKey} → ${destKey}`,
+      })
+      onMoveStarted?.(job)
+      onOpenChange(false)
+    } catch (err) {
+      const msg = err instanceof Error ? err.message : String(err)
+      toast.error('Failed to start move', { description: msg })
+    } finally {
+      setIsStarting(false)
+    }
+  }
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent showCloseButton={false} className="max-w-md gap-0 overflow-hidden p-0">
+        <DialogHeader className="border-b border-border px-6 py-4">
+          <DialogTitle className="flex items-center gap-2 text-sm font-semibold">
+            <FolderInput className="h-4 w-4 text-primary" />
+            {isFolder ? 'Move or Rename Folder' : 'Move or Rename'}
+          </DialogTitle>
+          <DialogDescription className="mt-0.5 text-xs">
+            <span className="font-mono text-foreground">{bucketName}/{objectKey}</span>
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="flex flex-col gap-3 px-6 py-4">
+          <div className="space-y-1.5">
+            <label htmlFor="move-destination" className="text-xs font-medium text-foreground">
+              New path
+            </label>
+            <input
+              ref={inputRef}
+              id="move-destination"
+              type="text"
+              value={destination}
+              onChange={(e) => setDestination(e.target.value)}
+              onKeyDown={(e) => {
+                if (e.key === 'Enter') handleMove()
+              }}
+              className={cn(
+                'w-full rounded-md border bg-background px-3 py-2 font-mono text-sm text-foreground placeholder:text-muted-foreground focus:outline-none focus:ring-1 transition-colors',
+                error
+                  ? 'border-destructive/50 focus:border-destructive focus:ring-destructive/30'
+                  : 'border-border focus:border-primary/50 focus:ring-primary/30',
+              )}
+            />
+            {error && (
+              <div className="flex items-center gap-1.5 text-destructive animate-in fade-in slide-in-from-top-1 duration-150">
+                <AlertCircle className="h-3 w-3 flex-shrink-0" />
+                <p className="text-[11px]">{error}</p>
+              </div>
+            )}
+          </div>
+
+          <label className="flex items-center gap-2 text-xs text-muted-foreground">
+            <input
+              type="checkbox"
+              checked={overwrite}
+              onChange={(e) => setOverwrite(e.target.checked)}
+              className="h-3.5 w-3.5 rounded border-border"
+            />
+            Overwrite objects that already exist at the new path
+          </label>
+
+          <p className="text-[11px] leading-relaxed text-muted-foreground">
+            S3 has no rename: every object is copied to the new path and only deleted once its copy is verified.
+            {isFolder && ' Large folders continue in the background and can be paused from the transfers panel.'}
+          </p>
+        </div>
+
+        <div className="flex items-center justify-end gap-2 border-t border-border px-6 py-3">
+          <button
+            type="button"
+            onClick={() => onOpenChange(false)}
+            className="rounded-md border border-border bg-transparent px-3 py-1.5 text-xs font-medium text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+          >
+            Cancel
+          </button>
+          <button
+            type="button"
+            onClick={handleMove}
+            disabled={!canMove}
+            className="flex items-center gap-1.5 rounded-md bg-primary px-4 py-1.5 text-xs font-medium text-primary-foreground transition-colors hover:bg-primary/90 disabled:cursor-not-allowed disabled:opacity-40"
+          >
+            <FolderInput className="h-3.5 w-3.5" />
+            {isStarting ? 'Starting...' : 'Move'}
+          </button>
+        </div>
+      </DialogContent>
+    </Dialog>
+  )
+}
@@ -11,7 +11,7 @@ import {
 import { cn } from '@/lib/utils'
 import { Share2, Copy, Check, AlertCircle } from 'lucide-react'
 import { toast } from 'sonner'
-import { targetObjectPresign } from '@/lib/tauri'
+import { shareLinkCreate } from '@/lib/tauri'
 
 interface PresignDialogProps {
   open: boolean
@@ -38,6 +38,8 @@ export function PresignDialog({ open, onOpenChange, targetId, bucketName, object
   const [generatedUrl, setGeneratedUrl] = useState<string | null>(null)
   const [copied, setCopied] = useState(false)
   const [error, setError] = useState<string | null>(null)
+  const [forceDownload, setForceDownload] = useState(false)
+  const [note, setNote] = useState('')
   const urlRef = useRef<HTMLTextAreaElement>(null)
 
   useEffect(() => {
@@ -49,6 +51,8 @@ export function PresignDialog({ open, onOpenChange, targetId, bucketName, object
       setGeneratedUrl(null)
       setCopied(false)
       setError(null)
+      setForceDownload(false)
+      setNote('')
     }
   }, [open])
 
@@ -60,8 +64,9 @@ export function PresignDialog({ open, onOpenChange, targetId, bucketName, object
     setIsGenerating(true)
     setError(null)
     try {
-      const url = await targetObjectPresign(targetId, bucketName, objectKey, effectiveSecs)
-      setGeneratedUrl(url)
+      const response = forceDownload ? { downloadFilename: objectName } : undefined
+      const link = await shareLinkCreate(targetId, bucketName, objectKey, effectiveSecs, undefined, response, note.trim() || undefined)
+      setGeneratedUrl(link.url)
     } catch (err) {
       const msg = err instanceof Error ? err.message : String(err)
       setError(msg)
@@ -158,6 +163,30 @@ export function PresignDialog({ open, onOpenChange, targetId, bucketName, object
             />
           </div>
 
+          {/* Response overrides */}
+          <label className="mt-3 flex items-center gap-2 text-xs text-muted-foreground">
+            <input
+              type="checkbox"
+              checked={forceDownload}
+              onChange={(e) => {
+                setForceDownload(e.target.checked)
+                setGeneratedUrl(null)
+              }}
+              className="h-3.5 w-3.5 accent-primary"
+            />
+            Force download as <span className="font-mono text-foreground">{objectName}</span>
+          </label>
+
+          {/* Note */}
+          <input
+            type="text"
+            placeholder="Note (optional), e.g. who it's for"
+            value={note}
+            onChange={(e) => setNote(e.target.value)}
+            disabled={generatedUrl !== null}
+            className="mt-3 w-full rounded-md border border-border bg-background px-3 py-2 text-sm text-foreground placeholder:text-muted-foreground focus:border-primary/50 focus:outline-none focus:ring-1 focus:ring-primary/30"
+          />
+
           {/* Summary */}
           {effectiveSecs > 0 && (
             <div className="mt-3 rounded-md border border-border/50 bg-secondary/30 px-3 py-2 animate-in fade-in slide-in-from-top-1 duration-150">
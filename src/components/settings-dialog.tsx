@@ -68,6 +68,12 @@ const DEFAULT_SETTINGS: AppSettings = {
   retryCount: 3,
   preserveTimestamps: true,
   verifyChecksum: true,
+  bandwidthLimitBytesPerSec: 0,
+  bandwidthScheduleEnabled: false,
+  bandwidthScheduleStartMinute: 540,
+  bandwidthScheduleEndMinute: 1080,
+  multipartSweepEnabled: false,
+  multipartSweepDays: 7,
 }
 
 interface SettingsDialogProps {
@@ -280,6 +286,101 @@ function TransfersTab({ settings, onUpdate }: { settings: AppSettings; onUpdate:
           </SelectContent>
         </Select>
       </SettingRow>
+      <SettingRow>
+        <SettingLabel
+          label="Clean up abandoned uploads"
+          description="Periodically abort incomplete multipart uploads on targets that opt in"
+        />
+        <Switch
+          checked={settings.multipartSweepEnabled}
+          onCheckedChange={(v) => onUpdate({ multipartSweepEnabled: v })}
+          className="h-5 w-9 [&>span]:h-4 [&>span]:w-4 data-[state=checked]:[&>span]:translate-x-4"
+        />
+      </SettingRow>
+      {settings.multipartSweepEnabled && (
+        <SettingRow>
+          <SettingLabel
+            label="Abort after"
+            description="Age at which an incomplete upload is aborted"
+          />
+          <Select value={String(settings.multipartSweepDays)} onValueChange={(v) => onUpdate({ multipartSweepDays: Number(v) })}>
+            <SelectTrigger className="h-7 w-24 border-border bg-secondary text-xs">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {['1', '3', '7', '14', '30'].map((v) => (
+                <SelectItem key={v} value={v} className="text-xs">{v === '1' ? '1 day' : `${v} days`}</SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </SettingRow>
+      )}
+
+      <Separator className="my-3" />
+
+      <h3 className="text-[10px] font-semibold uppercase tracking-wider text-muted-foreground">
+        Bandwidth
+      </h3>
+      <SettingRow>
+        <SettingLabel
+          label="Bandwidth limit"
+          description="Cap combined transfer speed across all targets"
+        />
+        <Select
+          value={String(settings.bandwidthLimitBytesPerSec)}
+          onValueChange={(v) => onUpdate({ bandwidthLimitBytesPerSec: Number(v) })}
+        >
+          <SelectTrigger className="h-7 w-28 border-border bg-secondary text-xs">
+            <SelectValue />
+          </SelectTrigger>
+          <SelectContent>
+            {[0, 1, 5, 10, 25, 50, 100].map((mb) => (
+              <SelectItem key={mb} value={String(mb * 1024 * 1024)} className="text-xs">
+                {mb === 0 ? 'Unlimited' : `${mb} MB/s`}
+              </SelectItem>
+            ))}
+          </SelectContent>
+        </Select>
+      </SettingRow>
+      <SettingRow>
+        <SettingLabel
+          label="Limit during hours only"
+          description="Only enforce bandwidth caps within a daily time window"
+        />
+        <Switch
+          checked={settings.bandwidthScheduleEnabled}
+          onCheckedChange={(v) => onUpdate({ bandwidthScheduleEnabled: v })}
+          className="h-5 w-9 [&>span]:h-4 [&>span]:w-4 data-[state=checked]:[&>span]:translate-x-4"
+        />
+      </SettingRow>
+      {settings.bandwidthScheduleEnabled && (
+        <SettingRow>
+          <SettingLabel
+            label="Limit window"
+            description="Local time when caps apply; the same start and end means all day"
+          />
+          <div className="flex items-center gap-1">
+            {(['bandwidthScheduleStartMinute', 'bandwidthScheduleEndMinute'] as const).map((field) => (
+              <Select
+                key={field}
+                value={String(settings[field])}
+                onValueChange={(v) => onUpdate({ [field]: Number(v) } as Partial<AppSettings>)}
+              >
+                <SelectTrigger className="h-7 w-20 border-border bg-secondary text-xs">
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  {Array.from({ length: 24 }, (_, h) => (
+                    <SelectItem key={h} value={String(h * 60)} className="text-xs">
+                      {`${String(h).padStart(2, '0')}:00`}
+                    </SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            ))}
+          </div>
+        </SettingRow>
+      )}
 
       <Separator className="my-3" />
 
@@ -329,7 +430,7 @@ function TransfersTab({ settings, onUpdate }: { settings: AppSettings; onUpdate:
       <SettingRow>
         <SettingLabel
           label="Preserve timestamps"
-          description="Keep original file modification timestamps on upload"
+          description="Keep file modification times on upload and download"
         />
         <Switch
           checked={settings.preserveTimestamps}
@@ -0,0 +1,210 @@
+'use client'
+
+import { useCallback, useEffect, useState } from 'react'
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogDescription,
+} from '@/components/ui/dialog'
+import { cn } from '@/lib/utils'
+import { Share2, Copy, RefreshCw, Trash2, FileDown, Info } from 'lucide-react'
+import { toast } from 'sonner'
+import {
+  shareLinksDelete,
+  shareLinksGeneratePrefix,
+  shareLinksList,
+  shareLinksPurgeExpired,
+  shareLinkRegenerate,
+} from '@/lib/tauri'
+import type { ShareLink } from '@/lib/types'
+
+interface ShareLinksDialogProps {
+  open: boolean
+  onOpenChange: (open: boolean) => void
+  targetId: string
+  bucketName: string
+  currentPath: string
+}
+
+type Status = 'active' | 'expired'
+
+const BULK_EXPIRY_SECS = 7 * 86400
+
+const formatTime = (epoch: number) => new Date(epoch * 1000).toLocaleString()
+
+export function ShareLinksDialog({ open, onOpenChange, targetId, bucketName, currentPath }: ShareLinksDialogProps) {
+  const [status, setStatus] = useState<Status>('active')
+  const [links, setLinks] = useState<ShareLink[]>([])
+  const [isLoading, setIsLoading] = useState(false)
+  const [isExporting, setIsExporting] = useState(false)
+
+  const load = useCallback(async () => {
+    setIsLoading(true)
+    try {
+      setLinks(await shareLinksList(targetId, status))
+    } catch (err) {
+      toast.error('Failed to load share links', { description: err instanceof Error ? err.message : String(err) })
+    } finally {
+      setIsLoading(false)
+    }
+  }, [targetId, status])
+
+  useEffect(() => {
+    if (open) void load()
+  }, [open, load])
+
+  const handleCopy = (link: ShareLink) => {
+    navigator.clipboard.writeText(link.url)
+    toast.success('Copied share link')
+  }
+
+  const handleRegenerate = async (link: ShareLink) => {
+    try {
+      const fresh = await shareLinkRegenerate(link.id)
+      navigator.clipboard.writeText(fresh.url)
+      toast.success('Link regenerated and copied')
+      void load()
+    } catch (err) {
+      toast.error('Failed to regenerate link', { description: err instanceof Error ? err.message : String(err) })
+    }
+  }
+
+  const handleDelete = async (link: ShareLink) => {
+    try {
+      await shareLinksDelete([link.id])
+      setLinks((prev) => prev.filter((l) => l.id !== link.id))
+    } catch (err) {
+      toast.error('Failed to remove link', { description: err instanceof Error ? err.message : String(err) })
+    }
+  }
+
+  const handlePurge = async () => {
+    try {
+      const removed = await shareLinksPurgeExpired()
+      toast.success(`Removed ${removed} expired link${removed === 1 ? '' : 's'}`)
+      void load()
+    } catch (err) {
+      toast.error('Failed to remove expired links', { description: err instanceof Error ? err.message : String(err) })
+    }
+  }
+
+  const handleExportFolder = async () => {
+    try {
+      const { save } = await import('@tauri-apps/plugin-dialog')
+      const folder = currentPath.split('/').filter(Boolean).pop() ?? bucketName
+      const destPath = await save({
+        defaultPath: `${folder}-links.csv`,
+        filters: [{ name: 'CSV', extensions: ['csv'] }],
+      })
+      if (!destPath) return
+
+      setIsExporting(true)
+      const count = await shareLinksGeneratePrefix(targetId, bucketName, currentPath, BULK_EXPIRY_SECS, destPath)
+      toast.success(`Exported ${count} share link${count === 1 ? '' : 's'}`, { description: 'Valid for 7 days' })
+      void load()
+    } catch (err) {
+      toast.error('Failed to export share links', { description: err instanceof Error ? err.message : String(err) })
+    } finally {
+      setIsExporting(false)
+    }
+  }
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-2xl gap-0 overflow-hidden p-0">
+        <DialogHeader className="border-b border-border px-6 py-4">
+          <DialogTitle className="flex items-center gap-2 text-sm font-semibold">
+            <Share2 className="h-4 w-4 text-primary" />
+            Share Links
+          </DialogTitle>
+          <DialogDescription className="mt-0.5 text-xs">Presigned links handed out from this source</DialogDescription>
+        </DialogHeader>
+
+        <div className="flex items-center justify-between gap-2 px-6 pt-4">
+          <div className="flex gap-1.5">
+            {(['active', 'expired'] as const).map((value) => (
+              <button
+                key={value}
+                type="button"
+                onClick={() => setStatus(value)}
+                className={cn(
+                  'rounded-md border px-2.5 py-1.5 text-xs font-medium capitalize transition-colors',
+                  status === value
+                    ? 'border-primary/50 bg-primary/10 text-primary'
+                    : 'border-border bg-transparent text-muted-foreground hover:bg-secondary hover:text-foreground',
+                )}
+              >
+                {value}
+              </button>
+            ))}
+          </div>
+          <div className="flex gap-1.5">
+            {status === 'expired' && links.length > 0 && (
+              <button
+                type="button"
+                onClick={handlePurge}
+                className="rounded-md border border-border px-2.5 py-1.5 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+              >
+                Remove all expired
+              </button>
+            )}
+            <button
+              type="button"
+              onClick={handleExportFolder}
+              disabled={isExporting}
+              className="flex items-center gap-1.5 rounded-md border border-border px-2.5 py-1.5 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground disabled:opacity-40"
+            >
+              <FileDown className="h-3.5 w-3.5" />
+              {isExporting ? 'Exporting...' : 'Links for this folder (CSV)'}
+            </button>
+          </div>
+        </div>
+
+        <div className="max-h-80 overflow-y-auto px-6 py-3">
+          {isLoading ? (
+            <p className="py-6 text-center text-xs text-muted-foreground">Loading...</p>
+          ) : links.length === 0 ? (
+            <p className="py-6 text-center text-xs text-muted-foreground">No {status} share links</p>
+          ) : (
+            <ul className="divide-y divide-border">
+              {links.map((link) => (
+                <li key={link.id} className="flex items-center gap-3 py-2">
+                  <div className="min-w-0 flex-1">
+                    <p className="truncate font-mono text-xs text-foreground">{link.bucket}/{link.key}</p>
+                    <p className="truncate text-[11px] text-muted-foreground">
+                      {status === 'active' ? 'Expires' : 'Expired'} {formatTime(link.expiresAt)}
+                      {link.note && <> · {link.note}</>}
+                    </p>
+                  </div>
+                  {status === 'active' && (
+                    <button type="button" onClick={() => handleCopy(link)} className="rounded-md p-1.5 text-muted-foreground hover:bg-secondary hover:text-foreground" aria-label="Copy link">
+                      <Copy className="h-3.5 w-3.5" />
+                    </button>
+                  )}
+                  <button type="button" onClick={() => handleRegenerate(link)} className="rounded-md p-1.5 text-muted-foreground hover:bg-secondary hover:text-foreground" aria-label="Regenerate link">
+                    <RefreshCw className="h-3.5 w-3.5" />
+                  </button>
+                  <button type="button" onClick={() => handleDelete(link)} className="rounded-md p-1.5 text-muted-foreground hover:bg-secondary hover:text-destructive" aria-label="Remove link">
+                    <Trash2 className="h-3.5 w-3.5" />
+                  </button>
+                </li>
+              ))}
+            </ul>
+          )}
+        </div>
+
+        <div className="flex items-start gap-2 border-t border-border bg-secondary/30 px-6 py-3 text-[11px] text-muted-foreground">
+          <Info className="mt-0.5 h-3 w-3 flex-shrink-0" />
+          <p>
+            Removing a link here doesn&apos;t revoke it: a presigned URL works until it expires. To revoke links early,
+            deactivate the access key that signed them
+            {links[0] && <> (<span className="font-mono text-foreground">{links[0].signingKeyId}</span>)</>} with your
+            provider and update this source&apos;s credentials. That invalidates every link signed with that key.
+          </p>
+        </div>
+      </DialogContent>
+    </Dialog>
+  )
+}
@@ -0,0 +1,187 @@
+'use client'
+
+import { useCallback, useEffect, useState } from 'react'
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogDescription,
+} from '@/components/ui/dialog'
+import { ArchiveRestore, Trash2 } from 'lucide-react'
+import { toast } from 'sonner'
+import { trashEmpty, trashList, trashRestore } from '@/lib/tauri'
+import { formatBytes } from '@/lib/format'
+import type { TrashEntry } from '@/lib/types'
+
+interface TrashDialogProps {
+  open: boolean
+  onOpenChange: (open: boolean) => void
+  targetId: string
+  bucketName: string
+  onRestored?: () => void
+}
+
+const formatTime = (epoch: number) => new Date(epoch * 1000).toLocaleString()
+
+export function TrashDialog({ open, onOpenChange, targetId, bucketName, onRestored }: TrashDialogProps) {
+  const [entries, setEntries] = useState<TrashEntry[]>([])
+  const [selected, setSelected] = useState<Set<string>>(new Set())
+  const [overwrite, setOverwrite] = useState(false)
+  const [isLoading, setIsLoading] = useState(false)
+  const [isWorking, setIsWorking] = useState(false)
+  const [confirmEmpty, setConfirmEmpty] = useState(false)
+
+  const load = useCallback(async () => {
+    setIsLoading(true)
+    try {
+      setEntries(await trashList(targetId, bucketName))
+      setSelected(new Set())
+    } catch (err) {
+      toast.error('Failed to load trash', { description: err instanceof Error ? err.message : String(err) })
+    } finally {
+      setIsLoading(false)
+    }
+  }, [targetId, bucketName])
+
+  useEffect(() => {
+    if (open) {
+      setOverwrite(false)
+      setConfirmEmpty(false)
+      void load()
+    }
+  }, [open, load])
+
+  const toggle = (trashKey: string) => {
+    setSelected((prev) => {
+      const next = new Set(prev)
+      if (next.has(trashKey)) next.delete(trashKey)
+      else next.add(trashKey)
+      return next
+    })
+  }
+
+  const handleRestore = async () => {
+    if (selected.size === 0) return
+    setIsWorking(true)
+    try {
+      const { restored, skipped, errors } = await trashRestore(targetId, bucketName, Array.from(selected), overwrite)
+      if (restored.length > 0) {
+        toast.success(`Restored ${restored.length} object${restored.length === 1 ? '' : 's'}`)
+        onRestored?.()
+      }
+      if (skipped.length > 0) {
+        toast.info(`${skipped.length} object${skipped.length === 1 ? '' : 's'} left in the trash`, {
+          description: 'Something already exists at the original path',
+        })
+      }
+      if (errors.length > 0) {
+        toast.error('Some objects could not be restored', { description: errors[0] })
+      }
+      void load()
+    } catch (err) {
+      toast.error('Failed to restore', { description: err instanceof Error ? err.message : String(err) })
+    } finally {
+      setIsWorking(false)
+    }
+  }
+
+  const handleEmpty = async () => {
+    if (!confirmEmpty) {
+      setConfirmEmpty(true)
+      return
+    }
+    setIsWorking(true)
+    try {
+      const { deleted, failed } = await trashEmpty(targetId, bucketName)
+      if (failed.length > 0) {
+        toast.error(`${failed.length} object${failed.length === 1 ? '' : 's'} could not be deleted`, {
+          description: `${failed[0].key}: ${failed[0].code ?? failed[0].message ?? 'Delete failed'}`,
+        })
+      } else {
+        toast.success(`Permanently deleted ${deleted.length} object${deleted.length === 1 ? '' : 's'}`)
+      }
+      setConfirmEmpty(false)
+      void load()
+    } catch (err) {
+      toast.error('Failed to empty trash', { description: err instanceof Error ? err.message : String(err) })
+    } finally {
+      setIsWorking(false)
+    }
+  }
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-2xl gap-0 overflow-hidden p-0">
+        <DialogHeader className="border-b border-border px-6 py-4">
+          <DialogTitle className="flex items-center gap-2 text-sm font-semibold">
+            <ArchiveRestore className="h-4 w-4 text-primary" />
+            Trash
+          </DialogTitle>
+          <DialogDescription className="mt-0.5 text-xs">Objects deleted from {bucketName}</DialogDescription>
+        </DialogHeader>
+
+        <div className="max-h-96 overflow-y-auto px-6 py-3">
+          {isLoading ? (
+            <p className="py-6 text-center text-xs text-muted-foreground">Loading...</p>
+          ) : entries.length === 0 ? (
+            <p className="py-6 text-center text-xs text-muted-foreground">The trash is empty</p>
+          ) : (
+            <ul className="divide-y divide-border">
+              {entries.map((entry) => (
+                <li key={entry.trashKey}>
+                  <label className="flex cursor-pointer items-center gap-3 py-2">
+                    <input
+                      type="checkbox"
+                      checked={selected.has(entry.trashKey)}
+                      onChange={() => toggle(entry.trashKey)}
+                      className="h-3.5 w-3.5 rounded border-border"
+                    />
+                    <div className="min-w-0 flex-1">
+                      <p className="truncate font-mono text-xs text-foreground">{entry.originalKey}</p>
+                      <p className="truncate text-[11px] text-muted-foreground">
+                        Deleted {formatTime(entry.deletedAt)} · {formatBytes(entry.size)}
+                      </p>
+                    </div>
+                  </label>
+                </li>
+              ))}
+            </ul>
+          )}
+        </div>
+
+        <div className="flex items-center justify-between gap-2 border-t border-border px-6 py-3">
+          <button
+            type="button"
+            onClick={handleEmpty}
+            disabled={isWorking || entries.length === 0}
+            className="flex items-center gap-1.5 rounded-md border border-border px-2.5 py-1.5 text-xs text-muted-foreground transition-colors hover:bg-secondary hover:text-destructive disabled:opacity-40"
+          >
+            <Trash2 className="h-3.5 w-3.5" />
+            {confirmEmpty ? 'Click again to delete everything for good' : 'Empty trash'}
+          </button>
+          <div className="flex items-center gap-3">
+            <label className="flex items-center gap-2 text-xs text-muted-foreground">
+              <input
+                type="checkbox"
+                checked={overwrite}
+                onChange={(e) => setOverwrite(e.target.checked)}
+                className="h-3.5 w-3.5 rounded border-border"
+              />
+              Overwrite existing
+            </label>
+            <button
+              type="button"
+              onClick={handleRestore}
+              disabled={isWorking || selected.size === 0}
+              className="flex items-center gap-1.5 rounded-md bg-primary px-4 py-1.5 text-xs font-medium text-primary-foreground transition-colors hover:bg-primary/90 disabled:cursor-not-allowed disabled:opacity-40"
+            >
+              <ArchiveRestore className="h-3.5 w-3.5" />
+              Restore {selected.size > 0 ? `(${selected.size})` : ''}
+            </button>
+          </div>
+        </div>
+      </DialogContent>
+    </Dialog>
+  )
+}
@@ -14,7 +14,8 @@ import { formatBytes } from '@/lib/s3-types'
 import { FileIcon } from '@/components/file-icon'
 import { transferStore } from '@/lib/transfer-store'
 import { toast } from 'sonner'
-import { isTauriRuntime, listDirectoryFiles } from '@/lib/tauri'
+import { batchUploadDirectoryStart, isTauriRuntime, targetUploadPlan } from '@/lib/tauri'
+import type { UploadPlan } from '@/lib/types'
 import {
   Upload,
   X,
@@ -31,6 +32,8 @@ export interface StagedFile {
   path: string
   relativePath: string
   size: number
+  /** A local folder, uploaded by a background job rather than file by file. */
+  isFolder?: boolean
 }
 
 interface UploadDialogProps {
@@ -41,11 +44,36 @@ interface UploadDialogProps {
   currentPath: string
   onUploadComplete: () => void
   initialFiles?: StagedFile[]
+  showHidden?: boolean
+  onFolderJobStarted?: (jobId: string) => void
 }
 
-export function UploadDialog({ open, onOpenChange, targetId, bucketName, currentPath, onUploadComplete, initialFiles }: UploadDialogProps) {
+const splitGlobs = (value: string) =>
+  value
+    .split(',')
+    .map((g) => g.trim())
+    .filter(Boolean)
+
+export function UploadDialog({
+  open,
+  onOpenChange,
+  targetId,
+  bucketName,
+  currentPath,
+  onUploadComplete,
+  initialFiles,
+  showHidden,
+  onFolderJobStarted,
+}: UploadDialogProps) {
   const [stagedFiles, setStagedFiles] = useState<StagedFile[]>([])
   const [isDragOver, setIsDragOver] = useState(false)
+  const [skipIdentical, setSkipIdentical] = useState(false)
+  const [plan, setPlan] = useState<UploadPlan | null>(null)
+  const [isPlanning, setIsPlanning] = useState(false)
+  const [include, setInclude] = useState('')
+  const [exclude, setExclude] = useState('')
+  const [respectIgnoreFiles, setRespectIgnoreFiles] = useState(true)
+  const [followSymlinks, setFollowSymlinks] = useState(false)
   const fileInputRef = useRef<HTMLInputElement>(null)
   const folderInputRef = useRef<HTMLInputElement>(null)
   const initialFilesApplied = useRef(false)
@@ -61,6 +89,11 @@ export function UploadDialog({ open, onOpenChange, targetId, bucketName, current
     }
   }, [open, initialFiles])
 
+  // A plan only holds for the files it was made from
+  React.useEffect(() => {
+    setPlan(null)
+  }, [stagedFiles, skipIdentical])
+
   const handleSelectFiles = useCallback(async () => {
     if (isTauriRuntime()) {
       try {
@@ -98,28 +131,18 @@ export function UploadDialog({ open, onOpenChange, targetId, bucketName, current
           const dirPath = Array.isArray(selected) ? selected[0] : selected
           const folderName = dirPath.split('/').pop() || dirPath.split('\\').pop() || dirPath
 
-          // Expand the directory into individual file entries
-          const files = await listDirectoryFiles(dirPath)
-          if (files.length === 0) {
-            toast.info('Folder is empty', { description: 'No files found in the selected folder.' })
-            return
-          }
-
-          const newFiles: StagedFile[] = files.map((f) => {
-            // Use forward slashes for S3 keys, keep folder name as prefix
-            const relativePath = `${folderName}/${f.relativePath.replace(/\\/g, '/')}`
-            const name = f.relativePath.split('/').pop() || f.relativePath.split('\\').pop() || f.relativePath
-            return {
+          // The folder is walked by the upload job, keeping its name as the prefix
+          setStagedFiles((prev) => [
+            ...prev,
+            {
               id: `${Date.now()}-${Math.random().toString(36).slice(2, 7)}`,
-              name,
-              path: f.absolutePath,
-              relativePath,
-              size: f.size,
-            }
-          })
-
-          setStagedFiles((prev) => [...prev, ...newFiles])
-          toast.success(`${files.length} file${files.length > 1 ? 's' : ''} staged from ${folderName}/`)
+              name: folderName,
+              path: dirPath,
+              relativePath: `${folderName}/`,
+              size: 0,
+              isFolder: true,
+            },
+          ])
         }
       } catch (err) {
         const msg = err instanceof Error ? err.message : String(err)
@@ -187,22 +210,101 @@ export function UploadDialog({ open, onOpenChange, targetId, bucketName, current
     setStagedFiles([])
   }, [])
 
-  const handleUpload = useCallback(() => {
+  const canSkipIdentical = isTauriRuntime() && stagedFiles.every((f) => f.path)
+  const stagedFolders = stagedFiles.filter((f) => f.isFolder)
+  const stagedPlainFiles = stagedFiles.filter((f) => !f.isFolder)
+  const needsPlan = skipIdentical && canSkipIdentical && !plan && stagedPlainFiles.length > 0
+
+  const handleUpload = useCallback(async () => {
     if (stagedFiles.length === 0) return
 
-    for (const staged of stagedFiles) {
+    // With skip identical on, the first click compares files against the
+    // bucket and shows what would be sent; the second click sends it.
+    // Folder jobs make the same comparison as they go.
+    if (needsPlan) {
+      setIsPlanning(true)
+      try {
+        setPlan(
+          await targetUploadPlan(
+            targetId,
+            bucketName,
+            stagedPlainFiles.map((f) => ({ localPath: f.path, key: currentPath + f.relativePath })),
+          ),
+        )
+      } catch (err) {
+        const msg = err instanceof Error ? err.message : String(err)
+        toast.error('Failed to compare with the bucket', { description: msg })
+      } finally {
+        setIsPlanning(false)
+      }
+      return
+    }
+
+    const unchanged = new Set(
+      (plan?.entries ?? []).filter((e) => e.status === 'unchanged').map((e) => `${e.localPath}\n${e.key}`),
+    )
+    const toUpload = stagedPlainFiles.filter((f) => !unchanged.has(`${f.path}\n${currentPath + f.relativePath}`))
+
+    for (const staged of toUpload) {
       const key = currentPath + staged.relativePath
       transferStore.addTransfer('upload', staged.name, key, bucketName, staged.size, targetId, staged.path)
     }
 
-    toast.success(`Uploading ${stagedFiles.length} file${stagedFiles.length > 1 ? 's' : ''}`, {
-      description: `To ${bucketName}/${currentPath || ''}`,
-    })
+    for (const folder of stagedFolders) {
+      try {
+        const job = await batchUploadDirectoryStart(targetId, bucketName, {
+          root: folder.path,
+          destPrefix: currentPath + folder.relativePath,
+          include: splitGlobs(include),
+          exclude: splitGlobs(exclude),
+          showHidden: showHidden ?? false,
+          respectIgnoreFiles,
+          symlinks: followSymlinks ? 'follow' : 'skip',
+          skipIdentical,
+        })
+        onFolderJobStarted?.(job.id)
+        toast.info(`Uploading ${folder.name}/ in the background`, {
+          description: `To ${bucketName}/${currentPath || ''}`,
+        })
+      } catch (err) {
+        const msg = err instanceof Error ? err.message : String(err)
+        toast.error(`Failed to upload ${folder.name}/`, { description: msg })
+      }
+    }
+
+    if (toUpload.length === 0 && stagedPlainFiles.length > 0) {
+      toast.info('Nothing to upload', { description: 'Every file is already in the bucket.' })
+    } else if (toUpload.length > 0) {
+      toast.success(`Uploading ${toUpload.length} file${toUpload.length > 1 ? 's' : ''}`, {
+        description:
+          unchanged.size > 0
+            ? `To ${bucketName}/${currentPath || ''}, skipping ${unchanged.size} unchanged`
+            : `To ${bucketName}/${currentPath || ''}`,
+      })
+    }
 
     setStagedFiles([])
     onOpenChange(false)
     onUploadComplete()
-  }, [stagedFiles, currentPath, bucketName, targetId, onOpenChange, onUploadComplete])
+  }, [
+    stagedFiles,
+    stagedFolders,
+    stagedPlainFiles,
+    needsPlan,
+    skipIdentical,
+    plan,
+    include,
+    exclude,
+    showHidden,
+    respectIgnoreFiles,
+    followSymlinks,
+    currentPath,
+    bucketName,
+    targetId,
+    onFolderJobStarted,
+    onOpenChange,
+    onUploadComplete,
+  ])
 
   const totalSize = stagedFiles.reduce((acc, f) => acc + f.size, 0)
 
@@ -210,6 +312,7 @@ export function UploadDialog({ open, onOpenChange, targetId, bucketName, current
     if (!val) {
       setStagedFiles([])
       setIsDragOver(false)
+      setPlan(null)
     }
     onOpenChange(val)
   }
@@ -322,13 +425,21 @@ export function UploadDialog({ open, onOpenChange, targetId, bucketName, current
                       key={staged.id}
                       className="group flex items-center gap-2.5 rounded-md px-2 py-1.5 transition-colors hover:bg-secondary/50"
                     >
-                      <FileIcon name={staged.name} type="file" className="h-3.5 w-3.5 flex-shrink-0" />
+                      <FileIcon
+                        name={staged.name}
+                        type={staged.isFolder ? 'folder' : 'file'}
+                        className="h-3.5 w-3.5 flex-shrink-0"
+                      />
                       <div className="flex min-w-0 flex-1 flex-col">
                         <span className="truncate text-xs font-medium text-foreground">
                           {staged.relativePath || staged.name}
                         </span>
                         <span className="truncate text-[10px] text-muted-foreground">
-                          {staged.size > 0 ? formatBytes(staged.size) : staged.path || 'File'}
+                          {staged.isFolder
+                            ? `${staged.path} · uploaded in the background`
+                            : staged.size > 0
+                              ? formatBytes(staged.size)
+                              : staged.path || 'File'}
                         </span>
                       </div>
                       <button
@@ -356,13 +467,78 @@ export function UploadDialog({ open, onOpenChange, targetId, bucketName, current
             </div>
           )}
 
+          {/* Folder filters */}
+          {stagedFolders.length > 0 && (
+            <div className="mx-6 mt-2 flex flex-col gap-2 rounded-md border border-border px-3 py-2">
+              <div className="grid grid-cols-2 gap-2">
+                <input
+                  value={include}
+                  onChange={(e) => setInclude(e.target.value)}
+                  placeholder="Include, e.g. *.jpg, docs/"
+                  className="rounded-md border border-border bg-transparent px-2 py-1 font-mono text-[11px] text-foreground placeholder:text-muted-foreground"
+                />
+                <input
+                  value={exclude}
+                  onChange={(e) => setExclude(e.target.value)}
+                  placeholder="Exclude, e.g. node_modules/, *.tmp"
+                  className="rounded-md border border-border bg-transparent px-2 py-1 font-mono text-[11px] text-foreground placeholder:text-muted-foreground"
+                />
+              </div>
+              <div className="flex items-center gap-4">
+                <label className="flex items-center gap-1.5 text-[11px] text-muted-foreground">
+                  <input
+                    type="checkbox"
+                    checked={respectIgnoreFiles}
+                    onChange={(e) => setRespectIgnoreFiles(e.target.checked)}
+                    className="h-3 w-3 rounded border-border"
+                  />
+                  Respect .gitignore
+                </label>
+                <label className="flex items-center gap-1.5 text-[11px] text-muted-foreground">
+                  <input
+                    type="checkbox"
+                    checked={followSymlinks}
+                    onChange={(e) => setFollowSymlinks(e.target.checked)}
+                    className="h-3 w-3 rounded border-border"
+                  />
+                  Follow symlinks
+                </label>
+              </div>
+            </div>
+          )}
+
+          {/* Upload plan */}
+          {plan && (
+            <div className="mx-6 mt-2 rounded-md border border-border bg-secondary/30 px-3 py-2 text-[11px] text-muted-foreground">
+              <span className="text-foreground">{plan.newCount} new</span>
+              {' · '}
+              <span className="text-foreground">{plan.changedCount} changed</span>
+              {' · '}
+              {plan.unchangedCount} unchanged
+              {plan.uploadBytes > 0 && <span className="ml-1.5">({formatBytes(plan.uploadBytes)} to send)</span>}
+            </div>
+          )}
+
           {/* Footer */}
           <div className="mt-3 flex items-center justify-between border-t border-border px-6 py-3">
-            <p className="text-[11px] text-muted-foreground">
-              {stagedFiles.length === 0
-                ? 'No files selected'
-                : `${stagedFiles.length} file${stagedFiles.length > 1 ? 's' : ''} ready`}
-            </p>
+            <div className="flex items-center gap-3">
+              <p className="text-[11px] text-muted-foreground">
+                {stagedFiles.length === 0
+                  ? 'No files selected'
+                  : `${stagedFiles.length} file${stagedFiles.length > 1 ? 's' : ''} ready`}
+              </p>
+              {canSkipIdentical && stagedFiles.length > 0 && (
+                <label className="flex items-center gap-1.5 text-[11px] text-muted-foreground">
+                  <input
+                    type="checkbox"
+                    checked={skipIdentical}
+                    onChange={(e) => setSkipIdentical(e.target.checked)}
+                    className="h-3 w-3 rounded border-border"
+                  />
+                  Skip identical files
+                </label>
+              )}
+            </div>
             <div className="flex items-center gap-2">
               <button
                 type="button"
@@ -374,11 +550,17 @@ export function UploadDialog({ open, onOpenChange, targetId, bucketName, current
               <button
                 type="button"
                 onClick={handleUpload}
-                disabled={stagedFiles.length === 0}
+                disabled={stagedFiles.length === 0 || isPlanning}
                 className="flex items-center gap-1.5 rounded-md bg-primary px-4 py-1.5 text-xs font-medium text-primary-foreground transition-colors hover:bg-primary/90 disabled:cursor-not-allowed disabled:opacity-40"
               >
                 <Upload className="h-3.5 w-3.5" />
-                Upload {stagedFiles.length > 0 ? `(${stagedFiles.length})` : ''}
+                {isPlanning
+                  ? 'Comparing...'
+                  : needsPlan
+                    ? 'Compare'
+                    : plan
+                      ? `Upload (${plan.newCount + plan.changedCount + stagedFolders.length})`
+                      : `Upload ${stagedFiles.length > 0 ? `(${stagedFiles.length})` : ''}`}
               </button>
             </div>
           </div>
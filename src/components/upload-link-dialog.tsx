@@ -0,0 +1,275 @@
+'use client'
+
+import React, { useState, useEffect } from 'react'
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogDescription,
+} from '@/components/ui/dialog'
+import { cn } from '@/lib/utils'
+import { Link2, Copy, Check, AlertCircle } from 'lucide-react'
+import { toast } from 'sonner'
+import { targetObjectPresignPut, targetPresignPost } from '@/lib/tauri'
+
+interface UploadLinkDialogProps {
+  open: boolean
+  onOpenChange: (open: boolean) => void
+  targetId: string
+  bucketName: string
+  currentPath: string
+}
+
+type LinkKind = 'form' | 'put'
+
+const PRESETS = [
+  { label: '1 hour', secs: 3600 },
+  { label: '1 day', secs: 86400 },
+  { label: '7 days', secs: 604800 },
+] as const
+
+const escapeHtml = (value: string) =>
+  value.replace(/&/g, '&amp;').replace(/"/g, '&quot;').replace(/</g, '&lt;').replace(/>/g, '&gt;')
+
+export function UploadLinkDialog({ open, onOpenChange, targetId, bucketName, currentPath }: UploadLinkDialogProps) {
+  const [kind, setKind] = useState<LinkKind>('form')
+  const [selectedSecs, setSelectedSecs] = useState<number>(86400)
+  const [maxSizeMb, setMaxSizeMb] = useState('')
+  const [contentType, setContentType] = useState('')
+  const [fileName, setFileName] = useState('')
+  const [isGenerating, setIsGenerating] = useState(false)
+  const [output, setOutput] = useState<string | null>(null)
+  const [copied, setCopied] = useState(false)
+  const [error, setError] = useState<string | null>(null)
+
+  useEffect(() => {
+    if (open) {
+      setKind('form')
+      setSelectedSecs(86400)
+      setMaxSizeMb('')
+      setContentType('')
+      setFileName('')
+      setIsGenerating(false)
+      setOutput(null)
+      setCopied(false)
+      setError(null)
+    }
+  }, [open])
+
+  const reset = () => {
+    setOutput(null)
+    setError(null)
+  }
+
+  const canGenerate = kind === 'form' || fileName.trim().length > 0
+
+  const handleGenerate = async () => {
+    if (!canGenerate) return
+
+    setIsGenerating(true)
+    setError(null)
+    try {
+      if (kind === 'form') {
+        const maxSize = maxSizeMb ? Math.round(parseFloat(maxSizeMb) * 1024 * 1024) : null
+        const post = await targetPresignPost(targetId, bucketName, currentPath, selectedSecs, maxSize, contentType.trim() || null)
+        const inputs = Object.entries(post.fields)
+          .map(([name, value]) => `  <input type="hidden" name="${escapeHtml(name)}" value="${escapeHtml(value)}">`)
+          .join('\n')
+        const typeInput = contentType.trim()
+          ? `\n  <input type="text" name="Content-Type" value="${escapeHtml(contentType.trim())}">`
+          : ''
+        setOutput(
+          `<form action="${escapeHtml(post.url)}" method="post" enctype="multipart/form-data">\n${inputs}${typeInput}\n  <input type="file" name="file">\n  <button type="submit">Upload</button>\n</form>`,
+        )
+      } else {
+        const put = await targetObjectPresignPut(targetId, bucketName, `${currentPath}${fileName.trim()}`, selectedSecs, contentType.trim() || null, null)
+        const headers = Object.entries(put.headers)
+          .map(([name, value]) => ` -H '${name}: ${value}'`)
+          .join('')
+        setOutput(`curl -X ${put.method}${headers} --upload-file <file> '${put.url}'`)
+      }
+    } catch (err) {
+      const msg = err instanceof Error ? err.message : String(err)
+      setError(msg)
+      toast.error('Failed to generate upload link', { description: msg })
+    } finally {
+      setIsGenerating(false)
+    }
+  }
+
+  const handleCopy = () => {
+    if (!output) return
+    navigator.clipboard.writeText(output)
+    setCopied(true)
+    toast.success(kind === 'form' ? 'Copied upload form' : 'Copied upload command')
+    setTimeout(() => setCopied(false), 2000)
+  }
+
+  const inputClass =
+    'w-full rounded-md border border-border bg-background px-3 py-2 text-sm text-foreground placeholder:text-muted-foreground focus:border-primary/50 focus:outline-none focus:ring-1 focus:ring-primary/30'
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent showCloseButton={false} className="max-w-md gap-0 overflow-hidden p-0">
+        <DialogHeader className="border-b border-border px-6 py-4">
+          <DialogTitle className="flex items-center gap-2 text-sm font-semibold">
+            <Link2 className="h-4 w-4 text-primary" />
+            Create Upload Link
+          </DialogTitle>
+          <DialogDescription className="mt-0.5 text-xs">
+            Let others upload into{' '}
+            <span className="font-mono text-foreground">{bucketName}/{currentPath}</span> without credentials
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="flex flex-col gap-3 px-6 py-4">
+          <div className="flex gap-1.5">
+            {([['form', 'Upload form'], ['put', 'Single file']] as const).map(([value, label]) => (
+              <button
+                key={value}
+                type="button"
+                onClick={() => {
+                  setKind(value)
+                  reset()
+                }}
+                className={cn(
+                  'rounded-md border px-2.5 py-1.5 text-xs font-medium transition-colors',
+                  kind === value
+                    ? 'border-primary/50 bg-primary/10 text-primary'
+                    : 'border-border bg-transparent text-muted-foreground hover:bg-secondary hover:text-foreground',
+                )}
+              >
+                {label}
+              </button>
+            ))}
+          </div>
+
+          <div className="space-y-1.5">
+            <label className="text-xs font-medium text-foreground">Expires in</label>
+            <div className="flex flex-wrap gap-1.5">
+              {PRESETS.map((preset) => (
+                <button
+                  key={preset.secs}
+                  type="button"
+                  onClick={() => {
+                    setSelectedSecs(preset.secs)
+                    reset()
+                  }}
+                  className={cn(
+                    'rounded-md border px-2.5 py-1.5 text-xs font-medium transition-colors',
+                    selectedSecs === preset.secs
+                      ? 'border-primary/50 bg-primary/10 text-primary'
+                      : 'border-border bg-transparent text-muted-foreground hover:bg-secondary hover:text-foreground',
+                  )}
+                >
+                  {preset.label}
+                </button>
+              ))}
+            </div>
+          </div>
+
+          {kind === 'put' && (
+            <div className="space-y-1.5">
+              <label htmlFor="upload-link-name" className="text-xs font-medium text-foreground">File name</label>
+              <input
+                id="upload-link-name"
+                type="text"
+                placeholder="report.pdf"
+                value={fileName}
+                onChange={(e) => {
+                  setFileName(e.target.value)
+                  reset()
+                }}
+                className={inputClass}
+              />
+            </div>
+          )}
+
+          <div className="grid grid-cols-2 gap-3">
+            {kind === 'form' && (
+              <div className="space-y-1.5">
+                <label htmlFor="upload-link-max" className="text-xs font-medium text-muted-foreground">Max size (MB)</label>
+                <input
+                  id="upload-link-max"
+                  type="text"
+                  inputMode="decimal"
+                  placeholder="no limit"
+                  value={maxSizeMb}
+                  onChange={(e) => {
+                    setMaxSizeMb(e.target.value.replace(/[^0-9.]/g, ''))
+                    reset()
+                  }}
+                  className={inputClass}
+                />
+              </div>
+            )}
+            <div className="space-y-1.5">
+              <label htmlFor="upload-link-type" className="text-xs font-medium text-muted-foreground">
+                {kind === 'form' ? 'Content type prefix' : 'Content type'}
+              </label>
+              <input
+                id="upload-link-type"
+                type="text"
+                placeholder={kind === 'form' ? 'e.g. image/' : 'any'}
+                value={contentType}
+                onChange={(e) => {
+                  setContentType(e.target.value)
+                  reset()
+                }}
+                className={inputClass}
+              />
+            </div>
+          </div>
+
+          {error && (
+            <div className="flex items-center gap-1.5 text-destructive animate-in fade-in slide-in-from-top-1 duration-150">
+              <AlertCircle className="h-3 w-3 flex-shrink-0" />
+              <p className="text-[11px]">{error}</p>
+            </div>
+          )}
+
+          {output && (
+            <div className="relative animate-in fade-in slide-in-from-top-1 duration-150">
+              <textarea
+                readOnly
+                value={output}
+                rows={6}
+                className="w-full resize-none rounded-md border border-border bg-secondary/30 px-3 py-2 font-mono text-[11px] text-foreground focus:outline-none"
+                onClick={(e) => (e.target as HTMLTextAreaElement).select()}
+              />
+              <button
+                type="button"
+                onClick={handleCopy}
+                className="absolute right-2 top-2 rounded-md border border-border bg-background p-1.5 text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+                aria-label="Copy"
+              >
+                {copied ? <Check className="h-3.5 w-3.5 text-emerald-400" /> : <Copy className="h-3.5 w-3.5" />}
+              </button>
+            </div>
+          )}
+        </div>
+
+        <div className="flex items-center justify-end gap-2 border-t border-border px-6 py-3">
+          <button
+            type="button"
+            onClick={() => onOpenChange(false)}
+            className="rounded-md border border-border bg-transparent px-3 py-1.5 text-xs font-medium text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground"
+          >
+            {output ? 'Done' : 'Cancel'}
+          </button>
+          {!output && (
+            <button
+              type="button"
+              onClick={handleGenerate}
+              disabled={!canGenerate || isGenerating}
+              className="flex items-center gap-1.5 rounded-md bg-primary px-4 py-1.5 text-xs font-medium text-primary-foreground transition-colors hover:bg-primary/90 disabled:cursor-not-allowed disabled:opacity-40"
+            >
+              {isGenerating ? 'Generating...' : 'Generate'}
+            </button>
+          )}
+        </div>
+      </DialogContent>
+    </Dialog>
+  )
+}
@@ -0,0 +1,264 @@
+'use client'
+
+import { useCallback, useEffect, useState } from 'react'
+import {
+  Dialog,
+  DialogContent,
+  DialogHeader,
+  DialogTitle,
+  DialogDescription,
+} from '@/components/ui/dialog'
+import { cn } from '@/lib/utils'
+import { ArchiveRestore, ChevronUp, Folder, History, RotateCcw, Trash2 } from 'lucide-react'
+import { toast } from 'sonner'
+import {
+  targetBucketVersioningGet,
+  targetObjectUndelete,
+  targetObjectVersionDelete,
+  targetObjectVersionRestore,
+  targetObjectVersionsList,
+} from '@/lib/tauri'
+import { formatBytes } from '@/lib/format'
+import type { ObjectVersion } from '@/lib/types'
+
+interface VersionsDialogProps {
+  open: boolean
+  onOpenChange: (open: boolean) => void
+  targetId: string
+  bucketName: string
+  /** Folder to browse, or the prefix of `objectKey`. */
+  currentPath: string
+  /** Show only this object's versions. */
+  objectKey?: string
+  onChanged?: () => void
+}
+
+const PAGE_SIZE = 200
+
+const formatTime = (value: string | null) => (value ? new Date(value).toLocaleString() : 'Unknown')
+
+const groupByKey = (versions: ObjectVersion[]) => {
+  const groups = new Map<string, ObjectVersion[]>()
+  for (const version of versions) {
+    const group = groups.get(version.key)
+    if (group) group.push(version)
+    else groups.set(version.key, [version])
+  }
+  return Array.from(groups.entries())
+}
+
+export function VersionsDialog({ open, onOpenChange, targetId, bucketName, currentPath, objectKey, onChanged }: VersionsDialogProps) {
+  const [prefix, setPrefix] = useState(currentPath)
+  const [versions, setVersions] = useState<ObjectVersion[]>([])
+  const [folders, setFolders] = useState<string[]>([])
+  const [markers, setMarkers] = useState<{ key: string | null; versionId: string | null } | null>(null)
+  const [versioning, setVersioning] = useState<string | null>(null)
+  const [isLoading, setIsLoading] = useState(false)
+  const [isWorking, setIsWorking] = useState(false)
+  const [confirmDelete, setConfirmDelete] = useState<string | null>(null)
+
+  const listPrefix = objectKey ?? prefix
+
+  const load = useCallback(async (marker: { key: string | null; versionId: string | null } | null) => {
+    setIsLoading(true)
+    try {
+      const page = await targetObjectVersionsList(
+        targetId, bucketName, listPrefix, PAGE_SIZE, marker?.key ?? null, marker?.versionId ?? null,
+      )
+      const pageVersions = objectKey ? page.versions.filter((v) => v.key === objectKey) : page.versions
+      setVersions((prev) => (marker ? [...prev, ...pageVersions] : pageVersions))
+      setFolders((prev) => (objectKey ? [] : marker ? [...prev, ...page.folders] : page.folders))
+      setMarkers(page.isTruncated ? { key: page.nextKeyMarker, versionId: page.nextVersionIdMarker } : null)
+    } catch (err) {
+      toast.error('Failed to list versions', { description: err instanceof Error ? err.message : String(err) })
+    } finally {
+      setIsLoading(false)
+    }
+  }, [targetId, bucketName, listPrefix, objectKey])
+
+  useEffect(() => {
+    if (open) setPrefix(currentPath)
+  }, [open, currentPath])
+
+  useEffect(() => {
+    if (!open) return
+    setConfirmDelete(null)
+    void load(null)
+  }, [open, load])
+
+  useEffect(() => {
+    if (!open) return
+    targetBucketVersioningGet(targetId, bucketName)
+      .then(setVersioning)
+      .catch(() => setVersioning(null))
+  }, [open, targetId, bucketName])
+
+  const run = async (action: () => Promise<void>, success: string) => {
+    setIsWorking(true)
+    try {
+      await action()
+      toast.success(success)
+      onChanged?.()
+      void load(null)
+    } catch (err) {
+      toast.error('Version action failed', { description: err instanceof Error ? err.message : String(err) })
+    } finally {
+      setIsWorking(false)
+    }
+  }
+
+  const handleDelete = (version: ObjectVersion) => {
+    const id = `${version.key}\u0000${version.versionId}`
+    if (confirmDelete !== id) {
+      setConfirmDelete(id)
+      return
+    }
+    setConfirmDelete(null)
+    void run(
+      () => targetObjectVersionDelete(targetId, bucketName, version.key, version.versionId, true),
+      version.isDeleteMarker ? 'Delete marker removed' : 'Version permanently deleted',
+    )
+  }
+
+  const goUp = () => {
+    const parts = prefix.split('/').filter(Boolean)
+    parts.pop()
+    setPrefix(parts.length > 0 ? `${parts.join('/')}/` : '')
+  }
+
+  const groups = groupByKey(versions)
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent className="max-w-2xl gap-0 overflow-hidden p-0">
+        <DialogHeader className="border-b border-border px-6 py-4">
+          <DialogTitle className="flex items-center gap-2 text-sm font-semibold">
+            <History className="h-4 w-4 text-primary" />
+            Versions
+          </DialogTitle>
+          <DialogDescription className="mt-0.5 truncate text-xs">
+            <span className="font-mono">{bucketName}/{listPrefix}</span>
+            {versioning !== 'Enabled' && (
+              <span> · Versioning is {versioning === 'Suspended' ? 'suspended' : 'not enabled'} on this bucket</span>
+            )}
+          </DialogDescription>
+        </DialogHeader>
+
+        <div className="max-h-[28rem] overflow-y-auto px-6 py-3">
+          {!objectKey && prefix && (
+            <button
+              type="button"
+              onClick={goUp}
+              className="flex w-full items-center gap-2 py-1.5 text-xs text-muted-foreground transition-colors hover:text-foreground"
+            >
+              <ChevronUp className="h-3.5 w-3.5" />
+              Up one level
+            </button>
+          )}
+          {folders.map((folder) => (
+            <button
+              key={folder}
+              type="button"
+              onClick={() => setPrefix(folder)}
+              className="flex w-full items-center gap-2 py-1.5 text-left text-xs text-foreground transition-colors hover:text-primary"
+            >
+              <Folder className="h-3.5 w-3.5 text-muted-foreground" />
+              <span className="truncate font-mono">{folder.slice(prefix.length)}</span>
+            </button>
+          ))}
+
+          {groups.length === 0 && folders.length === 0 && !isLoading && (
+            <p className="py-6 text-center text-xs text-muted-foreground">No versions here</p>
+          )}
+
+          <ul className="divide-y divide-border">
+            {groups.map(([key, keyVersions]) => {
+              const latest = keyVersions.find((v) => v.isLatest)
+              return (
+                <li key={key} className="py-2">
+                  <div className="flex items-center gap-2">
+                    <p className={cn('min-w-0 flex-1 truncate font-mono text-xs text-foreground', latest?.isDeleteMarker && 'line-through opacity-60')}>
+                      {objectKey ? key : key.slice(prefix.length)}
+                    </p>
+                    {latest?.isDeleteMarker && (
+                      <button
+                        type="button"
+                        onClick={() => run(() => targetObjectUndelete(targetId, bucketName, key), 'Object restored')}
+                        disabled={isWorking}
+                        className="flex items-center gap-1 rounded-md border border-border px-2 py-0.5 text-[11px] text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground disabled:opacity-40"
+                      >
+                        <ArchiveRestore className="h-3 w-3" />
+                        Undelete
+                      </button>
+                    )}
+                  </div>
+                  <ul className="mt-1 space-y-0.5">
+                    {keyVersions.map((version) => {
+                      const id = `${version.key}\u0000${version.versionId}`
+                      return (
+                        <li key={version.versionId} className="flex items-center gap-3 pl-3">
+                          <div className="min-w-0 flex-1">
+                            <p className="truncate text-[11px] text-muted-foreground">
+                              {version.isDeleteMarker ? 'Delete marker' : formatBytes(version.size)}
+                              {' · '}
+                              {formatTime(version.lastModified)}
+                              {version.storageClass && !version.isDeleteMarker ? ` · ${version.storageClass}` : ''}
+                              {version.isLatest && <span className="ml-1 text-primary">current</span>}
+                            </p>
+                            <p className="truncate font-mono text-[10px] text-muted-foreground/70">{version.versionId}</p>
+                          </div>
+                          {!version.isLatest && !version.isDeleteMarker && (
+                            <button
+                              type="button"
+                              onClick={() =>
+                                run(
+                                  () => targetObjectVersionRestore(targetId, bucketName, version.key, version.versionId),
+                                  'Version restored as current',
+                                )
+                              }
+                              disabled={isWorking}
+                              className="rounded p-1 text-muted-foreground transition-colors hover:bg-secondary hover:text-foreground disabled:opacity-40"
+                              aria-label="Restore this version"
+                              title="Make this the current version"
+                            >
+                              <RotateCcw className="h-3 w-3" />
+                            </button>
+                          )}
+                          <button
+                            type="button"
+                            onClick={() => handleDelete(version)}
+                            disabled={isWorking}
+                            className={cn(
+                              'flex items-center gap-1 rounded p-1 text-[11px] transition-colors hover:bg-secondary disabled:opacity-40',
+                              confirmDelete === id ? 'text-destructive' : 'text-muted-foreground hover:text-destructive',
+                            )}
+                            aria-label="Delete this version permanently"
+                            title="Delete this version permanently"
+                          >
+                            <Trash2 className="h-3 w-3" />
+                            {confirmDelete === id && 'Click again to delete for good'}
+                          </button>
+                        </li>
+                      )
+                    })}
+                  </ul>
+                </li>
+              )
+            })}
+          </ul>
+
+          {isLoading && <p className="py-3 text-center text-xs text-muted-foreground">Loading...</p>}
+          {markers && !isLoading && (
+            <button
+              type="button"
+              onClick={() => load(markers)}
+              className="w-full py-2 text-center text-xs text-muted-foreground transition-colors hover:text-foreground"
+            >
+              Load more
+            </button>
+          )}
+        </div>
+      </DialogContent>
+    </Dialog>
+  )
+}
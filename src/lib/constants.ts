@@ -54,3 +54,12 @@ export const shortcuts = {
   refresh: { key: "r", meta: true, label: "Cmd+R" },
   toggleTransferPanel: { key: "j", meta: true, label: "Cmd+J" },
 } as const;
+
+export const lifecycleStorageClasses = [
+  { value: "STANDARD_IA", label: "Standard-IA" },
+  { value: "ONEZONE_IA", label: "One Zone-IA" },
+  { value: "INTELLIGENT_TIERING", label: "Intelligent-Tiering" },
+  { value: "GLACIER_IR", label: "Glacier Instant Retrieval" },
+  { value: "GLACIER", label: "Glacier Flexible Retrieval" },
+  { value: "DEEP_ARCHIVE", label: "Glacier Deep Archive" },
+] as const;
@@ -4,20 +4,52 @@ import { invoke } from "@tauri-apps/api/core";
 
 import type {
   AppSettings,
+  AuditEntry,
+  AuditInitiator,
+  AuditLogFilter,
+  BatchJob,
+  BatchJobItem,
+  BucketAccessSummary,
   BucketIndexState,
   BucketStats,
   CachedBucketStats,
   CloneJob,
   CloneJobItem,
+  CorsRule,
+  DeleteObjectsResult,
   DirectoryFileEntry,
+  LifecyclePreview,
+  LifecycleRule,
+  MultipartAbortResult,
+  MultipartUploadRef,
+  MultipartUploadSummary,
+  ObjectMetadata,
+  ObjectMetadataUpdate,
+  ObjectRestore,
+  ObjectTag,
+  ObjectVersionPage,
+  PresignResponseOverrides,
+  PresignedPost,
+  PresignedRequest,
+  PublicAccessBlock,
+  RestoreTier,
   S3BucketSummary,
   S3ConnectionResult,
   S3ObjectEntry,
   S3ObjectListPage,
+  ServerSideEncryption,
+  ShareLink,
   StorageTarget,
   SyncProfile,
+  TagFilter,
+  TagJobMode,
   TargetCredentials,
   TransferQueueItem,
+  TrashEntry,
+  TrashRestoreResult,
+  UploadDirectoryParams,
+  UploadPlan,
+  UploadPlanFile,
 } from "@/lib/types";
 
 export const isTauriRuntime = (): boolean => {
@@ -40,8 +72,14 @@ export const targetCredentialsGet = (targetId: string) =>
   invokeSafe<TargetCredentials | null>("target_credentials_get", { targetId });
 export const targetCredentialsUpsert = (targetId: string, credentials: TargetCredentials) =>
   invokeSafe<void>("target_credentials_upsert", { targetId, credentials });
+export const targetClientEncryptionKeyGenerate = () =>
+  invokeSafe<string>("target_client_encryption_key_generate");
 export const targetBucketsList = (targetId: string) =>
   invokeSafe<S3BucketSummary[]>("target_buckets_list", { targetId });
+export const targetBucketCreate = (targetId: string, bucket: string, region: string | null, objectLock: boolean) =>
+  invokeSafe<void>("target_bucket_create", { targetId, bucket, region, objectLock });
+export const targetBucketDelete = (targetId: string, bucket: string) =>
+  invokeSafe<void>("target_bucket_delete", { targetId, bucket });
 export const targetConnectionTest = (targetId: string) =>
   invokeSafe<S3ConnectionResult>("target_connection_test", { targetId });
 
@@ -49,28 +87,123 @@ export const targetObjectsList = (targetId: string, bucket: string, prefix: stri
   invokeSafe<S3ObjectEntry[]>("target_objects_list", { targetId, bucket, prefix });
 export const targetObjectsListPage = (targetId: string, bucket: string, prefix: string, maxKeys: number, continuationToken: string | null) =>
   invokeSafe<S3ObjectListPage>("target_objects_list_page", { targetId, bucket, prefix, maxKeys, continuationToken });
-export const targetObjectUpload = (targetId: string, bucket: string, key: string, sourcePath: string) =>
-  invokeSafe<void>("target_object_upload", { targetId, bucket, key, sourcePath });
-export const targetObjectDownload = (targetId: string, bucket: string, key: string, destPath: string, transferId: string) =>
-  invokeSafe<void>("target_object_download", { targetId, bucket, key, destPath, transferId });
-export const targetObjectsDelete = (targetId: string, bucket: string, keys: string[]) =>
-  invokeSafe<void>("target_objects_delete", { targetId, bucket, keys });
-export const targetFolderCreate = (targetId: string, bucket: string, key: string) =>
-  invokeSafe<void>("target_folder_create", { targetId, bucket, key });
+export const targetObjectUpload = (
+  targetId: string, bucket: string, key: string, sourcePath: string,
+  encryption?: ServerSideEncryption, initiator?: AuditInitiator,
+) => invokeSafe<void>("target_object_upload", {
+  targetId, bucket, key, sourcePath, encryption: encryption ?? null, initiator: initiator ?? null,
+});
+export const targetUploadPlan = (targetId: string, bucket: string, files: UploadPlanFile[]) =>
+  invokeSafe<UploadPlan>("target_upload_plan", { targetId, bucket, files });
+export const targetObjectDownload = (targetId: string, bucket: string, key: string, destPath: string, transferId: string, versionId?: string, encryption?: ServerSideEncryption) =>
+  invokeSafe<void>("target_object_download", { targetId, bucket, key, versionId: versionId ?? null, destPath, transferId, encryption: encryption ?? null });
+export const targetObjectsDelete = (targetId: string, bucket: string, keys: string[], permanent?: boolean) =>
+  invokeSafe<DeleteObjectsResult>("target_objects_delete", { targetId, bucket, keys, permanent: permanent ?? null });
+export const targetFolderCreate = (targetId: string, bucket: string, key: string, encryption?: ServerSideEncryption) =>
+  invokeSafe<void>("target_folder_create", { targetId, bucket, key, encryption: encryption ?? null });
 export const targetBucketStats = (targetId: string, bucket: string) =>
   invokeSafe<BucketStats>("target_bucket_stats", { targetId, bucket });
 export const targetObjectsListRecursive = (targetId: string, bucket: string, prefix: string) =>
   invokeSafe<S3ObjectEntry[]>("target_objects_list_recursive", { targetId, bucket, prefix });
 export const targetObjectsDownloadZip = (targetId: string, bucket: string, keys: string[], basePrefix: string, destPath: string, transferId: string, totalSize: number) =>
   invokeSafe<number>("target_objects_download_zip", { targetId, bucket, keys, basePrefix, destPath, transferId, totalSize });
-export const targetObjectPresign = (targetId: string, bucket: string, key: string, expiresInSecs: number) =>
-  invokeSafe<string>("target_object_presign", { targetId, bucket, key, expiresInSecs });
+export const targetObjectPresign = (targetId: string, bucket: string, key: string, expiresInSecs: number, versionId?: string, encryption?: ServerSideEncryption, response?: PresignResponseOverrides) =>
+  invokeSafe<string>("target_object_presign", { targetId, bucket, key, versionId: versionId ?? null, expiresInSecs, encryption: encryption ?? null, response: response ?? null });
+export const targetObjectPresignPut = (targetId: string, bucket: string, key: string, expiresInSecs: number, contentType: string | null, contentLength: number | null, encryption?: ServerSideEncryption) =>
+  invokeSafe<PresignedRequest>("target_object_presign_put", { targetId, bucket, key, expiresInSecs, contentType, contentLength, encryption: encryption ?? null });
+export const targetPresignPost = (targetId: string, bucket: string, keyPrefix: string, expiresInSecs: number, maxSize: number | null, contentTypePrefix: string | null) =>
+  invokeSafe<PresignedPost>("target_presign_post", { targetId, bucket, keyPrefix, expiresInSecs, maxSize, contentTypePrefix });
+export const shareLinkCreate = (targetId: string, bucket: string, key: string, expiresInSecs: number, versionId?: string, response?: PresignResponseOverrides, note?: string) =>
+  invokeSafe<ShareLink>("share_link_create", { targetId, bucket, key, versionId: versionId ?? null, expiresInSecs, response: response ?? null, note: note ?? null });
+export const shareLinksList = (targetId?: string, status?: "active" | "expired") =>
+  invokeSafe<ShareLink[]>("share_links_list", { targetId: targetId ?? null, status: status ?? null });
+export const shareLinkRegenerate = (id: string) =>
+  invokeSafe<ShareLink>("share_link_regenerate", { id });
+export const shareLinksDelete = (ids: string[]) =>
+  invokeSafe<void>("share_links_delete", { ids });
+export const shareLinksPurgeExpired = () =>
+  invokeSafe<number>("share_links_purge_expired");
+export const shareLinksGeneratePrefix = (targetId: string, bucket: string, prefix: string, expiresInSecs: number, destPath: string, note?: string) =>
+  invokeSafe<number>("share_links_generate_prefix", { targetId, bucket, prefix, expiresInSecs, note: note ?? null, destPath });
+export const targetObjectMetadataGet = (targetId: string, bucket: string, key: string, encryption?: ServerSideEncryption) =>
+  invokeSafe<ObjectMetadata>("target_object_metadata_get", { targetId, bucket, key, encryption: encryption ?? null });
+export const targetObjectMetadataUpdate = (targetId: string, bucket: string, key: string, update: ObjectMetadataUpdate) =>
+  invokeSafe<ObjectMetadata>("target_object_metadata_update", { targetId, bucket, key, update });
+export const targetObjectTagsGet = (targetId: string, bucket: string, key: string) =>
+  invokeSafe<ObjectTag[]>("target_object_tags_get", { targetId, bucket, key });
+export const targetObjectTagsPut = (targetId: string, bucket: string, key: string, tags: ObjectTag[]) =>
+  invokeSafe<void>("target_object_tags_put", { targetId, bucket, key, tags });
+export const targetObjectTagsDelete = (targetId: string, bucket: string, key: string) =>
+  invokeSafe<void>("target_object_tags_delete", { targetId, bucket, key });
+export const targetBucketVersioningGet = (targetId: string, bucket: string) =>
+  invokeSafe<string | null>("target_bucket_versioning_get", { targetId, bucket });
+export const targetBucketLifecycleGet = (targetId: string, bucket: string) =>
+  invokeSafe<LifecycleRule[]>("target_bucket_lifecycle_get", { targetId, bucket });
+export const targetBucketLifecyclePut = (targetId: string, bucket: string, rules: LifecycleRule[]) =>
+  invokeSafe<LifecycleRule[]>("target_bucket_lifecycle_put", { targetId, bucket, rules });
+export const targetBucketLifecycleDelete = (targetId: string, bucket: string) =>
+  invokeSafe<void>("target_bucket_lifecycle_delete", { targetId, bucket });
+export const targetBucketLifecycleValidate = (rules: LifecycleRule[]) =>
+  invokeSafe<void>("target_bucket_lifecycle_validate", { rules });
+export const targetBucketLifecyclePreview = (targetId: string, bucket: string, rule: LifecycleRule) =>
+  invokeSafe<LifecyclePreview>("target_bucket_lifecycle_preview", { targetId, bucket, rule });
+export const targetBucketPolicyGet = (targetId: string, bucket: string) =>
+  invokeSafe<string | null>("target_bucket_policy_get", { targetId, bucket });
+export const targetBucketPolicyPut = (targetId: string, bucket: string, policy: string) =>
+  invokeSafe<void>("target_bucket_policy_put", { targetId, bucket, policy });
+export const targetBucketPolicyDelete = (targetId: string, bucket: string) =>
+  invokeSafe<void>("target_bucket_policy_delete", { targetId, bucket });
+export const targetBucketPolicyValidate = (bucket: string, policy: string) =>
+  invokeSafe<void>("target_bucket_policy_validate", { bucket, policy });
+export const targetBucketCorsGet = (targetId: string, bucket: string) =>
+  invokeSafe<CorsRule[]>("target_bucket_cors_get", { targetId, bucket });
+export const targetBucketCorsPut = (targetId: string, bucket: string, rules: CorsRule[]) =>
+  invokeSafe<void>("target_bucket_cors_put", { targetId, bucket, rules });
+export const targetBucketCorsDelete = (targetId: string, bucket: string) =>
+  invokeSafe<void>("target_bucket_cors_delete", { targetId, bucket });
+export const targetBucketCorsValidate = (rules: CorsRule[]) =>
+  invokeSafe<void>("target_bucket_cors_validate", { rules });
+export const targetBucketPublicAccessBlockGet = (targetId: string, bucket: string) =>
+  invokeSafe<PublicAccessBlock | null>("target_bucket_public_access_block_get", { targetId, bucket });
+export const targetBucketPublicAccessBlockPut = (targetId: string, bucket: string, settings: PublicAccessBlock) =>
+  invokeSafe<void>("target_bucket_public_access_block_put", { targetId, bucket, settings });
+export const targetBucketPublicAccessBlockDelete = (targetId: string, bucket: string) =>
+  invokeSafe<void>("target_bucket_public_access_block_delete", { targetId, bucket });
+export const targetBucketAccessSummary = (targetId: string, bucket: string) =>
+  invokeSafe<BucketAccessSummary>("target_bucket_access_summary", { targetId, bucket });
+export const targetBucketMultipartUploadsList = (targetId: string, bucket: string, prefix?: string) =>
+  invokeSafe<MultipartUploadSummary[]>("target_bucket_multipart_uploads_list", { targetId, bucket, prefix: prefix ?? null });
+export const targetBucketMultipartUploadsAbort = (targetId: string, bucket: string, uploads: MultipartUploadRef[]) =>
+  invokeSafe<MultipartAbortResult>("target_bucket_multipart_uploads_abort", { targetId, bucket, uploads });
+export const targetBucketMultipartUploadsAbortStale = (targetId: string, bucket: string, olderThanDays: number) =>
+  invokeSafe<MultipartAbortResult>("target_bucket_multipart_uploads_abort_stale", { targetId, bucket, olderThanDays });
+export const multipartSweepRun = () => invokeSafe<MultipartAbortResult | null>("multipart_sweep_run");
+export const targetObjectVersionsList = (
+  targetId: string, bucket: string, prefix: string, maxKeys: number,
+  keyMarker: string | null, versionIdMarker: string | null,
+) => invokeSafe<ObjectVersionPage>("target_object_versions_list", {
+  targetId, bucket, prefix, maxKeys, keyMarker, versionIdMarker,
+});
+export const targetObjectVersionRestore = (targetId: string, bucket: string, key: string, versionId: string) =>
+  invokeSafe<void>("target_object_version_restore", { targetId, bucket, key, versionId });
+export const targetObjectUndelete = (targetId: string, bucket: string, key: string) =>
+  invokeSafe<void>("target_object_undelete", { targetId, bucket, key });
+export const targetObjectRestore = (
+  targetId: string, bucket: string, key: string, versionId: string | null, days: number, tier: RestoreTier,
+) => invokeSafe<ObjectRestore>("target_object_restore", { targetId, bucket, key, versionId, days, tier });
+export const objectRestoresList = () => invokeSafe<ObjectRestore[]>("object_restores_list");
+export const objectRestoreDelete = (id: string) => invokeSafe<void>("object_restore_delete", { id });
+export const objectRestoresCheck = () => invokeSafe<number>("object_restores_check");
+export const targetObjectVersionDelete = (targetId: string, bucket: string, key: string, versionId: string, confirm: boolean) =>
+  invokeSafe<void>("target_object_version_delete", { targetId, bucket, key, versionId, confirm });
 export const bucketStatsCacheList = () =>
   invokeSafe<CachedBucketStats[]>("bucket_stats_cache_list");
 export const bucketStatsCacheUpsert = (targetId: string, bucket: string, objectCount: number, totalSize: number) =>
   invokeSafe<void>("bucket_stats_cache_upsert", { targetId, bucket, objectCount, totalSize });
 export const listDirectoryFiles = (path: string) =>
   invokeSafe<DirectoryFileEntry[]>("list_directory_files", { path });
+export const pathIsDirectory = (path: string) =>
+  invokeSafe<boolean>("path_is_directory", { path });
 
 export const syncProfilesList = () => invokeSafe<SyncProfile[]>("sync_profiles_list");
 export const syncProfilesUpsert = (profile: SyncProfile) =>
@@ -92,6 +225,15 @@ export const cloneStart = (
   sourceTargetId, sourceBucket, sourcePrefix,
   destTargetId, destBucket, destPrefix, conflictPolicy,
 });
+// Move or rename; a source prefix ending in "/" is a folder, otherwise one object
+export const moveStart = (
+  sourceTargetId: string, sourceBucket: string, sourcePrefix: string,
+  destTargetId: string, destBucket: string, destPrefix: string,
+  conflictPolicy: string,
+) => invokeSafe<CloneJob>("move_start", {
+  sourceTargetId, sourceBucket, sourcePrefix,
+  destTargetId, destBucket, destPrefix, conflictPolicy,
+});
 export const clonePause = (jobId: string) => invokeSafe<void>("clone_pause", { jobId });
 export const cloneResume = (jobId: string) => invokeSafe<void>("clone_resume", { jobId });
 export const cloneCancel = (jobId: string) => invokeSafe<void>("clone_cancel", { jobId });
@@ -102,13 +244,60 @@ export const cloneRetryFailed = (jobId: string) => invokeSafe<void>("clone_retry
 export const cloneJobItemsList = (jobId: string, statusFilter?: string, limit?: number, offset?: number) =>
   invokeSafe<CloneJobItem[]>("clone_job_items_list", { jobId, statusFilter, limit, offset });
 
+// Batch jobs
+export const batchTagStart = (
+  targetId: string, bucket: string, prefix: string, keys: string[] | null,
+  mode: TagJobMode, tags: ObjectTag[],
+) => invokeSafe<BatchJob>("batch_tag_start", { targetId, bucket, prefix, keys, mode, tags });
+export const batchEmptyBucketStart = (
+  targetId: string, bucket: string, deleteBucket: boolean, bypassGovernance: boolean, confirm: boolean,
+) => invokeSafe<BatchJob>("batch_empty_bucket_start", { targetId, bucket, deleteBucket, bypassGovernance, confirm });
+export const batchDeletePrefixStart = (
+  targetId: string, bucket: string, prefix: string, allVersions: boolean, bypassGovernance?: boolean, permanent?: boolean,
+) => invokeSafe<BatchJob>("batch_delete_prefix_start", {
+  targetId, bucket, prefix, allVersions, bypassGovernance: bypassGovernance ?? null, permanent: permanent ?? null,
+});
+export const batchStorageClassStart = (
+  targetId: string, bucket: string, prefix: string, keys: string[] | null, storageClass: string,
+) => invokeSafe<BatchJob>("batch_storage_class_start", { targetId, bucket, prefix, keys, storageClass });
+export const batchRestoreStart = (
+  targetId: string, bucket: string, prefix: string, keys: string[] | null, days: number, tier: RestoreTier,
+) => invokeSafe<BatchJob>("batch_restore_start", { targetId, bucket, prefix, keys, days, tier });
+export const batchUploadDirectoryStart = (targetId: string, bucket: string, params: UploadDirectoryParams) =>
+  invokeSafe<BatchJob>("batch_upload_directory_start", { targetId, bucket, params });
+export const batchPause = (jobId: string) => invokeSafe<void>("batch_pause", { jobId });
+export const batchResume = (jobId: string) => invokeSafe<void>("batch_resume", { jobId });
+export const batchCancel = (jobId: string) => invokeSafe<void>("batch_cancel", { jobId });
+export const batchRetryFailed = (jobId: string) => invokeSafe<void>("batch_retry_failed", { jobId });
+export const batchJobList = () => invokeSafe<BatchJob[]>("batch_job_list");
+export const batchJobGet = (jobId: string) => invokeSafe<BatchJob | null>("batch_job_get", { jobId });
+export const batchJobDelete = (jobId: string) => invokeSafe<void>("batch_job_delete", { jobId });
+export const batchJobItemsList = (jobId: string, statusFilter?: string, limit?: number, offset?: number) =>
+  invokeSafe<BatchJobItem[]>("batch_job_items_list", { jobId, statusFilter, limit, offset });
+
+// Trash
+export const trashList = (targetId: string, bucket: string) =>
+  invokeSafe<TrashEntry[]>("trash_list", { targetId, bucket });
+export const trashRestore = (targetId: string, bucket: string, trashKeys: string[], overwrite: boolean) =>
+  invokeSafe<TrashRestoreResult>("trash_restore", { targetId, bucket, trashKeys, overwrite });
+export const trashEmpty = (targetId: string, bucket: string) =>
+  invokeSafe<DeleteObjectsResult>("trash_empty", { targetId, bucket });
+export const trashPrefixStart = (targetId: string, bucket: string, prefix: string) =>
+  invokeSafe<CloneJob>("trash_prefix_start", { targetId, bucket, prefix });
+
+// Audit log
+export const auditLogList = (filter: AuditLogFilter = {}) =>
+  invokeSafe<AuditEntry[]>("audit_log_list", { filter });
+export const auditLogExport = (filter: AuditLogFilter, destPath: string) =>
+  invokeSafe<number>("audit_log_export", { filter, destPath });
+
 export const settingsGet = () => invokeSafe<AppSettings>("settings_get");
 export const settingsUpsert = (settings: AppSettings) =>
   invokeSafe<AppSettings>("settings_upsert", { settings });
 
 // Bucket Indexing
-export const indexStart = (targetId: string, bucket: string, fresh: boolean) =>
-  invokeSafe<BucketIndexState>("index_start", { targetId, bucket, fresh });
+export const indexStart = (targetId: string, bucket: string, fresh: boolean, includeTags?: boolean) =>
+  invokeSafe<BucketIndexState>("index_start", { targetId, bucket, fresh, includeTags: includeTags ?? null });
 export const indexCancel = (targetId: string, bucket: string) =>
   invokeSafe<void>("index_cancel", { targetId, bucket });
 export const indexDelete = (targetId: string, bucket: string) =>
@@ -123,5 +312,5 @@ export const indexBrowse = (
 ) => invokeSafe<S3ObjectListPage>("index_browse", {
   targetId, bucket, parentPrefix, sortField, sortDir, limit, offset,
 });
-export const indexSearch = (targetId: string, bucket: string, query: string, limit: number) =>
-  invokeSafe<S3ObjectEntry[]>("index_search", { targetId, bucket, query, limit });
+export const indexSearch = (targetId: string, bucket: string, query: string, limit: number, tags?: TagFilter[]) =>
+  invokeSafe<S3ObjectEntry[]>("index_search", { targetId, bucket, query, tags: tags ?? null, limit });
@@ -1,6 +1,7 @@
 'use client'
 
 import { useSyncExternalStore } from 'react'
+import type { AuditInitiator } from '@/lib/types'
 
 export type TransferStatus = 'queued' | 'active' | 'completed' | 'failed' | 'cancelled'
 export type TransferType = 'upload' | 'download'
@@ -21,6 +22,8 @@ export interface Transfer {
   targetId: string
   sourcePath?: string
   destPath?: string
+  /** Feature the transfer is recorded under in the audit log. */
+  initiator?: AuditInitiator
 }
 
 type Listener = () => void
@@ -85,7 +88,7 @@ async function executeTransfer(id: string) {
     const tauri = await import('@/lib/tauri')
 
     if (t.type === 'upload' && t.sourcePath) {
-      await tauri.targetObjectUpload(t.targetId, t.bucket, t.key, t.sourcePath)
+      await tauri.targetObjectUpload(t.targetId, t.bucket, t.key, t.sourcePath, undefined, t.initiator)
     } else if (t.type === 'download' && t.destPath) {
       if (t.key.startsWith('zip:')) {
         const keys = zipTransferKeys.get(id)
@@ -149,6 +152,7 @@ export const transferStore = {
     targetId: string,
     sourcePath?: string,
     destPath?: string,
+    initiator?: AuditInitiator,
   ): string {
     const id = generateId()
     const transfer: Transfer = {
@@ -165,6 +169,7 @@ export const transferStore = {
       targetId,
       sourcePath,
       destPath,
+      initiator,
     }
     transfers = [transfer, ...transfers]
     emit()
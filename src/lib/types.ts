@@ -9,6 +9,18 @@ export type StorageTarget = {
   scopedBucket: string | null;
   pinnedBuckets: string[];
   skipDestructiveConfirmations: boolean;
+  bandwidthLimitBytesPerSec: number | null;
+  encryptionMode: EncryptionMode | null;
+  encryptionKmsKeyId: string | null;
+  clientEncryption: boolean;
+  encryptKeyNames: boolean;
+  trashEnabled: boolean;
+  trashBucket: string | null;
+  trashRetentionDays: number;
+  readOnly: boolean;
+  protectedPrefixes: string[];
+  multipartSweep: boolean;
+  skipDownloadVerification: boolean;
   hasCredentials: boolean;
   updatedAt: number;
 };
@@ -17,6 +29,153 @@ export type TargetCredentials = {
   accessKeyId: string;
   secretAccessKey: string;
   sessionToken: string | null;
+  sseCustomerKey: string | null;
+  clientEncryptionKey: string | null;
+};
+
+export type EncryptionMode = "none" | "sseS3" | "sseKms" | "sseC";
+
+// Per-operation override of a target's default encryption
+export type ServerSideEncryption = {
+  mode: EncryptionMode;
+  kmsKeyId: string | null;
+  // Falls back to the target's stored SSE-C key when null
+  customerKey: string | null;
+};
+
+// Response headers baked into a presigned GET URL
+export type PresignResponseOverrides = {
+  contentType?: string | null;
+  contentDisposition?: string | null;
+  downloadFilename?: string | null;
+  cacheControl?: string | null;
+  contentLanguage?: string | null;
+  contentEncoding?: string | null;
+};
+
+export type PresignedRequest = {
+  url: string;
+  method: string;
+  headers: Record<string, string>;
+};
+
+// Presigned GET URL recorded for sharing. It stays valid until it expires
+// or the access key that signed it is deactivated.
+export type ShareLink = {
+  id: string;
+  targetId: string;
+  bucket: string;
+  key: string;
+  versionId: string | null;
+  url: string;
+  expiresInSecs: number;
+  response: PresignResponseOverrides | null;
+  signingKeyId: string;
+  note: string | null;
+  createdAt: number;
+  expiresAt: number;
+};
+
+// Incomplete multipart upload; its parts are billed until it is aborted
+export type MultipartUploadSummary = {
+  key: string;
+  uploadId: string;
+  initiated: number | null;
+  storageClass: string | null;
+  partCount: number;
+  size: number;
+};
+
+export type MultipartUploadRef = {
+  key: string;
+  uploadId: string;
+};
+
+export type MultipartAbortResult = {
+  aborted: number;
+  errors: string[];
+};
+
+export type ObjectDeleteError = {
+  key: string;
+  versionId: string | null;
+  code: string | null;
+  message: string | null;
+};
+
+export type DeleteObjectsResult = {
+  deleted: string[];
+  failed: ObjectDeleteError[];
+};
+
+export type TrashEntry = {
+  trashKey: string;
+  originalKey: string;
+  deletedAt: number;
+  size: number;
+};
+
+export type TrashRestoreResult = {
+  restored: string[];
+  skipped: string[];
+  errors: string[];
+};
+
+export type UploadPlanFile = {
+  localPath: string;
+  key: string;
+};
+
+export type UploadPlanStatus = "new" | "changed" | "unchanged";
+
+export type UploadPlanEntry = {
+  localPath: string;
+  key: string;
+  size: number;
+  status: UploadPlanStatus;
+};
+
+export type UploadPlan = {
+  entries: UploadPlanEntry[];
+  newCount: number;
+  changedCount: number;
+  unchangedCount: number;
+  uploadBytes: number;
+};
+
+export type AuditInitiator = "manual" | "clone" | "schedule";
+
+export type AuditEntry = {
+  id: string;
+  timestamp: number;
+  initiator: AuditInitiator;
+  operation: string;
+  targetId: string;
+  bucket: string | null;
+  keys: string[];
+  prefix: string | null;
+  result: "success" | "failure";
+  error: string | null;
+};
+
+export type AuditLogFilter = {
+  targetId?: string;
+  bucket?: string;
+  operation?: string;
+  initiator?: AuditInitiator;
+  result?: "success" | "failure";
+  key?: string;
+  since?: number;
+  until?: number;
+  limit?: number;
+  offset?: number;
+};
+
+// S3 POST policy form: post `fields` plus a `file` field to `url`
+export type PresignedPost = {
+  url: string;
+  fields: Record<string, string>;
+  expiresAt: string;
 };
 
 export type S3BucketSummary = {
@@ -59,6 +218,7 @@ export type SidebarBucket = {
   targetId: string;
   targetName: string;
   provider: string;
+  trashEnabled: boolean;
   objectCount: number | null;
   totalSize: number | null;
 };
@@ -94,6 +254,75 @@ export type S3ObjectListPage = {
   isTruncated: boolean;
 };
 
+export type ObjectMetadata = {
+  key: string;
+  size: number;
+  lastModified: string | null;
+  etag: string | null;
+  versionId: string | null;
+  storageClass: string | null;
+  contentType: string | null;
+  cacheControl: string | null;
+  contentDisposition: string | null;
+  contentEncoding: string | null;
+  contentLanguage: string | null;
+  expires: string | null;
+  metadata: Record<string, string>;
+  serverSideEncryption: string | null;
+  sseKmsKeyId: string | null;
+  sseCustomerAlgorithm: string | null;
+  bucketKeyEnabled: boolean | null;
+  checksumCrc32: string | null;
+  checksumCrc32c: string | null;
+  checksumCrc64nvme: string | null;
+  checksumSha1: string | null;
+  checksumSha256: string | null;
+  partsCount: number | null;
+  restore: string | null;
+  archiveStatus: string | null;
+  replicationStatus: string | null;
+  objectLockMode: string | null;
+  objectLockRetainUntil: string | null;
+  objectLockLegalHold: string | null;
+};
+
+export type ObjectMetadataUpdate = {
+  contentType: string | null;
+  cacheControl: string | null;
+  contentDisposition: string | null;
+  contentEncoding: string | null;
+  metadata: Record<string, string>;
+};
+
+export type ObjectVersion = {
+  key: string;
+  versionId: string;
+  isLatest: boolean;
+  isDeleteMarker: boolean;
+  size: number;
+  lastModified: string | null;
+  etag: string | null;
+  storageClass: string | null;
+};
+
+export type ObjectVersionPage = {
+  versions: ObjectVersion[];
+  folders: string[];
+  nextKeyMarker: string | null;
+  nextVersionIdMarker: string | null;
+  isTruncated: boolean;
+};
+
+export type ObjectTag = {
+  key: string;
+  value: string;
+};
+
+export type TagFilter = {
+  key: string;
+  value: string | null;
+};
+
 export type AppSettings = {
   theme: string;
   fontSize: number;
@@ -115,6 +344,12 @@ export type AppSettings = {
   retryCount: number;
   preserveTimestamps: boolean;
   verifyChecksum: boolean;
+  bandwidthLimitBytesPerSec: number;
+  bandwidthScheduleEnabled: boolean;
+  bandwidthScheduleStartMinute: number;
+  bandwidthScheduleEndMinute: number;
+  multipartSweepEnabled: boolean;
+  multipartSweepDays: number;
 };
 
 export type CloneConflictPolicy = "skip" | "overwrite" | "overwriteIfNewer";
@@ -123,6 +358,7 @@ export type CloneJobStatus =
   | "pending"
   | "enumerating"
   | "running"
+  | "deleting"
   | "paused"
   | "completed"
   | "failed"
@@ -150,6 +386,9 @@ export type CloneJob = {
   createdAt: number;
   updatedAt: number;
   completedAt: number | null;
+  // A move: sources are deleted once their copies are verified
+  deleteSource: boolean;
+  initiator: AuditInitiator;
 };
 
 export type CloneJobItem = {
@@ -160,7 +399,7 @@ export type CloneJobItem = {
   size: number;
   sourceEtag: string | null;
   sourceLastModified: string | null;
-  status: "pending" | "active" | "completed" | "skipped" | "failed";
+  status: "pending" | "active" | "copied" | "completed" | "skipped" | "failed";
   errorMessage: string | null;
   retryCount: number;
   createdAt: number;
@@ -179,6 +418,161 @@ export type CloneProgressEvent = {
   currentKey: string | null;
 };
 
+export type LifecycleTransition = {
+  days: number | null;
+  date: string | null;
+  storageClass: string;
+};
+
+export type LifecycleRule = {
+  id: string | null;
+  enabled: boolean;
+  prefix: string;
+  tags: ObjectTag[];
+  objectSizeGreaterThan: number | null;
+  objectSizeLessThan: number | null;
+  expirationDays: number | null;
+  expirationDate: string | null;
+  expiredObjectDeleteMarker: boolean;
+  transitions: LifecycleTransition[];
+  noncurrentExpirationDays: number | null;
+  noncurrentNewerVersions: number | null;
+  noncurrentTransitions: LifecycleTransition[];
+  abortIncompleteUploadDays: number | null;
+};
+
+export type LifecyclePreviewObject = {
+  key: string;
+  size: number;
+  lastModified: string | null;
+  storageClass: string | null;
+  action: "expire" | "transition";
+  transitionStorageClass: string | null;
+};
+
+export type LifecyclePreview = {
+  matchedObjects: number;
+  affectedObjects: number;
+  affectedSize: number;
+  objects: LifecyclePreviewObject[];
+  truncated: boolean;
+  lastIndexedAt: number | null;
+};
+
+export type CorsRule = {
+  id: string | null;
+  allowedMethods: string[];
+  allowedOrigins: string[];
+  allowedHeaders: string[];
+  exposeHeaders: string[];
+  maxAgeSeconds: number | null;
+};
+
+export type PublicAccessBlock = {
+  blockPublicAcls: boolean;
+  ignorePublicAcls: boolean;
+  blockPublicPolicy: boolean;
+  restrictPublicBuckets: boolean;
+};
+
+export type BucketReader = {
+  principal: string;
+  source: "policy" | "acl";
+  permissions: ("read" | "list")[];
+  resources: string[];
+  conditional: boolean;
+  isPublic: boolean;
+  blocked: boolean;
+};
+
+export type BucketAccessSummary = {
+  readers: BucketReader[];
+  publicRead: boolean;
+  publicList: boolean;
+  policyIsPublic: boolean | null;
+  publicAccessBlock: PublicAccessBlock | null;
+  hasDenyStatements: boolean;
+  warnings: string[];
+};
+
+export type BatchJobKind = "tag" | "emptyBucket" | "storageClass" | "restore" | "deletePrefix" | "uploadDirectory";
+
+export type TagJobMode = "merge" | "replace" | "remove";
+
+export type BatchJob = {
+  id: string;
+  kind: BatchJobKind;
+  status: CloneJobStatus;
+  targetId: string;
+  bucket: string;
+  prefix: string;
+  params: Record<string, unknown>;
+  enumerationToken: string | null;
+  enumerationComplete: boolean;
+  totalItems: number;
+  completedItems: number;
+  failedItems: number;
+  skippedItems: number;
+  createdAt: number;
+  updatedAt: number;
+  completedAt: number | null;
+  initiator: AuditInitiator;
+};
+
+export type BatchJobItem = {
+  id: string;
+  jobId: string;
+  key: string;
+  versionId: string | null;
+  uploadId: string | null;
+  size: number;
+  status: "pending" | "active" | "completed" | "skipped" | "failed";
+  errorMessage: string | null;
+  createdAt: number;
+  updatedAt: number;
+};
+
+export type UploadSymlinkPolicy = "skip" | "follow";
+
+export type UploadDirectoryParams = {
+  root: string;
+  destPrefix: string;
+  include: string[];
+  exclude: string[];
+  showHidden: boolean;
+  respectIgnoreFiles: boolean;
+  symlinks: UploadSymlinkPolicy;
+  skipIdentical: boolean;
+};
+
+export type BatchProgressEvent = {
+  jobId: string;
+  kind: BatchJobKind;
+  status: string;
+  totalItems: number;
+  completedItems: number;
+  failedItems: number;
+  skippedItems: number;
+};
+
+export type RestoreTier = "Standard" | "Bulk" | "Expedited";
+
+export type ObjectRestore = {
+  id: string;
+  targetId: string;
+  bucket: string;
+  key: string;
+  versionId: string | null;
+  tier: RestoreTier;
+  days: number;
+  status: "pending" | "restored" | "expired" | "failed";
+  expiryDate: string | null;
+  errorMessage: string | null;
+  requestedAt: number;
+  lastCheckedAt: number | null;
+  completedAt: number | null;
+};
+
 export type BucketIndexStatus = "idle" | "indexing" | "error";
 
 export type BucketIndexState = {
@@ -190,6 +584,7 @@ export type BucketIndexState = {
   totalSize: number;
   continuationToken: string | null;
   lastIndexedAt: number | null;
+  includeTags: boolean;
   createdAt: number;
   updatedAt: number;
 };